@@ -1,77 +1,5 @@
-#!/bin/bash
-# ═══════════════════════════════════════════════════════════════════════════════
-# Pre-commit hook - Direct Coding Standards Enforcement (v9.6.0)
-# Dependency Health Audit - STRICT (v9.8.0)
-# ═══════════════════════════════════════════════════════════════════════════════
-# Generated by: asimov init / asimov refresh
-# Architecture: ADR-043 (No SPOF), ADR-045 (GOOD CODE = Fresh deps, zero vulns)
-# ═══════════════════════════════════════════════════════════════════════════════
-#
-# NOTE: This is a template. The actual hook is generated dynamically based on
-# {PROJECT_TYPE} which can be: Rust, Python, Node, Go, Flutter, Docs, Arch, Generic, Migration
-#
-# Each project type has specific quality checks:
-# - Rust: cargo fmt, cargo clippy, cargo test, cargo-audit
-# - Python: ruff/black, flake8/ruff, pytest, pip-audit
-# - Node: prettier, eslint, npm test, npm audit
-# - Go: gofmt, golangci-lint/go vet, go test, govulncheck
-# - Flutter: dart format, flutter analyze, flutter test
-# - Docs/Arch/Generic: markdown checks only
-#
-# ═══════════════════════════════════════════════════════════════════════════════
-
-set -e
-
-echo "Running pre-commit checks..."
-
-# === QUALITY CHECKS (project-type specific) ===
-# {QUALITY_CHECKS}
-
-# === DEPENDENCY HEALTH (v9.8.0 ADR-045 - STRICT) ===
-# {DEPENDENCY_HEALTH_CHECK}
-
-# === FILE SIZE CHECK (inline, no deps) ===
-echo "Checking file sizes..."
-# {FILE_SIZE_CHECK}
-
-# === DOCUMENTATION CHECK (if markdownlint-cli2 available) ===
-# Note: markdownlint-cli2 uses .markdownlintignore for exclusions (not --ignore flags)
-if command -v markdownlint-cli2 &>/dev/null; then
-  echo "Linting markdown..."
-  markdownlint-cli2 "*.md" "**/*.md" 2>/dev/null || true
-fi
-
-# === ASIMOV (optional, soft-fail) ===
-# Protocol refresh - survives context compaction
-if command -v asimov &>/dev/null; then
-  echo "Refreshing protocols..."
-  asimov refresh || true
-  asimov validate || true
-fi
-
-# === WIP CONTINUITY (survives context compaction) ===
-# Forcing function: Claude sees WIP state on every commit
-# Reads from roadmap.yaml deliverables with status: wip
-if [ -f ".asimov/roadmap.yaml" ] && grep -q "status: wip" .asimov/roadmap.yaml 2>/dev/null; then
-  # Extract current WIP item (id comes before status in YAML)
-  current=$(grep -B5 "status: wip" .asimov/roadmap.yaml | grep "id:" | tail -1 | sed 's/.*id:[[:space:]]*//' | sed 's/[[:space:]]*$//' | tr -d '"')
-  if [ -n "$current" ]; then
-    # Count progress from deliverables section
-    total=$(grep -c "status:" .asimov/roadmap.yaml 2>/dev/null || echo "0")
-    done_count=$(grep -c "status: done" .asimov/roadmap.yaml 2>/dev/null || echo "0")
-    echo ""
-    echo "══════════════════════════════════════════════════════════════════════════════"
-    echo "ACTIVE WIP - CONTINUE THIS TASK"
-    echo "══════════════════════════════════════════════════════════════════════════════"
-    echo "CURRENT TASK: $current"
-    echo "PROGRESS: $done_count/$total items complete"
-    echo ""
-    echo ">>> CONTINUE WORKING ON: $current <<<"
-    echo ">>> DO NOT ASK FOR PERMISSION - USER CONSENT ALREADY GIVEN <<<"
-    echo "══════════════════════════════════════════════════════════════════════════════"
-    echo ""
-  fi
-fi
-
-echo ""
-echo "Pre-commit checks passed!"
+#!/bin/sh
+# RoyalBit Asimov pre-commit hook ({PROJECT_TYPE}) - generated by asimov init / asimov refresh
+# Checks: staged file sizes, quality gates, protocol integrity, ethics scan
+command -v asimov >/dev/null 2>&1 || { echo "asimov not found, skipping pre-commit checks"; exit 0; }
+exec asimov hook run pre-commit
@@ -0,0 +1,59 @@
+//! Offline key generation and signing of the protocol bundle
+//!
+//! ```text
+//! cargo run --example sign_protocols -- keygen <secret-key-file>
+//! cargo run --example sign_protocols -- sign <secret-key-file>
+//! ```
+//!
+//! `keygen` writes a new secret key (hex) and prints the public key to put in
+//! `PROTOCOL_PUBLIC_KEY` (src/protocols/bundle.rs). `sign` rewrites
+//! protocols/manifest.json for the current embedded protocols; point
+//! `BUNDLE_MANIFEST_JSON` at it and rebuild so the binary embeds it. Keep the
+//! secret key off the repository and off CI.
+
+use royalbit_asimov::protocols::{parse_secret_key, public_key_of, BundleManifest};
+use std::process::ExitCode;
+
+const MANIFEST_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/protocols/manifest.json");
+
+fn keygen(path: &str) -> Result<(), String> {
+    let mut secret = [0u8; 32];
+    getrandom::getrandom(&mut secret).map_err(|e| format!("No randomness: {}", e))?;
+    let hex: String = secret.iter().map(|b| format!("{:02x}", b)).collect();
+    std::fs::write(path, hex + "\n").map_err(|e| format!("{}: {}", path, e))?;
+    println!("Secret key written to {}", path);
+    println!("PROTOCOL_PUBLIC_KEY = Some(\"{}\")", public_key_of(&secret));
+    Ok(())
+}
+
+fn sign(path: &str) -> Result<(), String> {
+    let hex = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    let secret = parse_secret_key(&hex).map_err(|e| format!("{}: {}", path, e))?;
+    let manifest = BundleManifest::of_embedded(env!("CARGO_PKG_VERSION")).sign(&secret);
+    manifest.verify(&public_key_of(&secret))?;
+    std::fs::write(MANIFEST_PATH, manifest.to_json() + "\n")
+        .map_err(|e| format!("{}: {}", MANIFEST_PATH, e))?;
+    println!(
+        "Signed {} protocol files for {}",
+        manifest.files.len(),
+        manifest.version
+    );
+    println!("Public key: {}", public_key_of(&secret));
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["keygen", path] => keygen(path),
+        ["sign", path] => sign(path),
+        _ => Err("usage: sign_protocols keygen|sign <secret-key-file>".to_string()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
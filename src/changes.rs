@@ -0,0 +1,331 @@
+//! Changed-file scopes for scans
+//!
+//! The ethics scan and lint-docs walk the whole tree by default. In a large
+//! repository that is slow, and at commit time it flags legacy files nobody
+//! touched. `--staged` limits a scan to the git index and `--diff <base>` to
+//! changes since a base revision; findings are then kept only on added lines.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::process::Command;
+
+/// Which files and lines a scan covers
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ScanScope {
+    /// Every file in the tree
+    #[default]
+    All,
+    /// Files in the git index, as staged
+    Staged,
+    /// Files changed since the merge base with a revision, as in the working tree
+    Diff(String),
+}
+
+impl ScanScope {
+    /// Scope from `--staged` / `--diff <base>` flags
+    pub fn from_flags(staged: bool, diff: Option<String>) -> Self {
+        match (staged, diff) {
+            (true, _) => ScanScope::Staged,
+            (false, Some(base)) => ScanScope::Diff(base),
+            (false, None) => ScanScope::All,
+        }
+    }
+
+    pub fn is_all(&self) -> bool {
+        matches!(self, ScanScope::All)
+    }
+}
+
+/// A file changed within a scope
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedFile {
+    /// Path relative to the scanned directory
+    pub path: String,
+    /// Added or modified line numbers (1-indexed, new side of the diff)
+    pub added_lines: BTreeSet<usize>,
+    /// Content as staged (`Staged`) or in the working tree (`Diff`)
+    pub content: String,
+}
+
+impl ChangedFile {
+    pub fn is_added_line(&self, line: usize) -> bool {
+        self.added_lines.contains(&line)
+    }
+}
+
+/// Text files added, copied or modified within a scope (empty for `All`)
+///
+/// Binary files and files that are no longer readable are left out.
+pub fn changed_files_in_scope(dir: &Path, scope: &ScanScope) -> Result<Vec<ChangedFile>, String> {
+    // Pin what user config could change: path quoting and a/ b/ prefixes
+    let mut args = vec![
+        "-c",
+        "core.quotepath=off",
+        "diff",
+        "--no-color",
+        "--src-prefix=a/",
+        "--dst-prefix=b/",
+        "--no-ext-diff",
+        "--unified=0",
+        "--relative",
+        "--diff-filter=ACM",
+    ];
+    match scope {
+        ScanScope::All => return Ok(Vec::new()),
+        ScanScope::Staged => args.push("--cached"),
+        ScanScope::Diff(base) => {
+            args.push("--merge-base");
+            args.push(base);
+        }
+    }
+
+    let output = Command::new("git")
+        .args(&args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(match scope {
+            ScanScope::Diff(base) if !stderr.contains("not a git repository") => {
+                format!("Unknown base revision '{}': {}", base, stderr.trim())
+            }
+            _ => "Not a git repository (--staged and --diff need git)".to_string(),
+        });
+    }
+
+    let mut files = Vec::new();
+    for (path, added_lines) in parse_unified_diff(&String::from_utf8_lossy(&output.stdout)) {
+        let content = match scope {
+            ScanScope::Staged => staged_content(dir, &path),
+            _ => std::fs::read_to_string(dir.join(&path)).ok(),
+        };
+        if let Some(content) = content {
+            files.push(ChangedFile {
+                path,
+                added_lines,
+                content,
+            });
+        }
+    }
+    Ok(files)
+}
+
+/// Content of a file as staged, which may differ from the working tree
+///
+/// `file` is relative to `dir`, like the paths `git diff --relative` prints.
+pub fn staged_content(dir: &Path, file: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args(["show", &format!(":./{}", file)])
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+/// Files and added line numbers from `git diff --unified=0` output
+///
+/// With zero context every hunk `@@ -a,b +c,d @@` adds exactly lines
+/// `c..c+d`, so the hunk headers are enough. The `b + d` lines of a hunk are
+/// skipped by count, so an added line starting with `++ ` is not mistaken
+/// for a file header.
+pub fn parse_unified_diff(diff: &str) -> Vec<(String, BTreeSet<usize>)> {
+    let mut files: Vec<(String, BTreeSet<usize>)> = Vec::new();
+    let mut hunk_lines = 0;
+    for line in diff.lines() {
+        if hunk_lines > 0 {
+            if line.starts_with(['-', '+']) {
+                hunk_lines -= 1;
+                continue;
+            }
+            if line.starts_with('\\') {
+                // "\ No newline at end of file"
+                continue;
+            }
+            // Anything else ends a truncated hunk
+            hunk_lines = 0;
+        }
+        if let Some(path) = line.strip_prefix("+++ ") {
+            if path != "/dev/null" {
+                let path = unquote(path);
+                let path = path.strip_prefix("b/").unwrap_or(&path);
+                files.push((path.to_string(), BTreeSet::new()));
+            }
+        } else if line.starts_with("@@ ") {
+            let (Some((_, removed)), Some((start, count))) =
+                (hunk_range(line, '-'), hunk_range(line, '+'))
+            else {
+                continue;
+            };
+            hunk_lines = removed + count;
+            if let Some((_, added)) = files.last_mut() {
+                added.extend(start..start + count);
+            }
+        }
+    }
+    files
+}
+
+/// `(start, count)` of the old (`-`) or new (`+`) side of a hunk header
+fn hunk_range(header: &str, side: char) -> Option<(usize, usize)> {
+    let range = header
+        .split_whitespace()
+        .skip(1)
+        .find_map(|p| p.strip_prefix(side))?;
+    let mut parts = range.splitn(2, ',');
+    let start = parts.next()?.parse().ok()?;
+    let count = match parts.next() {
+        Some(count) => count.parse().ok()?,
+        None => 1,
+    };
+    Some((start, count))
+}
+
+/// git C-quotes paths with special characters: `"a\"b.md"`, `"\303\251.md"`
+fn unquote(path: &str) -> String {
+    let Some(quoted) = path.strip_prefix('"').and_then(|p| p.strip_suffix('"')) else {
+        return path.to_string();
+    };
+    let mut bytes = Vec::with_capacity(quoted.len());
+    let mut chars = quoted.bytes().peekable();
+    while let Some(b) = chars.next() {
+        if b != b'\\' {
+            bytes.push(b);
+            continue;
+        }
+        match chars.next() {
+            Some(d @ b'0'..=b'7') => {
+                let mut value = u32::from(d - b'0');
+                for _ in 0..2 {
+                    match chars.peek() {
+                        Some(&d @ b'0'..=b'7') => {
+                            value = value * 8 + u32::from(d - b'0');
+                            chars.next();
+                        }
+                        _ => break,
+                    }
+                }
+                bytes.push(value as u8);
+            }
+            Some(b'n') => bytes.push(b'\n'),
+            Some(b't') => bytes.push(b'\t'),
+            Some(b'r') => bytes.push(b'\r'),
+            Some(b'a') => bytes.push(0x07),
+            Some(b'b') => bytes.push(0x08),
+            Some(b'f') => bytes.push(0x0c),
+            Some(b'v') => bytes.push(0x0b),
+            Some(other) => bytes.push(other),
+            None => bytes.push(b'\\'),
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    fn test_parse_unified_diff() {
+        let diff = "diff --git a/a.md b/a.md\n--- a/a.md\n+++ b/a.md\n@@ -2 +2,2 @@ ctx\n-x\n+y\n+z\n@@ -9,0 +11 @@\n+w\ndiff --git a/new.rs b/new.rs\n--- /dev/null\n+++ b/new.rs\n@@ -0,0 +1,3 @@\n+a\n+b\n+c\n@@ -5,2 +7,0 @@\n";
+        let files = parse_unified_diff(diff);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].0, "a.md");
+        assert_eq!(
+            files[0].1.iter().copied().collect::<Vec<_>>(),
+            vec![2, 3, 11]
+        );
+        assert_eq!(files[1].0, "new.rs");
+        assert_eq!(files[1].1.len(), 3);
+    }
+
+    #[test]
+    fn test_parse_unified_diff_headers_and_quoting() {
+        // An added "++ x" line reads "+++ x" and is content, not a header
+        let diff = "--- a/a.md\n+++ b/a.md\n@@ -1 +1,2 @@\n-old\n\\ No newline at end of file\n+++ x\n+y\n--- \"a/\\303\\251 \\\"q\\\".md\"\n+++ \"b/\\303\\251 \\\"q\\\".md\"\n@@ -0,0 +1 @@\n+z\n";
+        let files = parse_unified_diff(diff);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].0, "a.md");
+        assert_eq!(files[0].1.len(), 2);
+        assert_eq!(files[1].0, "é \"q\".md");
+    }
+
+    #[test]
+    fn test_scope_from_flags() {
+        assert_eq!(ScanScope::from_flags(false, None), ScanScope::All);
+        assert_eq!(ScanScope::from_flags(true, None), ScanScope::Staged);
+        assert_eq!(
+            ScanScope::from_flags(false, Some("main".to_string())),
+            ScanScope::Diff("main".to_string())
+        );
+    }
+
+    #[test]
+    fn test_changed_files_staged_and_diff() {
+        let temp = TempDir::new().unwrap();
+        git(temp.path(), &["init", "-q"]);
+        std::fs::write(temp.path().join("a.md"), "one\ntwo\n").unwrap();
+        git(temp.path(), &["add", "a.md"]);
+        git(temp.path(), &["commit", "-q", "-m", "base"]);
+
+        std::fs::write(temp.path().join("a.md"), "one\nTWO\nthree\n").unwrap();
+        git(temp.path(), &["add", "a.md"]);
+        std::fs::write(temp.path().join("a.md"), "one\nTWO\nthree\nfour\n").unwrap();
+
+        let staged = changed_files_in_scope(temp.path(), &ScanScope::Staged).unwrap();
+        assert_eq!(staged.len(), 1);
+        assert_eq!(staged[0].content, "one\nTWO\nthree\n");
+        assert!(!staged[0].is_added_line(1));
+        assert!(staged[0].is_added_line(2) && staged[0].is_added_line(3));
+
+        let diff =
+            changed_files_in_scope(temp.path(), &ScanScope::Diff("HEAD".to_string())).unwrap();
+        assert_eq!(diff[0].added_lines.len(), 3);
+        assert!(diff[0].content.ends_with("four\n"));
+
+        let err = changed_files_in_scope(temp.path(), &ScanScope::Diff("nope".to_string()));
+        assert!(err.unwrap_err().contains("Unknown base revision 'nope'"));
+    }
+
+    #[test]
+    fn test_changed_files_ignore_user_diff_config() {
+        let temp = TempDir::new().unwrap();
+        git(temp.path(), &["init", "-q"]);
+        git(temp.path(), &["config", "diff.mnemonicPrefix", "true"]);
+        git(temp.path(), &["config", "diff.noprefix", "true"]);
+        std::fs::create_dir(temp.path().join("pkg")).unwrap();
+        std::fs::write(temp.path().join("pkg/é.md"), "staged\n").unwrap();
+        git(temp.path(), &["add", "."]);
+        std::fs::write(temp.path().join("pkg/é.md"), "worktree\n").unwrap();
+
+        // Paths from a subdirectory are relative to it, and read as staged
+        let staged = changed_files_in_scope(&temp.path().join("pkg"), &ScanScope::Staged).unwrap();
+        assert_eq!(staged.len(), 1);
+        assert_eq!(staged[0].path, "é.md");
+        assert_eq!(staged[0].content, "staged\n");
+    }
+
+    #[test]
+    fn test_changed_files_outside_git() {
+        let temp = TempDir::new().unwrap();
+        let err = changed_files_in_scope(temp.path(), &ScanScope::Staged).unwrap_err();
+        assert!(err.contains("Not a git repository"));
+        assert!(changed_files_in_scope(temp.path(), &ScanScope::All)
+            .unwrap()
+            .is_empty());
+    }
+}
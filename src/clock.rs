@@ -0,0 +1,177 @@
+//! Clock for dates in generated content
+//!
+//! Protocol files, stats and replays take "today" from here instead of the
+//! system clock, so tests and CI can pin it and get byte-identical output:
+//!
+//! - `ASIMOV_NOW` (or `--date`) - `YYYY-MM-DD` or an RFC 3339 timestamp
+//! - `ASIMOV_TZ` (or `--tz`) - `local` (default), `UTC` or an offset like `+05:30`
+
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, TimeZone, Utc};
+
+/// Environment variable pinning the current date or time
+pub const NOW_ENV: &str = "ASIMOV_NOW";
+
+/// Environment variable selecting the timezone of generated dates
+pub const TZ_ENV: &str = "ASIMOV_TZ";
+
+/// Timezone dates are rendered in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockZone {
+    /// The system's local timezone
+    Local,
+    /// A fixed UTC offset (`UTC` is +00:00)
+    Fixed(FixedOffset),
+}
+
+impl std::str::FromStr for ClockZone {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "" => Ok(ClockZone::Local),
+            zone if zone.eq_ignore_ascii_case("local") => Ok(ClockZone::Local),
+            zone if zone.eq_ignore_ascii_case("utc") || zone == "Z" => {
+                Ok(ClockZone::Fixed(FixedOffset::east_opt(0).expect("UTC")))
+            }
+            zone => zone
+                .parse::<FixedOffset>()
+                .map(ClockZone::Fixed)
+                .map_err(|_| {
+                    format!(
+                        "invalid timezone '{}' (expected local, UTC or an offset like +05:30)",
+                        zone
+                    )
+                }),
+        }
+    }
+}
+
+impl ClockZone {
+    fn at(&self, time: DateTime<Utc>) -> DateTime<FixedOffset> {
+        match self {
+            ClockZone::Local => time.with_timezone(&Local).fixed_offset(),
+            ClockZone::Fixed(offset) => time.with_timezone(offset),
+        }
+    }
+
+    fn midnight(&self, date: NaiveDate) -> Option<DateTime<FixedOffset>> {
+        let midnight = date.and_hms_opt(0, 0, 0)?;
+        match self {
+            ClockZone::Local => Local
+                .from_local_datetime(&midnight)
+                .earliest()
+                .map(|t| t.fixed_offset()),
+            ClockZone::Fixed(offset) => offset.from_local_datetime(&midnight).single(),
+        }
+    }
+}
+
+/// The current time as asimov sees it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Clock {
+    now: DateTime<FixedOffset>,
+}
+
+impl Clock {
+    /// The system clock in the local timezone
+    pub fn system() -> Self {
+        Clock {
+            now: ClockZone::Local.at(Utc::now()),
+        }
+    }
+
+    /// A clock stopped at `now`
+    pub fn fixed(now: DateTime<FixedOffset>) -> Self {
+        Clock { now }
+    }
+
+    /// A clock from an optional pinned date/time and timezone
+    ///
+    /// A plain date is midnight in the timezone; a timestamp is converted to
+    /// it. Without a pinned value the system clock is read.
+    pub fn from_settings(now: Option<&str>, zone: Option<&str>) -> Result<Self, String> {
+        let zone: ClockZone = zone.unwrap_or_default().parse()?;
+        let now = match now.map(str::trim).filter(|s| !s.is_empty()) {
+            None => zone.at(Utc::now()),
+            Some(value) => DateTime::parse_from_rfc3339(value)
+                .map(|t| zone.at(t.with_timezone(&Utc)))
+                .ok()
+                .or_else(|| {
+                    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                        .ok()
+                        .and_then(|date| zone.midnight(date))
+                })
+                .ok_or_else(|| {
+                    format!(
+                        "invalid date '{}' (expected YYYY-MM-DD or an RFC 3339 timestamp)",
+                        value
+                    )
+                })?,
+        };
+        Ok(Clock { now })
+    }
+
+    /// The clock `ASIMOV_NOW` and `ASIMOV_TZ` describe
+    pub fn from_env() -> Result<Self, String> {
+        let var = |name| std::env::var(name).ok();
+        Clock::from_settings(var(NOW_ENV).as_deref(), var(TZ_ENV).as_deref())
+            .map_err(|e| format!("{}/{}: {}", NOW_ENV, TZ_ENV, e))
+    }
+
+    pub fn now(&self) -> DateTime<FixedOffset> {
+        self.now
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        self.now.date_naive()
+    }
+
+    /// Today in YYYY-MM-DD format
+    pub fn today(&self) -> String {
+        self.now.format("%Y-%m-%d").to_string()
+    }
+
+    /// Current year
+    pub fn year(&self) -> String {
+        self.now.format("%Y").to_string()
+    }
+}
+
+/// The clock of this process; an invalid `ASIMOV_NOW`/`ASIMOV_TZ` (reported
+/// by the CLI at startup) falls back to the system clock
+pub fn clock() -> Clock {
+    Clock::from_env().unwrap_or_else(|_| Clock::system())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pinned_dates_and_zones() {
+        let clock = Clock::from_settings(Some("2025-03-01"), Some("UTC")).unwrap();
+        assert_eq!(clock.today(), "2025-03-01");
+        assert_eq!(clock.year(), "2025");
+        assert_eq!(clock.now().to_rfc3339(), "2025-03-01T00:00:00+00:00");
+
+        // A timestamp is read in the configured timezone
+        let late = Some("2024-12-31T23:30:00Z");
+        let utc = Clock::from_settings(late, Some("utc")).unwrap();
+        let tokyo = Clock::from_settings(late, Some("+09:00")).unwrap();
+        assert_eq!(utc.today(), "2024-12-31");
+        assert_eq!(tokyo.today(), "2025-01-01");
+        assert_eq!(tokyo.year(), "2025");
+
+        let empty = Clock::from_settings(Some(" "), None).unwrap();
+        assert_eq!(empty.today().len(), 10);
+    }
+
+    #[test]
+    fn test_invalid_settings() {
+        let err = Clock::from_settings(Some("yesterday"), None).unwrap_err();
+        assert!(err.contains("invalid date 'yesterday'"), "{err}");
+        let err = Clock::from_settings(None, Some("Mars/Olympus")).unwrap_err();
+        assert!(err.contains("invalid timezone"), "{err}");
+        assert_eq!("".parse::<ClockZone>(), Ok(ClockZone::Local));
+    }
+}
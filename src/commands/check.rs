@@ -0,0 +1,508 @@
+//! Check command implementation - quality gates from project.yaml
+//!
+//! Runs the `quality:` commands declared in `.asimov/project.yaml` (format,
+//! lint, test, ...) and records exit codes, durations and output tails.
+//! Reports are plain structs; output.rs renders them as text, JSON or JUnit.
+
+use crate::resolve_protocol_dir;
+use serde::Serialize;
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+
+/// Gates in execution order (fast, cheap checks first)
+pub const QUALITY_GATES: &[&str] = &["format", "lint", "vet", "types", "build", "test", "links"];
+
+/// `quality:` keys that are not gates
+const NON_GATE_KEYS: &[&str] = &["fix"];
+
+/// Placeholder replaced by the changed files (`--changed`) or `.`
+pub const FILES_PLACEHOLDER: &str = "{files}";
+
+/// Lines of output kept per gate
+const OUTPUT_TAIL_LINES: usize = 20;
+
+/// Options for check command
+#[derive(Debug, Clone, Default)]
+pub struct CheckOptions {
+    /// Run only these gates (empty = all declared)
+    pub only: Vec<String>,
+    /// Only check files changed since HEAD
+    pub changed: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GateStatus {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GateResult {
+    pub name: String,
+    pub command: String,
+    pub status: GateStatus,
+    pub exit_code: Option<i32>,
+    pub duration_ms: u64,
+    /// Last lines of combined stdout/stderr
+    pub output_tail: String,
+    pub skip_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    pub success: bool,
+    pub gates: Vec<GateResult>,
+    /// Files changed since HEAD (only with `--changed`)
+    pub changed_files: Option<Vec<String>>,
+    pub duration_ms: u64,
+    pub error: Option<String>,
+}
+
+impl CheckResult {
+    pub fn count(&self, status: GateStatus) -> usize {
+        self.gates.iter().filter(|g| g.status == status).count()
+    }
+}
+
+/// Run the quality gates declared in project.yaml
+pub fn run_check(dir: &Path, options: &CheckOptions) -> CheckResult {
+    match declared_gates(dir) {
+        Ok(gates) => run_gates(dir, gates, options),
+        Err(e) => CheckResult {
+            success: false,
+            gates: Vec::new(),
+            changed_files: None,
+            duration_ms: 0,
+            error: Some(e),
+        },
+    }
+}
+
+/// Run `(name, command)` gates in order
+pub fn run_gates(dir: &Path, gates: Vec<(String, String)>, options: &CheckOptions) -> CheckResult {
+    let started = Instant::now();
+    let mut result = CheckResult {
+        success: false,
+        gates: Vec::new(),
+        changed_files: None,
+        duration_ms: 0,
+        error: None,
+    };
+
+    for name in &options.only {
+        if !gates.iter().any(|(gate, _)| gate == name) {
+            let declared: Vec<&str> = gates.iter().map(|(gate, _)| gate.as_str()).collect();
+            result.error = Some(format!(
+                "Unknown gate '{}' (declared: {})",
+                name,
+                declared.join(", ")
+            ));
+            return result;
+        }
+    }
+
+    let files_arg = if options.changed {
+        match changed_files(dir) {
+            Ok(files) => {
+                let arg = files
+                    .iter()
+                    .map(|f| shell_quote(f))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                result.changed_files = Some(files);
+                arg
+            }
+            Err(e) => {
+                result.error = Some(e);
+                return result;
+            }
+        }
+    } else {
+        ".".to_string()
+    };
+    let nothing_changed = result.changed_files.as_ref().is_some_and(|f| f.is_empty());
+
+    for (name, command) in gates {
+        if !options.only.is_empty() && !options.only.contains(&name) {
+            continue;
+        }
+
+        let skip_reason = if command.trim_start().starts_with('#') || command.trim().is_empty() {
+            Some("no command declared".to_string())
+        } else if nothing_changed {
+            Some("no changed files".to_string())
+        } else {
+            None
+        };
+
+        let gate = match skip_reason {
+            Some(reason) => GateResult {
+                name,
+                command,
+                status: GateStatus::Skipped,
+                exit_code: None,
+                duration_ms: 0,
+                output_tail: String::new(),
+                skip_reason: Some(reason),
+            },
+            None => run_gate(dir, name, command.replace(FILES_PLACEHOLDER, &files_arg)),
+        };
+        result.gates.push(gate);
+    }
+
+    result.success = result.count(GateStatus::Failed) == 0;
+    result.duration_ms = started.elapsed().as_millis() as u64;
+    result
+}
+
+/// Gates from project.yaml `quality:`, known gates first in `QUALITY_GATES` order
+pub fn declared_gates(dir: &Path) -> Result<Vec<(String, String)>, String> {
+    let path = resolve_protocol_dir(dir).join("project.yaml");
+    let content = std::fs::read_to_string(&path)
+        .map_err(|_| "No .asimov/project.yaml found. Run: asimov init".to_string())?;
+    let yaml: serde_yaml_ng::Value = serde_yaml_ng::from_str(&content)
+        .map_err(|e| format!("Invalid YAML in project.yaml: {}", e))?;
+
+    let Some(quality) = yaml.get("quality").and_then(|q| q.as_mapping()) else {
+        return Ok(Vec::new());
+    };
+
+    let mut declared: Vec<(String, String)> = quality
+        .iter()
+        .filter_map(|(k, v)| Some((k.as_str()?.to_string(), v.as_str()?.to_string())))
+        .filter(|(k, _)| !NON_GATE_KEYS.contains(&k.as_str()))
+        .collect();
+    // Stable sort keeps custom gates in declaration order, after the known ones
+    declared.sort_by_key(|(k, _)| {
+        QUALITY_GATES
+            .iter()
+            .position(|g| g == k)
+            .unwrap_or(QUALITY_GATES.len())
+    });
+    Ok(declared)
+}
+
+fn run_gate(dir: &Path, name: String, command: String) -> GateResult {
+    let started = Instant::now();
+    let output = shell_command(&command).current_dir(dir).output();
+    let duration_ms = started.elapsed().as_millis() as u64;
+
+    match output {
+        Ok(output) => {
+            let mut combined = String::from_utf8_lossy(&output.stdout).to_string();
+            combined.push_str(&String::from_utf8_lossy(&output.stderr));
+            GateResult {
+                name,
+                command,
+                status: if output.status.success() {
+                    GateStatus::Passed
+                } else {
+                    GateStatus::Failed
+                },
+                exit_code: output.status.code(),
+                duration_ms,
+                output_tail: tail(&combined, OUTPUT_TAIL_LINES),
+                skip_reason: None,
+            }
+        }
+        Err(e) => GateResult {
+            name,
+            command,
+            status: GateStatus::Failed,
+            exit_code: None,
+            duration_ms,
+            output_tail: format!("Failed to start: {}", e),
+            skip_reason: None,
+        },
+    }
+}
+
+#[cfg(unix)]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
+
+#[cfg(windows)]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
+/// Files changed relative to HEAD: staged, unstaged and untracked
+///
+/// Deleted files are left out; a gate cannot lint a file that is gone.
+pub fn changed_files(dir: &Path) -> Result<Vec<String>, String> {
+    let git = |args: &[&str]| -> Option<String> {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+    };
+
+    // A repository without commits has no HEAD to diff against
+    let tracked = git(&["diff", "--name-only", "--diff-filter=d", "HEAD"])
+        .or_else(|| git(&["diff", "--name-only", "--diff-filter=d", "--cached"]))
+        .ok_or_else(|| "Not a git repository (--changed needs git)".to_string())?;
+    let untracked = git(&["ls-files", "--others", "--exclude-standard"]).unwrap_or_default();
+
+    let mut files: Vec<String> = tracked
+        .lines()
+        .chain(untracked.lines())
+        .filter(|l| !l.is_empty())
+        .map(String::from)
+        .collect();
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+#[cfg(unix)]
+fn shell_quote(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
+
+/// `cmd /C` has no single quotes; double quotes are doubled inside them
+#[cfg(windows)]
+fn shell_quote(arg: &str) -> String {
+    format!("\"{}\"", arg.replace('"', "\"\""))
+}
+
+fn tail(text: &str, lines: usize) -> String {
+    let all: Vec<&str> = text.trim_end().lines().collect();
+    all[all.len().saturating_sub(lines)..].join("\n")
+}
+
+/// JUnit XML report (one testcase per gate)
+pub fn check_junit_report(result: &CheckResult) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuite name=\"asimov check\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\">\n",
+        result.gates.len(),
+        result.count(GateStatus::Failed),
+        result.count(GateStatus::Skipped),
+        result.duration_ms as f64 / 1000.0
+    ));
+    for gate in &result.gates {
+        xml.push_str(&format!(
+            "  <testcase name=\"{}\" classname=\"asimov.quality\" time=\"{:.3}\">\n",
+            xml_escape(&gate.name),
+            gate.duration_ms as f64 / 1000.0
+        ));
+        match gate.status {
+            GateStatus::Passed => {}
+            GateStatus::Failed => xml.push_str(&format!(
+                "    <failure message=\"{}\">{}</failure>\n",
+                xml_escape(&match gate.exit_code {
+                    Some(code) => format!("{} exited with {}", gate.command, code),
+                    None => format!("{} did not complete", gate.command),
+                }),
+                xml_escape(&gate.output_tail)
+            )),
+            GateStatus::Skipped => xml.push_str(&format!(
+                "    <skipped message=\"{}\"/>\n",
+                xml_escape(gate.skip_reason.as_deref().unwrap_or_default())
+            )),
+        }
+        xml.push_str("  </testcase>\n");
+    }
+    xml.push_str("</testsuite>\n");
+    xml
+}
+
+/// Escape markup and drop ANSI sequences and control characters, which XML
+/// does not allow
+fn xml_escape(text: &str) -> String {
+    strip_control(text)
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn strip_control(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\u{1b}' => match chars.next() {
+                // CSI: ESC [ parameters final-byte
+                Some('[') => {
+                    for c in chars.by_ref() {
+                        if ('@'..='~').contains(&c) {
+                            break;
+                        }
+                    }
+                }
+                // OSC: ESC ] text (BEL | ESC \\)
+                Some(']') => {
+                    while let Some(c) = chars.next() {
+                        if c == '\u{7}' {
+                            break;
+                        }
+                        if c == '\u{1b}' && chars.peek() == Some(&'\\') {
+                            chars.next();
+                            break;
+                        }
+                    }
+                }
+                _ => {}
+            },
+            '\n' | '\t' => out.push(c),
+            c if c.is_control() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn project(quality: &str) -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let asimov_dir = temp.path().join(".asimov");
+        std::fs::create_dir_all(&asimov_dir).unwrap();
+        std::fs::write(
+            asimov_dir.join("project.yaml"),
+            format!("identity:\n  name: t\n  type: rust\nquality:\n{}", quality),
+        )
+        .unwrap();
+        temp
+    }
+
+    #[test]
+    fn test_declared_gates_order() {
+        let temp = project("  test: t\n  audit: a\n  fix: f\n  format: fmt\n");
+        let gates = declared_gates(temp.path()).unwrap();
+        let names: Vec<&str> = gates.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, vec!["format", "test", "audit"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_check_pass_fail_skip() {
+        let temp = project("  lint: \"# Use IDE\"\n  test: echo ok\n  build: echo boom; exit 3\n");
+        let result = run_check(temp.path(), &CheckOptions::default());
+
+        assert!(!result.success);
+        let build = result.gates.iter().find(|g| g.name == "build").unwrap();
+        assert_eq!(build.status, GateStatus::Failed);
+        assert_eq!(build.exit_code, Some(3));
+        assert_eq!(build.output_tail, "boom");
+        let test = result.gates.iter().find(|g| g.name == "test").unwrap();
+        assert_eq!(test.status, GateStatus::Passed);
+        assert_eq!(result.count(GateStatus::Skipped), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_check_only() {
+        let temp = project("  test: echo ok\n  build: exit 1\n");
+        let options = CheckOptions {
+            only: vec!["test".to_string()],
+            changed: false,
+        };
+        let result = run_check(temp.path(), &options);
+        assert!(result.success);
+        assert_eq!(result.gates.len(), 1);
+
+        let options = CheckOptions {
+            only: vec!["nope".to_string()],
+            changed: false,
+        };
+        let result = run_check(temp.path(), &options);
+        assert!(result.error.unwrap().contains("Unknown gate 'nope'"));
+    }
+
+    #[test]
+    fn test_run_check_without_project_yaml() {
+        let temp = TempDir::new().unwrap();
+        let result = run_check(temp.path(), &CheckOptions::default());
+        assert!(!result.success);
+        assert!(result.error.is_some());
+    }
+
+    #[test]
+    fn test_tail() {
+        assert_eq!(tail("a\nb\nc\n", 2), "b\nc");
+        assert_eq!(tail("", 2), "");
+    }
+
+    #[test]
+    fn test_junit_report() {
+        let result = CheckResult {
+            success: false,
+            gates: vec![
+                GateResult {
+                    name: "test".to_string(),
+                    command: "cargo test".to_string(),
+                    status: GateStatus::Failed,
+                    exit_code: Some(101),
+                    duration_ms: 1500,
+                    output_tail: "assert <a> & b".to_string(),
+                    skip_reason: None,
+                },
+                GateResult {
+                    name: "links".to_string(),
+                    command: String::new(),
+                    status: GateStatus::Skipped,
+                    exit_code: None,
+                    duration_ms: 0,
+                    output_tail: String::new(),
+                    skip_reason: Some("no command declared".to_string()),
+                },
+            ],
+            changed_files: None,
+            duration_ms: 1500,
+            error: None,
+        };
+        let xml = check_junit_report(&result);
+        assert!(xml.contains("tests=\"2\" failures=\"1\" skipped=\"1\" time=\"1.500\""));
+        assert!(xml.contains("message=\"cargo test exited with 101\">assert &lt;a&gt; &amp; b"));
+        assert!(xml.contains("<skipped message=\"no command declared\"/>"));
+
+        assert_eq!(
+            xml_escape("\u{1b}[1;31merror\u{1b}[0m: x\r\n\u{1b}]8;;http://a\u{7}link\u{8}\n"),
+            "error: x\nlink\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_changed_files_skips_deleted() {
+        let temp = TempDir::new().unwrap();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+                .args(args)
+                .current_dir(temp.path())
+                .output()
+                .unwrap()
+        };
+        git(&["init", "-q"]);
+        std::fs::write(temp.path().join("gone.rs"), "").unwrap();
+        std::fs::write(temp.path().join("kept.rs"), "").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "init"]);
+        std::fs::remove_file(temp.path().join("gone.rs")).unwrap();
+        std::fs::write(temp.path().join("kept.rs"), "fn x() {}\n").unwrap();
+        std::fs::write(temp.path().join("new file.rs"), "").unwrap();
+
+        let files = changed_files(temp.path()).unwrap();
+        assert_eq!(files, vec!["kept.rs", "new file.rs"]);
+        assert_eq!(shell_quote("new file.rs"), "'new file.rs'");
+    }
+}
@@ -1,7 +1,17 @@
 //! Doctor command implementation
 //! v9.7.0: Add coding standards tool checks (ADR-044)
 
-use crate::{check_for_update, validate_file, validator::check_protocol_integrity, ProjectType};
+use super::run_hooks_status;
+use crate::{
+    check_for_update,
+    context::AsimovContext,
+    protocols::{embedded_bundle, BundleStatus},
+    validate_file,
+    validator::{
+        check_protocol_integrity, check_protocol_trust, validate_protocol_overrides, TrustState,
+    },
+    ProjectType,
+};
 use std::path::Path;
 use std::process::Command;
 
@@ -140,11 +150,27 @@ pub fn run_doctor(dir: &Path) -> DoctorResult {
     if !git_dir.exists() {
         result.warnings.push("Not a git repository".to_string());
     } else {
-        let precommit = git_dir.join("hooks").join("pre-commit");
-        if !precommit.exists() {
-            result
-                .warnings
-                .push("Git pre-commit hook missing".to_string());
+        // Same view as `asimov hooks status`
+        let status = run_hooks_status(dir);
+        let running: Vec<&str> = status
+            .hooks
+            .iter()
+            .filter(|h| h.state.runs_asimov())
+            .map(|h| h.hook.as_str())
+            .collect();
+        if let Some(e) = status.error {
+            result.warnings.push(format!("Git hooks: {}", e));
+        } else if running.contains(&"pre-commit") {
+            result.checks.push(DoctorCheck {
+                name: "git hooks".to_string(),
+                passed: true,
+                message: running.join(", "),
+                auto_fixed: false,
+            });
+        } else {
+            result.warnings.push(
+                "Git pre-commit hook does not run asimov - run 'asimov hooks install'".to_string(),
+            );
         }
     }
 
@@ -154,8 +180,17 @@ pub fn run_doctor(dir: &Path) -> DoctorResult {
 
         let mut missing = Vec::new();
         let mut outdated = Vec::new();
+        // Customized files are kept by refresh; conflicts need review
+        let mut customized = 0;
+        let mut conflicts = Vec::new();
 
         for check in &protocol_checks {
+            if check.customized {
+                customized += 1;
+            }
+            if check.conflict_pending {
+                conflicts.push(format!("{}.conflict", check.filename));
+            }
             if !check.exists {
                 missing.push(check.filename.clone());
             } else if check.outdated {
@@ -164,10 +199,19 @@ pub fn run_doctor(dir: &Path) -> DoctorResult {
         }
 
         if missing.is_empty() && outdated.is_empty() {
+            let message = if customized > 0 {
+                format!(
+                    "{} files OK ({} customized)",
+                    protocol_checks.len(),
+                    customized
+                )
+            } else {
+                format!("{} files OK", protocol_checks.len())
+            };
             result.checks.push(DoctorCheck {
                 name: "protocol files".to_string(),
                 passed: true,
-                message: format!("{} files OK", protocol_checks.len()),
+                message,
                 auto_fixed: false,
             });
         } else {
@@ -196,10 +240,103 @@ pub fn run_doctor(dir: &Path) -> DoctorResult {
                 ));
             }
         }
+        if !conflicts.is_empty() {
+            result.checks.push(DoctorCheck {
+                name: "protocol merge".to_string(),
+                passed: false,
+                message: format!("{} conflict(s) pending", conflicts.len()),
+                auto_fixed: false,
+            });
+            result.issues.push(format!(
+                "Unresolved refresh conflicts: .asimov/{} - resolve them into the protocol file, delete the .conflict file and run 'asimov refresh'",
+                conflicts.join(", .asimov/")
+            ));
+        }
+    }
+
+    // Protocol files against the signed bundle and the recorded hashes
+    if asimov_dir.exists() {
+        let trust = check_protocol_trust(dir);
+        let count = |state| trust.iter().filter(|t| t.state == state).count();
+        let tampered: Vec<&str> = trust
+            .iter()
+            .filter(|t| t.state == TrustState::Tampered)
+            .map(|t| t.filename.as_str())
+            .collect();
+        if !trust.is_empty() {
+            result.checks.push(DoctorCheck {
+                name: "protocol signatures".to_string(),
+                passed: tampered.is_empty(),
+                message: if tampered.is_empty() {
+                    format!(
+                        "{} verified, {} unsigned-local",
+                        count(TrustState::Verified),
+                        count(TrustState::UnsignedLocal)
+                    )
+                } else {
+                    format!("{} tampered", tampered.len())
+                },
+                auto_fixed: false,
+            });
+        }
+        if !tampered.is_empty() {
+            result.issues.push(format!(
+                "Tampered protocol files: .asimov/{} - changed outside asimov; review the edits, then run 'asimov refresh' to keep them (or move them to .asimov/protocols/<name>.json) or delete the files and refresh to restore the defaults",
+                tampered.join(", .asimov/")
+            ));
+        }
+        if let BundleStatus::Unsigned(why) = embedded_bundle() {
+            result
+                .warnings
+                .push(format!("Protocol bundle not verified: {}", why));
+        }
+    }
+
+    // Protocol overrides that fall back to the default or have ignored keys
+    if asimov_dir.exists() {
+        let overrides = validate_protocol_overrides(dir);
+        let invalid: Vec<_> = overrides.iter().filter(|r| !r.is_valid).collect();
+        if !overrides.is_empty() {
+            // Schema violations load but are flagged, so they are not "valid"
+            let warned = overrides
+                .iter()
+                .filter(|r| r.is_valid && !r.warnings.is_empty())
+                .count();
+            let clean = overrides.len() - invalid.len() - warned;
+            let message = [
+                (clean, "valid"),
+                (warned, "with schema warnings"),
+                (invalid.len(), "invalid"),
+            ]
+            .iter()
+            .filter(|(n, _)| *n > 0)
+            .map(|(n, label)| format!("{} {}", n, label))
+            .collect::<Vec<_>>()
+            .join(", ");
+            result.checks.push(DoctorCheck {
+                name: "protocol overrides".to_string(),
+                passed: invalid.is_empty(),
+                message,
+                auto_fixed: false,
+            });
+        }
+        for r in &invalid {
+            result
+                .issues
+                .push(format!("{}: {}", r.file, r.errors.join("; ")));
+        }
+        for r in overrides.iter().filter(|r| r.is_valid) {
+            for w in &r.warnings {
+                result.warnings.push(format!("{}: {}", r.file, w));
+            }
+        }
     }
 
     // Check 6: Coding standards tools (v9.7.0 ADR-044)
-    if let Some(project_type) = detect_project_type_from_yaml(dir) {
+    if let Some(context) =
+        AsimovContext::discover(dir).filter(|c| c.resolve("project.yaml").is_some())
+    {
+        let project_type = context.project_type();
         check_coding_tools(project_type, &mut result);
         // Check 7: Dependency health audit tools (v9.8.0 ADR-045)
         check_audit_tools(project_type, &mut result);
@@ -213,31 +350,6 @@ pub fn run_doctor(dir: &Path) -> DoctorResult {
     result
 }
 
-/// Detect project type from project.yaml
-fn detect_project_type_from_yaml(dir: &Path) -> Option<ProjectType> {
-    let project_path = dir.join(".asimov").join("project.yaml");
-    if !project_path.exists() {
-        return None;
-    }
-
-    let content = std::fs::read_to_string(&project_path).ok()?;
-    if content.contains("type: rust") {
-        Some(ProjectType::Rust)
-    } else if content.contains("type: python") {
-        Some(ProjectType::Python)
-    } else if content.contains("type: node") {
-        Some(ProjectType::Node)
-    } else if content.contains("type: go") {
-        Some(ProjectType::Go)
-    } else if content.contains("type: flutter") {
-        Some(ProjectType::Flutter)
-    } else if content.contains("type: docs") {
-        Some(ProjectType::Docs)
-    } else {
-        Some(ProjectType::Generic)
-    }
-}
-
 /// Detect project license from LICENSE file or package manifest (v9.8.0 ADR-045)
 pub fn detect_license(dir: &Path) -> Option<String> {
     // Check LICENSE file variants
@@ -362,6 +474,25 @@ fn check_coding_tools(project_type: ProjectType, result: &mut DoctorResult) {
             check_command("dart", &["--version"], result);
             check_command("flutter", &["--version"], result);
         }
+        ProjectType::Java => {
+            check_command("java", &["-version"], result);
+        }
+        ProjectType::CSharp => {
+            check_command("dotnet", &["--version"], result);
+        }
+        ProjectType::Ruby => {
+            check_command("ruby", &["--version"], result);
+            check_command("bundle", &["--version"], result);
+            check_command("rubocop", &["--version"], result);
+        }
+        ProjectType::Php => {
+            check_command("php", &["--version"], result);
+            check_command("composer", &["--version"], result);
+        }
+        ProjectType::Cpp => {
+            check_command("clang-format", &["--version"], result);
+            check_command("cmake", &["--version"], result);
+        }
         ProjectType::Docs | ProjectType::Arch => {
             // Note: --help exits 2, --version exits 0
             check_command("markdownlint-cli2", &["--version"], result);
@@ -470,6 +601,14 @@ fn check_audit_tools(project_type: ProjectType, result: &mut DoctorResult) {
                 result,
             );
         }
+        ProjectType::Ruby => {
+            check_optional_command(
+                "bundle-audit",
+                &["version"],
+                "gem install bundler-audit",
+                result,
+            );
+        }
         _ => {}
     }
 }
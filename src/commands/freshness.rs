@@ -0,0 +1,182 @@
+//! Freshness command - model knowledge cutoffs
+//!
+//! `asimov freshness` prints the cutoff table and how many months old the
+//! knowledge of the model asimov launches is; `--import <file>` replaces the
+//! project's table after validating it. See `crate::freshness`.
+
+use super::{check_launch_conditions, AiProfile, LaunchResult, AI_PROFILES};
+use crate::clock::clock;
+use crate::freshness::{
+    load_model_cutoffs, months_since, project_cutoffs_path, CutoffSource, ModelCutoffs,
+    MODEL_CUTOFFS_FILE,
+};
+use std::path::Path;
+
+/// One row of the cutoff table
+#[derive(Debug, Clone, PartialEq)]
+pub struct CutoffRow {
+    pub provider: String,
+    pub model: String,
+    /// YYYY-MM
+    pub cutoff: String,
+    pub months_since: Option<i32>,
+}
+
+/// Knowledge age of the model an AI CLI profile launches
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelAge {
+    pub profile: String,
+    pub provider: String,
+    pub model: String,
+    /// None when the table has no entry for the model
+    pub cutoff: Option<String>,
+    pub months_since: Option<i32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FreshnessResult {
+    pub success: bool,
+    /// Date the ages are computed for (see `crate::clock`)
+    pub today: String,
+    pub source: Option<CutoffSource>,
+    pub rows: Vec<CutoffRow>,
+    pub note: Option<String>,
+    /// Models of the profiles asimov would launch (the current session's,
+    /// else the installed ones)
+    pub launched: Vec<ModelAge>,
+    /// Whether `--import` wrote the project table
+    pub imported: bool,
+    pub error: Option<String>,
+}
+
+/// Profiles asimov launches in this environment
+pub fn launch_candidates() -> Vec<AiProfile> {
+    match check_launch_conditions() {
+        LaunchResult::NoAiFound => Vec::new(),
+        LaunchResult::InsideAi(name) => AI_PROFILES
+            .iter()
+            .filter(|p| p.name == name)
+            .cloned()
+            .collect(),
+        LaunchResult::Launching(profile) => vec![profile],
+        LaunchResult::MultipleFound(profiles) => profiles,
+    }
+}
+
+fn import_cutoffs(dir: &Path, file: &Path) -> Result<(), String> {
+    if !dir.join(".asimov").exists() {
+        return Err("Not in an asimov project (.asimov/ not found)".to_string());
+    }
+    let content = std::fs::read_to_string(file)
+        .map_err(|e| format!("Cannot read {}: {}", file.display(), e))?;
+    let cutoffs = ModelCutoffs::parse(&content).map_err(|errors| {
+        format!(
+            "{} is not a valid cutoff table:\n  {}",
+            file.display(),
+            errors.join("\n  ")
+        )
+    })?;
+    std::fs::write(project_cutoffs_path(dir), cutoffs.to_json() + "\n")
+        .map_err(|e| format!("Failed to write .asimov/{}: {}", MODEL_CUTOFFS_FILE, e))
+}
+
+pub fn run_freshness(dir: &Path, import: Option<&Path>) -> FreshnessResult {
+    run_freshness_for(dir, import, &launch_candidates())
+}
+
+/// `run_freshness` for given launch profiles
+pub fn run_freshness_for(
+    dir: &Path,
+    import: Option<&Path>,
+    profiles: &[AiProfile],
+) -> FreshnessResult {
+    let today = clock().date();
+    let mut result = FreshnessResult {
+        success: false,
+        today: today.format("%Y-%m-%d").to_string(),
+        source: None,
+        rows: Vec::new(),
+        note: None,
+        launched: Vec::new(),
+        imported: false,
+        error: None,
+    };
+
+    if let Some(file) = import {
+        if let Err(e) = import_cutoffs(dir, file) {
+            result.error = Some(e);
+            return result;
+        }
+        result.imported = true;
+    }
+
+    let (cutoffs, source) = match load_model_cutoffs(dir) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            result.error = Some(e);
+            return result;
+        }
+    };
+    for (provider, models) in &cutoffs.providers {
+        for (model, cutoff) in models {
+            result.rows.push(CutoffRow {
+                provider: provider.clone(),
+                model: model.clone(),
+                cutoff: cutoff.clone(),
+                months_since: months_since(cutoff, today),
+            });
+        }
+    }
+    result.launched = profiles
+        .iter()
+        .map(|profile| {
+            let cutoff = cutoffs.cutoff(profile.provider, profile.model);
+            ModelAge {
+                profile: profile.name.to_string(),
+                provider: profile.provider.to_string(),
+                model: profile.model.to_string(),
+                cutoff: cutoff.map(String::from),
+                months_since: cutoff.and_then(|c| months_since(c, today)),
+            }
+        })
+        .collect();
+    result.note = cutoffs.note;
+    result.source = Some(source);
+    result.success = true;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_freshness_table_and_import() {
+        let temp = TempDir::new().unwrap();
+        let result = run_freshness_for(temp.path(), None, &AI_PROFILES[..1]);
+        assert!(result.success);
+        assert_eq!(result.source, Some(CutoffSource::Embedded));
+        assert!(result.rows.iter().any(|r| r.provider == "xai"));
+        assert_eq!(result.launched[0].model, "opus");
+        assert!(result.launched[0].cutoff.is_some());
+
+        // Imports need a project and a valid table
+        let file = temp.path().join("cutoffs.json");
+        std::fs::write(&file, r#"{"claude": {"sonnet": "2026-02"}}"#).unwrap();
+        let result = run_freshness_for(temp.path(), Some(&file), &AI_PROFILES[..1]);
+        assert!(result.error.unwrap().contains("Not in an asimov project"));
+
+        std::fs::create_dir_all(temp.path().join(".asimov")).unwrap();
+        let result = run_freshness_for(temp.path(), Some(&file), &AI_PROFILES[..1]);
+        assert!(result.success && result.imported);
+        assert_eq!(result.rows.len(), 1);
+        assert_eq!(result.launched[0].cutoff, None);
+
+        std::fs::write(&file, r#"{"claude": {"sonnet": "Feb 2026"}}"#).unwrap();
+        let result = run_freshness_for(temp.path(), Some(&file), &[]);
+        assert!(result.error.unwrap().contains("claude.sonnet"));
+        let kept = std::fs::read_to_string(project_cutoffs_path(temp.path())).unwrap();
+        assert!(kept.contains("2026-02"));
+    }
+}
@@ -0,0 +1,695 @@
+//! Hook command implementation - native git hooks
+//!
+//! `asimov hook run pre-commit` replaces the per-language bash that
+//! `precommit_hook_template` used to generate. The installed hook is a shim
+//! that execs this command, so the checks run the same on every platform:
+//! staged file sizes, quality gates, protocol integrity and the ethics scan.
+//! commit-msg checks the message and pre-push runs the quality gates.
+
+use super::{declared_gates, run_gates, CheckOptions, CheckResult, GateResult, GateStatus};
+use crate::changes::{changed_files_in_scope, ScanScope};
+use crate::commit_msg::{check_commit_message, commit_msg_policy, CommitMsgPolicy, MessageCleanup};
+use crate::context::project_type;
+use crate::ethics::{scan_changes_for_red_flags, RedFlagMatch};
+use crate::templates::ProjectType;
+use crate::validator::check_protocol_integrity;
+use std::path::{Path, PathBuf};
+
+/// Hooks `asimov hook run` implements
+pub const SUPPORTED_HOOKS: &[&str] = &["pre-commit", "commit-msg", "pre-push"];
+
+/// Line limit for staged source files (ADR-040)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileSizePolicy {
+    pub extensions: &'static [&'static str],
+    pub max_lines: usize,
+    pub exclude_dirs: &'static [&'static str],
+    /// Code projects fail the commit; docs projects only warn
+    pub blocking: bool,
+}
+
+/// File size policy for a project type
+pub fn file_size_policy(project_type: ProjectType) -> FileSizePolicy {
+    match project_type {
+        ProjectType::Rust => FileSizePolicy {
+            extensions: &["rs"],
+            max_lines: 1500,
+            exclude_dirs: &["target", "cli/target"],
+            blocking: true,
+        },
+        ProjectType::Python => FileSizePolicy {
+            extensions: &["py"],
+            max_lines: 1000,
+            exclude_dirs: &["venv", "__pycache__", ".venv"],
+            blocking: true,
+        },
+        ProjectType::Node => FileSizePolicy {
+            extensions: &["ts", "js", "tsx", "jsx"],
+            max_lines: 800,
+            exclude_dirs: &["node_modules", "dist", "build"],
+            blocking: true,
+        },
+        ProjectType::Go => FileSizePolicy {
+            extensions: &["go"],
+            max_lines: 1000,
+            exclude_dirs: &["vendor"],
+            blocking: true,
+        },
+        ProjectType::Flutter => FileSizePolicy {
+            extensions: &["dart"],
+            max_lines: 800,
+            exclude_dirs: &[".dart_tool", "build"],
+            blocking: true,
+        },
+        ProjectType::Java => FileSizePolicy {
+            extensions: &["java", "kt", "kts"],
+            max_lines: 1000,
+            exclude_dirs: &["target", "build", ".gradle"],
+            blocking: true,
+        },
+        ProjectType::CSharp => FileSizePolicy {
+            extensions: &["cs"],
+            max_lines: 1000,
+            exclude_dirs: &["bin", "obj"],
+            blocking: true,
+        },
+        ProjectType::Ruby => FileSizePolicy {
+            extensions: &["rb"],
+            max_lines: 800,
+            exclude_dirs: &["vendor", "tmp"],
+            blocking: true,
+        },
+        ProjectType::Php => FileSizePolicy {
+            extensions: &["php"],
+            max_lines: 1000,
+            exclude_dirs: &["vendor"],
+            blocking: true,
+        },
+        ProjectType::Cpp => FileSizePolicy {
+            extensions: &["c", "h", "cc", "cpp", "cxx", "hh", "hpp", "hxx"],
+            max_lines: 1500,
+            exclude_dirs: &["build", "third_party", "vendor"],
+            blocking: true,
+        },
+        ProjectType::Docs | ProjectType::Arch | ProjectType::Generic | ProjectType::Migration => {
+            FileSizePolicy {
+                extensions: &["md"],
+                max_lines: 800,
+                exclude_dirs: &["node_modules"],
+                blocking: false,
+            }
+        }
+    }
+}
+
+impl FileSizePolicy {
+    /// Whether the policy covers a repository-relative path
+    pub fn applies_to(&self, file: &str) -> bool {
+        let path = Path::new(file);
+        let excluded = self
+            .exclude_dirs
+            .iter()
+            .any(|dir| path.starts_with(dir) && path != Path::new(dir));
+        !excluded
+            && path
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|ext| self.extensions.contains(&ext))
+    }
+}
+
+/// Gates used when project.yaml declares no `quality:` section
+///
+/// Same tools the generated bash hook called, including the STRICT
+/// dependency health audit (ADR-045): vulnerabilities, licenses and outdated
+/// dependencies fail the commit. Gates whose tool is not installed, and on
+/// Windows those that need a POSIX shell, are skipped, not failed (ADR-043:
+/// no SPOF).
+pub fn default_quality_gates(project_type: ProjectType) -> Vec<(String, String)> {
+    let gates: &[(&str, &str)] = match project_type {
+        ProjectType::Rust => &[
+            ("format", "cargo fmt --all -- --check"),
+            ("lint", "cargo clippy --all-targets -- -D warnings"),
+            ("test", "cargo test"),
+            ("audit", "cargo audit --deny warnings"),
+            ("licenses", "cargo deny check licenses"),
+            (
+                "outdated",
+                "cargo outdated --depth 1 --root-deps-only --exit-code 1",
+            ),
+        ],
+        ProjectType::Python => &[
+            ("format", "ruff format --check ."),
+            ("lint", "ruff check ."),
+            ("test", "pytest"),
+            ("audit", "pip-audit"),
+            (
+                "outdated",
+                "test -z \"$(pip list --outdated --format=freeze)\"",
+            ),
+        ],
+        ProjectType::Node => &[
+            ("lint", "npm run lint --if-present"),
+            ("test", "npm run test --if-present"),
+            ("audit", "npm audit --audit-level=high"),
+            ("licenses", "license-checker --failOn 'GPL;AGPL'"),
+            ("outdated", "npm outdated"),
+        ],
+        ProjectType::Go => &[
+            ("format", "test -z \"$(gofmt -l .)\""),
+            ("vet", "go vet ./..."),
+            ("test", "go test ./..."),
+            ("audit", "govulncheck ./..."),
+            ("outdated", "test -z \"$(go list -m -u all | grep '\\[')\""),
+        ],
+        ProjectType::Flutter => &[
+            ("format", "dart format --set-exit-if-changed lib/ test/"),
+            ("lint", "flutter analyze"),
+            ("test", "flutter test"),
+        ],
+        // Only the wrapper the project commits is found, the other is skipped
+        ProjectType::Java => &[("gradle", "./gradlew check"), ("maven", "./mvnw -q verify")],
+        ProjectType::CSharp => &[
+            ("format", "dotnet format --verify-no-changes"),
+            ("test", "dotnet test"),
+        ],
+        ProjectType::Ruby => &[
+            ("lint", "bundle exec rubocop"),
+            ("test", "bundle exec rake"),
+        ],
+        ProjectType::Php => &[("lint", "vendor/bin/phpcs"), ("test", "vendor/bin/phpunit")],
+        ProjectType::Cpp => &[
+            ("build", "cmake -S . -B build && cmake --build build"),
+            ("test", "ctest --test-dir build --output-on-failure"),
+        ],
+        ProjectType::Docs | ProjectType::Arch | ProjectType::Generic | ProjectType::Migration => {
+            &[]
+        }
+    };
+    gates
+        .iter()
+        .map(|(name, command)| (name.to_string(), command.to_string()))
+        .collect()
+}
+
+/// A staged file over the line limit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OversizedFile {
+    pub file: String,
+    pub lines: usize,
+    pub max_lines: usize,
+}
+
+/// Result of `asimov hook run <hook>`
+#[derive(Debug, Clone)]
+pub struct HookRunResult {
+    pub success: bool,
+    pub hook: String,
+    pub project_type: ProjectType,
+    pub staged_files: Vec<String>,
+    pub oversized_files: Vec<OversizedFile>,
+    /// Whether oversized files fail the hook (see `FileSizePolicy::blocking`)
+    pub size_limit_blocking: bool,
+    pub gates: Option<CheckResult>,
+    /// Gates came from project.yaml rather than `default_quality_gates`
+    pub gates_declared: bool,
+    /// Protocol files that differ from this binary's version
+    pub outdated_protocols: Vec<String>,
+    /// Red flags on staged added lines (human review, not blocking)
+    pub red_flags: Vec<RedFlagMatch>,
+    /// commit-msg policy violations (blocking)
+    pub message_errors: Vec<String>,
+    /// Commit message policy came from project.yaml `commit_msg:`
+    pub policy_declared: bool,
+    pub error: Option<String>,
+}
+
+impl HookRunResult {
+    fn new(hook: &str) -> Self {
+        Self {
+            success: false,
+            hook: hook.to_string(),
+            project_type: ProjectType::Generic,
+            staged_files: Vec::new(),
+            oversized_files: Vec::new(),
+            size_limit_blocking: false,
+            gates: None,
+            gates_declared: false,
+            outdated_protocols: Vec::new(),
+            red_flags: Vec::new(),
+            message_errors: Vec::new(),
+            policy_declared: false,
+            error: None,
+        }
+    }
+}
+
+/// Run a git hook natively; `args` are the arguments git passed to the hook
+pub fn run_hook(dir: &Path, hook: &str, args: &[String]) -> HookRunResult {
+    match hook {
+        "pre-commit" => run_pre_commit(dir),
+        "commit-msg" => run_commit_msg(dir, args.first().map(String::as_str)),
+        "pre-push" => run_pre_push(dir),
+        _ => {
+            let mut result = HookRunResult::new(hook);
+            result.error = Some(format!(
+                "Unknown hook '{}' (supported: {})",
+                hook,
+                SUPPORTED_HOOKS.join(", ")
+            ));
+            result
+        }
+    }
+}
+
+/// Pre-commit: staged file sizes, quality gates, protocol integrity, ethics scan
+pub fn run_pre_commit(dir: &Path) -> HookRunResult {
+    let mut result = HookRunResult::new("pre-commit");
+    result.project_type = project_type(dir);
+
+    // Ethics findings on added lines only; sizes count the whole staged file
+    let staged = match changed_files_in_scope(dir, &ScanScope::Staged) {
+        Ok(staged) => staged,
+        Err(e) => {
+            result.error = Some(e);
+            return result;
+        }
+    };
+    result.staged_files = staged.iter().map(|c| c.path.clone()).collect();
+
+    let policy = file_size_policy(result.project_type);
+    result.size_limit_blocking = policy.blocking;
+    for change in staged.iter().filter(|c| policy.applies_to(&c.path)) {
+        let lines = change.content.lines().count();
+        if lines > policy.max_lines {
+            result.oversized_files.push(OversizedFile {
+                file: change.path.clone(),
+                lines,
+                max_lines: policy.max_lines,
+            });
+        }
+    }
+    result.red_flags = scan_changes_for_red_flags(&staged);
+
+    run_quality_gates(dir, &mut result);
+
+    if dir.join(".asimov").is_dir() {
+        result.outdated_protocols = check_protocol_integrity(dir)
+            .into_iter()
+            .filter(|check| check.outdated)
+            .map(|check| check.filename)
+            .collect();
+    }
+
+    let size_ok = !policy.blocking || result.oversized_files.is_empty();
+    let gates_ok = result.gates.as_ref().is_none_or(|g| g.success);
+    result.success = size_ok && gates_ok;
+    result
+}
+
+/// Commit-msg: the message file git passes as the first argument
+pub fn run_commit_msg(dir: &Path, message_file: Option<&str>) -> HookRunResult {
+    let mut result = HookRunResult::new("commit-msg");
+    result.project_type = project_type(dir);
+
+    let Some(message_file) = message_file else {
+        result.error = Some("commit-msg needs the commit message file".to_string());
+        return result;
+    };
+    let message = match std::fs::read_to_string(dir.join(message_file)) {
+        Ok(message) => message,
+        Err(e) => {
+            result.error = Some(format!("Failed to read {}: {}", message_file, e));
+            return result;
+        }
+    };
+
+    // Without a commit_msg: section only empty messages fail
+    let policy = match commit_msg_policy(dir) {
+        Ok(Some(policy)) => {
+            result.policy_declared = true;
+            policy
+        }
+        Ok(None) => CommitMsgPolicy {
+            max_subject_length: 0,
+            ..CommitMsgPolicy::default()
+        },
+        Err(e) => {
+            result.error = Some(e);
+            return result;
+        }
+    };
+    result.message_errors =
+        check_commit_message(&message, &policy, &MessageCleanup::from_git_config(dir));
+
+    result.success = result.message_errors.is_empty();
+    result
+}
+
+/// Pre-push: the quality gates, for projects that keep pre-commit fast
+pub fn run_pre_push(dir: &Path) -> HookRunResult {
+    let mut result = HookRunResult::new("pre-push");
+    result.project_type = project_type(dir);
+    run_quality_gates(dir, &mut result);
+    result.success = result.gates.as_ref().is_none_or(|g| g.success);
+    result
+}
+
+/// Declared gates, or the project type's defaults
+fn run_quality_gates(dir: &Path, result: &mut HookRunResult) {
+    let gates = match declared_gates(dir) {
+        Ok(gates) if !gates.is_empty() => {
+            result.gates_declared = true;
+            run_gates(dir, gates, &CheckOptions::default())
+        }
+        _ => run_default_gates(dir, result.project_type),
+    };
+    result.gates = Some(gates);
+}
+
+/// Cargo subcommands that are separate installs (`cargo audit` is `cargo-audit`)
+const CARGO_PLUGINS: &[&str] = &["audit", "deny", "outdated"];
+
+/// Config files a default gate cannot run without
+const GATE_CONFIG_FILES: &[(&str, &str)] = &[("cargo deny", "deny.toml")];
+
+/// Whether a gate command only runs under a POSIX shell: `$(...)`
+/// substitution, `test`, or a `./` script (Windows runs gates with `cmd /C`)
+fn needs_posix_shell(command: &str) -> bool {
+    command.contains("$(") || command.starts_with("test ") || command.starts_with("./")
+}
+
+/// Program a gate command needs: the first word, the cargo plugin it
+/// calls, or the program in a `test -z "$(...)"` check
+fn required_program(command: &str) -> String {
+    let command = command.strip_prefix("test -z \"$(").unwrap_or(command);
+    let mut words = command.split_whitespace();
+    let program = words.next().unwrap_or_default();
+    match (program, words.next()) {
+        ("cargo", Some(sub)) if CARGO_PLUGINS.contains(&sub) => format!("cargo-{}", sub),
+        _ => program.to_string(),
+    }
+}
+
+/// Why a default gate cannot run here, if it cannot
+fn gate_skip_reason(dir: &Path, command: &str) -> Option<String> {
+    if cfg!(windows) && needs_posix_shell(command) {
+        return Some("needs a POSIX shell".to_string());
+    }
+    let program = required_program(command);
+    if !program_available(dir, &program) {
+        let reason = if program.contains('/') {
+            "not found"
+        } else {
+            "not installed"
+        };
+        return Some(format!("{} {}", program, reason));
+    }
+    GATE_CONFIG_FILES
+        .iter()
+        .find(|(prefix, file)| command.starts_with(prefix) && !dir.join(file).is_file())
+        .map(|(_, file)| format!("no {}", file))
+}
+
+/// Directory the default gates run in: Rust projects with the crate under
+/// `cli/` run cargo there, as the generated bash hook did
+pub fn default_gates_dir(dir: &Path, project_type: ProjectType) -> PathBuf {
+    let cli = dir.join("cli");
+    if project_type == ProjectType::Rust && cli.join("Cargo.toml").is_file() {
+        cli
+    } else {
+        dir.to_path_buf()
+    }
+}
+
+/// Default gates, skipping those whose tool is not installed
+fn run_default_gates(dir: &Path, project_type: ProjectType) -> CheckResult {
+    let dir = &default_gates_dir(dir, project_type);
+    let (available, missing): (Vec<_>, Vec<_>) = default_quality_gates(project_type)
+        .into_iter()
+        .map(|(name, command)| {
+            let skip_reason = gate_skip_reason(dir, &command);
+            (name, command, skip_reason)
+        })
+        .partition(|(_, _, skip_reason)| skip_reason.is_none());
+
+    let available = available
+        .into_iter()
+        .map(|(name, command, _)| (name, command))
+        .collect();
+    let mut result = run_gates(dir, available, &CheckOptions::default());
+    for (name, command, skip_reason) in missing {
+        result.gates.push(GateResult {
+            name,
+            skip_reason,
+            command,
+            status: GateStatus::Skipped,
+            exit_code: None,
+            duration_ms: 0,
+            output_tail: String::new(),
+        });
+    }
+    result
+}
+
+/// Whether a gate's program can run: project-relative paths (`./gradlew`,
+/// `vendor/bin/phpunit`) must exist, other programs must be on PATH
+fn program_available(dir: &Path, program: &str) -> bool {
+    if program.contains('/') {
+        return dir.join(program).is_file();
+    }
+    program_on_path(program)
+}
+
+/// Whether a program is found on PATH
+fn program_on_path(program: &str) -> bool {
+    let Some(path) = std::env::var_os("PATH") else {
+        return false;
+    };
+    std::env::split_paths(&path).any(|dir| {
+        dir.join(program).is_file()
+            || (cfg!(windows) && dir.join(format!("{}.exe", program)).is_file())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    fn repo(project_yaml: &str) -> TempDir {
+        let temp = TempDir::new().unwrap();
+        git(temp.path(), &["init", "-q"]);
+        let asimov_dir = temp.path().join(".asimov");
+        std::fs::create_dir_all(&asimov_dir).unwrap();
+        std::fs::write(asimov_dir.join("project.yaml"), project_yaml).unwrap();
+        temp
+    }
+
+    #[test]
+    fn test_file_size_policy_applies_to() {
+        let policy = file_size_policy(ProjectType::Rust);
+        assert!(policy.applies_to("src/main.rs"));
+        assert!(!policy.applies_to("target/debug/build.rs"));
+        assert!(!policy.applies_to("README.md"));
+        assert!(!file_size_policy(ProjectType::Docs).blocking);
+    }
+
+    #[test]
+    fn test_default_quality_gates() {
+        let gates = default_quality_gates(ProjectType::Rust);
+        let names: Vec<&str> = gates.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["format", "lint", "test", "audit", "licenses", "outdated"]
+        );
+        assert!(default_quality_gates(ProjectType::Docs).is_empty());
+        for pt in ProjectType::ALL.into_iter().filter(|pt| pt.is_code()) {
+            assert!(!default_quality_gates(pt).is_empty(), "{}", pt);
+            assert!(file_size_policy(pt).blocking, "{}", pt);
+        }
+    }
+
+    #[test]
+    fn test_default_gates_dependency_audit() {
+        let names = |pt| -> Vec<String> {
+            default_quality_gates(pt)
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect()
+        };
+        for pt in [ProjectType::Rust, ProjectType::Python, ProjectType::Node] {
+            assert!(names(pt).contains(&"audit".to_string()), "{}", pt);
+        }
+
+        assert_eq!(
+            required_program("cargo audit --deny warnings"),
+            "cargo-audit"
+        );
+        assert_eq!(required_program("cargo test"), "cargo");
+        assert_eq!(required_program("test -z \"$(gofmt -l .)\""), "gofmt");
+
+        let temp = TempDir::new().unwrap();
+        assert_eq!(
+            gate_skip_reason(temp.path(), "no-such-audit-tool --strict"),
+            Some("no-such-audit-tool not installed".to_string())
+        );
+
+        // cmd /C cannot run these, so Windows skips them instead of failing
+        assert!(needs_posix_shell("test -z \"$(gofmt -l .)\""));
+        assert!(needs_posix_shell("./gradlew check"));
+        assert!(!needs_posix_shell("cargo fmt --all -- --check"));
+        assert_eq!(
+            gate_skip_reason(temp.path(), "test -n x").is_some(),
+            cfg!(windows)
+        );
+
+        // The crate in cli/ is where cargo runs
+        assert_eq!(
+            default_gates_dir(temp.path(), ProjectType::Rust),
+            temp.path()
+        );
+        std::fs::create_dir(temp.path().join("cli")).unwrap();
+        std::fs::write(temp.path().join("cli/Cargo.toml"), "[package]\n").unwrap();
+        assert_eq!(
+            default_gates_dir(temp.path(), ProjectType::Rust),
+            temp.path().join("cli")
+        );
+        assert_eq!(default_gates_dir(temp.path(), ProjectType::Go), temp.path());
+    }
+
+    #[test]
+    fn test_default_gates_skip_missing_wrapper() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("mvnw"), "#!/bin/sh\n").unwrap();
+        assert!(program_available(temp.path(), "./mvnw"));
+        assert!(!program_available(temp.path(), "./gradlew"));
+
+        let result = run_default_gates(temp.path(), ProjectType::Php);
+        assert!(result.gates.iter().all(|g| g.status == GateStatus::Skipped));
+        assert_eq!(
+            result.gates[0].skip_reason.as_deref(),
+            Some("vendor/bin/phpcs not found")
+        );
+    }
+
+    #[test]
+    fn test_run_hook_unknown() {
+        let temp = TempDir::new().unwrap();
+        let result = run_hook(temp.path(), "post-merge", &[]);
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Unknown hook 'post-merge'"));
+    }
+
+    #[test]
+    fn test_pre_commit_outside_git() {
+        let temp = TempDir::new().unwrap();
+        let result = run_pre_commit(temp.path());
+        assert!(result.error.unwrap().contains("Not a git repository"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_pre_commit_checks_staged_content() {
+        let temp = repo("identity:\n  name: t\n  type: rust\nquality:\n  test: echo ok\n");
+        let big = "fn f() {}\n".repeat(1501);
+        std::fs::write(temp.path().join("big.rs"), &big).unwrap();
+        std::fs::write(temp.path().join("notes.md"), "Build a keylogger\n").unwrap();
+        git(temp.path(), &["add", "big.rs", "notes.md"]);
+        // Unstaged edits must not hide the staged problem
+        std::fs::write(temp.path().join("big.rs"), "fn f() {}\n").unwrap();
+
+        let result = run_pre_commit(temp.path());
+        assert_eq!(result.project_type, ProjectType::Rust);
+        assert!(result.gates_declared);
+        assert!(!result.success);
+        assert_eq!(
+            result.oversized_files,
+            vec![OversizedFile {
+                file: "big.rs".to_string(),
+                lines: 1501,
+                max_lines: 1500,
+            }]
+        );
+        assert_eq!(result.red_flags.len(), 1);
+        assert_eq!(result.red_flags[0].file, "notes.md");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_pre_commit_docs_sizes_warn_only() {
+        let temp = repo("identity:\n  name: t\n  type: docs\nquality:\n  links: exit 0\n");
+        std::fs::write(temp.path().join("guide.md"), "line\n".repeat(801)).unwrap();
+        git(temp.path(), &["add", "guide.md"]);
+
+        let result = run_pre_commit(temp.path());
+        assert!(result.success, "{:?}", result.error);
+        assert!(!result.size_limit_blocking);
+        assert_eq!(result.oversized_files.len(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_pre_commit_failing_gate() {
+        let temp = repo("identity:\n  name: t\n  type: docs\nquality:\n  lint: exit 2\n");
+        let result = run_pre_commit(temp.path());
+        assert!(!result.success);
+        let gates = result.gates.unwrap();
+        assert_eq!(gates.gates[0].exit_code, Some(2));
+    }
+
+    #[test]
+    fn test_commit_msg_rejects_empty_message() {
+        let temp = repo("identity:\n  name: t\n  type: docs\n");
+        std::fs::write(temp.path().join("MSG"), "# Please enter a message\n\n").unwrap();
+        let args = vec!["MSG".to_string()];
+        let result = run_hook(temp.path(), "commit-msg", &args);
+        assert!(!result.success);
+        assert_eq!(result.message_errors, vec!["Commit message is empty"]);
+
+        std::fs::write(temp.path().join("MSG"), "Fix parser\n").unwrap();
+        assert!(run_hook(temp.path(), "commit-msg", &args).success);
+        assert!(run_hook(temp.path(), "commit-msg", &[]).error.is_some());
+    }
+
+    #[test]
+    fn test_commit_msg_applies_project_policy() {
+        let temp = repo("identity:\n  name: t\n  type: docs\ncommit_msg:\n  types: [docs]\n");
+        std::fs::write(temp.path().join("MSG"), "Fix parser\n").unwrap();
+        let result = run_commit_msg(temp.path(), Some("MSG"));
+        assert!(result.policy_declared);
+        assert!(!result.success);
+        assert_eq!(result.message_errors.len(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_pre_push_runs_gates() {
+        let temp = repo("identity:\n  name: t\n  type: docs\nquality:\n  test: exit 1\n");
+        let result = run_pre_push(temp.path());
+        assert!(!result.success);
+        assert!(result.gates_declared);
+        assert!(result.staged_files.is_empty());
+    }
+
+    #[test]
+    fn test_pre_commit_reports_outdated_protocols() {
+        let temp = repo("identity:\n  name: t\n  type: docs\n");
+        std::fs::write(temp.path().join(".asimov").join("green.json"), "{}").unwrap();
+        let result = run_pre_commit(temp.path());
+        assert!(result.success);
+        assert!(!result.gates_declared);
+        assert_eq!(result.outdated_protocols, vec!["green.json".to_string()]);
+    }
+}
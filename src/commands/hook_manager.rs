@@ -0,0 +1,686 @@
+//! Hook manager - asimov hooks install/uninstall/status
+//!
+//! `asimov init` used to overwrite .git/hooks/pre-commit with `--force` and
+//! skip it silently otherwise. Hooks are now shims written to the directory
+//! git actually runs (`core.hooksPath` included) that chain whatever hook was
+//! there before. Projects using a hook manager (husky, lefthook, pre-commit)
+//! get asimov added to the manager's config instead of a clobbered hooks dir.
+
+use super::SUPPORTED_HOOKS;
+use crate::templates::{hook_shim_template, CHAINED_HOOK_SUFFIX, MANAGED_HOOK_MARKER};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Hook managers that own the hooks directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookManager {
+    Husky,
+    Lefthook,
+    PreCommit,
+    SimpleGitHooks,
+}
+
+impl fmt::Display for HookManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HookManager::Husky => write!(f, "husky"),
+            HookManager::Lefthook => write!(f, "lefthook"),
+            HookManager::PreCommit => write!(f, "pre-commit"),
+            HookManager::SimpleGitHooks => write!(f, "simple-git-hooks"),
+        }
+    }
+}
+
+const LEFTHOOK_CONFIGS: &[&str] = &[
+    "lefthook.yml",
+    "lefthook.yaml",
+    ".lefthook.yml",
+    ".lefthook.yaml",
+    "lefthook.toml",
+    "lefthook.json",
+];
+
+/// Where git hooks live for a repository
+#[derive(Debug, Clone)]
+pub struct HooksLocation {
+    /// Repository root
+    pub root: PathBuf,
+    /// Directory git runs hooks from
+    pub hooks_dir: PathBuf,
+    /// `core.hooksPath`, when set
+    pub hooks_path_config: Option<String>,
+    /// Hook manager and the config file asimov integrates into
+    pub manager: Option<(HookManager, PathBuf)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookState {
+    Missing,
+    /// asimov shim
+    Installed,
+    /// asimov shim that runs a previous hook first
+    Chained,
+    /// A hook asimov did not write
+    Foreign,
+    /// Owned by a hook manager; `integrated` when its config runs asimov
+    Managed {
+        manager: HookManager,
+        integrated: bool,
+    },
+}
+
+impl HookState {
+    /// Whether git runs asimov for this hook
+    pub fn runs_asimov(&self) -> bool {
+        matches!(
+            self,
+            HookState::Installed
+                | HookState::Chained
+                | HookState::Managed {
+                    integrated: true,
+                    ..
+                }
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HookStatus {
+    pub hook: String,
+    pub path: PathBuf,
+    pub state: HookState,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookAction {
+    Installed,
+    Updated,
+    Unchanged,
+    /// The previous hook was moved aside and runs before asimov
+    Chained {
+        backup: String,
+    },
+    AddedToManager {
+        manager: HookManager,
+        file: String,
+    },
+    /// The manager's config must be edited by hand
+    ManualSetup {
+        manager: HookManager,
+        file: String,
+        snippet: String,
+    },
+    Removed,
+    /// The chained hook was moved back
+    Restored {
+        backup: String,
+    },
+    RemovedFromManager {
+        manager: HookManager,
+        file: String,
+    },
+    NotInstalled,
+}
+
+/// A hook file `install_hook` would write
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookWrite {
+    pub path: PathBuf,
+    /// Current content (`None` when the file does not exist)
+    pub old: Option<String>,
+    pub new: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct HookChange {
+    pub hook: String,
+    pub action: HookAction,
+}
+
+#[derive(Debug, Clone)]
+pub struct HooksResult {
+    pub success: bool,
+    pub location: Option<HooksLocation>,
+    /// install/uninstall only
+    pub changes: Vec<HookChange>,
+    /// status only
+    pub hooks: Vec<HookStatus>,
+    pub error: Option<String>,
+}
+
+impl HooksResult {
+    fn new() -> Self {
+        Self {
+            success: false,
+            location: None,
+            changes: Vec::new(),
+            hooks: Vec::new(),
+            error: None,
+        }
+    }
+}
+
+/// Resolve the hooks directory and hook manager of the repository containing `dir`
+pub fn hooks_location(dir: &Path) -> Result<HooksLocation, String> {
+    let git = |args: &[&str]| -> Option<String> {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+
+    let (root, hooks_dir) = match (
+        git(&["rev-parse", "--show-toplevel"]),
+        git(&["rev-parse", "--git-path", "hooks"]),
+    ) {
+        (Some(root), Some(hooks)) => (PathBuf::from(root), dir.join(hooks)),
+        // A .git directory git does not recognise (e.g. partially initialised)
+        _ if dir.join(".git").is_dir() => (dir.to_path_buf(), dir.join(".git").join("hooks")),
+        _ => return Err("Not a git repository".to_string()),
+    };
+
+    Ok(HooksLocation {
+        manager: detect_hook_manager(&root),
+        hooks_path_config: git(&["config", "--get", "core.hooksPath"]),
+        root,
+        hooks_dir,
+    })
+}
+
+/// Detect a hook manager from its config files
+pub fn detect_hook_manager(root: &Path) -> Option<(HookManager, PathBuf)> {
+    let husky = root.join(".husky");
+    if husky.is_dir() {
+        return Some((HookManager::Husky, husky));
+    }
+    if let Some(config) = LEFTHOOK_CONFIGS
+        .iter()
+        .map(|name| root.join(name))
+        .find(|path| path.is_file())
+    {
+        return Some((HookManager::Lefthook, config));
+    }
+    let pre_commit = root.join(".pre-commit-config.yaml");
+    if pre_commit.is_file() {
+        return Some((HookManager::PreCommit, pre_commit));
+    }
+    let package_json = root.join("package.json");
+    if std::fs::read_to_string(&package_json).is_ok_and(|c| c.contains("\"simple-git-hooks\"")) {
+        return Some((HookManager::SimpleGitHooks, package_json));
+    }
+    None
+}
+
+/// Status of every supported hook
+pub fn run_hooks_status(dir: &Path) -> HooksResult {
+    let mut result = HooksResult::new();
+    let location = match hooks_location(dir) {
+        Ok(location) => location,
+        Err(e) => {
+            result.error = Some(e);
+            return result;
+        }
+    };
+
+    for hook in SUPPORTED_HOOKS {
+        result.hooks.push(hook_status(&location, hook));
+    }
+    result.location = Some(location);
+    result.success = true;
+    result
+}
+
+/// Status of one hook
+pub fn hook_status(location: &HooksLocation, hook: &str) -> HookStatus {
+    let path = location.hooks_dir.join(hook);
+    let state = if let Some((manager, ref config)) = location.manager {
+        HookState::Managed {
+            manager,
+            integrated: manager_runs_asimov(manager, config, hook),
+        }
+    } else {
+        match std::fs::read_to_string(&path) {
+            Err(_) => HookState::Missing,
+            Ok(content) if !is_asimov_hook(&content) => HookState::Foreign,
+            Ok(_) if chained_path(&path).exists() => HookState::Chained,
+            Ok(_) => HookState::Installed,
+        }
+    };
+    HookStatus {
+        hook: hook.to_string(),
+        path,
+        state,
+    }
+}
+
+/// Install hooks (all supported hooks when `hooks` is empty)
+pub fn run_hooks_install(dir: &Path, hooks: &[String]) -> HooksResult {
+    run_for_hooks(dir, hooks, install_hook_at)
+}
+
+/// Uninstall hooks (all supported hooks when `hooks` is empty)
+pub fn run_hooks_uninstall(dir: &Path, hooks: &[String]) -> HooksResult {
+    run_for_hooks(dir, hooks, uninstall_hook_at)
+}
+
+/// Install one hook; used by init and refresh
+pub fn install_hook(dir: &Path, hook: &str) -> Result<HookAction, String> {
+    install_hook_at(&hooks_location(dir)?, hook)
+}
+
+fn run_for_hooks(
+    dir: &Path,
+    hooks: &[String],
+    apply: fn(&HooksLocation, &str) -> Result<HookAction, String>,
+) -> HooksResult {
+    let mut result = HooksResult::new();
+
+    if let Some(unknown) = hooks
+        .iter()
+        .find(|h| !SUPPORTED_HOOKS.contains(&h.as_str()))
+    {
+        result.error = Some(format!(
+            "Unknown hook '{}' (supported: {})",
+            unknown,
+            SUPPORTED_HOOKS.join(", ")
+        ));
+        return result;
+    }
+    let location = match hooks_location(dir) {
+        Ok(location) => location,
+        Err(e) => {
+            result.error = Some(e);
+            return result;
+        }
+    };
+
+    let selected: Vec<&str> = if hooks.is_empty() {
+        SUPPORTED_HOOKS.to_vec()
+    } else {
+        hooks.iter().map(String::as_str).collect()
+    };
+    for hook in selected {
+        match apply(&location, hook) {
+            Ok(action) => result.changes.push(HookChange {
+                hook: hook.to_string(),
+                action,
+            }),
+            Err(e) => {
+                result.error = Some(format!("{}: {}", hook, e));
+                result.location = Some(location);
+                return result;
+            }
+        }
+    }
+    result.location = Some(location);
+    result.success = true;
+    result
+}
+
+/// The file installing `hook` would write, without writing it
+///
+/// `None` when the hook is up to date or a hook manager's config has to be
+/// edited by hand.
+pub fn plan_hook_install(dir: &Path, hook: &str) -> Result<Option<HookWrite>, String> {
+    Ok(planned_hook_write(&hooks_location(dir)?, hook))
+}
+
+fn planned_hook_write(location: &HooksLocation, hook: &str) -> Option<HookWrite> {
+    if let Some((manager, ref config)) = location.manager {
+        if manager != HookManager::Husky || manager_runs_asimov(manager, config, hook) {
+            return None;
+        }
+        let path = config.join(hook);
+        let old = std::fs::read_to_string(&path).ok();
+        let mut new = old.as_deref().unwrap_or_default().trim_end().to_string();
+        if !new.is_empty() {
+            new.push_str("\n\n");
+        }
+        new.push_str(&husky_block(hook));
+        return Some(HookWrite { path, old, new });
+    }
+
+    let path = location.hooks_dir.join(hook);
+    let new = hook_shim_template(hook);
+    let old = std::fs::read_to_string(&path).ok();
+    (old.as_deref() != Some(new.as_str())).then_some(HookWrite { path, old, new })
+}
+
+fn install_hook_at(location: &HooksLocation, hook: &str) -> Result<HookAction, String> {
+    if let Some((manager, ref config)) = location.manager {
+        return install_into_manager(location, manager, config, hook);
+    }
+
+    let Some(write) = planned_hook_write(location, hook) else {
+        return Ok(HookAction::Unchanged);
+    };
+    let path = &write.path;
+    let action = match write.old {
+        None => HookAction::Installed,
+        Some(ref content) if is_asimov_hook(content) => HookAction::Updated,
+        Some(_) => {
+            let backup = chained_path(path);
+            if backup.exists() {
+                return Err(format!(
+                    "{} exists and {} is already taken; merge them by hand",
+                    path.display(),
+                    backup.display()
+                ));
+            }
+            std::fs::rename(path, &backup)
+                .map_err(|e| format!("Failed to move {}: {}", path.display(), e))?;
+            HookAction::Chained {
+                backup: backup.display().to_string(),
+            }
+        }
+    };
+
+    std::fs::create_dir_all(&location.hooks_dir)
+        .map_err(|e| format!("Failed to create hooks directory: {}", e))?;
+    write_executable(path, &write.new)?;
+    Ok(action)
+}
+
+fn uninstall_hook_at(location: &HooksLocation, hook: &str) -> Result<HookAction, String> {
+    if let Some((manager, ref config)) = location.manager {
+        return uninstall_from_manager(manager, config, hook);
+    }
+
+    let path = location.hooks_dir.join(hook);
+    match std::fs::read_to_string(&path) {
+        Ok(content) if is_asimov_hook(&content) => {
+            std::fs::remove_file(&path)
+                .map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+            let backup = chained_path(&path);
+            if backup.exists() {
+                std::fs::rename(&backup, &path)
+                    .map_err(|e| format!("Failed to restore {}: {}", backup.display(), e))?;
+                return Ok(HookAction::Restored {
+                    backup: backup.display().to_string(),
+                });
+            }
+            Ok(HookAction::Removed)
+        }
+        _ => Ok(HookAction::NotInstalled),
+    }
+}
+
+/// Husky hooks are plain scripts we can add to; other managers get a config snippet
+fn install_into_manager(
+    location: &HooksLocation,
+    manager: HookManager,
+    config: &Path,
+    hook: &str,
+) -> Result<HookAction, String> {
+    if manager == HookManager::Husky {
+        let Some(write) = planned_hook_write(location, hook) else {
+            return Ok(HookAction::Unchanged);
+        };
+        write_executable(&write.path, &write.new)?;
+        return Ok(HookAction::AddedToManager {
+            manager,
+            file: relative_to(&write.path, &location.root),
+        });
+    }
+
+    let file = relative_to(config, &location.root);
+    if manager_runs_asimov(manager, config, hook) {
+        return Ok(HookAction::Unchanged);
+    }
+    Ok(HookAction::ManualSetup {
+        manager,
+        file,
+        snippet: manager_snippet(manager, hook),
+    })
+}
+
+fn uninstall_from_manager(
+    manager: HookManager,
+    config: &Path,
+    hook: &str,
+) -> Result<HookAction, String> {
+    if !manager_runs_asimov(manager, config, hook) {
+        return Ok(HookAction::NotInstalled);
+    }
+    if manager != HookManager::Husky {
+        return Ok(HookAction::ManualSetup {
+            manager,
+            file: config.display().to_string(),
+            snippet: format!("Remove the `asimov hook run {}` entry", hook),
+        });
+    }
+
+    let file = config.join(hook);
+    let content = std::fs::read_to_string(&file)
+        .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+    let block = husky_block(hook);
+    let updated = content.replace(&block, "");
+    let updated = format!("{}\n", updated.trim_end());
+    std::fs::write(&file, updated)
+        .map_err(|e| format!("Failed to write {}: {}", file.display(), e))?;
+    Ok(HookAction::RemovedFromManager {
+        manager,
+        file: file.display().to_string(),
+    })
+}
+
+fn husky_block(hook: &str) -> String {
+    format!(
+        "{}: {}\nif command -v asimov >/dev/null 2>&1; then asimov hook run {} \"$@\" || exit $?; fi\n",
+        MANAGED_HOOK_MARKER, hook, hook
+    )
+}
+
+/// Config to add for managers asimov does not edit
+pub fn manager_snippet(manager: HookManager, hook: &str) -> String {
+    let args = if hook == "commit-msg" { " {1}" } else { "" };
+    match manager {
+        HookManager::Husky => husky_block(hook),
+        HookManager::Lefthook => format!(
+            "{}:\n  commands:\n    asimov:\n      run: asimov hook run {}{}\n",
+            hook, hook, args
+        ),
+        HookManager::PreCommit => format!(
+            "- repo: local\n  hooks:\n    - id: asimov-{hook}\n      name: asimov {hook}\n      entry: asimov hook run {hook}\n      language: system\n      pass_filenames: {}\n      always_run: true\n      stages: [{hook}]\n",
+            hook == "commit-msg",
+            hook = hook
+        ),
+        HookManager::SimpleGitHooks => format!(
+            "\"simple-git-hooks\": {{ \"{}\": \"asimov hook run {}{}\" }}\n",
+            hook,
+            hook,
+            if hook == "commit-msg" { " $1" } else { "" }
+        ),
+    }
+}
+
+fn manager_runs_asimov(manager: HookManager, config: &Path, hook: &str) -> bool {
+    let file = match manager {
+        HookManager::Husky => config.join(hook),
+        _ => config.to_path_buf(),
+    };
+    std::fs::read_to_string(file).is_ok_and(|c| c.contains(&format!("asimov hook run {}", hook)))
+}
+
+/// Hooks written by asimov, including the older bash hooks and shims
+fn is_asimov_hook(content: &str) -> bool {
+    content.contains(MANAGED_HOOK_MARKER)
+        || content.contains("Generated by: asimov init / asimov refresh")
+        || content.contains("generated by asimov init / asimov refresh")
+}
+
+fn chained_path(hook_path: &Path) -> PathBuf {
+    let mut name = hook_path.file_name().unwrap_or_default().to_os_string();
+    name.push(CHAINED_HOOK_SUFFIX);
+    hook_path.with_file_name(name)
+}
+
+fn relative_to(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+fn write_executable(path: &Path, content: &str) -> Result<(), String> {
+    std::fs::write(path, content)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))
+            .map_err(|e| format!("Failed to set permissions on {}: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn repo() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(temp.path())
+            .output()
+            .unwrap();
+        temp
+    }
+
+    fn hook_file(temp: &TempDir, hook: &str) -> PathBuf {
+        temp.path().join(".git").join("hooks").join(hook)
+    }
+
+    #[test]
+    fn test_install_chains_existing_hook_and_uninstall_restores() {
+        let temp = repo();
+        std::fs::write(hook_file(&temp, "pre-commit"), "#!/bin/sh\necho mine\n").unwrap();
+
+        let result = run_hooks_install(temp.path(), &["pre-commit".to_string()]);
+        assert!(result.success, "{:?}", result.error);
+        assert!(matches!(
+            result.changes[0].action,
+            HookAction::Chained { .. }
+        ));
+        let backup = std::fs::read_to_string(hook_file(&temp, "pre-commit.pre-asimov")).unwrap();
+        assert!(backup.contains("echo mine"));
+
+        let status = run_hooks_status(temp.path());
+        assert_eq!(status.hooks[0].state, HookState::Chained);
+        assert_eq!(status.hooks[1].state, HookState::Missing);
+
+        // Reinstalling is a no-op, not a second chain
+        let again = run_hooks_install(temp.path(), &["pre-commit".to_string()]);
+        assert_eq!(again.changes[0].action, HookAction::Unchanged);
+
+        let result = run_hooks_uninstall(temp.path(), &[]);
+        assert!(matches!(
+            result.changes[0].action,
+            HookAction::Restored { .. }
+        ));
+        assert_eq!(result.changes[1].action, HookAction::NotInstalled);
+        let restored = std::fs::read_to_string(hook_file(&temp, "pre-commit")).unwrap();
+        assert!(restored.contains("echo mine"));
+    }
+
+    #[test]
+    fn test_install_replaces_old_asimov_hook() {
+        let temp = repo();
+        std::fs::write(
+            hook_file(&temp, "pre-commit"),
+            "#!/bin/bash\n# Generated by: asimov init / asimov refresh\ncargo test\n",
+        )
+        .unwrap();
+        assert_eq!(
+            install_hook(temp.path(), "pre-commit").unwrap(),
+            HookAction::Updated
+        );
+        assert!(!hook_file(&temp, "pre-commit.pre-asimov").exists());
+    }
+
+    #[test]
+    fn test_install_respects_core_hooks_path() {
+        let temp = repo();
+        Command::new("git")
+            .args(["config", "core.hooksPath", "githooks"])
+            .current_dir(temp.path())
+            .output()
+            .unwrap();
+
+        let result = run_hooks_install(temp.path(), &["pre-push".to_string()]);
+        assert!(result.success);
+        assert!(temp.path().join("githooks").join("pre-push").exists());
+        let location = result.location.unwrap();
+        assert_eq!(location.hooks_path_config.as_deref(), Some("githooks"));
+    }
+
+    #[test]
+    fn test_husky_block_added_and_removed() {
+        let temp = repo();
+        let husky = temp.path().join(".husky");
+        std::fs::create_dir_all(&husky).unwrap();
+        std::fs::write(husky.join("pre-commit"), "npx lint-staged\n").unwrap();
+
+        let result = run_hooks_install(temp.path(), &["pre-commit".to_string()]);
+        assert_eq!(
+            result.changes[0].action,
+            HookAction::AddedToManager {
+                manager: HookManager::Husky,
+                file: ".husky/pre-commit".to_string(),
+            }
+        );
+        let content = std::fs::read_to_string(husky.join("pre-commit")).unwrap();
+        assert!(content.starts_with("npx lint-staged\n\n# asimov-managed hook: pre-commit\n"));
+        assert!(!hook_file(&temp, "pre-commit").exists());
+
+        let status = run_hooks_status(temp.path());
+        assert!(status.hooks[0].state.runs_asimov());
+
+        run_hooks_uninstall(temp.path(), &["pre-commit".to_string()]);
+        let content = std::fs::read_to_string(husky.join("pre-commit")).unwrap();
+        assert_eq!(content, "npx lint-staged\n");
+    }
+
+    #[test]
+    fn test_lefthook_gets_snippet() {
+        let temp = repo();
+        std::fs::write(temp.path().join("lefthook.yml"), "pre-commit:\n").unwrap();
+
+        let result = run_hooks_install(temp.path(), &["commit-msg".to_string()]);
+        let HookAction::ManualSetup {
+            manager, snippet, ..
+        } = &result.changes[0].action
+        else {
+            panic!("expected manual setup: {:?}", result.changes);
+        };
+        assert_eq!(*manager, HookManager::Lefthook);
+        assert!(snippet.contains("run: asimov hook run commit-msg {1}"));
+        assert_eq!(
+            run_hooks_status(temp.path()).hooks[1].state,
+            HookState::Managed {
+                manager: HookManager::Lefthook,
+                integrated: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_unknown_hook_and_no_git() {
+        let temp = TempDir::new().unwrap();
+        let result = run_hooks_install(temp.path(), &["post-merge".to_string()]);
+        assert!(result.error.unwrap().contains("Unknown hook 'post-merge'"));
+        assert!(run_hooks_status(temp.path())
+            .error
+            .unwrap()
+            .contains("Not a git repository"));
+    }
+}
@@ -1,10 +1,15 @@
 //! Init command implementation
 //! v9.7.0: Add dev dependencies for coding standards tools (ADR-044)
 
-use crate::{
-    get_template_by_name, git_precommit_hook, protocols::PROTOCOL_FILES, roadmap_template,
-    ProjectType,
+use super::{install_hook, HookAction};
+use crate::protocols::{is_builtin_active, ProjectFacts, MIGRATIONS_FILE};
+use crate::schemas::with_current_schema_version;
+use crate::templates::{
+    base_template, find_template, migrations_template, render_template, DevSetup,
 };
+use crate::validator::validate_rendered_template;
+use crate::validator::{record_protocol_base, record_protocol_hashes};
+use crate::{get_template_by_name, protocols::protocol_files, roadmap_template, ProjectType};
 use std::path::Path;
 
 #[derive(Debug, Clone)]
@@ -21,6 +26,20 @@ pub struct InitResult {
 }
 
 pub fn run_init(dir: &Path, name: &str, type_str: &str, force: bool) -> InitResult {
+    run_init_with_vars(dir, name, type_str, force, &[])
+}
+
+/// Initialize with template variables (`asimov init --var KEY=VALUE`)
+///
+/// The template is rendered (variables, conditionals, includes,
+/// `@extends`) and checked against the project schema before anything is written.
+pub fn run_init_with_vars(
+    dir: &Path,
+    name: &str,
+    type_str: &str,
+    force: bool,
+    vars: &[(String, String)],
+) -> InitResult {
     let mut result = InitResult {
         success: false,
         project_type: None,
@@ -33,48 +52,46 @@ pub fn run_init(dir: &Path, name: &str, type_str: &str, force: bool) -> InitResu
         error: None,
     };
 
-    // v10.3.1: Support all 21 templates, map to base ProjectType for hooks/deps
-    let template_name = type_str.to_lowercase();
-    let project_type = match template_name.as_str() {
-        // Base templates
-        "rust" | "rs" => ProjectType::Rust,
-        "python" | "py" => ProjectType::Python,
-        "node" | "nodejs" | "javascript" | "js" | "typescript" | "ts" => ProjectType::Node,
-        "go" | "golang" => ProjectType::Go,
-        "flutter" | "dart" => ProjectType::Flutter,
-        "docs" | "documentation" => ProjectType::Docs,
-        "arch" | "architecture" => ProjectType::Arch,
-        "generic" => ProjectType::Generic,
-        // Extended templates map to their base types
-        "api-rust" => ProjectType::Rust,
-        "api-go" => ProjectType::Go,
-        "api-fastapi" => ProjectType::Python,
-        "api-nestjs" => ProjectType::Node,
-        "api-spring" => ProjectType::Generic, // Java not yet a ProjectType
-        "web-nextjs" | "web-react" | "web-vue" | "web-angular" => ProjectType::Node,
-        "mono-turbo" | "mono-nx" | "mono-pnpm" => ProjectType::Node,
-        "admin-dashboard" => ProjectType::Node,
-        other => {
+    // The template manifest declares each template's language, hooks
+    // and dev setup; custom .asimov/templates/ files take theirs from identity.type
+    let (template_name, manifest) = match find_template(type_str) {
+        Some(manifest) => (manifest.name.to_string(), Some(manifest)),
+        None if get_template_by_name(&type_str.to_lowercase()).is_some() => {
+            (type_str.to_lowercase(), None)
+        }
+        None => {
             result.error = Some(format!(
                 "Unknown template: '{}'. Use 'asimov init --help' to see all templates",
-                other
+                type_str
             ));
             return result;
         }
     };
-    result.project_type = Some(project_type);
 
-    // Normalize template name for lookup
-    let template_key = match template_name.as_str() {
-        "rs" => "rust",
-        "py" => "python",
-        "nodejs" | "javascript" | "js" | "typescript" | "ts" => "node",
-        "golang" => "go",
-        "dart" => "flutter",
-        "documentation" => "docs",
-        "architecture" => "arch",
-        other => other,
+    let mut given = vec![("PROJECT_NAME".to_string(), name.to_string())];
+    given.extend(vars.iter().cloned());
+    let project_yaml = match render_template(&template_name, &given) {
+        Ok(rendered) => with_current_schema_version("project", &rendered),
+        Err(e) => {
+            result.error = Some(format!("Template '{}': {}", template_name, e));
+            return result;
+        }
     };
+    let check = validate_rendered_template(Path::new("project.yaml"), &project_yaml);
+    if !check.is_valid {
+        result.error = Some(format!(
+            "Template '{}' renders an invalid project.yaml: {}",
+            template_name,
+            check.errors.join("; ")
+        ));
+        return result;
+    }
+
+    let manifest = manifest.unwrap_or_else(|| {
+        base_template(declared_language(&project_yaml).unwrap_or(ProjectType::Generic))
+    });
+    let project_type = manifest.language;
+    result.project_type = Some(project_type);
 
     let asimov_dir = dir.join(".asimov");
     if let Err(e) = std::fs::create_dir_all(&asimov_dir) {
@@ -104,15 +121,7 @@ pub fn run_init(dir: &Path, name: &str, type_str: &str, force: bool) -> InitResu
     let project_path = asimov_dir.join("project.yaml");
     let project_existed = project_path.exists();
     if !project_existed || force {
-        // v10.3.1: Use unified template lookup for all 21 templates
-        let template = get_template_by_name(template_key).unwrap_or_else(|| {
-            // Fallback to generic if template not found (shouldn't happen)
-            get_template_by_name("generic").unwrap()
-        });
-        let content = template
-            .replace("{PROJECT_NAME}", name)
-            .replace("{PROJECT_TAGLINE}", "Your project tagline");
-        if let Err(e) = std::fs::write(&project_path, content) {
+        if let Err(e) = std::fs::write(&project_path, &project_yaml) {
             result.error = Some(format!("Failed to write project.yaml: {}", e));
             return result;
         }
@@ -125,16 +134,44 @@ pub fn run_init(dir: &Path, name: &str, type_str: &str, force: bool) -> InitResu
         result.files_kept.push("project.yaml".to_string());
     }
 
+    // Migration projects get the functional-equivalence protocol
+    let migrations_path = dir.join(MIGRATIONS_FILE);
+    if is_builtin_active(MIGRATIONS_FILE, &ProjectFacts::discover(dir)) {
+        let migrations_existed = migrations_path.exists();
+        if !migrations_existed || force {
+            if let Err(e) = std::fs::write(&migrations_path, migrations_template()) {
+                result.error = Some(format!("Failed to write migrations.yaml: {}", e));
+                return result;
+            }
+            if migrations_existed {
+                result.files_updated.push("migrations.yaml".to_string());
+            } else {
+                result.files_created.push("migrations.yaml".to_string());
+            }
+        } else {
+            result.files_kept.push("migrations.yaml".to_string());
+        }
+    }
+
     // v9.0.0: Create protocol JSON files
-    for (filename, generator) in PROTOCOL_FILES {
+    // Including custom protocols that apply to the project
+    for (filename, content) in protocol_files(dir) {
+        let filename = filename.as_str();
         let file_path = asimov_dir.join(filename);
         let existed = file_path.exists();
         if !existed || force {
-            let content = generator();
             if let Err(e) = std::fs::write(&file_path, &content) {
                 result.error = Some(format!("Failed to write {}: {}", filename, e));
                 return result;
             }
+            // Record the default so refresh can merge local edits later,
+            // and its hash so edits outside asimov show up as tampered
+            if let Err(e) = record_protocol_base(dir, filename, &content)
+                .and_then(|_| record_protocol_hashes(dir, &[(filename, &content)]))
+            {
+                result.error = Some(format!("Failed to record base of {}: {}", filename, e));
+                return result;
+            }
             if existed {
                 result.files_updated.push(filename.to_string());
             } else {
@@ -165,45 +202,43 @@ pub fn run_init(dir: &Path, name: &str, type_str: &str, force: bool) -> InitResu
     // Users who want Claude-specific hooks can create them manually
     // asimov warmup outputs all context directly - no hooks needed
 
-    // Install git pre-commit hook if in git repo
-    let git_hooks_dir = dir.join(".git").join("hooks");
-    if git_hooks_dir.exists() {
-        let precommit_path = git_hooks_dir.join("pre-commit");
-        if !precommit_path.exists() || force {
-            if let Err(e) = std::fs::write(&precommit_path, git_precommit_hook()) {
-                // Non-fatal
-                let _ = e;
-            } else {
-                #[cfg(unix)]
-                {
-                    use std::os::unix::fs::PermissionsExt;
-                    let _ = std::fs::set_permissions(
-                        &precommit_path,
-                        std::fs::Permissions::from_mode(0o755),
-                    );
-                }
-                result.hooks_installed.push("git pre-commit".to_string());
+    // Install the template's hook shims, chaining any existing hook
+    if dir.join(".git").exists() {
+        for hook in manifest.hooks {
+            if matches!(
+                install_hook(dir, hook),
+                Ok(HookAction::Installed
+                    | HookAction::Updated
+                    | HookAction::Chained { .. }
+                    | HookAction::AddedToManager { .. })
+            ) {
+                result.hooks_installed.push(format!("git {}", hook));
             }
         }
     }
 
     // v9.7.0: Setup dev dependencies for coding standards (ADR-044)
-    setup_dev_dependencies(dir, project_type, &mut result);
+    setup_dev_dependencies(dir, manifest.dev_setup, &mut result);
 
     result.success = true;
     result
 }
 
+/// Language declared by a template's `identity.type`
+fn declared_language(template: &str) -> Option<ProjectType> {
+    let yaml: serde_yaml_ng::Value = serde_yaml_ng::from_str(template).ok()?;
+    yaml.get("identity")?.get("type")?.as_str()?.parse().ok()
+}
+
 /// Setup dev dependencies for coding standards tools (v9.7.0 ADR-044)
-fn setup_dev_dependencies(dir: &Path, project_type: ProjectType, result: &mut InitResult) {
-    match project_type {
-        ProjectType::Rust => setup_rust_deps(dir, result),
-        ProjectType::Python => setup_python_deps(dir, result),
-        ProjectType::Node => setup_node_deps(dir, result),
-        ProjectType::Go => setup_go_deps(result),
-        ProjectType::Flutter => setup_flutter_deps(result),
-        ProjectType::Docs | ProjectType::Arch | ProjectType::Generic => setup_docs_deps(result),
-        ProjectType::Migration => {} // No deps needed
+fn setup_dev_dependencies(dir: &Path, dev_setup: DevSetup, result: &mut InitResult) {
+    match dev_setup {
+        DevSetup::CargoHusky => setup_rust_deps(dir, result),
+        DevSetup::Ruff => setup_python_deps(dir, result),
+        DevSetup::NodeLinters => setup_node_deps(dir, result),
+        DevSetup::Instructions(instructions) => result
+            .install_instructions
+            .extend(instructions.iter().map(|i| i.to_string())),
     }
 }
 
@@ -335,30 +370,6 @@ fn setup_node_deps(dir: &Path, result: &mut InitResult) {
     result.install_instructions.push("npm install".to_string());
 }
 
-/// Print install instructions for Go (golangci-lint is GPL)
-fn setup_go_deps(result: &mut InitResult) {
-    result
-        .install_instructions
-        .push("go install github.com/golangci/golangci-lint/cmd/golangci-lint@latest".to_string());
-    result
-        .install_instructions
-        .push("Note: golangci-lint is GPL-3.0 licensed (tool use is fine)".to_string());
-}
-
-/// Print install instructions for Flutter
-fn setup_flutter_deps(result: &mut InitResult) {
-    result
-        .install_instructions
-        .push("dart pub add --dev test".to_string());
-}
-
-/// Print install instructions for docs projects
-fn setup_docs_deps(result: &mut InitResult) {
-    result
-        .install_instructions
-        .push("npm install -g markdownlint-cli2".to_string());
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -371,11 +382,49 @@ mod tests {
         assert!(result.success);
         assert!(result.files_created.contains(&"roadmap.yaml".to_string()));
         assert!(result.files_created.contains(&"project.yaml".to_string()));
+
+        // Created at the current schema version: refresh has nothing to migrate
+        let project = std::fs::read_to_string(temp.path().join(".asimov/project.yaml")).unwrap();
+        assert!(crate::schemas::plan_migration("project", &project)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_run_init_migration_writes_protocol() {
+        let temp = TempDir::new().unwrap();
+        let result = run_init(temp.path(), "Port", "migration", false);
+        assert!(result.success, "{:?}", result.error);
+        assert!(result
+            .files_created
+            .contains(&"migrations.yaml".to_string()));
+        assert_eq!(
+            std::fs::read_to_string(temp.path().join(MIGRATIONS_FILE)).unwrap(),
+            migrations_template()
+        );
+
+        let rust = TempDir::new().unwrap();
+        assert!(run_init(rust.path(), "Tool", "rust", false).success);
+        assert!(!rust.path().join(MIGRATIONS_FILE).exists());
     }
 
     #[test]
     fn test_run_init_all_types() {
-        for t in &["rust", "python", "node", "go", "flutter", "docs", "generic"] {
+        for t in &[
+            "rust",
+            "python",
+            "node",
+            "go",
+            "flutter",
+            "docs",
+            "generic",
+            "java",
+            "csharp",
+            "ruby",
+            "php",
+            "cpp",
+            "migration",
+        ] {
             let temp = TempDir::new().unwrap();
             let result = run_init(temp.path(), "Test", t, false);
             assert!(result.success, "Failed for type: {}", t);
@@ -548,4 +597,28 @@ mod tests {
         assert!(result.success);
         assert!(matches!(result.project_type, Some(ProjectType::Generic)));
     }
+
+    #[test]
+    fn test_run_init_uses_template_manifest() {
+        let temp = TempDir::new().unwrap();
+        let result = run_init(temp.path(), "Api", "api-spring", false);
+        assert!(result.success);
+        assert_eq!(result.project_type, Some(ProjectType::Java));
+        assert!(result
+            .install_instructions
+            .iter()
+            .any(|i| i.contains("wrapper")));
+
+        let temp = TempDir::new().unwrap();
+        std::process::Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(temp.path())
+            .output()
+            .unwrap();
+        let result = run_init(temp.path(), "Mono", "mono-turbo", false);
+        assert_eq!(
+            result.hooks_installed,
+            vec!["git pre-commit", "git pre-push"]
+        );
+    }
 }
@@ -7,6 +7,9 @@ pub struct AiProfile {
     pub binary: &'static str,
     pub env_vars: &'static [&'static str],
     pub auto_mode_args: &'static [&'static str],
+    /// Provider and model it launches, as keyed in the model cutoff table
+    pub provider: &'static str,
+    pub model: &'static str,
 }
 
 impl AiProfile {
@@ -38,18 +41,24 @@ pub const AI_PROFILES: &[AiProfile] = &[
         binary: "claude",
         env_vars: &["CLAUDECODE", "CLAUDE_CODE_ENTRYPOINT"],
         auto_mode_args: &["--dangerously-skip-permissions", "--model", "opus"],
+        provider: "claude",
+        model: "opus",
     },
     AiProfile {
         name: "Gemini CLI",
         binary: "gemini",
         env_vars: &["GEMINI_CLI"],
         auto_mode_args: &["--yolo"],
+        provider: "google",
+        model: "gemini-2.5-pro",
     },
     AiProfile {
         name: "Codex CLI",
         binary: "codex",
         env_vars: &["CODEX_CLI"],
         auto_mode_args: &["--full-auto"],
+        provider: "openai",
+        model: "gpt-5",
     },
 ];
 
@@ -1,10 +1,12 @@
 //! Lint-docs command implementation
 
+use crate::stale_claims::DEFAULT_MAX_CLAIM_AGE_MONTHS;
 use crate::{
-    check_markdown_file, check_semantic, find_markdown_files, fix_markdown_file,
-    load_deprecated_patterns, SemanticConfig, Severity,
+    changed_files_in_scope, check_markdown_content, check_markdown_file, check_semantic,
+    find_markdown_files, fix_markdown_file, load_deprecated_patterns, ChangedFile, ScanScope,
+    SemanticConfig, Severity,
 };
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone)]
 pub struct LintFileResult {
@@ -30,9 +32,34 @@ pub struct LintDocsResult {
     pub lint_results: Vec<LintFileResult>,
     pub semantic_issues: Vec<SemanticIssue>,
     pub semantic_files_checked: usize,
+    /// Whole tree, or added lines only
+    pub scope: ScanScope,
+    pub error: Option<String>,
 }
 
 pub fn run_lint_docs(dir: &Path, fix: bool, semantic: bool) -> LintDocsResult {
+    run_lint_docs_with_scope(
+        dir,
+        fix,
+        semantic,
+        &ScanScope::All,
+        DEFAULT_MAX_CLAIM_AGE_MONTHS,
+    )
+}
+
+/// Lint docs, limited to staged or changed markdown with `--staged` / `--diff`
+///
+/// Scoped runs lint the staged (or working tree) content of changed files and
+/// keep only errors on added lines, so untouched legacy docs stay quiet.
+/// Semantic checks flag claims more than `max_claim_age_months`
+/// older than their file's last change (see `stale_claims`).
+pub fn run_lint_docs_with_scope(
+    dir: &Path,
+    fix: bool,
+    semantic: bool,
+    scope: &ScanScope,
+    max_claim_age_months: u32,
+) -> LintDocsResult {
     let mut result = LintDocsResult {
         success: true,
         files_checked: 0,
@@ -41,12 +68,88 @@ pub fn run_lint_docs(dir: &Path, fix: bool, semantic: bool) -> LintDocsResult {
         lint_results: Vec::new(),
         semantic_issues: Vec::new(),
         semantic_files_checked: 0,
+        scope: scope.clone(),
+        error: None,
+    };
+
+    // A single file is scoped from its directory
+    let (root, only) = if dir.is_file() {
+        (
+            dir.parent().unwrap_or(Path::new(".")),
+            dir.file_name().map(|n| n.to_string_lossy().to_string()),
+        )
+    } else {
+        (dir, None)
+    };
+
+    let changes = if scope.is_all() {
+        None
+    } else {
+        match changed_files_in_scope(root, scope) {
+            Ok(changes) => Some(
+                changes
+                    .into_iter()
+                    .filter(|c| is_markdown(Path::new(&c.path)))
+                    .filter(|c| only.as_ref().is_none_or(|name| &c.path == name))
+                    .collect::<Vec<ChangedFile>>(),
+            ),
+            Err(e) => {
+                result.success = false;
+                result.error = Some(e);
+                return result;
+            }
+        }
     };
 
-    let files = find_markdown_files(dir);
+    match changes {
+        None => lint_files(&find_markdown_files(dir), fix, &mut result),
+        Some(ref changes) => lint_changes(root, changes, fix, &mut result),
+    }
+
+    if semantic {
+        let patterns = load_deprecated_patterns(root);
+        let config = SemanticConfig {
+            deprecated_patterns: patterns,
+            expected_version: None,
+            check_help: false,
+            max_claim_age_months: Some(max_claim_age_months),
+        };
+
+        let semantic_result = check_semantic(dir, &config);
+        result.semantic_files_checked = semantic_result.files_checked;
+
+        for issue in semantic_result.issues {
+            if let Some(ref changes) = changes {
+                let relative = issue.file.strip_prefix(root).unwrap_or(&issue.file);
+                let in_scope = changes.iter().any(|c| {
+                    Path::new(&c.path) == relative
+                        && issue.line.is_none_or(|line| c.is_added_line(line))
+                });
+                if !in_scope {
+                    continue;
+                }
+            }
+
+            result.semantic_issues.push(SemanticIssue {
+                file: issue.file.display().to_string(),
+                line: issue.line.unwrap_or(0),
+                severity: format!("{:?}", issue.severity),
+                message: issue.message,
+            });
+
+            if issue.severity == Severity::Error {
+                result.success = false;
+            }
+        }
+    }
+
+    result
+}
+
+fn lint_files(files: &[PathBuf], fix: bool, result: &mut LintDocsResult) {
     result.files_checked = files.len();
 
-    for file in &files {
+    for file in files {
         let mut file_result = LintFileResult {
             file: file.display().to_string(),
             errors: Vec::new(),
@@ -81,33 +184,43 @@ pub fn run_lint_docs(dir: &Path, fix: bool, semantic: bool) -> LintDocsResult {
 
         result.lint_results.push(file_result);
     }
+}
 
-    if semantic {
-        let patterns = load_deprecated_patterns(dir);
-        let config = SemanticConfig {
-            deprecated_patterns: patterns,
-            expected_version: None,
-            check_help: false,
-        };
+/// Lint changed files, reporting errors on added lines only
+///
+/// `--fix` rewrites the working tree copy; staged changes need re-adding.
+fn lint_changes(root: &Path, changes: &[ChangedFile], fix: bool, result: &mut LintDocsResult) {
+    result.files_checked = changes.len();
 
-        let semantic_result = check_semantic(dir, &config);
-        result.semantic_files_checked = semantic_result.files_checked;
+    for change in changes {
+        let path = root.join(&change.path);
+        let errors: Vec<String> = check_markdown_content(&change.content)
+            .into_iter()
+            .filter(|err| change.is_added_line(err.line))
+            .map(|err| format!("Line {}: {}", err.line, err.message))
+            .collect();
 
-        for issue in semantic_result.issues {
-            result.semantic_issues.push(SemanticIssue {
-                file: issue.file.display().to_string(),
-                line: issue.line.unwrap_or(0),
-                severity: format!("{:?}", issue.severity),
-                message: issue.message,
-            });
-
-            if issue.severity == Severity::Error {
+        let mut file_result = LintFileResult {
+            file: path.display().to_string(),
+            fixed: false,
+            errors,
+        };
+        if !file_result.errors.is_empty() {
+            result.files_with_errors += 1;
+            if fix && fix_markdown_file(&path).is_ok() {
+                file_result.fixed = true;
+                result.files_fixed += 1;
+            } else {
                 result.success = false;
             }
         }
+        result.lint_results.push(file_result);
     }
+}
 
-    result
+fn is_markdown(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext == "md" || ext == "markdown")
 }
 
 #[cfg(test)]
@@ -174,6 +287,8 @@ mod tests {
             lint_results: vec![],
             semantic_issues: vec![],
             semantic_files_checked: 3,
+            scope: ScanScope::All,
+            error: None,
         };
         assert_eq!(r.files_checked, 5);
     }
@@ -243,4 +358,60 @@ mod tests {
         // No files to check
         assert!(result.success);
     }
+
+    #[test]
+    fn test_run_lint_docs_staged_added_lines_only() {
+        let temp = TempDir::new().unwrap();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+                .args(args)
+                .current_dir(temp.path())
+                .output()
+                .unwrap()
+        };
+        let legacy = "# Legacy\n\n```rust\ncode\n```rust\n";
+        git(&["init", "-q"]);
+        std::fs::write(temp.path().join("legacy.md"), legacy).unwrap();
+        std::fs::write(temp.path().join("other.md"), legacy).unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "legacy"]);
+
+        std::fs::write(
+            temp.path().join("legacy.md"),
+            format!("{}\n~~~\nnew\n~~~yaml\n", legacy),
+        )
+        .unwrap();
+        git(&["add", "legacy.md"]);
+
+        let result = run_lint_docs_with_scope(
+            temp.path(),
+            false,
+            false,
+            &ScanScope::Staged,
+            DEFAULT_MAX_CLAIM_AGE_MONTHS,
+        );
+        assert!(!result.success);
+        assert_eq!(result.files_checked, 1);
+        assert_eq!(result.lint_results[0].errors.len(), 1);
+        assert!(result.lint_results[0].errors[0].starts_with("Line 9:"));
+
+        // Whole-tree mode still reports the legacy errors
+        let all = run_lint_docs(temp.path(), false, false);
+        assert_eq!(all.files_with_errors, 2);
+    }
+
+    #[test]
+    fn test_run_lint_docs_scoped_outside_git() {
+        let temp = TempDir::new().unwrap();
+        let result = run_lint_docs_with_scope(
+            temp.path(),
+            false,
+            false,
+            &ScanScope::Diff("main".into()),
+            DEFAULT_MAX_CLAIM_AGE_MONTHS,
+        );
+        assert!(!result.success);
+        assert!(result.error.is_some());
+    }
 }
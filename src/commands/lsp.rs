@@ -0,0 +1,13 @@
+//! Language server command implementation
+//!
+//! Speaks LSP over stdin/stdout until the editor sends `exit`.
+
+use crate::lsp::serve;
+use std::io::{self, BufReader};
+
+/// Run the language server on the process's stdio
+pub fn run_lsp() -> io::Result<()> {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    serve(BufReader::new(stdin.lock()), stdout.lock())
+}
@@ -2,26 +2,42 @@
 //!
 //! All command logic is here for testability. main.rs handles only CLI parsing and output.
 
+mod check;
 mod doctor;
+mod freshness;
+mod hook;
+mod hook_manager;
 mod init;
 mod launch;
 mod lint_docs;
+mod lsp;
+mod protocols;
 mod refresh;
 mod replay;
 mod role;
+mod schema;
 mod stats;
+mod template;
 mod update;
 mod validate;
 mod warmup;
 
+pub use check::*;
 pub use doctor::*;
+pub use freshness::*;
+pub use hook::*;
+pub use hook_manager::*;
 pub use init::*;
 pub use launch::*;
 pub use lint_docs::*;
+pub use lsp::*;
+pub use protocols::*;
 pub use refresh::*;
 pub use replay::*;
 pub use role::*;
+pub use schema::*;
 pub use stats::*;
+pub use template::*;
 pub use update::*;
 pub use validate::*;
 pub use warmup::*;
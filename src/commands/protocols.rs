@@ -0,0 +1,95 @@
+//! Protocol layer inspection
+//!
+//! `asimov protocols explain <name>` shows the layers a protocol is merged
+//! from and which layer each field came from. See `protocols::layers`.
+
+use crate::protocols::{
+    check_protocol_override, embedded_protocol, merge_layers, project_protocol_path,
+    protocol_layers, ProtocolLayer, PROTOCOL_NAMES,
+};
+
+/// A merged field and the layers that set it
+#[derive(Debug, Clone)]
+pub struct ProtocolField {
+    /// Dotted path (`rfc2119.MUST`)
+    pub field: String,
+    /// Value as compact JSON
+    pub value: String,
+    /// Labels of the layers that set it, lowest first (several when appended)
+    pub layers: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProtocolsExplainResult {
+    pub success: bool,
+    pub layers: Vec<ProtocolLayer>,
+    pub fields: Vec<ProtocolField>,
+    /// Why the merged protocol does not parse (the embedded one is used)
+    pub fallback: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Explain where each field of a protocol comes from
+pub fn run_protocols_explain(name: &str) -> ProtocolsExplainResult {
+    let mut result = ProtocolsExplainResult {
+        success: false,
+        layers: Vec::new(),
+        fields: Vec::new(),
+        fallback: None,
+        error: None,
+    };
+    let Some(embedded) = embedded_protocol(name) else {
+        result.error = Some(format!(
+            "Unknown protocol '{}' (known: {})",
+            name,
+            PROTOCOL_NAMES.join(", ")
+        ));
+        return result;
+    };
+
+    let layers = protocol_layers(name, embedded, Some(&project_protocol_path(name)));
+    let merged = merge_layers(&layers);
+    result.fields = merged
+        .fields()
+        .into_iter()
+        .map(|(field, origin)| ProtocolField {
+            value: merged
+                .get(&field)
+                .map(|value| value.to_string())
+                .unwrap_or_default(),
+            layers: origin.iter().map(|&i| layers[i].label()).collect(),
+            field: field.join("."),
+        })
+        .collect();
+    if let Some(Err(e)) = check_protocol_override(name, &merged.to_json()) {
+        result.fallback = Some(e);
+    }
+    result.layers = layers;
+    result.success = true;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explain_embedded_protocol() {
+        let result = run_protocols_explain("coding-standards");
+        assert!(result.success);
+        assert_eq!(result.layers[0].label(), "embedded");
+        let must = result
+            .fields
+            .iter()
+            .find(|f| f.field == "rfc2119.MUST")
+            .unwrap();
+        assert!(must.value.starts_with('"'));
+        assert!(!must.layers.is_empty());
+        assert!(result.fallback.is_none());
+
+        assert!(run_protocols_explain("nope")
+            .error
+            .unwrap()
+            .contains("Unknown protocol 'nope'"));
+    }
+}
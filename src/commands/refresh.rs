@@ -1,18 +1,22 @@
 //! Refresh command implementation
 //! v9.5.0: Enhanced with project migration assistant (ADR-042)
 //! v9.6.0: Always regenerate pre-commit hooks (ADR-043)
-
-use crate::templates::{
-    detect_project_type, precommit_hook_template, project_template, ProjectType,
+//! Versioned schema migrations for project data files
+
+use super::install_hook;
+use super::{plan_hook_install, HookWrite};
+use crate::commit_msg::commit_msg_policy;
+use crate::merge::unified_diff;
+use crate::schemas::{plan_migration, MigrationError};
+use crate::templates::{detect_project, project_template, ProjectDetection, ProjectType};
+use crate::validate_directory_with_regeneration;
+use crate::validator::{
+    apply_protocol_refresh, missing_data_files, plan_protocol_refresh, strict_protocol_errors,
+    ProtocolOutcome, ProtocolRefresh, CONFLICT_SUFFIX,
 };
-use crate::{validate_directory_with_regeneration, validator::regenerate_protocol_files};
 use std::io::{self, BufRead, Write};
 use std::path::Path;
 
-// Unix-specific imports for setting executable permissions
-#[cfg(unix)]
-use std::os::unix::fs::PermissionsExt;
-
 /// Options for refresh command
 #[derive(Debug, Clone, Default)]
 pub struct RefreshOptions {
@@ -20,6 +24,8 @@ pub struct RefreshOptions {
     pub yes: bool,
     /// Show what would change without writing
     pub dry_run: bool,
+    /// Fail on invalid protocol overrides instead of falling back
+    pub strict: bool,
 }
 
 /// User choice for upgrade prompts
@@ -38,16 +44,66 @@ pub struct RefreshResult {
     pub protocols_updated: Vec<String>, // v9.0.0: outdated protocols that were updated
     pub protocols_created: Vec<String>, // v9.0.0: missing protocols that were created
     pub protocols_ok: Vec<String>,      // v9.0.0: protocols that matched expected
+    // Three-way merge of locally edited protocols
+    pub protocols_merged: Vec<String>,
+    pub protocols_customized: Vec<String>,
+    pub protocols_conflicted: Vec<String>,
+    /// Planned protocol writes, with their diffs
+    pub protocol_refresh: Vec<ProtocolRefresh>,
     // v9.5.0: Migration assistant fields
     pub project_type_detected: Option<ProjectType>,
     pub project_type_was_missing: bool,
     pub coding_standards_upgraded: bool,
     // v9.6.0: Hook regeneration (ADR-043)
     pub hook_regenerated: bool,
+    // Schema version migrations (planned under dry run)
+    pub schema_migrations: Vec<SchemaMigration>,
+    // Workspace members and other components found in the repository
+    pub detection: ProjectDetection,
+    /// project.yaml already lists `components:`
+    pub components_declared: bool,
+    /// Every file written (or, under dry run, to be written)
+    pub changes: Vec<PlannedWrite>,
     pub dry_run: bool,
     pub error: Option<String>,
 }
 
+/// A file refresh writes, or would write under dry run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedWrite {
+    /// Path relative to the project root
+    pub file: String,
+    /// Content before the refresh (`None` for a new file)
+    pub old: Option<String>,
+    pub new: String,
+}
+
+impl PlannedWrite {
+    /// Unified diff of the write
+    pub fn diff(&self) -> String {
+        unified_diff(
+            &self.file,
+            self.old.as_deref().unwrap_or_default(),
+            &self.new,
+        )
+    }
+}
+
+/// A data file migrated (or to be migrated) to the current schema version
+#[derive(Debug, Clone)]
+pub struct SchemaMigration {
+    pub file: String,
+    pub from: u32,
+    pub to: u32,
+    pub steps: Vec<String>,
+    /// Unified diff of the change
+    pub diff: String,
+}
+
+/// Data files with versioned schemas, and their schema type
+const VERSIONED_DATA_FILES: [(&str, &str); 2] =
+    [("project.yaml", "project"), ("roadmap.yaml", "roadmap")];
+
 /// Run refresh with default options (backwards compatible)
 pub fn run_refresh(dir: &Path) -> RefreshResult {
     run_refresh_with_options(dir, RefreshOptions::default())
@@ -63,10 +119,18 @@ pub fn run_refresh_with_options(dir: &Path, options: RefreshOptions) -> RefreshR
         protocols_updated: Vec::new(),
         protocols_created: Vec::new(),
         protocols_ok: Vec::new(),
+        protocols_merged: Vec::new(),
+        protocols_customized: Vec::new(),
+        protocols_conflicted: Vec::new(),
+        protocol_refresh: Vec::new(),
         project_type_detected: None,
         project_type_was_missing: false,
         coding_standards_upgraded: false,
         hook_regenerated: false,
+        schema_migrations: Vec::new(),
+        detection: ProjectDetection::default(),
+        components_declared: false,
+        changes: Vec::new(),
         dry_run: options.dry_run,
         error: None,
     };
@@ -77,50 +141,128 @@ pub fn run_refresh_with_options(dir: &Path, options: RefreshOptions) -> RefreshR
     }
     result.is_asimov_project = true;
 
+    // Protocol files are generated from the overrides; --strict
+    // refuses to write them from a silently substituted default
+    if options.strict {
+        let errors = strict_protocol_errors(dir);
+        if !errors.is_empty() {
+            result.error = Some(format!(
+                "Invalid protocol overrides (--strict):\n  {}",
+                errors.join("\n  ")
+            ));
+            return result;
+        }
+    }
+
+    // Schema migrations run first - a file from a newer asimov aborts
+    // the refresh before anything is written
+    match migrate_data_files(dir, options.dry_run) {
+        Ok(migrations) => {
+            for m in &migrations {
+                stage(
+                    &mut result.changes,
+                    &m.file,
+                    m.old.clone(),
+                    m.migrated.clone(),
+                );
+            }
+            result.schema_migrations = migrations.into_iter().map(|m| m.migration).collect();
+        }
+        Err(e) => {
+            result.error = Some(e);
+            return result;
+        }
+    }
+
     // v9.0.0: Check and regenerate protocol JSON files
+    // Merge local edits with the new defaults instead of overwriting
+    let plan = match plan_protocol_refresh(dir) {
+        Ok(plan) => plan,
+        Err(e) => {
+            result.error = Some(format!("Protocol regeneration failed: {}", e));
+            return result;
+        }
+    };
+    for item in &plan {
+        let file = format!(".asimov/{}", item.filename);
+        if let Some(ref content) = item.content {
+            stage(
+                &mut result.changes,
+                &file,
+                read(&dir.join(&file)),
+                content.clone(),
+            );
+        }
+        if let Some(ref conflict) = item.conflict {
+            let file = format!("{}{}", file, CONFLICT_SUFFIX);
+            stage(
+                &mut result.changes,
+                &file,
+                read(&dir.join(&file)),
+                conflict.clone(),
+            );
+        }
+    }
     if !options.dry_run {
-        match regenerate_protocol_files(dir) {
-            Ok(protocol_results) => {
-                for (filename, was_different) in protocol_results {
-                    let file_path = dir.join(".asimov").join(&filename);
-                    let existed_before = file_path.exists() || was_different;
-
-                    if was_different {
-                        if existed_before {
-                            result.protocols_updated.push(filename);
-                        } else {
-                            result.protocols_created.push(filename);
-                        }
-                    } else {
-                        result.protocols_ok.push(filename);
-                    }
-                }
-            }
-            Err(e) => {
-                result.error = Some(format!("Protocol regeneration failed: {}", e));
-                return result;
-            }
+        if let Err(e) = apply_protocol_refresh(dir, &plan) {
+            result.error = Some(format!("Protocol regeneration failed: {}", e));
+            return result;
+        }
+    }
+    for item in &plan {
+        let filename = item.filename.clone();
+        match item.outcome {
+            ProtocolOutcome::Unchanged => result.protocols_ok.push(filename),
+            ProtocolOutcome::Created => result.protocols_created.push(filename),
+            ProtocolOutcome::Updated => result.protocols_updated.push(filename),
+            ProtocolOutcome::Merged => result.protocols_merged.push(filename),
+            ProtocolOutcome::Customized => result.protocols_customized.push(filename),
+            ProtocolOutcome::Conflict => result.protocols_conflicted.push(filename),
         }
     }
+    result.protocol_refresh = plan;
+
+    // Component-aware detection for monorepos and polyglot repositories
+    result.detection = detect_project(dir);
 
     // v9.5.0: Check project.yaml for migration opportunities
+    // Dry runs plan against the schema-migrated content and never prompt
     let project_yaml_path = dir.join(".asimov").join("project.yaml");
-    if project_yaml_path.exists() {
-        match check_project_migration(dir, &project_yaml_path, &options) {
+    let project_file = ".asimov/project.yaml";
+    let project_yaml = staged(&result.changes, project_file).or_else(|| read(&project_yaml_path));
+    let check_options = RefreshOptions {
+        yes: options.yes || options.dry_run,
+        ..options.clone()
+    };
+    result.components_declared = project_yaml
+        .as_deref()
+        .and_then(|content| serde_yaml_ng::from_str::<serde_yaml_ng::Value>(content).ok())
+        .is_some_and(|yaml| yaml.get("components").is_some());
+    if let Some(ref content) = project_yaml {
+        match check_project_migration(dir, content, &check_options) {
             Ok(migration) => {
                 result.project_type_detected = Some(migration.project_type);
                 result.project_type_was_missing = migration.type_was_missing;
                 result.coding_standards_upgraded = migration.coding_standards_upgraded;
 
-                // Apply migrations if not dry run
-                if !options.dry_run
-                    && (migration.type_was_missing || migration.coding_standards_upgraded)
-                {
-                    if let Err(e) = apply_project_migration(dir, &project_yaml_path, &migration) {
-                        result.error = Some(format!("Migration failed: {}", e));
-                        return result;
+                if let Some(ref new_content) = migration.new_content {
+                    stage(
+                        &mut result.changes,
+                        project_file,
+                        read(&project_yaml_path),
+                        new_content.clone(),
+                    );
+                    // Apply migrations if not dry run
+                    if !options.dry_run {
+                        if let Err(e) = std::fs::write(&project_yaml_path, new_content) {
+                            result.error = Some(format!(
+                                "Migration failed: Failed to write project.yaml: {}",
+                                e
+                            ));
+                            return result;
+                        }
+                        result.files_regenerated.push("project.yaml".to_string());
                     }
-                    result.files_regenerated.push("project.yaml".to_string());
                 }
             }
             Err(e) => {
@@ -130,19 +272,20 @@ pub fn run_refresh_with_options(dir: &Path, options: RefreshOptions) -> RefreshR
         }
     } else {
         // No project.yaml - detect type and offer to create
-        let detected_type = detect_project_type(dir);
+        let detected_type = result.detection.primary_type();
         result.project_type_detected = Some(detected_type);
         result.project_type_was_missing = true;
 
+        let project_type = if options.yes || options.dry_run {
+            detected_type
+        } else {
+            prompt_project_type(detected_type)
+        };
+
+        // Generate project.yaml
+        let template = project_template("my-project", "Project description", project_type);
+        stage(&mut result.changes, project_file, None, template.clone());
         if !options.dry_run {
-            let project_type = if options.yes {
-                detected_type
-            } else {
-                prompt_project_type(detected_type)
-            };
-
-            // Generate project.yaml
-            let template = project_template("my-project", "Project description", project_type);
             if let Err(e) = std::fs::write(&project_yaml_path, template) {
                 result.error = Some(format!("Failed to create project.yaml: {}", e));
                 return result;
@@ -153,18 +296,35 @@ pub fn run_refresh_with_options(dir: &Path, options: RefreshOptions) -> RefreshR
     }
 
     // v9.6.0: Always regenerate pre-commit hook (ADR-043 - No SPOF)
-    if !options.dry_run {
-        if let Some(project_type) = result.project_type_detected {
-            if let Err(e) = regenerate_precommit_hook(dir, project_type) {
-                // Non-fatal: git might not be initialized
-                eprintln!("Note: Could not regenerate pre-commit hook: {}", e);
-            } else {
-                result.hook_regenerated = true;
-            }
+    if result.project_type_detected.is_some() && dir.join(".git").is_dir() {
+        for write in planned_hook_writes(dir) {
+            let file = write
+                .path
+                .strip_prefix(dir)
+                .unwrap_or(&write.path)
+                .display()
+                .to_string();
+            stage(&mut result.changes, &file, write.old, write.new);
+        }
+    }
+    if !options.dry_run && result.project_type_detected.is_some() {
+        if let Err(e) = regenerate_precommit_hook(dir) {
+            // Non-fatal: git might not be initialized
+            eprintln!("Note: Could not regenerate pre-commit hook: {}", e);
+        } else {
+            result.hook_regenerated = true;
         }
     }
 
     // Also check roadmap.yaml etc.
+    for (filename, content) in missing_data_files(dir) {
+        stage(
+            &mut result.changes,
+            &format!(".asimov/{}", filename),
+            None,
+            content,
+        );
+    }
     if !options.dry_run {
         match validate_directory_with_regeneration(dir, true) {
             Ok((_, regen_info)) => {
@@ -188,6 +348,94 @@ pub fn run_refresh_with_options(dir: &Path, options: RefreshOptions) -> RefreshR
     }
 
     result
+        .changes
+        .retain(|change| change.old.as_deref() != Some(change.new.as_str()));
+    result
+}
+
+fn read(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path).ok()
+}
+
+/// Record a write; a file written twice keeps its original content as `old`
+fn stage(changes: &mut Vec<PlannedWrite>, file: &str, old: Option<String>, new: String) {
+    match changes.iter_mut().find(|change| change.file == file) {
+        Some(change) => change.new = new,
+        None => changes.push(PlannedWrite {
+            file: file.to_string(),
+            old,
+            new,
+        }),
+    }
+}
+
+/// Content a file will have once the staged writes are applied
+fn staged(changes: &[PlannedWrite], file: &str) -> Option<String> {
+    changes
+        .iter()
+        .find(|change| change.file == file)
+        .map(|change| change.new.clone())
+}
+
+/// Hook files `regenerate_precommit_hook` would write
+fn planned_hook_writes(dir: &Path) -> Vec<HookWrite> {
+    let mut hooks = vec!["pre-commit"];
+    if let Ok(Some(_)) = commit_msg_policy(dir) {
+        hooks.push("commit-msg");
+    }
+    hooks
+        .into_iter()
+        .filter_map(|hook| plan_hook_install(dir, hook).ok().flatten())
+        .collect()
+}
+
+/// A schema migration with the file contents before and after
+struct StagedMigration {
+    file: String,
+    old: Option<String>,
+    migrated: String,
+    migration: SchemaMigration,
+}
+
+/// Migrate versioned data files to the current schema version
+///
+/// Files that are not valid YAML are left alone; validation reports them.
+fn migrate_data_files(dir: &Path, dry_run: bool) -> Result<Vec<StagedMigration>, String> {
+    let mut migrations = Vec::new();
+
+    for (filename, schema_type) in VERSIONED_DATA_FILES {
+        let path = dir.join(".asimov").join(filename);
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+
+        let plan = match plan_migration(schema_type, &content) {
+            Ok(Some(plan)) => plan,
+            Ok(None) | Err(MigrationError::InvalidYaml(_)) => continue,
+            Err(e) => return Err(format!("{}: {}", filename, e)),
+        };
+
+        if !dry_run {
+            std::fs::write(&path, &plan.migrated)
+                .map_err(|e| format!("Failed to write {}: {}", filename, e))?;
+        }
+
+        let file = format!(".asimov/{}", filename);
+        migrations.push(StagedMigration {
+            migration: SchemaMigration {
+                file: filename.to_string(),
+                from: plan.from,
+                to: plan.to,
+                steps: plan.steps.iter().map(|s| s.to_string()).collect(),
+                diff: unified_diff(&file, &content, &plan.migrated),
+            },
+            file,
+            old: Some(content),
+            migrated: plan.migrated,
+        });
+    }
+
+    Ok(migrations)
 }
 
 /// Migration information for a project
@@ -202,13 +450,10 @@ struct ProjectMigration {
 /// Check if project.yaml needs migration
 fn check_project_migration(
     dir: &Path,
-    project_yaml_path: &Path,
+    content: &str,
     options: &RefreshOptions,
 ) -> Result<ProjectMigration, String> {
-    let content = std::fs::read_to_string(project_yaml_path)
-        .map_err(|e| format!("Failed to read project.yaml: {}", e))?;
-
-    let yaml: serde_yaml_ng::Value = serde_yaml_ng::from_str(&content)
+    let yaml: serde_yaml_ng::Value = serde_yaml_ng::from_str(content)
         .map_err(|e| format!("Invalid YAML in project.yaml: {}", e))?;
 
     // Check identity.type
@@ -220,9 +465,9 @@ fn check_project_migration(
     let type_was_missing = type_str.is_none();
     let project_type = if let Some(t) = type_str {
         t.parse::<ProjectType>()
-            .unwrap_or_else(|_| detect_project_type(dir))
+            .unwrap_or_else(|_| detect_project(dir).primary_type())
     } else {
-        let detected = detect_project_type(dir);
+        let detected = detect_project(dir).primary_type();
         if options.yes {
             detected
         } else {
@@ -246,16 +491,7 @@ fn check_project_migration(
         .is_some();
 
     // Need upgrade if: has coding_standards but missing new sections (for programming types)
-    let is_programming_type = matches!(
-        project_type,
-        ProjectType::Rust
-            | ProjectType::Python
-            | ProjectType::Node
-            | ProjectType::Go
-            | ProjectType::Flutter
-    );
-
-    let needs_coding_upgrade = is_programming_type
+    let needs_coding_upgrade = project_type.is_code()
         && has_coding_standards
         && (!has_code_section || !has_documentation_section || !has_architecture_section);
 
@@ -296,74 +532,39 @@ fn check_project_migration(
     })
 }
 
-/// Apply migration to project.yaml
-fn apply_project_migration(
-    _dir: &Path,
-    project_yaml_path: &Path,
-    migration: &ProjectMigration,
-) -> Result<(), String> {
-    if let Some(ref new_content) = migration.new_content {
-        std::fs::write(project_yaml_path, new_content)
-            .map_err(|e| format!("Failed to write project.yaml: {}", e))?;
-    }
-    Ok(())
-}
-
 /// Regenerate pre-commit hook for direct coding standards enforcement (v9.6.0 ADR-043)
-fn regenerate_precommit_hook(dir: &Path, project_type: ProjectType) -> Result<(), String> {
-    let git_dir = dir.join(".git");
-    if !git_dir.is_dir() {
+/// Installed through `asimov hooks install`, chaining any existing hook,
+/// plus commit-msg when project.yaml declares a `commit_msg:` policy
+fn regenerate_precommit_hook(dir: &Path) -> Result<(), String> {
+    if !dir.join(".git").is_dir() {
         return Err("Not a git repository".to_string());
     }
-
-    let hooks_dir = git_dir.join("hooks");
-    std::fs::create_dir_all(&hooks_dir)
-        .map_err(|e| format!("Failed to create hooks directory: {}", e))?;
-
-    let hook_path = hooks_dir.join("pre-commit");
-    let hook_content = precommit_hook_template(project_type);
-
-    std::fs::write(&hook_path, hook_content)
-        .map_err(|e| format!("Failed to write pre-commit hook: {}", e))?;
-
-    // Make executable (Unix only - Windows doesn't need this)
-    #[cfg(unix)]
-    {
-        let mut perms = std::fs::metadata(&hook_path)
-            .map_err(|e| format!("Failed to get hook metadata: {}", e))?
-            .permissions();
-        perms.set_mode(0o755);
-        std::fs::set_permissions(&hook_path, perms)
-            .map_err(|e| format!("Failed to set hook permissions: {}", e))?;
+    install_hook(dir, "pre-commit")?;
+    if let Ok(Some(_)) = commit_msg_policy(dir) {
+        install_hook(dir, "commit-msg")?;
     }
-
     Ok(())
 }
 
 /// Prompt user for project type selection
 fn prompt_project_type(detected: ProjectType) -> ProjectType {
-    let types = [
-        ("rust", ProjectType::Rust),
-        ("python", ProjectType::Python),
-        ("node", ProjectType::Node),
-        ("go", ProjectType::Go),
-        ("flutter", ProjectType::Flutter),
-        ("docs", ProjectType::Docs),
-        ("arch", ProjectType::Arch),
-        ("generic", ProjectType::Generic),
-    ];
+    // Migration is not selectable: it is a generic project with a migrations protocol
+    let types: Vec<ProjectType> = ProjectType::ALL
+        .into_iter()
+        .filter(|t| *t != ProjectType::Migration)
+        .collect();
 
     eprintln!();
     eprintln!("Project type not specified. Please select:");
-    for (i, (name, _)) in types.iter().enumerate() {
-        let marker = if types[i].1 == detected {
+    for (i, project_type) in types.iter().enumerate() {
+        let marker = if *project_type == detected {
             " (detected)"
         } else {
             ""
         };
-        eprintln!("  {}. {}{}", i + 1, name, marker);
+        eprintln!("  {}. {}{}", i + 1, project_type, marker);
     }
-    eprint!("Enter number [1-8] (default: {}): ", detected);
+    eprint!("Enter number [1-{}] (default: {}): ", types.len(), detected);
     io::stderr().flush().ok();
 
     let stdin = io::stdin();
@@ -376,7 +577,7 @@ fn prompt_project_type(detected: ProjectType) -> ProjectType {
         }
         if let Ok(n) = input.parse::<usize>() {
             if n >= 1 && n <= types.len() {
-                return types[n - 1].1;
+                return types[n - 1];
             }
         }
     }
@@ -444,10 +645,18 @@ mod tests {
             protocols_updated: vec!["outdated.json".to_string()],
             protocols_created: vec![],
             protocols_ok: vec!["ok.json".to_string()],
+            protocols_merged: vec![],
+            protocols_customized: vec![],
+            protocols_conflicted: vec![],
+            protocol_refresh: vec![],
             project_type_detected: Some(ProjectType::Rust),
             project_type_was_missing: false,
             coding_standards_upgraded: false,
             hook_regenerated: true,
+            schema_migrations: vec![],
+            detection: ProjectDetection::default(),
+            components_declared: false,
+            changes: vec![],
             dry_run: false,
             error: None,
         };
@@ -503,6 +712,7 @@ mod tests {
         let options = RefreshOptions {
             yes: true,
             dry_run: false,
+            strict: false,
         };
         let result = run_refresh_with_options(temp.path(), options);
         assert!(result.is_asimov_project);
@@ -523,6 +733,7 @@ mod tests {
         let options = RefreshOptions {
             yes: false,
             dry_run: true,
+            strict: false,
         };
         let result = run_refresh_with_options(temp.path(), options);
         assert!(result.is_asimov_project);
@@ -546,6 +757,7 @@ mod tests {
         let options = RefreshOptions {
             yes: true,
             dry_run: false,
+            strict: false,
         };
         let result = run_refresh_with_options(temp.path(), options);
         assert!(result.success);
@@ -554,6 +766,29 @@ mod tests {
         assert!(asimov_dir.join("project.yaml").exists());
     }
 
+    #[test]
+    fn test_run_refresh_detects_go_work_components() {
+        let temp = TempDir::new().unwrap();
+        let asimov_dir = temp.path().join(".asimov");
+        std::fs::create_dir_all(&asimov_dir).unwrap();
+        std::fs::write(temp.path().join("go.work"), "use (\n  ./api\n  ./web\n)\n").unwrap();
+        for module in ["api", "web"] {
+            std::fs::create_dir_all(temp.path().join(module)).unwrap();
+            std::fs::write(temp.path().join(module).join("go.mod"), "module x\n").unwrap();
+        }
+
+        let options = RefreshOptions {
+            yes: true,
+            dry_run: true,
+            strict: false,
+        };
+        let result = run_refresh_with_options(temp.path(), options);
+        // No root manifest: the members' language, not docs/generic
+        assert_eq!(result.project_type_detected, Some(ProjectType::Go));
+        assert_eq!(result.detection.components.len(), 2);
+        assert!(!result.components_declared);
+    }
+
     #[test]
     fn test_run_refresh_with_existing_project_yaml() {
         let temp = TempDir::new().unwrap();
@@ -573,6 +808,7 @@ mod tests {
         let options = RefreshOptions {
             yes: true,
             dry_run: false,
+            strict: false,
         };
         let result = run_refresh_with_options(temp.path(), options);
         assert!(result.success);
@@ -608,6 +844,7 @@ coding_standards:
         let options = RefreshOptions {
             yes: true,
             dry_run: false,
+            strict: false,
         };
         let result = run_refresh_with_options(temp.path(), options);
         assert!(result.success);
@@ -642,6 +879,7 @@ coding_standards:
         let options = RefreshOptions {
             yes: true,
             dry_run: false,
+            strict: false,
         };
         let result = run_refresh_with_options(temp.path(), options);
         assert!(result.success);
@@ -663,6 +901,7 @@ coding_standards:
         let options = RefreshOptions {
             yes: true,
             dry_run: false,
+            strict: false,
         };
         let result = run_refresh_with_options(temp.path(), options);
         assert!(result.success);
@@ -687,6 +926,7 @@ coding_standards:
         let options = RefreshOptions {
             yes: true,
             dry_run: false,
+            strict: false,
         };
         let result = run_refresh_with_options(temp.path(), options);
         assert!(result.success);
@@ -696,8 +936,8 @@ coding_standards:
         let hook_path = git_dir.join("hooks/pre-commit");
         assert!(hook_path.exists());
         let content = std::fs::read_to_string(hook_path).unwrap();
-        assert!(content.contains("cargo fmt"));
-        assert!(content.contains("asimov refresh || true")); // Soft-fail
+        assert!(content.contains("# asimov-managed hook: pre-commit"));
+        assert!(content.contains("exec asimov hook run pre-commit"));
     }
 
     #[test]
@@ -716,9 +956,130 @@ coding_standards:
         let options = RefreshOptions {
             yes: true,
             dry_run: false,
+            strict: false,
         };
         let result = run_refresh_with_options(temp.path(), options);
         assert!(result.success);
         assert!(!result.hook_regenerated); // No git, no hook
     }
+
+    #[test]
+    fn test_refresh_migrates_unversioned_roadmap() {
+        let temp = TempDir::new().unwrap();
+        let asimov_dir = temp.path().join(".asimov");
+        std::fs::create_dir_all(&asimov_dir).unwrap();
+        let roadmap = "current:\n  version: '1.0'\n  status: planned\n  summary: Test\n";
+        std::fs::write(asimov_dir.join("roadmap.yaml"), roadmap).unwrap();
+
+        let dry_run = run_refresh_with_options(
+            temp.path(),
+            RefreshOptions {
+                yes: true,
+                dry_run: true,
+                strict: false,
+            },
+        );
+        assert!(dry_run.success);
+        assert_eq!(dry_run.schema_migrations.len(), 1);
+        let migration = &dry_run.schema_migrations[0];
+        assert_eq!(migration.file, "roadmap.yaml");
+        assert_eq!(migration.from, 1);
+        assert!(migration.diff.contains(&format!(
+            "+schema_version: {}",
+            crate::schemas::ROADMAP_SCHEMA_VERSION
+        )));
+        assert_eq!(
+            std::fs::read_to_string(asimov_dir.join("roadmap.yaml")).unwrap(),
+            roadmap
+        );
+
+        let result = run_refresh_with_options(
+            temp.path(),
+            RefreshOptions {
+                yes: true,
+                dry_run: false,
+                strict: false,
+            },
+        );
+        assert!(result.success);
+        let migrated = std::fs::read_to_string(asimov_dir.join("roadmap.yaml")).unwrap();
+        assert!(migrated.starts_with("schema_version: "));
+    }
+
+    #[test]
+    fn test_refresh_refuses_schema_downgrade() {
+        let temp = TempDir::new().unwrap();
+        let asimov_dir = temp.path().join(".asimov");
+        std::fs::create_dir_all(&asimov_dir).unwrap();
+        let roadmap =
+            "schema_version: 999\ncurrent:\n  version: '1.0'\n  status: planned\n  summary: Test\n";
+        std::fs::write(asimov_dir.join("roadmap.yaml"), roadmap).unwrap();
+
+        let result = run_refresh_with_options(
+            temp.path(),
+            RefreshOptions {
+                yes: true,
+                dry_run: false,
+                strict: false,
+            },
+        );
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("newer than this asimov"));
+        assert_eq!(
+            std::fs::read_to_string(asimov_dir.join("roadmap.yaml")).unwrap(),
+            roadmap
+        );
+        assert!(result.protocols_created.is_empty());
+    }
+
+    #[test]
+    fn test_dry_run_plans_every_write_without_writing() {
+        let temp = TempDir::new().unwrap();
+        let asimov_dir = temp.path().join(".asimov");
+        std::fs::create_dir_all(&asimov_dir).unwrap();
+        std::fs::create_dir_all(temp.path().join(".git")).unwrap();
+        std::fs::write(temp.path().join("Cargo.toml"), "[package]").unwrap();
+        std::fs::write(asimov_dir.join("green.json"), "{}").unwrap();
+
+        let result = run_refresh_with_options(
+            temp.path(),
+            RefreshOptions {
+                yes: false,
+                dry_run: true,
+                strict: false,
+            },
+        );
+        assert!(result.success, "{:?}", result.error);
+        let files: Vec<&str> = result.changes.iter().map(|c| c.file.as_str()).collect();
+        for file in [
+            ".asimov/sprint.json",
+            ".asimov/project.yaml",
+            ".asimov/roadmap.yaml",
+            ".git/hooks/pre-commit",
+        ] {
+            assert!(files.contains(&file), "{} not planned: {:?}", file, files);
+        }
+        let green = result
+            .changes
+            .iter()
+            .find(|c| c.file == ".asimov/green.json.conflict")
+            .unwrap();
+        assert!(green.diff().contains("+<<<<<<< local .asimov/green.json"));
+
+        // Nothing was written
+        assert!(!asimov_dir.join("sprint.json").exists());
+        assert!(!asimov_dir.join("project.yaml").exists());
+        assert!(!temp.path().join(".git/hooks/pre-commit").exists());
+    }
+
+    #[test]
+    fn test_staged_writes_keep_original_content() {
+        let mut changes = Vec::new();
+        stage(&mut changes, "a", Some("v1".into()), "v2".into());
+        stage(&mut changes, "a", Some("v2".into()), "v3".into());
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].old.as_deref(), Some("v1"));
+        assert_eq!(staged(&changes, "a").as_deref(), Some("v3"));
+        assert!(changes[0].diff().contains("-v1"));
+    }
 }
@@ -28,8 +28,7 @@ pub fn run_replay(
     yesterday: bool,
     since: Option<String>,
 ) -> ReplayResult {
-    use chrono::Local;
-
+    let clock = crate::clock::clock();
     let mut result = ReplayResult {
         success: false,
         is_git_repo: false,
@@ -57,7 +56,7 @@ pub fn run_replay(
         args.push(format!("-{}", n));
         format!("Last {} commits", n)
     } else if yesterday {
-        let yesterday_date = Local::now().date_naive() - chrono::Duration::days(1);
+        let yesterday_date = clock.date() - chrono::Duration::days(1);
         args.push(format!("--since={} 00:00:00", yesterday_date));
         args.push(format!("--until={} 23:59:59", yesterday_date));
         format!("Yesterday ({})", yesterday_date)
@@ -65,7 +64,7 @@ pub fn run_replay(
         args.push(format!("--since={}", since_arg));
         format!("Since {}", since_arg)
     } else {
-        let today = Local::now().format("%Y-%m-%d").to_string();
+        let today = clock.today();
         args.push(format!("--since={} 00:00:00", today));
         format!("Today ({})", today)
     };
@@ -3,8 +3,10 @@
 //! Switch between specialized roles for different tasks.
 //! Roles are loaded from .asimov/roles/*.json
 
+use crate::context::{current_context, project_root, resolve_asimov_file};
+use crate::schemas::ROLE_SCHEMA;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Role definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,36 +20,81 @@ pub struct Role {
 }
 
 /// Get the roles directory path
+/// Under the discovered project root, so subdirectories find it
 pub fn roles_dir() -> PathBuf {
-    PathBuf::from(".asimov/roles")
+    project_root().join(".asimov/roles")
+}
+
+/// Read a role file, checking it against the role schema
+///
+/// The file stem must match `code`, since `asimov role <code>` loads `<code>.json`.
+pub fn read_role(path: &Path) -> Result<Role, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read: {}", e))?;
+    let value: serde_json::Value =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid JSON: {}", e))?;
+
+    let schema: serde_json::Value =
+        serde_json::from_str(ROLE_SCHEMA).expect("Embedded role schema must be valid");
+    let validator = jsonschema::Validator::new(&schema).expect("Role schema must compile");
+    let errors: Vec<String> = validator
+        .iter_errors(&value)
+        .map(|e| {
+            let path = e.instance_path().to_string();
+            if path.is_empty() {
+                e.to_string()
+            } else {
+                format!("{}: {}", path.trim_start_matches('/'), e)
+            }
+        })
+        .collect();
+    if !errors.is_empty() {
+        return Err(errors.join("; "));
+    }
+
+    let role: Role = serde_json::from_value(value).map_err(|e| e.to_string())?;
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    if role.code != stem {
+        return Err(format!(
+            "code '{}' does not match file name '{}.json'",
+            role.code, stem
+        ));
+    }
+    Ok(role)
+}
+
+/// Role file for a code; a package's roles override the root's
+fn role_path(code: &str) -> PathBuf {
+    resolve_asimov_file(&format!("roles/{}.json", code))
 }
 
 /// Load a specific role by code
 pub fn load_role(code: &str) -> Option<Role> {
-    let path = roles_dir().join(format!("{}.json", code));
-    let content = std::fs::read_to_string(&path).ok()?;
-    serde_json::from_str(&content).ok()
+    read_role(&role_path(code)).ok()
 }
 
-/// List all available roles
-pub fn list_roles() -> Vec<Role> {
+/// List all available roles, with errors for role files that failed to load
+pub fn list_roles() -> (Vec<Role>, Vec<String>) {
     let mut roles = Vec::new();
-
-    if let Ok(entries) = std::fs::read_dir(roles_dir()) {
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if path.extension().is_some_and(|ext| ext == "json") {
-                if let Ok(content) = std::fs::read_to_string(&path) {
-                    if let Ok(role) = serde_json::from_str::<Role>(&content) {
-                        roles.push(role);
-                    }
-                }
-            }
+    let mut invalid = Vec::new();
+
+    // Roles inherited from enclosing .asimov/ contexts
+    let files = match current_context() {
+        Some(context) => context.inherited_files("roles", "json"),
+        None => Vec::new(),
+    };
+    for path in files {
+        match read_role(&path) {
+            Ok(role) => roles.push(role),
+            Err(e) => invalid.push(format!("{}: {}", path.display(), e)),
         }
     }
 
     roles.sort_by(|a, b| a.code.cmp(&b.code));
-    roles
+    invalid.sort();
+    (roles, invalid)
 }
 
 /// Execute the role command
@@ -55,18 +102,21 @@ pub fn run_role(role_code: Option<&str>) -> Result<RoleResult, RoleError> {
     match role_code {
         None => {
             // List all available roles
-            let roles = list_roles();
-            if roles.is_empty() {
+            let (roles, invalid) = list_roles();
+            if roles.is_empty() && invalid.is_empty() {
                 return Err(RoleError::NoRolesFound);
             }
-            Ok(RoleResult::List(roles))
+            Ok(RoleResult::List { roles, invalid })
         }
         Some(code) => {
             // Load and display specific role
-            match load_role(code) {
-                Some(role) => Ok(RoleResult::Selected(role)),
-                None => Err(RoleError::RoleNotFound(code.to_string())),
+            let path = role_path(code);
+            if !path.exists() {
+                return Err(RoleError::RoleNotFound(code.to_string()));
             }
+            read_role(&path)
+                .map(RoleResult::Selected)
+                .map_err(|e| RoleError::InvalidRole(code.to_string(), e))
         }
     }
 }
@@ -74,8 +124,11 @@ pub fn run_role(role_code: Option<&str>) -> Result<RoleResult, RoleError> {
 /// Role command result
 #[derive(Debug)]
 pub enum RoleResult {
-    /// List of available roles
-    List(Vec<Role>),
+    /// Available roles, and role files that failed to load
+    List {
+        roles: Vec<Role>,
+        invalid: Vec<String>,
+    },
     /// Selected role
     Selected(Role),
 }
@@ -87,6 +140,8 @@ pub enum RoleError {
     NoRolesFound,
     /// Specified role not found
     RoleNotFound(String),
+    /// Role file exists but is malformed (code, reason)
+    InvalidRole(String, String),
 }
 
 impl std::fmt::Display for RoleError {
@@ -105,6 +160,9 @@ impl std::fmt::Display for RoleError {
                     code
                 )
             }
+            RoleError::InvalidRole(code, reason) => {
+                write!(f, "Role '{}' is invalid: {}", code, reason)
+            }
         }
     }
 }
@@ -118,7 +176,7 @@ mod tests {
     #[test]
     fn test_roles_dir() {
         let dir = roles_dir();
-        assert_eq!(dir.to_str().unwrap(), ".asimov/roles");
+        assert!(dir.ends_with(".asimov/roles"));
     }
 
     #[test]
@@ -146,4 +204,32 @@ mod tests {
         // Either returns empty list or error
         assert!(result.is_ok() || matches!(result, Err(RoleError::NoRolesFound)));
     }
+
+    #[test]
+    fn test_read_role_reports_schema_errors() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("eng.json");
+        std::fs::write(&path, r#"{"name": "Engineer", "code": "eng"}"#).unwrap();
+
+        let err = read_role(&path).unwrap_err();
+        assert!(
+            err.contains("\"description\" is a required property"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_read_role_requires_matching_code() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("engineer.json");
+        std::fs::write(
+            &path,
+            r#"{"name": "E", "code": "eng", "description": "d", "focus": [], "prompt_prefix": "p", "avoid": []}"#,
+        )
+        .unwrap();
+
+        let err = read_role(&path).unwrap_err();
+        assert!(err.contains("does not match file name"), "{}", err);
+    }
 }
@@ -0,0 +1,272 @@
+//! Schema command implementation
+//!
+//! Exposes the embedded JSON schemas so editors (yaml-language-server, VS Code,
+//! JetBrains) can validate `.asimov/` files against the exact schema version
+//! of the installed binary.
+
+use crate::schemas::{schema_by_name, SchemaInfo, SCHEMAS};
+use crate::CURRENT_VERSION;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// SchemaStore catalog schema
+const CATALOG_SCHEMA: &str = "https://json.schemastore.org/schema-catalog.json";
+
+/// Catalog file written next to the exported schemas
+pub const CATALOG_FILE: &str = "catalog.json";
+
+/// One row of `asimov schema list`
+#[derive(Debug, Clone)]
+pub struct SchemaListEntry {
+    pub name: String,
+    pub description: String,
+    pub file_match: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SchemaExportResult {
+    pub success: bool,
+    pub version: String,
+    pub files_written: Vec<String>,
+    pub catalog: Option<String>,
+    pub error: Option<String>,
+}
+
+/// List all embedded schemas
+pub fn run_schema_list() -> Vec<SchemaListEntry> {
+    SCHEMAS
+        .iter()
+        .map(|info| SchemaListEntry {
+            name: info.name.to_string(),
+            description: info.description(),
+            file_match: info.file_match.iter().map(|s| s.to_string()).collect(),
+        })
+        .collect()
+}
+
+/// Pretty-printed schema JSON by name
+pub fn run_schema_show(name: &str) -> Option<String> {
+    let info = schema_by_name(name)?;
+    let schema: Value = serde_json::from_str(info.schema).ok()?;
+    serde_json::to_string_pretty(&schema).ok()
+}
+
+/// Versioned file name for an exported schema (`v12.2.0/roadmap.schema.json`)
+pub fn schema_export_path(name: &str) -> PathBuf {
+    PathBuf::from(format!("v{}", CURRENT_VERSION)).join(format!("{}.schema.json", name))
+}
+
+/// Export all schemas to `<dir>/v<version>/` and write a SchemaStore catalog
+///
+/// `base_url` is the URL the directory will be served from. When omitted,
+/// catalog entries use `file://` URLs so local tooling works without hosting.
+/// Versions from earlier exports already listed in `catalog.json` are kept,
+/// so one directory can serve several installed binaries.
+pub fn run_schema_export(dir: &Path, base_url: Option<&str>) -> SchemaExportResult {
+    let mut result = SchemaExportResult {
+        success: false,
+        version: CURRENT_VERSION.to_string(),
+        files_written: Vec::new(),
+        catalog: None,
+        error: None,
+    };
+
+    let version_dir = dir.join(format!("v{}", CURRENT_VERSION));
+    if let Err(e) = std::fs::create_dir_all(&version_dir) {
+        result.error = Some(format!("Failed to create {}: {}", version_dir.display(), e));
+        return result;
+    }
+
+    let base_url = match base_url {
+        Some(url) => url.trim_end_matches('/').to_string(),
+        None => match dir.canonicalize() {
+            Ok(abs) => format!("file://{}", abs.display()),
+            Err(e) => {
+                result.error = Some(format!("Failed to resolve {}: {}", dir.display(), e));
+                return result;
+            }
+        },
+    };
+
+    let catalog_path = dir.join(CATALOG_FILE);
+    let previous_versions = read_catalog_versions(&catalog_path);
+    let mut catalog_entries = Vec::new();
+
+    for info in SCHEMAS {
+        let relative = schema_export_path(info.name);
+        let url = format!("{}/{}", base_url, relative.to_string_lossy());
+
+        let content = match versioned_schema(info, &url) {
+            Some(content) => content,
+            None => {
+                result.error = Some(format!("Embedded {} schema is invalid", info.name));
+                return result;
+            }
+        };
+        let path = dir.join(&relative);
+        if let Err(e) = std::fs::write(&path, content) {
+            result.error = Some(format!("Failed to write {}: {}", path.display(), e));
+            return result;
+        }
+        result
+            .files_written
+            .push(relative.to_string_lossy().to_string());
+
+        let mut versions = previous_versions
+            .get(info.name)
+            .cloned()
+            .unwrap_or_default();
+        versions.insert(CURRENT_VERSION.to_string(), url.clone());
+        catalog_entries.push(json!({
+            "name": format!("asimov {}", info.name),
+            "description": info.description(),
+            "fileMatch": info.file_match,
+            "url": url,
+            "versions": versions,
+        }));
+    }
+
+    let catalog = json!({
+        "$schema": CATALOG_SCHEMA,
+        "version": 1.0,
+        "schemas": catalog_entries,
+    });
+    let catalog_json =
+        serde_json::to_string_pretty(&catalog).expect("Catalog serialization should never fail");
+    if let Err(e) = std::fs::write(&catalog_path, catalog_json + "\n") {
+        result.error = Some(format!("Failed to write {}: {}", catalog_path.display(), e));
+        return result;
+    }
+
+    result.catalog = Some(CATALOG_FILE.to_string());
+    result.success = true;
+    result
+}
+
+/// Schema JSON with `$id` pointing at its exported, versioned location
+fn versioned_schema(info: &SchemaInfo, url: &str) -> Option<String> {
+    let mut schema: Value = serde_json::from_str(info.schema).ok()?;
+    schema["$id"] = Value::String(url.to_string());
+    serde_json::to_string_pretty(&schema).ok().map(|s| s + "\n")
+}
+
+/// Versions recorded by an earlier export, keyed by schema name
+fn read_catalog_versions(path: &Path) -> BTreeMap<String, BTreeMap<String, String>> {
+    let mut versions = BTreeMap::new();
+    let Some(catalog) = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|c| serde_json::from_str::<Value>(&c).ok())
+    else {
+        return versions;
+    };
+
+    for entry in catalog["schemas"].as_array().into_iter().flatten() {
+        let Some(name) = entry["name"]
+            .as_str()
+            .and_then(|n| n.strip_prefix("asimov "))
+        else {
+            continue;
+        };
+        let map: BTreeMap<String, String> = entry["versions"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter_map(|(v, url)| Some((v.clone(), url.as_str()?.to_string())))
+            .collect();
+        versions.insert(name.to_string(), map);
+    }
+    versions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_run_schema_list() {
+        let list = run_schema_list();
+        assert_eq!(list.len(), SCHEMAS.len());
+        assert_eq!(list[0].name, "roadmap");
+        assert!(list[0]
+            .file_match
+            .contains(&"**/.asimov/roadmap.yaml".to_string()));
+    }
+
+    #[test]
+    fn test_run_schema_show() {
+        let shown = run_schema_show("project").unwrap();
+        assert!(shown.contains("\"identity\""));
+        assert!(run_schema_show("nope").is_none());
+    }
+
+    #[test]
+    fn test_run_schema_export_writes_versioned_files_and_catalog() {
+        let temp = TempDir::new().unwrap();
+        let result = run_schema_export(temp.path(), Some("https://example.com/schemas/"));
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.files_written.len(), SCHEMAS.len());
+
+        let roadmap_path = temp.path().join(schema_export_path("roadmap"));
+        let roadmap: Value =
+            serde_json::from_str(&std::fs::read_to_string(roadmap_path).unwrap()).unwrap();
+        let expected_url = format!(
+            "https://example.com/schemas/v{}/roadmap.schema.json",
+            CURRENT_VERSION
+        );
+        assert_eq!(roadmap["$id"], expected_url.as_str());
+
+        let catalog: Value =
+            serde_json::from_str(&std::fs::read_to_string(temp.path().join(CATALOG_FILE)).unwrap())
+                .unwrap();
+        assert_eq!(catalog["$schema"], CATALOG_SCHEMA);
+        assert_eq!(catalog["schemas"][0]["url"], expected_url.as_str());
+        assert_eq!(
+            catalog["schemas"][0]["fileMatch"][0],
+            "**/.asimov/roadmap.yaml"
+        );
+
+        // JSON protocols are exported under their own names, YAML ones as legacy
+        assert!(temp.path().join(schema_export_path("sprint")).exists());
+        let entries = catalog["schemas"].as_array().unwrap();
+        let sprint = entries
+            .iter()
+            .find(|e| e["name"] == "asimov sprint")
+            .unwrap();
+        assert_eq!(sprint["fileMatch"][0], "**/.asimov/sprint.json");
+        assert_eq!(sprint["fileMatch"][1], "**/.asimov/protocols/sprint.json");
+        let legacy = entries
+            .iter()
+            .find(|e| e["name"] == "asimov sprint-yaml")
+            .unwrap();
+        assert!(legacy["description"]
+            .as_str()
+            .unwrap()
+            .starts_with("Legacy YAML protocol"));
+    }
+
+    #[test]
+    fn test_run_schema_export_keeps_previous_versions() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join(CATALOG_FILE),
+            r#"{"schemas":[{"name":"asimov roadmap","versions":{"1.0.0":"file:///old/roadmap.schema.json"}}]}"#,
+        )
+        .unwrap();
+
+        let result = run_schema_export(temp.path(), None);
+        assert!(result.success, "{:?}", result.error);
+
+        let catalog: Value =
+            serde_json::from_str(&std::fs::read_to_string(temp.path().join(CATALOG_FILE)).unwrap())
+                .unwrap();
+        let versions = catalog["schemas"][0]["versions"].as_object().unwrap();
+        assert!(versions.contains_key("1.0.0"));
+        assert!(versions.contains_key(CURRENT_VERSION));
+        assert!(catalog["schemas"][0]["url"]
+            .as_str()
+            .unwrap()
+            .starts_with("file://"));
+    }
+}
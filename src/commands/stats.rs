@@ -15,9 +15,7 @@ pub struct StatsResult {
 }
 
 pub fn run_stats(dir: &Path) -> StatsResult {
-    use chrono::Local;
-
-    let today = Local::now().format("%Y-%m-%d").to_string();
+    let today = crate::clock::clock().today();
     let mut result = StatsResult {
         total_commits: 0,
         asimov_commits: 0,
@@ -0,0 +1,340 @@
+//! Template authoring commands
+//!
+//! `asimov template new|show|lint|diff` for custom project templates in
+//! `.asimov/templates/`. See `templates::render` for the template language.
+
+use super::ValidateFileResult;
+use crate::context::{cwd_relative, resolve_asimov_file, AsimovContext};
+use crate::merge::unified_diff;
+use crate::templates::{
+    find_template, get_template_by_name, parse_template_header, render_template_preview,
+    template_variables, TemplateVar,
+};
+use crate::validator::validate_template;
+use std::path::Path;
+
+#[derive(Debug, Clone)]
+pub struct TemplateNewResult {
+    pub success: bool,
+    pub path: Option<String>,
+    /// Template the new one extends
+    pub extends: String,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TemplateShowResult {
+    pub success: bool,
+    /// File the template came from (`None` for embedded templates)
+    pub source: Option<String>,
+    pub extends: Option<String>,
+    pub variables: Vec<TemplateVar>,
+    pub rendered: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TemplateLintResult {
+    pub success: bool,
+    pub results: Vec<ValidateFileResult>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TemplateDiffResult {
+    pub success: bool,
+    /// What the custom template is compared with (`embedded rust`, `parent rust`)
+    pub against: String,
+    /// Unified diff of the rendered templates (empty when identical)
+    pub diff: String,
+    pub error: Option<String>,
+}
+
+/// Scaffold `.asimov/templates/<name>.yaml` extending `from`
+///
+/// Without `from`, an embedded template's name is overridden by extending it,
+/// anything else extends `generic`.
+pub fn run_template_new(
+    dir: &Path,
+    name: &str,
+    from: Option<&str>,
+    force: bool,
+) -> TemplateNewResult {
+    let extends = from
+        .map(|f| f.to_lowercase())
+        .or_else(|| find_template(name).map(|t| t.name.to_string()))
+        .unwrap_or_else(|| "generic".to_string());
+    let mut result = TemplateNewResult {
+        success: false,
+        path: None,
+        extends: extends.clone(),
+        error: None,
+    };
+
+    let valid_name = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !valid_name {
+        result.error = Some(format!(
+            "Invalid template name '{}' (use lowercase letters, digits and '-')",
+            name
+        ));
+        return result;
+    }
+    if extends != name && get_template_by_name(&extends).is_none() {
+        result.error = Some(format!("Unknown template to extend: '{}'", extends));
+        return result;
+    }
+
+    let templates_dir = dir.join(".asimov/templates");
+    let path = templates_dir.join(format!("{}.yaml", name));
+    if path.exists() && !force {
+        result.error = Some(format!(
+            "{} already exists (use --force to overwrite)",
+            path.display()
+        ));
+        return result;
+    }
+    if let Err(e) = std::fs::create_dir_all(&templates_dir) {
+        result.error = Some(format!(
+            "Failed to create {}: {}",
+            templates_dir.display(),
+            e
+        ));
+        return result;
+    }
+    if let Err(e) = std::fs::write(&path, template_skeleton(name, &extends)) {
+        result.error = Some(format!("Failed to write {}: {}", path.display(), e));
+        return result;
+    }
+
+    result.path = Some(path.display().to_string());
+    result.success = true;
+    result
+}
+
+fn template_skeleton(name: &str, extends: &str) -> String {
+    format!(
+        r#"# Project template: {name}
+# Generated by: asimov template new {name}
+
+# Directives (comment lines, removed when rendered):
+#   # @extends <template>           deep-merge this file over another template
+#   # @var NAME = default | Prompt  declare a variable for placeholders
+#   # @include <template>           paste another template's body here
+#   # @if NAME / # @if NAME == value / # @else / # @endif
+# Use `asimov template show {name}` to preview, `asimov init --type {name}` to apply.
+# @extends {extends}
+# @var OWNER = platform-team | Team that owns the project
+
+identity:
+  tagline: "{{PROJECT_TAGLINE}}"
+
+owner: "{{OWNER}}"
+"#
+    )
+}
+
+/// Where a template comes from and its text: `.asimov/templates/` first
+fn template_source(name: &str) -> Option<(Option<String>, String)> {
+    let path = resolve_asimov_file(&format!("templates/{}.yaml", name));
+    if let Ok(content) = std::fs::read_to_string(&path) {
+        return Some((Some(cwd_relative(&path).display().to_string()), content));
+    }
+    find_template(name).map(|t| (None, t.content.to_string()))
+}
+
+/// Render a template with `vars`, listing the variables it declares
+pub fn run_template_show(name: &str, vars: &[(String, String)]) -> TemplateShowResult {
+    let mut result = TemplateShowResult {
+        success: false,
+        source: None,
+        extends: None,
+        variables: Vec::new(),
+        rendered: None,
+        error: None,
+    };
+    let Some((source, content)) = template_source(name) else {
+        result.error = Some(format!("Unknown template: '{}'", name));
+        return result;
+    };
+    result.source = source;
+
+    let rendered = parse_template_header(&content)
+        .map(|header| result.extends = header.extends)
+        .and_then(|_| template_variables(name, &content))
+        .and_then(|variables| {
+            result.variables = variables;
+            render_template_preview(name, &content, vars)
+        });
+    match rendered {
+        Ok(rendered) => {
+            result.rendered = Some(rendered);
+            result.success = true;
+        }
+        Err(e) => result.error = Some(e),
+    }
+    result
+}
+
+/// Lint one custom template by name, or every template of `dir`'s context
+pub fn run_template_lint(dir: &Path, name: Option<&str>) -> TemplateLintResult {
+    let mut result = TemplateLintResult {
+        success: false,
+        results: Vec::new(),
+        error: None,
+    };
+
+    let paths = match name {
+        Some(name) => match template_source(name) {
+            Some((Some(path), _)) => vec![path.into()],
+            Some((None, _)) => {
+                result.error = Some(format!(
+                    "'{}' is an embedded template (no .asimov/templates/{}.yaml)",
+                    name, name
+                ));
+                return result;
+            }
+            None => {
+                result.error = Some(format!("Unknown template: '{}'", name));
+                return result;
+            }
+        },
+        None => AsimovContext::discover(dir)
+            .map(|context| context.inherited_files("templates", "yaml"))
+            .unwrap_or_default()
+            .iter()
+            .map(|path| cwd_relative(path))
+            .collect(),
+    };
+
+    result.results = paths
+        .iter()
+        .map(|path| {
+            let r = validate_template(path);
+            ValidateFileResult {
+                file: r.file,
+                valid: r.is_valid,
+                errors: r.errors,
+                warnings: r.warnings,
+                regenerated: r.regenerated,
+                diagnostics: r.diagnostics,
+            }
+        })
+        .collect();
+    result.success = result.results.iter().all(|r| r.valid);
+    result
+}
+
+/// Diff a custom template against the embedded one it overrides or its parent
+///
+/// Both sides are rendered with default variables.
+pub fn run_template_diff(name: &str) -> TemplateDiffResult {
+    let mut result = TemplateDiffResult {
+        success: false,
+        against: String::new(),
+        diff: String::new(),
+        error: None,
+    };
+    let Some((Some(path), content)) = template_source(name) else {
+        result.error = Some(format!(
+            "'{}' is not a custom template (no .asimov/templates/{}.yaml)",
+            name, name
+        ));
+        return result;
+    };
+
+    let (against, base) = match find_template(name).filter(|t| t.name == name) {
+        Some(embedded) => (
+            format!("embedded {}", name),
+            render_template_preview(name, embedded.content, &[]),
+        ),
+        None => match parse_template_header(&content).map(|h| h.extends) {
+            Ok(Some(parent)) => {
+                let base = get_template_by_name(&parent)
+                    .ok_or_else(|| format!("Unknown template: '{}'", parent))
+                    .and_then(|parent_content| {
+                        render_template_preview(&parent, &parent_content, &[])
+                    });
+                (format!("parent {}", parent), base)
+            }
+            Ok(None) => {
+                result.error = Some(format!(
+                    "'{}' neither overrides an embedded template nor uses @extends",
+                    name
+                ));
+                return result;
+            }
+            Err(e) => (String::new(), Err(e)),
+        },
+    };
+    result.against = against;
+
+    match base.and_then(|base| Ok((base, render_template_preview(name, &content, &[])?))) {
+        Ok((base, rendered)) => {
+            let label = Path::new(&path)
+                .file_name()
+                .map(|f| f.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.clone());
+            result.diff = unified_diff(&label, &base, &rendered);
+            result.success = true;
+        }
+        Err(e) => result.error = Some(e),
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_template_new_scaffolds_a_valid_template() {
+        let temp = TempDir::new().unwrap();
+        let result = run_template_new(temp.path(), "service", Some("rust"), false);
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.extends, "rust");
+
+        let path = temp.path().join(".asimov/templates/service.yaml");
+        let lint = validate_template(&path);
+        assert!(lint.is_valid, "{:?}", lint.errors);
+
+        let again = run_template_new(temp.path(), "service", None, false);
+        assert!(again.error.unwrap().contains("already exists"));
+
+        let bad = run_template_new(temp.path(), "Bad Name", None, false);
+        assert!(bad.error.unwrap().contains("Invalid template name"));
+        let unknown = run_template_new(temp.path(), "x", Some("cobol"), false);
+        assert!(unknown
+            .error
+            .unwrap()
+            .contains("Unknown template to extend"));
+    }
+
+    #[test]
+    fn test_template_new_defaults_to_overriding_embedded() {
+        let temp = TempDir::new().unwrap();
+        assert_eq!(
+            run_template_new(temp.path(), "go", None, false).extends,
+            "go"
+        );
+        assert_eq!(
+            run_template_new(temp.path(), "internal", None, false).extends,
+            "generic"
+        );
+    }
+
+    #[test]
+    fn test_template_show_embedded() {
+        let result = run_template_show("api-rust", &[]);
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.source.is_none() || result.source.as_deref().unwrap().ends_with(".yaml"));
+        assert_eq!(result.extends.as_deref(), Some("rust"));
+        assert!(result.variables.iter().any(|v| v.name == "PROJECT_NAME"));
+        assert!(result.rendered.unwrap().contains("backend-api"));
+
+        assert!(run_template_show("cobol", &[]).error.is_some());
+    }
+}
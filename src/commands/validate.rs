@@ -1,8 +1,10 @@
 //! Validate command implementation
 
+use crate::validator::{check_protocol_trust, ProtocolTrust, TrustState};
 use crate::{
-    check_ethics_status, check_green_status, check_sycophancy_status, scan_directory_for_red_flags,
-    validate_directory_with_regeneration, EthicsStatus, GreenStatus, SycophancyStatus,
+    changed_files_in_scope, check_ethics_status, check_green_status, check_sycophancy_status,
+    scan_changes_for_red_flags, scan_directory_for_red_flags, validate_directory_with_regeneration,
+    EthicsStatus, GreenStatus, RedFlagMatch, ScanScope, SchemaDiagnostic, SycophancyStatus,
 };
 use std::path::Path;
 
@@ -13,12 +15,17 @@ pub struct ValidateFileResult {
     pub errors: Vec<String>,
     pub warnings: Vec<String>,
     pub regenerated: bool,
+    pub diagnostics: Vec<SchemaDiagnostic>,
 }
 
 #[derive(Debug, Clone)]
 pub struct EthicsScanResult {
     pub red_flags_found: usize,
     pub matches: Vec<EthicsMatch>,
+    /// Whole tree, or added lines only
+    pub scope: ScanScope,
+    /// Why a scoped scan could not run (e.g. not a git repository)
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -35,6 +42,10 @@ pub struct ValidateResult {
     pub protocol_files: Vec<ValidateFileResult>,
     pub roadmap: Option<ValidateFileResult>,
     pub project: Option<ValidateFileResult>,
+    /// Roles, templates, protocol overrides and hook templates
+    pub customizations: Vec<ValidateFileResult>,
+    /// Protocol files against the signed bundle; tampered fails
+    pub protocol_trust: Vec<ProtocolTrust>,
     pub ethics: EthicsStatus,
     pub sycophancy: SycophancyStatus,
     pub green: GreenStatus,
@@ -42,12 +53,37 @@ pub struct ValidateResult {
     pub regenerated: Vec<String>,
 }
 
+impl ValidateResult {
+    /// `--strict`: warnings about protocol overrides (keys the
+    /// loaders ignore) fail validation
+    pub fn make_strict(&mut self) {
+        for r in &mut self.customizations {
+            let is_override = Path::new(&r.file)
+                .parent()
+                .and_then(|dir| dir.file_name())
+                .is_some_and(|name| name == "protocols");
+            if is_override && !r.warnings.is_empty() {
+                r.errors.append(&mut r.warnings);
+                r.valid = false;
+                self.success = false;
+            }
+        }
+    }
+}
+
 pub fn run_validate(dir: &Path, ethics_scan: bool) -> ValidateResult {
+    run_validate_with_scope(dir, ethics_scan, &ScanScope::All)
+}
+
+/// Validate, limiting the ethics scan to staged or changed lines
+pub fn run_validate_with_scope(dir: &Path, ethics_scan: bool, scope: &ScanScope) -> ValidateResult {
     let mut result = ValidateResult {
         success: true,
         protocol_files: Vec::new(),
         roadmap: None,
         project: None,
+        customizations: Vec::new(),
+        protocol_trust: check_protocol_trust(dir),
         ethics: check_ethics_status(dir),
         sycophancy: check_sycophancy_status(dir),
         green: check_green_status(dir),
@@ -64,14 +100,16 @@ pub fn run_validate(dir: &Path, ethics_scan: bool) -> ValidateResult {
                     errors: r.errors.clone(),
                     warnings: r.warnings.clone(),
                     regenerated: r.regenerated,
+                    diagnostics: r.diagnostics.clone(),
                 };
 
-                if r.file.contains("roadmap") {
-                    result.roadmap = Some(file_result);
-                } else if r.file.contains("project") {
-                    result.project = Some(file_result);
-                } else {
-                    result.protocol_files.push(file_result);
+                match r.schema_type.as_str() {
+                    "roadmap" => result.roadmap = Some(file_result),
+                    "project" => result.project = Some(file_result),
+                    "role" | "template" | "protocol" | "hook" => {
+                        result.customizations.push(file_result)
+                    }
+                    _ => result.protocol_files.push(file_result),
                 }
 
                 if !r.is_valid {
@@ -92,27 +130,48 @@ pub fn run_validate(dir: &Path, ethics_scan: bool) -> ValidateResult {
         }
     }
 
+    if result
+        .protocol_trust
+        .iter()
+        .any(|t| t.state == TrustState::Tampered)
+    {
+        result.success = false;
+    }
+
     if ethics_scan {
-        if let Ok(matches) = scan_directory_for_red_flags(dir) {
-            let ethics_matches: Vec<EthicsMatch> = matches
-                .iter()
-                .map(|m| EthicsMatch {
-                    file: m.file.clone(),
-                    line: m.line,
-                    pattern: m.pattern.clone(),
-                    category: format!("{:?}", m.category),
-                })
-                .collect();
-
-            if !ethics_matches.is_empty() {
+        let matches: Result<Vec<RedFlagMatch>, String> = match scope {
+            ScanScope::All => scan_directory_for_red_flags(dir).map_err(|e| e.to_string()),
+            _ => changed_files_in_scope(dir, scope)
+                .map(|changes| scan_changes_for_red_flags(&changes)),
+        };
+        let mut scan = EthicsScanResult {
+            red_flags_found: 0,
+            matches: Vec::new(),
+            scope: scope.clone(),
+            error: None,
+        };
+        match matches {
+            Ok(matches) => {
+                scan.matches = matches
+                    .iter()
+                    .map(|m| EthicsMatch {
+                        file: m.file.clone(),
+                        line: m.line,
+                        pattern: m.pattern.clone(),
+                        category: format!("{:?}", m.category),
+                    })
+                    .collect();
+                scan.red_flags_found = scan.matches.len();
+                if scan.red_flags_found > 0 {
+                    result.success = false;
+                }
+            }
+            Err(e) => {
                 result.success = false;
+                scan.error = Some(e);
             }
-
-            result.ethics_scan = Some(EthicsScanResult {
-                red_flags_found: ethics_matches.len(),
-                matches: ethics_matches,
-            });
         }
+        result.ethics_scan = Some(scan);
     }
 
     result
@@ -161,6 +220,7 @@ mod tests {
             errors: vec![],
             warnings: vec![],
             regenerated: false,
+            diagnostics: vec![],
         };
         assert!(r.valid);
     }
@@ -186,6 +246,8 @@ mod tests {
                 pattern: "rm -rf".to_string(),
                 category: "Security".to_string(),
             }],
+            scope: ScanScope::All,
+            error: None,
         };
         assert_eq!(r.red_flags_found, 5);
         assert_eq!(r.matches.len(), 1);
@@ -198,6 +260,8 @@ mod tests {
             protocol_files: vec![],
             roadmap: None,
             project: None,
+            customizations: vec![],
+            protocol_trust: vec![],
             ethics: EthicsStatus::Hardcoded,
             sycophancy: SycophancyStatus::Hardcoded,
             green: GreenStatus::Hardcoded,
@@ -290,6 +354,7 @@ mod tests {
             errors: vec!["Error 1".to_string(), "Error 2".to_string()],
             warnings: vec!["Warning 1".to_string()],
             regenerated: false,
+            diagnostics: vec![],
         };
         assert!(!r.valid);
         assert_eq!(r.errors.len(), 2);
@@ -304,6 +369,27 @@ mod tests {
         assert!(!result.success || result.protocol_files.is_empty());
     }
 
+    #[test]
+    fn test_run_validate_reports_source_locations() {
+        let temp = TempDir::new().unwrap();
+        let asimov_dir = temp.path().join(".asimov");
+        std::fs::create_dir_all(&asimov_dir).unwrap();
+        std::fs::write(
+            asimov_dir.join("roadmap.yaml"),
+            "current:\n  version: '1.0'\n  status: done\n  summary: Test\n",
+        )
+        .unwrap();
+
+        let result = run_validate(temp.path(), false);
+        let roadmap = result.roadmap.unwrap();
+        assert!(!roadmap.valid);
+        let diagnostic = &roadmap.diagnostics[0];
+        assert_eq!((diagnostic.line, diagnostic.column), (3, 11));
+        assert!(roadmap.errors[0].ends_with(
+            "roadmap.yaml:3:11: current.status must be one of pending, planned, in_progress, released (found \"done\")"
+        ));
+    }
+
     #[test]
     fn test_validate_result_ethics_scan() {
         let temp = TempDir::new().unwrap();
@@ -320,4 +406,57 @@ mod tests {
         // Ethics scan will check for red flags
         assert!(result.ethics_scan.is_some());
     }
+
+    #[test]
+    fn test_validate_ethics_scan_staged_added_lines_only() {
+        let temp = TempDir::new().unwrap();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+                .args(args)
+                .current_dir(temp.path())
+                .output()
+                .unwrap()
+        };
+        git(&["init", "-q"]);
+        std::fs::write(temp.path().join("legacy.md"), "old phishing notes\n").unwrap();
+        git(&["add", "legacy.md"]);
+        git(&["commit", "-q", "-m", "legacy"]);
+        std::fs::write(
+            temp.path().join("legacy.md"),
+            "old phishing notes\nnew keylogger idea\n",
+        )
+        .unwrap();
+        git(&["add", "legacy.md"]);
+
+        let result = run_validate_with_scope(temp.path(), true, &ScanScope::Staged);
+        let scan = result.ethics_scan.unwrap();
+        assert_eq!(scan.red_flags_found, 1);
+        assert_eq!(scan.matches[0].line, 2);
+        assert_eq!(scan.matches[0].pattern, "keylogger");
+
+        let outside = TempDir::new().unwrap();
+        let result = run_validate_with_scope(outside.path(), true, &ScanScope::Staged);
+        assert!(!result.success);
+        assert!(result.ethics_scan.unwrap().error.is_some());
+    }
+
+    #[test]
+    fn test_run_validate_reports_malformed_role() {
+        let temp = TempDir::new().unwrap();
+        let asimov_dir = temp.path().join(".asimov");
+        std::fs::create_dir_all(asimov_dir.join("roles")).unwrap();
+        std::fs::write(
+            asimov_dir.join("roadmap.yaml"),
+            "current:\n  version: '1.0'\n  status: planned\n  summary: Test\n",
+        )
+        .unwrap();
+        std::fs::write(asimov_dir.join("roles").join("eng.json"), "{\"code\": 1}").unwrap();
+
+        let result = run_validate(temp.path(), false);
+        assert!(!result.success);
+        assert_eq!(result.customizations.len(), 1);
+        assert!(result.customizations[0].file.ends_with("eng.json"));
+        assert!(!result.customizations[0].valid);
+    }
 }
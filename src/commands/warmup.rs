@@ -1,6 +1,12 @@
 //! Warmup command implementation
 //! v12.2.0: Minimal warmup - just warmup protocol + tools
 
+use crate::context::{relative_to_ancestor, AsimovContext};
+use crate::protocols::{
+    active_builtin_files, active_custom_protocols, layered_protocol, warmup_files_with,
+    warmup_protocol_from, ProjectFacts,
+};
+use crate::validator::strict_protocol_errors;
 use crate::WarmupProtocol;
 use std::path::Path;
 
@@ -22,6 +28,8 @@ pub struct WarmupResult {
     pub error: Option<String>,
     /// v9.17.0: Tool detection
     pub tools_available: Vec<ToolInfo>,
+    /// Nearest .asimov/ context (a package in a monorepo, or the root)
+    pub context: Option<AsimovContext>,
 }
 
 /// Detect CLI tools available in PATH
@@ -93,17 +101,32 @@ pub fn run_warmup(dir: &Path, _check_updates: bool) -> WarmupResult {
         warmup_protocol: None,
         error: None,
         tools_available: Vec::new(),
+        context: None,
     };
 
-    // Check if .asimov directory exists
-    let asimov_dir = dir.join(".asimov");
-    if !asimov_dir.exists() || !asimov_dir.is_dir() {
+    // Nearest .asimov/ at or above dir (monorepo packages have their own)
+    let Some(context) = AsimovContext::discover(dir) else {
         result.error = Some(".asimov directory not found".to_string());
         return result;
-    }
+    };
 
-    // Load warmup protocol
-    result.warmup_protocol = Some(crate::protocols::load_warmup_protocol());
+    // Load warmup protocol, pointing its files at the package or the context it inherits from
+    let project_layer = context.resolve("protocols/warmup.json");
+    let mut protocol = warmup_protocol_from(layered_protocol("warmup", project_layer.as_deref()));
+    // Active built-ins, and custom protocols that apply placed by priority
+    protocol.files = warmup_files_with(
+        active_builtin_files(protocol.files, &ProjectFacts::discover(dir)),
+        &active_custom_protocols(dir),
+    );
+    if let Ok(start) = std::fs::canonicalize(dir) {
+        protocol.files = protocol
+            .files
+            .iter()
+            .map(|file| resolve_warmup_file(&context, &start, file))
+            .collect();
+    }
+    result.warmup_protocol = Some(protocol);
+    result.context = Some(context);
 
     // Detect available CLI tools
     result.tools_available = detect_tools();
@@ -112,6 +135,37 @@ pub fn run_warmup(dir: &Path, _check_updates: bool) -> WarmupResult {
     result
 }
 
+/// Warmup that fails on invalid protocol overrides instead of falling back
+/// to the embedded protocols (`--strict`)
+pub fn run_warmup_strict(dir: &Path) -> WarmupResult {
+    let errors = strict_protocol_errors(dir);
+    if errors.is_empty() {
+        return run_warmup(dir, false);
+    }
+    WarmupResult {
+        success: false,
+        warmup_protocol: None,
+        error: Some(format!(
+            "Invalid protocol overrides (--strict):\n  {}",
+            errors.join("\n  ")
+        )),
+        tools_available: Vec::new(),
+        context: None,
+    }
+}
+
+/// A warmup file relative to `start`; files missing everywhere stay as listed
+fn resolve_warmup_file(context: &AsimovContext, start: &Path, file: &str) -> String {
+    file.strip_prefix(".asimov/")
+        .and_then(|relative| context.resolve(relative))
+        .and_then(|path| {
+            let owner = path.parent()?.parent()?;
+            Some(relative_to_ancestor(start, owner, &path))
+        })
+        .map(|path| path.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_else(|| file.to_string())
+}
+
 /// v12.2.0: Simplified tests for minimal warmup
 #[cfg(test)]
 mod tests {
@@ -175,6 +229,7 @@ mod tests {
             }),
             error: None,
             tools_available: vec![],
+            context: None,
         };
         assert!(r.success);
         assert!(r.warmup_protocol.is_some());
@@ -217,4 +272,26 @@ mod tests {
             assert!(!tool.name.is_empty());
         }
     }
+
+    #[test]
+    fn test_warmup_in_monorepo_package() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::create_dir(root.join(".git")).unwrap();
+        std::fs::create_dir_all(root.join(".asimov")).unwrap();
+        std::fs::create_dir_all(root.join("packages/web/.asimov")).unwrap();
+        std::fs::create_dir_all(root.join("packages/web/src")).unwrap();
+        std::fs::write(root.join(".asimov/project.yaml"), "identity: {}").unwrap();
+        std::fs::write(root.join(".asimov/asimov.json"), "{}").unwrap();
+        std::fs::write(root.join("packages/web/.asimov/roadmap.yaml"), "").unwrap();
+
+        let result = run_warmup(&root.join("packages/web/src"), false);
+        assert!(result.success);
+        assert!(result.context.unwrap().is_nested());
+        let files = result.warmup_protocol.unwrap().files;
+        // The package's roadmap; project.yaml and protocols inherited from the root
+        assert!(files.contains(&"../.asimov/roadmap.yaml".to_string()));
+        assert!(files.contains(&"../../../.asimov/project.yaml".to_string()));
+        assert!(files.contains(&"../../../.asimov/asimov.json".to_string()));
+    }
 }
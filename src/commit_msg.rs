@@ -0,0 +1,519 @@
+//! Commit message policy
+//!
+//! `asimov stats` counts commits by message and `asimov replay` shows subjects
+//! verbatim, so AI-authored commits need consistent messages. The policy is
+//! declared in project.yaml and checked by the commit-msg hook:
+//!
+//! ```yaml
+//! commit_msg:
+//!   types: [feat, fix, docs, refactor, test, chore]  # conventional commit types
+//!   require_reference: true                          # v1.2.0 or ADR-040 in the message
+//!   attribution: require                             # require | forbid | allow
+//!   max_subject_length: 72
+//! ```
+
+use crate::resolve_protocol_dir;
+use regex::Regex;
+use std::path::Path;
+use std::process::Command;
+
+/// Subject length limit when `max_subject_length` is not set
+pub const DEFAULT_MAX_SUBJECT_LENGTH: usize = 72;
+
+/// Trailers that credit an AI author
+const ATTRIBUTION_TRAILERS: &[&str] = &["co-authored-by", "assisted-by", "generated-by"];
+
+/// Addresses AI tools sign their trailers with
+const AI_AUTHOR_EMAILS: &[&str] = &[
+    "noreply@anthropic.com",
+    "cursoragent@cursor.com",
+    "noreply@aider.chat",
+];
+
+/// GitHub logins of AI apps (`<id>+<login>@users.noreply.github.com`)
+const AI_GITHUB_LOGINS: &[&str] = &[
+    "copilot",
+    "claude[bot]",
+    "devin-ai-integration[bot]",
+    "gemini-code-assist[bot]",
+    "chatgpt-codex-connector[bot]",
+];
+
+/// Display names of AI tools, matched against the whole name so people
+/// called Devin or Claude are not mistaken for one
+const AI_AUTHOR_NAME_PATTERN: &str = r"(?i)^(claude( (code|opus|sonnet|haiku)\b.*)?|chatgpt|gpt-?\d[\w.-]*|(github )?copilot|gemini( code assist)?|(openai )?codex|cursor agent|devin ai|aider)$";
+
+/// Characters `core.commentChar=auto` picks from, in git's order
+const AUTO_COMMENT_CHARS: &str = "#;@!$%^&|:";
+
+/// Subjects git writes itself, which only need to be non-empty
+const GENERATED_SUBJECT_PREFIXES: &[&str] =
+    &["Merge ", "Revert \"", "fixup! ", "squash! ", "amend! "];
+
+/// What to do with AI attribution trailers (`Co-Authored-By: Claude ...`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AttributionPolicy {
+    #[default]
+    Allow,
+    Require,
+    Forbid,
+}
+
+/// What `commit.cleanup` removes before git records the message
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CleanupMode {
+    /// Comment lines and everything below the scissors line (git's default
+    /// when the message comes from an editor)
+    #[default]
+    Strip,
+    /// Only everything below the scissors line
+    Scissors,
+    /// Nothing but surrounding whitespace
+    Whitespace,
+    Verbatim,
+}
+
+/// How git turns the commit-msg file into the recorded message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageCleanup {
+    /// `core.commentChar`; `None` is `auto`
+    pub comment: Option<String>,
+    pub mode: CleanupMode,
+}
+
+impl Default for MessageCleanup {
+    fn default() -> Self {
+        Self {
+            comment: Some("#".to_string()),
+            mode: CleanupMode::Strip,
+        }
+    }
+}
+
+impl MessageCleanup {
+    /// `core.commentChar` and `commit.cleanup` of the repository at `dir`
+    pub fn from_git_config(dir: &Path) -> Self {
+        let config = |key: &str| {
+            let output = Command::new("git")
+                .args(["config", "--get", key])
+                .current_dir(dir)
+                .output()
+                .ok()?;
+            output
+                .status
+                .success()
+                .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        };
+        let comment = match config("core.commentChar") {
+            Some(value) if value == "auto" => None,
+            Some(value) if !value.is_empty() => Some(value),
+            _ => Some("#".to_string()),
+        };
+        let mode = match config("commit.cleanup").as_deref() {
+            Some("scissors") => CleanupMode::Scissors,
+            Some("whitespace") => CleanupMode::Whitespace,
+            Some("verbatim") => CleanupMode::Verbatim,
+            _ => CleanupMode::Strip,
+        };
+        Self { comment, mode }
+    }
+
+    /// The comment prefix of a message; `auto` is the one git appended its
+    /// instructions with, found on the last line
+    fn comment_for(&self, message: &str) -> String {
+        if let Some(ref comment) = self.comment {
+            return comment.clone();
+        }
+        message
+            .lines()
+            .rev()
+            .find_map(|line| line.chars().next())
+            .filter(|c| AUTO_COMMENT_CHARS.contains(*c))
+            .unwrap_or('#')
+            .to_string()
+    }
+}
+
+/// `commit_msg:` section of project.yaml
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitMsgPolicy {
+    /// Conventional commit types; empty means any subject
+    pub types: Vec<String>,
+    /// Message must mention a milestone (v1.2.0) or an ADR (ADR-040)
+    pub require_reference: bool,
+    pub attribution: AttributionPolicy,
+    /// 0 means no limit
+    pub max_subject_length: usize,
+}
+
+impl Default for CommitMsgPolicy {
+    fn default() -> Self {
+        Self {
+            types: Vec::new(),
+            require_reference: false,
+            attribution: AttributionPolicy::Allow,
+            max_subject_length: DEFAULT_MAX_SUBJECT_LENGTH,
+        }
+    }
+}
+
+/// Policy declared in .asimov/project.yaml, if any
+pub fn commit_msg_policy(dir: &Path) -> Result<Option<CommitMsgPolicy>, String> {
+    let Ok(content) = std::fs::read_to_string(resolve_protocol_dir(dir).join("project.yaml"))
+    else {
+        return Ok(None);
+    };
+    let yaml: serde_yaml_ng::Value = serde_yaml_ng::from_str(&content)
+        .map_err(|e| format!("Invalid YAML in project.yaml: {}", e))?;
+    yaml.get("commit_msg")
+        .filter(|section| !section.is_null())
+        .map(parse_policy)
+        .transpose()
+}
+
+/// Parse a `commit_msg:` section
+pub fn parse_policy(section: &serde_yaml_ng::Value) -> Result<CommitMsgPolicy, String> {
+    let mut policy = CommitMsgPolicy::default();
+    if section.as_mapping().is_none() {
+        return Err("commit_msg must be a mapping".to_string());
+    }
+
+    if let Some(types) = section.get("types") {
+        policy.types = types
+            .as_sequence()
+            .and_then(|seq| {
+                seq.iter()
+                    .map(|t| t.as_str().map(str::to_string))
+                    .collect::<Option<Vec<_>>>()
+            })
+            .ok_or("commit_msg.types must be a list of strings")?;
+    }
+    if let Some(required) = section.get("require_reference") {
+        policy.require_reference = required
+            .as_bool()
+            .ok_or("commit_msg.require_reference must be true or false")?;
+    }
+    if let Some(attribution) = section.get("attribution") {
+        policy.attribution = match attribution.as_str() {
+            Some("allow") => AttributionPolicy::Allow,
+            Some("require") => AttributionPolicy::Require,
+            Some("forbid") => AttributionPolicy::Forbid,
+            _ => return Err("commit_msg.attribution must be allow, require or forbid".to_string()),
+        };
+    }
+    if let Some(max) = section.get("max_subject_length") {
+        policy.max_subject_length =
+            max.as_u64()
+                .ok_or("commit_msg.max_subject_length must be a number")? as usize;
+    }
+    Ok(policy)
+}
+
+/// Message as git will record it under `cleanup`
+pub fn clean_message(message: &str, cleanup: &MessageCleanup) -> String {
+    if cleanup.mode == CleanupMode::Verbatim {
+        return message.to_string();
+    }
+    let comment = cleanup.comment_for(message);
+    let scissors = format!("{} ------------------------ >8", comment);
+    message
+        .lines()
+        .take_while(|line| !line.starts_with(&scissors))
+        .filter(|line| cleanup.mode != CleanupMode::Strip || !line.starts_with(&comment))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// Policy violations in a commit message (empty when it passes)
+pub fn check_commit_message(
+    message: &str,
+    policy: &CommitMsgPolicy,
+    cleanup: &MessageCleanup,
+) -> Vec<String> {
+    let message = clean_message(message, cleanup);
+    let Some(subject) = message.lines().next().filter(|s| !s.trim().is_empty()) else {
+        return vec!["Commit message is empty".to_string()];
+    };
+    if GENERATED_SUBJECT_PREFIXES
+        .iter()
+        .any(|prefix| subject.starts_with(prefix))
+    {
+        return Vec::new();
+    }
+
+    let mut errors = Vec::new();
+    let length = subject.chars().count();
+    if policy.max_subject_length > 0 && length > policy.max_subject_length {
+        errors.push(format!(
+            "Subject is {} characters (max: {})",
+            length, policy.max_subject_length
+        ));
+    }
+
+    if !policy.types.is_empty() {
+        match conventional_type(subject) {
+            Some(commit_type) if policy.types.iter().any(|t| t == commit_type) => {}
+            Some(commit_type) => errors.push(format!(
+                "Unknown commit type '{}' (allowed: {})",
+                commit_type,
+                policy.types.join(", ")
+            )),
+            None => errors.push(format!(
+                "Subject must look like '<type>(<scope>): <description>' with type one of: {}",
+                policy.types.join(", ")
+            )),
+        }
+    }
+
+    if policy.require_reference && !has_reference(&message) {
+        errors.push("Missing milestone or ADR reference (e.g. v1.2.0 or ADR-040)".to_string());
+    }
+
+    let trailers = ai_attribution_trailers(&message);
+    match policy.attribution {
+        AttributionPolicy::Require if trailers.is_empty() => errors.push(
+            "Missing AI attribution trailer (e.g. Co-Authored-By: Claude <noreply@anthropic.com>)"
+                .to_string(),
+        ),
+        AttributionPolicy::Forbid => {
+            for trailer in trailers {
+                errors.push(format!("AI attribution trailer not allowed: {}", trailer));
+            }
+        }
+        _ => {}
+    }
+
+    errors
+}
+
+/// Type of a conventional commit subject: `feat(lsp)!: description` -> `feat`
+pub fn conventional_type(subject: &str) -> Option<&str> {
+    let pattern = Regex::new(r"^([a-z]+)(\([^()]+\))?!?: \S").expect("Subject regex is valid");
+    pattern
+        .captures(subject)
+        .and_then(|c| c.get(1))
+        .map(|m| m.as_str())
+}
+
+fn has_reference(message: &str) -> bool {
+    let pattern =
+        Regex::new(r"(?i)\bADR-\d+\b|\bv\d+\.\d+(\.\d+)?\b").expect("Reference regex is valid");
+    pattern.is_match(message)
+}
+
+/// Trailer lines that credit an AI author; people named in the same
+/// trailers are not attribution
+pub fn ai_attribution_trailers(message: &str) -> Vec<String> {
+    let ai_name = Regex::new(AI_AUTHOR_NAME_PATTERN).expect("AI name regex is valid");
+    message
+        .lines()
+        .map(str::trim)
+        .filter(|line| {
+            let Some((key, value)) = line.split_once(':') else {
+                return false;
+            };
+            let key = key.trim().to_ascii_lowercase();
+            ATTRIBUTION_TRAILERS.contains(&key.as_str()) && is_ai_author(value, &ai_name)
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// `Name <email>`, or the kernel's `AGENT:MODEL`, naming an AI tool
+fn is_ai_author(value: &str, ai_name: &Regex) -> bool {
+    let (name, email) = match value.split_once('<') {
+        Some((name, rest)) => (
+            name.trim(),
+            Some(
+                rest.trim_end()
+                    .trim_end_matches('>')
+                    .trim()
+                    .to_ascii_lowercase(),
+            ),
+        ),
+        None => (value.trim(), None),
+    };
+    if let Some(ref email) = email {
+        if AI_AUTHOR_EMAILS.contains(&email.as_str()) {
+            return true;
+        }
+        if let Some(local) = email.strip_suffix("@users.noreply.github.com") {
+            let login = local.split_once('+').map_or(local, |(_, login)| login);
+            if AI_GITHUB_LOGINS.contains(&login) {
+                return true;
+            }
+        }
+    }
+    let agent = name.split_once(':').map_or(name, |(agent, _)| agent).trim();
+    // A person's own address outweighs a name that happens to match
+    ai_name.is_match(agent) && email.is_none_or(|email| email.contains("noreply"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(message: &str, policy: &CommitMsgPolicy) -> Vec<String> {
+        check_commit_message(message, policy, &MessageCleanup::default())
+    }
+
+    fn policy(yaml: &str) -> CommitMsgPolicy {
+        let value: serde_yaml_ng::Value = serde_yaml_ng::from_str(yaml).unwrap();
+        parse_policy(&value).unwrap()
+    }
+
+    #[test]
+    fn test_parse_policy() {
+        let parsed = policy(
+            "types: [feat, fix]\nrequire_reference: true\nattribution: forbid\nmax_subject_length: 50\n",
+        );
+        assert_eq!(parsed.types, vec!["feat", "fix"]);
+        assert!(parsed.require_reference);
+        assert_eq!(parsed.attribution, AttributionPolicy::Forbid);
+        assert_eq!(parsed.max_subject_length, 50);
+
+        assert_eq!(policy("{}"), CommitMsgPolicy::default());
+        let bad: serde_yaml_ng::Value = serde_yaml_ng::from_str("attribution: maybe").unwrap();
+        assert!(parse_policy(&bad)
+            .unwrap_err()
+            .contains("allow, require or forbid"));
+    }
+
+    #[test]
+    fn test_conventional_types() {
+        let policy = policy("types: [feat, fix]\n");
+        assert!(check("feat(lsp)!: hover for roles\n", &policy).is_empty());
+        assert!(check("fix: typo", &policy).is_empty());
+
+        let errors = check("chore: bump", &policy);
+        assert_eq!(
+            errors,
+            vec!["Unknown commit type 'chore' (allowed: feat, fix)"]
+        );
+        let errors = check("Add hover", &policy);
+        assert!(errors[0].starts_with("Subject must look like"));
+        // git-generated subjects are exempt
+        assert!(check("Merge branch 'main'", &policy).is_empty());
+    }
+
+    #[test]
+    fn test_subject_length_and_comments() {
+        let policy = CommitMsgPolicy::default();
+        let long = "x".repeat(73);
+        assert_eq!(
+            check(&long, &policy),
+            vec!["Subject is 73 characters (max: 72)"]
+        );
+        assert_eq!(
+            check("# Please enter\n\n", &policy),
+            vec!["Commit message is empty"]
+        );
+        let verbose = "fix: x\n# ------------------------ >8 ------------------------\ndiff";
+        assert_eq!(clean_message(verbose, &MessageCleanup::default()), "fix: x");
+    }
+
+    #[test]
+    fn test_clean_message_follows_git_config() {
+        let message = "fix: x\n\n#123 is fixed\n; Please enter\n; ------------------------ >8 ------------------------\ndiff\n";
+        let semicolon = MessageCleanup {
+            comment: Some(";".to_string()),
+            mode: CleanupMode::Strip,
+        };
+        assert_eq!(
+            clean_message(message, &semicolon),
+            "fix: x\n\n#123 is fixed"
+        );
+        let auto = MessageCleanup {
+            comment: None,
+            ..semicolon.clone()
+        };
+        assert_eq!(clean_message("fix: x\n; Please enter\n", &auto), "fix: x");
+
+        let scissors = MessageCleanup {
+            mode: CleanupMode::Scissors,
+            ..semicolon.clone()
+        };
+        assert_eq!(
+            clean_message(message, &scissors),
+            "fix: x\n\n#123 is fixed\n; Please enter"
+        );
+        let verbatim = MessageCleanup {
+            mode: CleanupMode::Verbatim,
+            ..semicolon
+        };
+        assert_eq!(clean_message(message, &verbatim), message);
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(temp.path())
+                .output()
+                .unwrap()
+        };
+        git(&["init", "-q"]);
+        assert_eq!(
+            MessageCleanup::from_git_config(temp.path()),
+            MessageCleanup::default()
+        );
+        git(&["config", "core.commentChar", ";"]);
+        git(&["config", "commit.cleanup", "whitespace"]);
+        let cleanup = MessageCleanup::from_git_config(temp.path());
+        assert_eq!(cleanup.comment.as_deref(), Some(";"));
+        assert_eq!(cleanup.mode, CleanupMode::Whitespace);
+    }
+
+    #[test]
+    fn test_reference_required() {
+        let policy = policy("require_reference: true\n");
+        assert!(check("Split output (ADR-040)", &policy).is_empty());
+        assert!(check("Release\n\nShips v12.2.0", &policy).is_empty());
+        assert_eq!(check("Tidy", &policy).len(), 1);
+    }
+
+    #[test]
+    fn test_attribution_policy() {
+        let with_ai = "fix: x\n\nCo-Authored-By: Claude <noreply@anthropic.com>\n";
+        let with_human = "fix: x\n\nCo-Authored-By: Ada <ada@example.com>\n";
+
+        let require = policy("attribution: require\n");
+        assert!(check(with_ai, &require).is_empty());
+        assert_eq!(check(with_human, &require).len(), 1);
+
+        let forbid = policy("attribution: forbid\n");
+        assert!(check(with_human, &forbid).is_empty());
+        let errors = check(with_ai, &forbid);
+        assert!(errors[0].contains("Co-Authored-By: Claude"));
+
+        // People whose names or addresses contain a tool's name are not AI
+        for human in [
+            "Co-authored-by: Devin Patel <devin.patel@acme.com>",
+            "Co-authored-by: Jane Doe <jane@cursor.example>",
+            "Co-authored-by: Claude <claude@acme.fr>",
+            "Co-authored-by: Mistral Ndiaye <m@example.com>",
+        ] {
+            assert!(ai_attribution_trailers(human).is_empty(), "{human}");
+        }
+        for ai in [
+            "Co-authored-by: Copilot <175728472+Copilot@users.noreply.github.com>",
+            "Co-authored-by: Claude Opus 4 <noreply@anthropic.com>",
+            "Assisted-by: Claude:claude-3-opus",
+            "Generated-by: ChatGPT",
+        ] {
+            assert_eq!(ai_attribution_trailers(ai), vec![ai]);
+        }
+
+        // Assisted-by/Generated-by only count when they name an AI
+        assert!(check(
+            "fix: x\n\nAssisted-by: Grace Hopper <g@example.com>\n",
+            &forbid
+        )
+        .is_empty());
+        assert_eq!(
+            ai_attribution_trailers("fix: x\n\nAssisted-by: Copilot\nGenerated-by: make docs\n"),
+            vec!["Assisted-by: Copilot"]
+        );
+    }
+}
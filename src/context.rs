@@ -0,0 +1,329 @@
+//! Nested .asimov/ contexts for monorepos
+//!
+//! A monorepo keeps one `.asimov/` at the repository root and may give any
+//! package its own `.asimov/`. Commands use the nearest context above the
+//! working directory and inherit from the enclosing ones: a package's
+//! `project.yaml` and `roadmap.yaml` override the root's, and a file in its
+//! `protocols/`, `roles/` or `templates/` replaces the root's file of the
+//! same name while the rest still come from the root.
+//!
+//! ```text
+//! repo/.asimov/{project,roadmap}.yaml, protocols/, roles/   <- root context
+//! repo/packages/web/.asimov/{project,roadmap}.yaml           <- package context
+//! ```
+
+use crate::templates::{detect_project_type, ProjectType};
+use crate::validator::PROTOCOL_DIR;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Directories never searched for package contexts
+const SKIP_DIRS: &[&str] = &["node_modules", "target", "vendor", "dist", "build"];
+
+/// The nearest `.asimov/` context and the contexts enclosing it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsimovContext {
+    /// Directory containing the nearest `.asimov/`
+    pub dir: PathBuf,
+    /// Enclosing contexts up to the repository root, nearest first
+    pub parents: Vec<PathBuf>,
+}
+
+impl AsimovContext {
+    /// Nearest context at or above `start`
+    ///
+    /// Enclosing contexts are collected up to the git root (the directory
+    /// holding `.git`), so an unrelated `~/.asimov` is never inherited.
+    pub fn discover(start: &Path) -> Option<Self> {
+        let start = std::fs::canonicalize(start).ok()?;
+        let mut contexts = Vec::new();
+        for dir in start.ancestors() {
+            if dir.join(PROTOCOL_DIR).is_dir() {
+                contexts.push(dir.to_path_buf());
+            }
+            if dir.join(".git").exists() {
+                break;
+            }
+        }
+        let mut contexts = contexts.into_iter();
+        Some(Self {
+            dir: contexts.next()?,
+            parents: contexts.collect(),
+        })
+    }
+
+    /// The outermost context (the repository root's)
+    pub fn root(&self) -> &Path {
+        self.parents.last().unwrap_or(&self.dir)
+    }
+
+    /// Whether this is a package inside an enclosing context
+    pub fn is_nested(&self) -> bool {
+        !self.parents.is_empty()
+    }
+
+    /// `.asimov/` directories, nearest first
+    pub fn asimov_dirs(&self) -> Vec<PathBuf> {
+        std::iter::once(&self.dir)
+            .chain(&self.parents)
+            .map(|dir| dir.join(PROTOCOL_DIR))
+            .collect()
+    }
+
+    /// A file in `.asimov/`, from the nearest context that has it
+    pub fn resolve(&self, relative: &str) -> Option<PathBuf> {
+        self.asimov_dirs()
+            .into_iter()
+            .map(|dir| dir.join(relative))
+            .find(|path| path.is_file())
+    }
+
+    /// Files in an inherited `.asimov/<subdir>/`, nearer contexts overriding
+    /// farther ones by file name; sorted by file name
+    pub fn inherited_files(&self, subdir: &str, extension: &str) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = Vec::new();
+        for dir in self.asimov_dirs() {
+            let Ok(entries) = std::fs::read_dir(dir.join(subdir)) else {
+                continue;
+            };
+            for path in entries.flatten().map(|e| e.path()) {
+                let overridden = files.iter().any(|f| f.file_name() == path.file_name());
+                if path.extension().is_some_and(|e| e == extension) && !overridden {
+                    files.push(path);
+                }
+            }
+        }
+        files.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+        files
+    }
+
+    /// `identity.type` of the nearest `project.yaml`, or the type detected
+    /// from the marker files of the context directory
+    pub fn project_type(&self) -> ProjectType {
+        self.resolve("project.yaml")
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_yaml_ng::from_str::<serde_yaml_ng::Value>(&content).ok())
+            .and_then(|yaml| yaml.get("identity")?.get("type")?.as_str()?.parse().ok())
+            .unwrap_or_else(|| detect_project_type(&self.dir))
+    }
+}
+
+/// Project type of `dir`: declared by its context, else detected
+pub fn project_type(dir: &Path) -> ProjectType {
+    AsimovContext::discover(dir).map_or_else(|| detect_project_type(dir), |c| c.project_type())
+}
+
+/// Nearest context of the working directory
+pub fn current_context() -> Option<AsimovContext> {
+    AsimovContext::discover(Path::new("."))
+}
+
+/// Project root for the working directory
+///
+/// The single discovery routine behind every command: the nearest `.asimov/`
+/// at or above the cwd, stopping at the git root, as a cwd-relative path
+/// (`.`, `..`, `../..`). Outside any context the cwd itself is the root.
+/// `asimov -C <dir>` changes the cwd before discovery runs.
+pub fn project_root() -> PathBuf {
+    current_context().map_or_else(|| PathBuf::from("."), |context| cwd_relative(&context.dir))
+}
+
+/// An absolute directory as seen from the working directory
+///
+/// Paths under the cwd become `./sub`, enclosing directories become `../..`,
+/// anything else stays absolute.
+pub fn cwd_relative(dir: &Path) -> PathBuf {
+    let Ok(cwd) = std::fs::canonicalize(".") else {
+        return dir.to_path_buf();
+    };
+    if let Ok(rest) = dir.strip_prefix(&cwd) {
+        return Path::new(".").join(rest);
+    }
+    if cwd.starts_with(dir) {
+        return relative_to_ancestor(&cwd, dir, dir);
+    }
+    dir.to_path_buf()
+}
+
+/// An inherited `.asimov/` file for the working directory
+///
+/// Falls back to the plain relative path outside any context, so callers
+/// keep their embedded defaults.
+pub fn resolve_asimov_file(relative: &str) -> PathBuf {
+    current_context()
+        .and_then(|context| context.resolve(relative))
+        .unwrap_or_else(|| Path::new(PROTOCOL_DIR).join(relative))
+}
+
+/// Every directory under `root` with its own `.asimov/` (root first)
+pub fn find_contexts(root: &Path) -> Vec<PathBuf> {
+    let mut contexts: Vec<PathBuf> = WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            entry.depth() == 0 || !(name.starts_with('.') || SKIP_DIRS.contains(&name.as_ref()))
+        })
+        .flatten()
+        .filter(|entry| entry.file_type().is_dir() && entry.path().join(PROTOCOL_DIR).is_dir())
+        .map(|entry| entry.into_path())
+        .collect();
+    contexts.sort();
+    contexts
+}
+
+/// Path from `from` to a file in an enclosing directory (`../../.asimov/x`)
+///
+/// `from` must be inside `ancestor`; both absolute.
+pub fn relative_to_ancestor(from: &Path, ancestor: &Path, file: &Path) -> PathBuf {
+    let depth = from
+        .strip_prefix(ancestor)
+        .map(|rest| rest.components().count())
+        .unwrap_or(0);
+    let mut path = PathBuf::new();
+    for _ in 0..depth {
+        path.push("..");
+    }
+    path.join(file.strip_prefix(ancestor).unwrap_or(file))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn monorepo() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::create_dir(root.join(".git")).unwrap();
+        for dir in [
+            ".asimov/roles",
+            ".asimov/protocols",
+            "packages/web/.asimov/roles",
+            "packages/web/src",
+            "packages/api",
+        ] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        for file in [
+            ".asimov/project.yaml",
+            ".asimov/roadmap.yaml",
+            ".asimov/roles/eng.json",
+            ".asimov/roles/pm.json",
+            ".asimov/protocols/green.json",
+            "packages/web/.asimov/roadmap.yaml",
+            "packages/web/.asimov/roles/eng.json",
+        ] {
+            std::fs::write(root.join(file), "{}").unwrap();
+        }
+        temp
+    }
+
+    #[test]
+    fn test_discover_nearest_context() {
+        let temp = monorepo();
+        let root = std::fs::canonicalize(temp.path()).unwrap();
+
+        let context = AsimovContext::discover(&root.join("packages/web/src")).unwrap();
+        assert_eq!(context.dir, root.join("packages/web"));
+        assert_eq!(context.parents, vec![root.clone()]);
+        assert!(context.is_nested());
+        assert_eq!(context.root(), root);
+
+        // A package without its own .asimov/ uses the root context
+        let context = AsimovContext::discover(&root.join("packages/api")).unwrap();
+        assert_eq!(context.dir, root);
+        assert!(!context.is_nested());
+    }
+
+    #[test]
+    fn test_project_type_inherited_from_root() {
+        let temp = monorepo();
+        let root = temp.path();
+        std::fs::write(
+            root.join(".asimov/project.yaml"),
+            "identity:\n  type: ruby\n# type: rust\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("packages/web/package.json"), "{}").unwrap();
+
+        assert_eq!(project_type(root), ProjectType::Ruby);
+        // The package inherits the root's project.yaml
+        assert_eq!(
+            project_type(&root.join("packages/web/src")),
+            ProjectType::Ruby
+        );
+
+        std::fs::write(
+            root.join("packages/web/.asimov/project.yaml"),
+            "identity:\n  name: web\n",
+        )
+        .unwrap();
+        assert_eq!(project_type(&root.join("packages/web")), ProjectType::Node);
+    }
+
+    #[test]
+    fn test_discover_stops_at_git_root() {
+        let outer = monorepo();
+        let inner = outer.path().join("vendored");
+        std::fs::create_dir_all(inner.join(".git")).unwrap();
+        std::fs::create_dir_all(inner.join(".asimov")).unwrap();
+
+        let context = AsimovContext::discover(&inner).unwrap();
+        assert!(context.parents.is_empty());
+
+        std::fs::remove_dir(inner.join(".asimov")).unwrap();
+        assert!(AsimovContext::discover(&inner).is_none());
+    }
+
+    #[test]
+    fn test_resolve_package_and_inherited_files() {
+        let temp = monorepo();
+        let root = std::fs::canonicalize(temp.path()).unwrap();
+        let context = AsimovContext::discover(&root.join("packages/web")).unwrap();
+
+        // The package's roadmap overrides the root's; its project.yaml is inherited
+        assert_eq!(
+            context.resolve("roadmap.yaml"),
+            Some(root.join("packages/web/.asimov/roadmap.yaml"))
+        );
+        assert_eq!(
+            context.resolve("project.yaml"),
+            Some(root.join(".asimov/project.yaml"))
+        );
+        // Protocol overrides come from the root
+        assert_eq!(
+            context.resolve("protocols/green.json"),
+            Some(root.join(".asimov/protocols/green.json"))
+        );
+
+        let roles = context.inherited_files("roles", "json");
+        assert_eq!(
+            roles,
+            vec![
+                root.join("packages/web/.asimov/roles/eng.json"),
+                root.join(".asimov/roles/pm.json"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_contexts() {
+        let temp = monorepo();
+        std::fs::create_dir_all(temp.path().join("node_modules/dep/.asimov")).unwrap();
+        let contexts = find_contexts(temp.path());
+        assert_eq!(
+            contexts,
+            vec![temp.path().to_path_buf(), temp.path().join("packages/web")]
+        );
+    }
+
+    #[test]
+    fn test_relative_to_ancestor() {
+        let path = relative_to_ancestor(
+            Path::new("/repo/packages/web"),
+            Path::new("/repo"),
+            Path::new("/repo/.asimov/asimov.json"),
+        );
+        assert_eq!(path, PathBuf::from("../../.asimov/asimov.json"));
+    }
+}
@@ -6,6 +6,7 @@
 //! See: ADR-011 Hardcoded Ethics
 //! See: asimov.yaml for user-extensible ethics configuration (ADR-031)
 
+use crate::changes::ChangedFile;
 use std::path::Path;
 
 /// Core ethical principles - hardcoded, non-removable
@@ -138,6 +139,14 @@ impl std::fmt::Display for RedFlagCategory {
 /// Scan a single file for red flag patterns
 pub fn scan_file_for_red_flags(path: &Path) -> std::io::Result<Vec<RedFlagMatch>> {
     let content = std::fs::read_to_string(path)?;
+    Ok(scan_content_for_red_flags(
+        &path.display().to_string(),
+        &content,
+    ))
+}
+
+/// Scan file content for red flag patterns (staged blobs have no path on disk)
+pub fn scan_content_for_red_flags(file: &str, content: &str) -> Vec<RedFlagMatch> {
     let mut matches = Vec::new();
 
     for (line_num, line) in content.lines().enumerate() {
@@ -147,7 +156,7 @@ pub fn scan_file_for_red_flags(path: &Path) -> std::io::Result<Vec<RedFlagMatch>
         for pattern in red_flags::FINANCIAL {
             if line_lower.contains(pattern) {
                 matches.push(RedFlagMatch {
-                    file: path.display().to_string(),
+                    file: file.to_string(),
                     line: line_num + 1,
                     pattern: pattern.to_string(),
                     category: RedFlagCategory::Financial,
@@ -160,7 +169,7 @@ pub fn scan_file_for_red_flags(path: &Path) -> std::io::Result<Vec<RedFlagMatch>
         for pattern in red_flags::SECURITY {
             if line_lower.contains(pattern) {
                 matches.push(RedFlagMatch {
-                    file: path.display().to_string(),
+                    file: file.to_string(),
                     line: line_num + 1,
                     pattern: pattern.to_string(),
                     category: RedFlagCategory::Security,
@@ -173,7 +182,7 @@ pub fn scan_file_for_red_flags(path: &Path) -> std::io::Result<Vec<RedFlagMatch>
         for pattern in red_flags::PRIVACY {
             if line_lower.contains(pattern) {
                 matches.push(RedFlagMatch {
-                    file: path.display().to_string(),
+                    file: file.to_string(),
                     line: line_num + 1,
                     pattern: pattern.to_string(),
                     category: RedFlagCategory::Privacy,
@@ -186,7 +195,7 @@ pub fn scan_file_for_red_flags(path: &Path) -> std::io::Result<Vec<RedFlagMatch>
         for pattern in red_flags::DECEPTION {
             if line_lower.contains(pattern) {
                 matches.push(RedFlagMatch {
-                    file: path.display().to_string(),
+                    file: file.to_string(),
                     line: line_num + 1,
                     pattern: pattern.to_string(),
                     category: RedFlagCategory::Deception,
@@ -196,7 +205,20 @@ pub fn scan_file_for_red_flags(path: &Path) -> std::io::Result<Vec<RedFlagMatch>
         }
     }
 
-    Ok(matches)
+    matches
+}
+
+/// Scan changed files, keeping only matches on added lines
+pub fn scan_changes_for_red_flags(changes: &[ChangedFile]) -> Vec<RedFlagMatch> {
+    changes
+        .iter()
+        .filter(|change| is_scannable_file(Path::new(&change.path)))
+        .flat_map(|change| {
+            scan_content_for_red_flags(&change.path, &change.content)
+                .into_iter()
+                .filter(|m| change.is_added_line(m.line))
+        })
+        .collect()
 }
 
 /// File extensions to scan for red flags
@@ -205,6 +227,13 @@ const SCANNABLE_EXTENSIONS: &[&str] = &[
     "yaml", "yml", "json", "toml", "md", "txt",
 ];
 
+/// Whether a file has an extension the red flag scan covers
+pub fn is_scannable_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| SCANNABLE_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
 /// Directories to skip during scanning
 const SKIP_DIRS: &[&str] = &[
     "target",
@@ -250,14 +279,9 @@ fn scan_directory_recursive(dir: &Path, matches: &mut Vec<RedFlagMatch>) -> std:
 
         if path.is_dir() {
             scan_directory_recursive(&path, matches)?;
-        } else if path.is_file() {
-            // Check if file extension is scannable
-            if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-                if SCANNABLE_EXTENSIONS.contains(&ext.to_lowercase().as_str()) {
-                    if let Ok(file_matches) = scan_file_for_red_flags(&path) {
-                        matches.extend(file_matches);
-                    }
-                }
+        } else if path.is_file() && is_scannable_file(&path) {
+            if let Ok(file_matches) = scan_file_for_red_flags(&path) {
+                matches.extend(file_matches);
             }
         }
     }
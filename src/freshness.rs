@@ -0,0 +1,202 @@
+//! Model knowledge cutoffs for the freshness protocol
+//!
+//! Stale data is a different failure than hallucination: a model answers
+//! from what it learned up to its training cutoff. The cutoff table below
+//! ships with the binary and goes stale with every model release, so a
+//! project can import an updated one into `.asimov/model-cutoffs.json`
+//! (`asimov freshness --import <file>`). Imports are validated against
+//! `model_cutoffs` of `FRESHNESS_SCHEMA`.
+
+use crate::schemas::FRESHNESS_SCHEMA;
+use chrono::{Datelike, NaiveDate};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Project cutoff table under `.asimov/`
+pub const MODEL_CUTOFFS_FILE: &str = "model-cutoffs.json";
+
+/// Training data cutoffs of the models the AI CLI profiles launch
+const EMBEDDED_MODEL_CUTOFFS: &str = r#"{
+  "claude": { "opus": "2025-03", "sonnet": "2025-07", "haiku": "2025-02" },
+  "openai": { "gpt-5": "2024-09", "gpt-4.1": "2024-06", "o3": "2024-06" },
+  "google": { "gemini-2.5-pro": "2025-01", "gemini-2.5-flash": "2025-01" },
+  "xai": { "grok-3": "2024-11" },
+  "note": "Training data cutoffs (YYYY-MM) from provider model documentation. Import an updated table with 'asimov freshness --import <file>'."
+}"#;
+
+/// Knowledge cutoffs by provider, then model
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModelCutoffs {
+    /// Provider -> model -> cutoff (YYYY-MM)
+    pub providers: BTreeMap<String, BTreeMap<String, String>>,
+    pub note: Option<String>,
+}
+
+/// Where a cutoff table came from
+#[derive(Debug, Clone, PartialEq)]
+pub enum CutoffSource {
+    /// Compiled into the binary
+    Embedded,
+    /// Imported into the project
+    Project(PathBuf),
+}
+
+fn schema_errors(table: &Value) -> Vec<String> {
+    let schema: Value =
+        serde_json::from_str(FRESHNESS_SCHEMA).expect("Embedded freshness schema must be valid");
+    let validator =
+        jsonschema::Validator::new(&schema).expect("Embedded freshness schema must compile");
+    let document = serde_json::json!({ "model_cutoffs": table });
+    validator
+        .iter_errors(&document)
+        .map(|e| {
+            let path = e.instance_path().to_string();
+            let path = path
+                .trim_start_matches("/model_cutoffs")
+                .trim_start_matches('/');
+            if path.is_empty() {
+                e.to_string()
+            } else {
+                format!("{}: {}", path.replace('/', "."), e)
+            }
+        })
+        .collect()
+}
+
+impl ModelCutoffs {
+    /// Parse a cutoff table, bare or as the `model_cutoffs` of a freshness
+    /// protocol, validated against the schema
+    pub fn parse(content: &str) -> Result<Self, Vec<String>> {
+        let value: Value =
+            serde_json::from_str(content).map_err(|e| vec![format!("not valid JSON: {}", e)])?;
+        let table = match value.get("model_cutoffs") {
+            Some(table) => table.clone(),
+            None => value,
+        };
+        let errors = schema_errors(&table);
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let mut cutoffs = ModelCutoffs::default();
+        for (key, entry) in table.as_object().into_iter().flatten() {
+            match entry {
+                Value::String(note) if key == "note" => cutoffs.note = Some(note.clone()),
+                Value::Object(models) => {
+                    let models = models
+                        .iter()
+                        .filter_map(|(model, cutoff)| {
+                            Some((model.clone(), cutoff.as_str()?.into()))
+                        })
+                        .collect();
+                    cutoffs.providers.insert(key.clone(), models);
+                }
+                _ => {}
+            }
+        }
+        if cutoffs.providers.values().all(BTreeMap::is_empty) {
+            return Err(vec!["no model cutoffs".to_string()]);
+        }
+        Ok(cutoffs)
+    }
+
+    /// The table compiled into the binary
+    pub fn embedded() -> Self {
+        ModelCutoffs::parse(EMBEDDED_MODEL_CUTOFFS)
+            .expect("Embedded model cutoffs must match the freshness schema")
+    }
+
+    /// Cutoff of a model (YYYY-MM)
+    pub fn cutoff(&self, provider: &str, model: &str) -> Option<&str> {
+        self.providers.get(provider)?.get(model).map(String::as_str)
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut table = serde_json::Map::new();
+        for (provider, models) in &self.providers {
+            table.insert(provider.clone(), serde_json::json!(models));
+        }
+        if let Some(ref note) = self.note {
+            table.insert("note".to_string(), Value::String(note.clone()));
+        }
+        serde_json::to_string_pretty(&table).expect("Cutoff serialization should never fail")
+    }
+}
+
+/// Whole months from a cutoff (YYYY-MM) to `today`
+pub fn months_since(cutoff: &str, today: NaiveDate) -> Option<i32> {
+    let (year, month) = cutoff.split_once('-')?;
+    let (year, month): (i32, i32) = (year.parse().ok()?, month.parse().ok()?);
+    Some((today.year() - year) * 12 + today.month() as i32 - month)
+}
+
+/// `.asimov/model-cutoffs.json` of a project
+pub fn project_cutoffs_path(dir: &Path) -> PathBuf {
+    dir.join(".asimov").join(MODEL_CUTOFFS_FILE)
+}
+
+/// Cutoff table of the project in `dir`: the imported one, else the embedded
+pub fn load_model_cutoffs(dir: &Path) -> Result<(ModelCutoffs, CutoffSource), String> {
+    let path = project_cutoffs_path(dir);
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return Ok((ModelCutoffs::embedded(), CutoffSource::Embedded));
+    };
+    ModelCutoffs::parse(&content)
+        .map(|cutoffs| (cutoffs, CutoffSource::Project(path)))
+        .map_err(|errors| {
+            format!(
+                ".asimov/{} is not a valid cutoff table: {}",
+                MODEL_CUTOFFS_FILE,
+                errors.join("; ")
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_embedded_cutoffs_cover_profiles() {
+        let cutoffs = ModelCutoffs::embedded();
+        for profile in crate::commands::AI_PROFILES {
+            assert!(
+                cutoffs.cutoff(profile.provider, profile.model).is_some(),
+                "{} has no cutoff",
+                profile.name
+            );
+        }
+        assert_eq!(ModelCutoffs::parse(&cutoffs.to_json()), Ok(cutoffs));
+    }
+
+    #[test]
+    fn test_parse_validates_against_schema() {
+        let wrapped = r#"{"model_cutoffs": {"claude": {"opus": "2026-01"}}, "motto": "x"}"#;
+        let cutoffs = ModelCutoffs::parse(wrapped).unwrap();
+        assert_eq!(cutoffs.cutoff("claude", "opus"), Some("2026-01"));
+        assert_eq!(cutoffs.note, None);
+
+        let errors = ModelCutoffs::parse(r#"{"openai": {"gpt-6": "next year"}}"#).unwrap_err();
+        assert!(errors[0].starts_with("openai.gpt-6:"), "{errors:?}");
+        assert!(ModelCutoffs::parse(r#"{"openai": {"gpt-6": "2026-13"}}"#).is_err());
+        assert!(ModelCutoffs::parse(r#"{"note": "empty"}"#).is_err());
+        assert!(ModelCutoffs::parse("nope").unwrap_err()[0].contains("not valid JSON"));
+    }
+
+    #[test]
+    fn test_months_since_and_project_table() {
+        let day = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        assert_eq!(months_since("2025-03", day), Some(10));
+        assert_eq!(months_since("2026-01", day), Some(0));
+        assert_eq!(months_since("soon", day), None);
+
+        let temp = TempDir::new().unwrap();
+        let (_, source) = load_model_cutoffs(temp.path()).unwrap();
+        assert_eq!(source, CutoffSource::Embedded);
+        std::fs::create_dir_all(temp.path().join(".asimov")).unwrap();
+        std::fs::write(project_cutoffs_path(temp.path()), "{}").unwrap();
+        assert!(load_model_cutoffs(temp.path()).is_err());
+    }
+}
@@ -32,23 +32,39 @@
 //! }
 //! ```
 
+pub mod changes;
+pub mod clock;
 pub mod commands;
+pub mod commit_msg;
+pub mod context;
 pub mod error;
 pub mod ethics;
+pub mod freshness;
 pub mod green;
+pub mod lsp;
 pub mod markdown;
+pub mod merge;
 pub mod protocols;
 pub mod schemas;
 pub mod semantic;
+pub mod stale_claims;
 pub mod sycophancy;
 pub mod templates;
 pub mod update;
 pub mod validator;
+pub mod yaml_span;
 
 // Re-export main types
+pub use changes::{changed_files_in_scope, ChangedFile, ScanScope};
+pub use commit_msg::{
+    check_commit_message, commit_msg_policy, AttributionPolicy, CleanupMode, CommitMsgPolicy,
+    MessageCleanup, DEFAULT_MAX_SUBJECT_LENGTH,
+};
+pub use context::{find_contexts, AsimovContext};
 pub use error::{Error, Result};
 pub use ethics::{
-    check_ethics_status, red_flags, scan_directory_for_red_flags, scan_file_for_red_flags,
+    check_ethics_status, is_scannable_file, red_flags, scan_changes_for_red_flags,
+    scan_content_for_red_flags, scan_directory_for_red_flags, scan_file_for_red_flags,
     CorePrinciples, EthicsStatus, RedFlagCategory, RedFlagMatch, CORE_PRINCIPLES,
     HUMAN_VETO_COMMANDS,
 };
@@ -57,8 +73,8 @@ pub use green::{
     GreenPrinciples, GreenStatus, GREEN_PRINCIPLES, MOTTO as GREEN_MOTTO,
 };
 pub use markdown::{
-    check_file as check_markdown_file, find_markdown_files, fix_file as fix_markdown_file,
-    LintError, LintResult,
+    check_content as check_markdown_content, check_file as check_markdown_file,
+    find_markdown_files, fix_file as fix_markdown_file, LintError, LintResult,
 };
 pub use semantic::{
     check_semantic, get_cargo_version, load_deprecated_patterns, DeprecatedPattern, IssueCategory,
@@ -82,6 +98,8 @@ pub use templates::{
     git_precommit_hook,
     green_template,
     hook_installer_template,
+    // Hook shims for asimov hooks install
+    hook_shim_template,
     // v10.3.0: List all templates (ADR-057)
     list_templates,
     precommit_hook_template,
@@ -93,20 +111,25 @@ pub use templates::{
     uses_cargo_husky,
     warmup_template,
     ProjectType,
+    CHAINED_HOOK_SUFFIX,
+    MANAGED_HOOK_MARKER,
 };
 pub use validator::{
-    check_asimov_structure, check_protocol_integrity, check_warmup_structure,
-    delete_deprecated_claude_md, ensure_protocol_dir, is_protocol_file, regenerate_protocol_files,
-    resolve_protocol_dir, validate_directory, validate_directory_with_options,
+    apply_protocol_refresh, check_asimov_structure, check_protocol_integrity,
+    check_warmup_structure, delete_deprecated_claude_md, ensure_protocol_dir, is_protocol_file,
+    missing_data_files, plan_protocol_refresh, record_protocol_base, resolve_protocol_dir,
+    validate_content, validate_directory, validate_directory_with_options,
     validate_directory_with_regeneration, validate_file, FileSizeLimits, ProtocolCheck,
-    RegenerationInfo, ValidationResult, PROTOCOL_DIR,
+    ProtocolOutcome, ProtocolRefresh, RegenerationInfo, SchemaDiagnostic, ValidationResult,
+    PROTOCOL_DIR,
 };
 
 // Schema exports for editor integration (v7.2.0)
 // NOTE: PROJECT_SCHEMA added in v8.1.0 (ADR-032)
+pub use schemas::{schema_by_name, SchemaInfo, SCHEMAS};
 pub use schemas::{
     ASIMOV_SCHEMA, FRESHNESS_SCHEMA, GREEN_SCHEMA, MIGRATIONS_SCHEMA, PROJECT_SCHEMA,
-    ROADMAP_SCHEMA, SPRINT_SCHEMA, SYCOPHANCY_SCHEMA, WARMUP_SCHEMA,
+    ROADMAP_SCHEMA, ROLE_SCHEMA, SPRINT_SCHEMA, SYCOPHANCY_SCHEMA, WARMUP_SCHEMA,
 };
 
 // Update exports for self-update functionality (v7.8.0)
@@ -119,6 +142,8 @@ pub use update::{check_for_update, perform_update, VersionCheck, CURRENT_VERSION
 // v9.18.0: Protocol layer refactoring
 // v10.8.0: Migrations removed (ADR-062) - now part of API templates
 pub use protocols::{
+    // User-defined protocols
+    active_custom_protocols,
     // v8.14.0: Individual protocol JSON files
     asimov_json,
     compile_protocols,
@@ -129,6 +154,7 @@ pub use protocols::{
     inject_dates,
     // v12.1.0: Bootstrap approach - load warmup protocol
     load_warmup_protocol,
+    protocol_files,
     // v10.8.0: migrations_json removed (ADR-062)
     sprint_json,
     sycophancy_json,
@@ -137,6 +163,7 @@ pub use protocols::{
     to_pretty_json,
     warmup_entry_json,
     CompiledProtocols,
+    CustomProtocol,
     WarmupEntry,
     WarmupProtocol,
     PROTOCOL_FILES,
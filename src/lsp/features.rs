@@ -0,0 +1,331 @@
+//! LSP features: diagnostics, completion, hover and code actions
+//!
+//! Each function takes the document path (only the file name matters, it
+//! selects the schema) and the current buffer text, and returns LSP JSON.
+//! Positions are LSP positions: 0-based lines, UTF-16 character offsets.
+
+use crate::error::Error;
+use crate::schemas::{schema_for_file, schema_node, schema_type_for_file};
+use crate::validator::validate_content;
+use crate::yaml_span::{locate, path_at, SourceSpan};
+use serde_json::{json, Value};
+use std::path::Path;
+
+/// LSP DiagnosticSeverity
+const SEVERITY_ERROR: u8 = 1;
+const SEVERITY_WARNING: u8 = 2;
+
+/// LSP CompletionItemKind
+const KIND_PROPERTY: u8 = 10;
+const KIND_ENUM_MEMBER: u8 = 20;
+
+/// Milestone statuses offered as code actions, with their titles
+const MILESTONE_ACTIONS: [(&str, &str); 2] = [
+    ("in_progress", "Mark milestone in progress"),
+    ("released", "Mark milestone released"),
+];
+
+/// Schema for a document, if it is an asimov YAML data file
+fn document_schema(path: &Path) -> Option<Value> {
+    let filename = path.file_name()?.to_str()?;
+    let lower = filename.to_lowercase();
+    if !(lower.ends_with(".yaml") || lower.ends_with(".yml")) {
+        return None;
+    }
+    serde_json::from_str(schema_for_file(filename)?).ok()
+}
+
+/// Validate a buffer and return LSP diagnostics
+pub fn diagnostics(path: &Path, text: &str) -> Vec<Value> {
+    if document_schema(path).is_none() {
+        return Vec::new();
+    }
+
+    match validate_content(path, text) {
+        Ok(result) => {
+            let mut items: Vec<Value> = result
+                .diagnostics
+                .iter()
+                .map(|d| {
+                    diagnostic(
+                        text,
+                        SourceSpan {
+                            line: d.line,
+                            column: d.column,
+                        },
+                        SEVERITY_ERROR,
+                        &d.message,
+                    )
+                })
+                .collect();
+
+            let start = SourceSpan { line: 1, column: 1 };
+            // Structural errors (e.g. asimov.yaml sections) have no source location
+            for error in result.errors.iter().skip(result.diagnostics.len()) {
+                items.push(diagnostic(text, start, SEVERITY_ERROR, error));
+            }
+            for warning in &result.warnings {
+                items.push(diagnostic(text, start, SEVERITY_WARNING, warning));
+            }
+            items
+        }
+        Err(Error::YamlError(e)) => {
+            let span = e
+                .location()
+                .map(|l| SourceSpan {
+                    line: l.line(),
+                    column: l.column(),
+                })
+                .unwrap_or(SourceSpan { line: 1, column: 1 });
+            vec![diagnostic(text, span, SEVERITY_ERROR, &e.to_string())]
+        }
+        Err(e) => vec![diagnostic(
+            text,
+            SourceSpan { line: 1, column: 1 },
+            SEVERITY_ERROR,
+            &e.to_string(),
+        )],
+    }
+}
+
+/// Completion items at a position: enum values after `key:`, property names otherwise
+pub fn completions(path: &Path, text: &str, line: usize, character: usize) -> Vec<Value> {
+    let Some(schema) = document_schema(path) else {
+        return Vec::new();
+    };
+    let line_text = text.lines().nth(line).unwrap_or("");
+    let column = byte_column(line_text, character);
+    let prefix = &line_text[..column];
+    let span = SourceSpan {
+        line: line + 1,
+        column: column + 1,
+    };
+
+    let entry = prefix.trim_start();
+    let entry = entry.strip_prefix("- ").unwrap_or(entry);
+    let in_value = entry.contains(": ") || entry.ends_with(':');
+
+    if in_value {
+        let path = path_at(text, span);
+        let Some(node) = schema_node(&schema, &path) else {
+            return Vec::new();
+        };
+        node["enum"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(|value| {
+                json!({
+                    "label": value,
+                    "kind": KIND_ENUM_MEMBER,
+                    "detail": node["description"].as_str().unwrap_or_default(),
+                })
+            })
+            .collect()
+    } else {
+        // A partially typed key has no colon yet, so path_at stops at the parent
+        let parent = path_at(text, span);
+        let Some(node) = schema_node(&schema, &parent) else {
+            return Vec::new();
+        };
+        node["properties"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .map(|(key, property)| {
+                json!({
+                    "label": key,
+                    "kind": KIND_PROPERTY,
+                    "detail": property["description"].as_str().unwrap_or_default(),
+                    "insertText": format!("{}: ", key),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Hover contents for the key at a position, from the schema description
+pub fn hover(path: &Path, text: &str, line: usize, character: usize) -> Option<Value> {
+    let schema = document_schema(path)?;
+    let line_text = text.lines().nth(line)?;
+    let span = SourceSpan {
+        line: line + 1,
+        column: byte_column(line_text, character) + 1,
+    };
+    let segments = path_at(text, span);
+    let key = segments.last()?;
+    let node = schema_node(&schema, &segments)?;
+
+    let mut contents = format!("**{}**", key);
+    if let Some(kind) = node["type"].as_str() {
+        contents.push_str(&format!(" (`{}`)", kind));
+    }
+    if let Some(description) = node["description"].as_str() {
+        contents.push_str(&format!("\n\n{}", description));
+    }
+    if let Some(values) = node["enum"].as_array() {
+        let values: Vec<String> = values
+            .iter()
+            .filter_map(Value::as_str)
+            .map(|v| format!("`{}`", v))
+            .collect();
+        contents.push_str(&format!("\n\nAllowed: {}", values.join(", ")));
+    }
+
+    Some(json!({ "contents": { "kind": "markdown", "value": contents } }))
+}
+
+/// Code actions for a document: milestone status transitions in roadmap.yaml
+pub fn code_actions(uri: &str, path: &Path, text: &str) -> Vec<Value> {
+    let is_roadmap = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .and_then(schema_type_for_file)
+        == Some("roadmap");
+    if !is_roadmap {
+        return Vec::new();
+    }
+
+    let Some(span) = locate(text, "/current/status") else {
+        return Vec::new();
+    };
+    let Some(line_text) = text.lines().nth(span.line - 1) else {
+        return Vec::new();
+    };
+    let value_start = span.column - 1;
+    let value = line_text[value_start..]
+        .split(" #")
+        .next()
+        .unwrap_or_default()
+        .trim_end();
+
+    // Only offer edits when the pointer resolved to the scalar written after
+    // `status:` itself; flow mappings resolve to their enclosing key
+    let status = value.trim_matches(|c| c == '"' || c == '\'');
+    if value.is_empty()
+        || value.starts_with(['{', '[', '|', '>', '&', '*'])
+        || path_at(text, span) != ["current", "status"]
+    {
+        return Vec::new();
+    }
+
+    MILESTONE_ACTIONS
+        .iter()
+        .filter(|(target, _)| *target != status)
+        .map(|(target, title)| {
+            let start = position(line_text, span.line - 1, value_start);
+            let end = position(line_text, span.line - 1, value_start + value.len());
+            json!({
+                "title": title,
+                "kind": "refactor.rewrite",
+                "edit": {
+                    "changes": {
+                        uri: [{ "range": { "start": start, "end": end }, "newText": target }]
+                    }
+                }
+            })
+        })
+        .collect()
+}
+
+/// Build an LSP diagnostic spanning from the location to the end of its line
+fn diagnostic(text: &str, span: SourceSpan, severity: u8, message: &str) -> Value {
+    let line = span.line.saturating_sub(1);
+    let line_text = text.lines().nth(line).unwrap_or("");
+    let start = span.column.saturating_sub(1).min(line_text.len());
+    let end = line_text.trim_end().len().max(start);
+
+    json!({
+        "range": {
+            "start": position(line_text, line, start),
+            "end": position(line_text, line, end),
+        },
+        "severity": severity,
+        "source": "asimov",
+        "message": message,
+    })
+}
+
+/// LSP position for a byte column in a line
+fn position(line_text: &str, line: usize, byte_column: usize) -> Value {
+    let byte_column = floor_char_boundary(line_text, byte_column);
+    let character = line_text[..byte_column].encode_utf16().count();
+    json!({ "line": line, "character": character })
+}
+
+/// Byte column for an LSP (UTF-16) character offset in a line
+fn byte_column(line_text: &str, character: usize) -> usize {
+    let mut units = 0;
+    for (index, c) in line_text.char_indices() {
+        if units >= character {
+            return index;
+        }
+        units += c.len_utf16();
+    }
+    line_text.len()
+}
+
+fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+    index = index.min(text.len());
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnostics_ignore_unknown_files() {
+        assert!(diagnostics(Path::new("notes.yaml"), "a: [").is_empty());
+        assert!(diagnostics(Path::new("roadmap.json"), "{}").is_empty());
+    }
+
+    #[test]
+    fn test_diagnostics_yaml_syntax_error() {
+        let items = diagnostics(Path::new("roadmap.yaml"), "current:\n  version: [\n");
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0]["severity"], SEVERITY_ERROR);
+    }
+
+    #[test]
+    fn test_completion_project_type_enum() {
+        let text = "identity:\n  name: x\n  type: \n";
+        let items = completions(Path::new("project.yaml"), text, 2, 8);
+        assert!(items.iter().any(|i| i["label"] == "rust"));
+        assert!(items.iter().any(|i| i["label"] == "migration"));
+    }
+
+    #[test]
+    fn test_completion_property_names() {
+        let text = "current:\n  ver\n";
+        let items = completions(Path::new("roadmap.yaml"), text, 1, 5);
+        assert!(items.iter().any(|i| i["label"] == "version"));
+        assert!(items.iter().any(|i| i["label"] == "deliverables"));
+    }
+
+    #[test]
+    fn test_code_actions_skip_released_and_other_files() {
+        let released = "current:\n  version: '1.0'\n  status: released\n  summary: x\n";
+        let actions = code_actions("file:///r", Path::new("roadmap.yaml"), released);
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0]["title"], "Mark milestone in progress");
+
+        assert!(code_actions("file:///p", Path::new("project.yaml"), released).is_empty());
+
+        // A flow mapping locates to `current:`; rewriting it would drop the milestone
+        let flow = "current: {version: \"1\", status: planned, summary: \"x\"}\n";
+        assert!(code_actions("file:///r", Path::new("roadmap.yaml"), flow).is_empty());
+        let block_scalar = "current:\n  status: |\n    planned\n";
+        assert!(code_actions("file:///r", Path::new("roadmap.yaml"), block_scalar).is_empty());
+    }
+
+    #[test]
+    fn test_byte_column_utf16() {
+        assert_eq!(byte_column("é: x", 1), 2);
+        assert_eq!(byte_column("abc", 10), 3);
+    }
+}
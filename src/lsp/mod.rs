@@ -0,0 +1,440 @@
+//! Language server for `.asimov/` data files (LSP over stdio)
+//!
+//! A small, synchronous JSON-RPC server: one request in, zero or more
+//! messages out. It reuses the same schemas and `validate_content` as
+//! `asimov validate`, so editors and the CLI never disagree.
+//!
+//! Supported:
+//! - Diagnostics on open/change/save (schema errors with exact ranges)
+//! - Completion of schema enums (`status`, `type`) and property names
+//! - Hover with schema descriptions
+//! - Code actions for roadmap milestones ("Mark milestone released")
+
+mod features;
+
+pub use features::{code_actions, completions, diagnostics, hover};
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+/// JSON-RPC error: method not found
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// JSON-RPC error: server received a request after shutdown
+const INVALID_REQUEST: i64 = -32600;
+
+/// Open documents and lifecycle state for one client connection
+#[derive(Debug, Default)]
+pub struct Server {
+    documents: HashMap<String, String>,
+    shutdown_requested: bool,
+    exited: bool,
+}
+
+impl Server {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// True once the client has sent `exit`
+    pub fn has_exited(&self) -> bool {
+        self.exited
+    }
+
+    /// Handle one incoming message and return the messages to send back
+    pub fn handle(&mut self, message: &Value) -> Vec<Value> {
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+        let id = message.get("id").cloned();
+
+        if self.shutdown_requested && method != "exit" {
+            return id
+                .map(|id| {
+                    vec![error_response(
+                        id,
+                        INVALID_REQUEST,
+                        "Server is shutting down",
+                    )]
+                })
+                .unwrap_or_default();
+        }
+
+        match method {
+            "initialize" => id
+                .map(|id| vec![response(id, initialize_result())])
+                .unwrap_or_default(),
+            "shutdown" => {
+                self.shutdown_requested = true;
+                id.map(|id| vec![response(id, Value::Null)])
+                    .unwrap_or_default()
+            }
+            "exit" => {
+                self.exited = true;
+                Vec::new()
+            }
+            "textDocument/didOpen" => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                self.documents.insert(uri.to_string(), text.to_string());
+                vec![self.publish_diagnostics(uri)]
+            }
+            "textDocument/didChange" => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+                // Full sync: the last change carries the whole document
+                if let Some(text) = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str())
+                {
+                    self.documents.insert(uri.to_string(), text.to_string());
+                }
+                vec![self.publish_diagnostics(uri)]
+            }
+            "textDocument/didSave" => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+                if let Some(text) = params["text"].as_str() {
+                    self.documents.insert(uri.to_string(), text.to_string());
+                }
+                vec![self.publish_diagnostics(uri)]
+            }
+            "textDocument/didClose" => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+                self.documents.remove(uri);
+                vec![notification(
+                    "textDocument/publishDiagnostics",
+                    json!({ "uri": uri, "diagnostics": [] }),
+                )]
+            }
+            "textDocument/completion" => self.with_document(id, params, |uri, text, line, col| {
+                json!(completions(&uri_to_path(uri), text, line, col))
+            }),
+            "textDocument/hover" => self.with_document(id, params, |uri, text, line, col| {
+                hover(&uri_to_path(uri), text, line, col).unwrap_or(Value::Null)
+            }),
+            "textDocument/codeAction" => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+                let actions = self
+                    .documents
+                    .get(uri)
+                    .map(|text| code_actions(uri, &uri_to_path(uri), text))
+                    .unwrap_or_default();
+                id.map(|id| vec![response(id, json!(actions))])
+                    .unwrap_or_default()
+            }
+            _ => match id {
+                // Unknown requests get an error; unknown notifications are ignored
+                Some(id) => vec![error_response(
+                    id,
+                    METHOD_NOT_FOUND,
+                    &format!("Method not found: {}", method),
+                )],
+                None => Vec::new(),
+            },
+        }
+    }
+
+    /// Run a position-based request against an open document
+    fn with_document(
+        &self,
+        id: Option<Value>,
+        params: &Value,
+        f: impl Fn(&str, &str, usize, usize) -> Value,
+    ) -> Vec<Value> {
+        let Some(id) = id else {
+            return Vec::new();
+        };
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+        let line = params["position"]["line"].as_u64().unwrap_or(0) as usize;
+        let character = params["position"]["character"].as_u64().unwrap_or(0) as usize;
+
+        let result = match self.documents.get(uri) {
+            Some(text) => f(uri, text, line, character),
+            None => Value::Null,
+        };
+        vec![response(id, result)]
+    }
+
+    fn publish_diagnostics(&self, uri: &str) -> Value {
+        let text = self.documents.get(uri).map(String::as_str).unwrap_or("");
+        notification(
+            "textDocument/publishDiagnostics",
+            json!({
+                "uri": uri,
+                "diagnostics": diagnostics(&uri_to_path(uri), text),
+            }),
+        )
+    }
+}
+
+/// Serve LSP over the given streams until the client sends `exit`
+pub fn serve<R: BufRead, W: Write>(mut reader: R, mut writer: W) -> io::Result<()> {
+    let mut server = Server::new();
+
+    while let Some(message) = read_message(&mut reader)? {
+        for outgoing in server.handle(&message) {
+            write_message(&mut writer, &outgoing)?;
+        }
+        if server.has_exited() {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Read one `Content-Length` framed message. Returns `None` at end of stream.
+pub fn read_message<R: BufRead>(reader: &mut R) -> io::Result<Option<Value>> {
+    let mut content_length = None;
+
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some(value) = header.strip_prefix("Content-Length:") {
+            content_length = value.trim().parse::<usize>().ok();
+        }
+    }
+
+    let length = content_length.ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, "Missing Content-Length header")
+    })?;
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Write one `Content-Length` framed message
+pub fn write_message<W: Write>(writer: &mut W, message: &Value) -> io::Result<()> {
+    let body = message.to_string();
+    write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    writer.flush()
+}
+
+fn initialize_result() -> Value {
+    json!({
+        "capabilities": {
+            // 1 = full document sync
+            "textDocumentSync": { "openClose": true, "change": 1, "save": { "includeText": true } },
+            "completionProvider": { "triggerCharacters": [":", " "] },
+            "hoverProvider": true,
+            "codeActionProvider": true
+        },
+        "serverInfo": {
+            "name": "asimov",
+            "version": env!("CARGO_PKG_VERSION")
+        }
+    })
+}
+
+fn response(id: Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+/// Convert a `file://` URI to a filesystem path (only the file name selects the schema)
+fn uri_to_path(uri: &str) -> std::path::PathBuf {
+    let path = uri.strip_prefix("file://").unwrap_or(uri);
+    let mut decoded = Vec::with_capacity(path.len());
+    let bytes = path.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = |b: u8| (b as char).to_digit(16);
+            if let (Some(high), Some(low)) = (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                decoded.push((high * 16 + low) as u8);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    std::path::PathBuf::from(String::from_utf8_lossy(&decoded).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    const URI: &str = "file:///work/my%20project/.asimov/roadmap.yaml";
+
+    const ROADMAP: &str = "current:\n  version: '1.0'\n  status: done\n  summary: Test\n";
+
+    /// In-process client: frame messages, run the server, parse the replies
+    fn exchange(messages: &[Value]) -> Vec<Value> {
+        let mut input = Vec::new();
+        for message in messages {
+            write_message(&mut input, message).unwrap();
+        }
+        let mut output = Vec::new();
+        serve(Cursor::new(input), &mut output).unwrap();
+
+        let mut reader = Cursor::new(output);
+        let mut replies = Vec::new();
+        while let Some(reply) = read_message(&mut reader).unwrap() {
+            replies.push(reply);
+        }
+        replies
+    }
+
+    fn request(id: i64, method: &str, params: Value) -> Value {
+        json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+    }
+
+    fn did_open(text: &str) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": { "textDocument": { "uri": URI, "languageId": "yaml", "version": 1, "text": text } }
+        })
+    }
+
+    fn at(line: usize, character: usize) -> Value {
+        json!({ "textDocument": { "uri": URI }, "position": { "line": line, "character": character } })
+    }
+
+    #[test]
+    fn test_initialize_advertises_capabilities() {
+        let replies = exchange(&[request(1, "initialize", json!({}))]);
+        let capabilities = &replies[0]["result"]["capabilities"];
+        assert_eq!(capabilities["hoverProvider"], true);
+        assert_eq!(capabilities["codeActionProvider"], true);
+        assert_eq!(replies[0]["result"]["serverInfo"]["name"], "asimov");
+    }
+
+    #[test]
+    fn test_did_open_publishes_located_diagnostics() {
+        let replies = exchange(&[did_open(ROADMAP)]);
+        assert_eq!(replies[0]["method"], "textDocument/publishDiagnostics");
+        let diagnostic = &replies[0]["params"]["diagnostics"][0];
+        assert_eq!(diagnostic["range"]["start"]["line"], 2);
+        assert_eq!(diagnostic["range"]["start"]["character"], 10);
+        assert!(diagnostic["message"]
+            .as_str()
+            .unwrap()
+            .contains("current.status must be one of"));
+    }
+
+    #[test]
+    fn test_did_change_clears_fixed_diagnostics() {
+        let fixed = format!(
+            "schema_version: {}\n{}",
+            crate::schemas::ROADMAP_SCHEMA_VERSION,
+            ROADMAP.replace("done", "released")
+        );
+        let change = json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didChange",
+            "params": { "textDocument": { "uri": URI, "version": 2 }, "contentChanges": [{ "text": fixed }] }
+        });
+        let replies = exchange(&[did_open(ROADMAP), change]);
+        assert_eq!(replies[1]["params"]["diagnostics"], json!([]));
+    }
+
+    #[test]
+    fn test_completion_offers_status_enum() {
+        let text = "current:\n  version: '1.0'\n  status: \n";
+        let replies = exchange(&[
+            did_open(text),
+            request(2, "textDocument/completion", at(2, 10)),
+        ]);
+        let labels: Vec<&str> = replies[1]["result"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["label"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            labels,
+            vec!["pending", "planned", "in_progress", "released"]
+        );
+    }
+
+    #[test]
+    fn test_hover_shows_schema_description() {
+        let replies = exchange(&[
+            did_open(ROADMAP),
+            request(2, "textDocument/hover", at(2, 4)),
+        ]);
+        let contents = replies[1]["result"]["contents"]["value"].as_str().unwrap();
+        assert!(contents.contains("Milestone status"));
+        assert!(contents.contains("released"));
+    }
+
+    #[test]
+    fn test_code_action_marks_milestone_released() {
+        let params = json!({
+            "textDocument": { "uri": URI },
+            "range": { "start": { "line": 0, "character": 0 }, "end": { "line": 0, "character": 0 } },
+            "context": { "diagnostics": [] }
+        });
+        let replies = exchange(&[
+            did_open(ROADMAP),
+            request(2, "textDocument/codeAction", params),
+        ]);
+        let actions = replies[1]["result"].as_array().unwrap();
+        let released = actions
+            .iter()
+            .find(|a| a["title"] == "Mark milestone released")
+            .unwrap();
+        let edit = &released["edit"]["changes"][URI][0];
+        assert_eq!(edit["newText"], "released");
+        assert_eq!(
+            edit["range"]["start"],
+            json!({ "line": 2, "character": 10 })
+        );
+        assert_eq!(edit["range"]["end"], json!({ "line": 2, "character": 14 }));
+    }
+
+    #[test]
+    fn test_unknown_request_and_shutdown() {
+        let replies = exchange(&[
+            request(1, "workspace/symbol", json!({})),
+            request(2, "shutdown", Value::Null),
+            request(3, "textDocument/hover", at(0, 0)),
+            json!({ "jsonrpc": "2.0", "method": "exit" }),
+            request(4, "initialize", json!({})),
+        ]);
+        assert_eq!(replies[0]["error"]["code"], METHOD_NOT_FOUND);
+        assert_eq!(replies[1]["result"], Value::Null);
+        assert_eq!(replies[2]["error"]["code"], INVALID_REQUEST);
+        // Nothing is processed after exit
+        assert_eq!(replies.len(), 3);
+    }
+
+    #[test]
+    fn test_read_message_requires_content_length() {
+        let mut reader = Cursor::new(b"Content-Type: x\r\n\r\n{}".to_vec());
+        assert!(read_message(&mut reader).is_err());
+    }
+
+    #[test]
+    fn test_uri_to_path_decodes_percent_escapes() {
+        assert_eq!(
+            uri_to_path(URI),
+            std::path::PathBuf::from("/work/my project/.asimov/roadmap.yaml")
+        );
+        // Multibyte characters after '%' are kept, not sliced through
+        assert_eq!(
+            uri_to_path("file:///w/%é/%C3%A9%"),
+            std::path::PathBuf::from("/w/%é/é%")
+        );
+    }
+}
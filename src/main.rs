@@ -5,12 +5,18 @@
 //! is in lib.rs for testability. This file only handles CLI parsing and output.
 
 use clap::{Parser, Subcommand};
+use colored::Colorize;
+use royalbit_asimov::stale_claims::DEFAULT_MAX_CLAIM_AGE_MONTHS;
+use royalbit_asimov::{clock, ScanScope};
 use std::path::PathBuf;
 use std::process::ExitCode;
 
 mod output;
 use output::{
-    cmd_doctor, cmd_init, cmd_launch, cmd_lint_docs, cmd_refresh, cmd_replay, cmd_role, cmd_stats,
+    cmd_check, cmd_doctor, cmd_freshness, cmd_hook_run, cmd_hooks_install, cmd_hooks_status,
+    cmd_hooks_uninstall, cmd_init, cmd_launch, cmd_lint_docs, cmd_lsp, cmd_protocols_explain,
+    cmd_refresh, cmd_replay, cmd_role, cmd_schema_export, cmd_schema_list, cmd_schema_show,
+    cmd_stats, cmd_template_diff, cmd_template_lint, cmd_template_new, cmd_template_show,
     cmd_update, cmd_validate, cmd_warmup,
 };
 
@@ -36,10 +42,19 @@ EXAMPLES:
   asimov role                        # List available roles
   asimov role eng                    # Switch to Principal Engineer role
   asimov validate                    # Validate roadmap.yaml
+  asimov -C packages/web validate    # Run from another directory
+  asimov check                       # Run quality gates from project.yaml
+  asimov hooks install               # Install git hooks (chains existing ones)
+  asimov commit-msg <file>           # Check a commit message against the policy
   asimov update                      # Update binary
   asimov init                        # Initialize new project
+  asimov template new api --from rust  # Author a custom project template
+  asimov protocols explain sycophancy  # Show which layer each protocol field comes from
+  asimov lsp                         # Language server for editors (stdio)
+  asimov schema export schemas/      # Export JSON schemas + SchemaStore catalog
 
-PROTOCOLS (7 total, loaded from .asimov/protocols/ with embedded fallback):
+PROTOCOLS (7 total, embedded, merged with ASIMOV_PROTOCOL_PATH or ~/.config/asimov/protocols/,
+then .asimov/protocols/):
   - asimov     - The Three Laws (do no harm, obey human, self-preserve)
   - freshness  - Date-aware search (WebSearch/WebFetch with current date)
   - sycophancy - Truth over comfort, honest disagreement
@@ -51,6 +66,18 @@ PROTOCOLS (7 total, loaded from .asimov/protocols/ with embedded fallback):
 Docs: https://github.com/royalbit/asimov")]
 #[command(version)]
 struct Cli {
+    /// Run as if asimov was started in DIR (project root is discovered from there)
+    #[arg(short = 'C', global = true, value_name = "DIR")]
+    directory: Option<PathBuf>,
+
+    /// Pin the date of generated content: YYYY-MM-DD or RFC 3339 (or ASIMOV_NOW)
+    #[arg(long, global = true, value_name = "DATE")]
+    date: Option<String>,
+
+    /// Timezone of generated dates: local, UTC or +HH:MM (or ASIMOV_TZ)
+    #[arg(long, global = true, value_name = "TZ")]
+    tz: Option<String>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -62,6 +89,22 @@ enum Commands {
         /// Scan project files for red flag patterns
         #[arg(long)]
         ethics_scan: bool,
+
+        /// Only scan lines added in the git index
+        #[arg(long, requires = "ethics_scan", conflicts_with = "diff")]
+        staged: bool,
+
+        /// Only scan lines added since the merge base with BASE (e.g. origin/main)
+        #[arg(long, value_name = "BASE", requires = "ethics_scan")]
+        diff: Option<String>,
+
+        /// Validate every package .asimov/ context in the repository
+        #[arg(long)]
+        all: bool,
+
+        /// Fail on protocol override keys the loaders would ignore
+        #[arg(long)]
+        strict: bool,
     },
 
     /// Initialize or migrate an asimov project
@@ -70,7 +113,8 @@ enum Commands {
         #[arg(short, long)]
         name: String,
 
-        /// Template type. Base: rust, python, node, go, flutter, docs, arch, generic.
+        /// Template type. Base: rust, python, node, go, flutter, java, csharp, ruby, php,
+        /// cpp, docs, arch, generic, migration.
         /// API: api-rust, api-go, api-fastapi, api-nestjs, api-spring.
         /// Web: web-nextjs, web-react, web-vue, web-angular.
         /// Mono: mono-turbo, mono-nx, mono-pnpm. Other: admin-dashboard.
@@ -84,6 +128,10 @@ enum Commands {
         /// Overwrite existing files
         #[arg(long)]
         force: bool,
+
+        /// Set a template variable (repeatable), e.g. --var DATABASE=sqlite
+        #[arg(long = "var", value_name = "KEY=VALUE")]
+        vars: Vec<String>,
     },
 
     /// Lint markdown documentation
@@ -97,9 +145,21 @@ enum Commands {
         #[arg(long)]
         fix: bool,
 
-        /// Enable semantic checks (version consistency, etc.)
+        /// Enable semantic checks (version consistency, stale claims, etc.)
         #[arg(long)]
         semantic: bool,
+
+        /// Flag claims dated more than MONTHS before their file's last change
+        #[arg(long, value_name = "MONTHS", default_value_t = DEFAULT_MAX_CLAIM_AGE_MONTHS)]
+        max_age: u32,
+
+        /// Only report issues on lines added in the git index
+        #[arg(long, conflicts_with = "diff")]
+        staged: bool,
+
+        /// Only report issues on lines added since the merge base with BASE
+        #[arg(long, value_name = "BASE")]
+        diff: Option<String>,
     },
 
     /// Refresh protocol context and migrate project files
@@ -112,9 +172,14 @@ enum Commands {
         #[arg(short, long)]
         yes: bool,
 
-        /// Show what would change without writing
+        /// Show diffs of what would change without writing
+        /// (exit 0: up to date, 2: changes pending, 1: error)
         #[arg(long)]
         dry_run: bool,
+
+        /// Fail on invalid protocol overrides instead of using the defaults
+        #[arg(long)]
+        strict: bool,
     },
 
     /// Check for updates and self-update
@@ -133,20 +198,85 @@ enum Commands {
         /// Show verbose output
         #[arg(short, long)]
         verbose: bool,
+
+        /// Fail on invalid protocol overrides instead of using the defaults
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Show model knowledge cutoffs and how stale the launched model is
+    Freshness {
+        /// Replace the project's cutoff table with this file (validated)
+        #[arg(long, value_name = "FILE")]
+        import: Option<PathBuf>,
     },
 
     /// Show session statistics
     Stats,
 
+    /// Run the quality gates declared in .asimov/project.yaml
+    Check {
+        /// Run only these gates (comma-separated, e.g. lint,test)
+        #[arg(long, value_delimiter = ',')]
+        only: Vec<String>,
+
+        /// Only check files changed since HEAD (passed to gates as {files})
+        #[arg(long)]
+        changed: bool,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Write a JUnit XML report to this file
+        #[arg(long)]
+        junit: Option<PathBuf>,
+    },
+
     /// Diagnose autonomous mode issues
     Doctor,
 
+    /// Install, remove or inspect the git hooks that run asimov
+    #[command(visible_alias = "hook")]
+    Hooks {
+        #[command(subcommand)]
+        action: HooksAction,
+    },
+
+    /// Check a commit message against the project.yaml commit_msg policy
+    #[command(name = "commit-msg")]
+    CommitMsg {
+        /// Commit message file (e.g. .git/COMMIT_EDITMSG)
+        file: String,
+    },
+
     /// Switch or list available roles (v10.0.0)
     Role {
         /// Role code (eng, biz, fin, ai, pm, qa). Omit to list all.
         code: Option<String>,
     },
 
+    /// Language server for .asimov files (LSP over stdio)
+    Lsp,
+
+    /// List, show or export the JSON schemas for .asimov files
+    Schema {
+        #[command(subcommand)]
+        action: SchemaAction,
+    },
+
+    /// Inspect protocol layers (embedded, user/org, project)
+    Protocols {
+        #[command(subcommand)]
+        action: ProtocolsAction,
+    },
+
+    /// Author custom project templates in .asimov/templates/
+    Template {
+        #[command(subcommand)]
+        action: TemplateAction,
+    },
+
     /// Replay a session from git history
     Replay {
         /// Number of commits to show
@@ -167,34 +297,214 @@ enum Commands {
     },
 }
 
+#[derive(Subcommand)]
+enum HooksAction {
+    /// Install hooks, chaining existing ones (default: pre-commit, commit-msg, pre-push)
+    Install {
+        /// Hooks to install
+        hooks: Vec<String>,
+    },
+
+    /// Remove asimov hooks and restore chained ones
+    Uninstall {
+        /// Hooks to remove
+        hooks: Vec<String>,
+    },
+
+    /// Show which hooks run asimov
+    Status,
+
+    /// Run a hook's checks natively (called by the installed hook)
+    Run {
+        /// Hook name (pre-commit, commit-msg, pre-push)
+        hook: String,
+
+        /// Arguments git passed to the hook
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum SchemaAction {
+    /// List embedded schemas and the files they apply to
+    List,
+
+    /// Print a schema as JSON
+    Show {
+        /// Schema name (roadmap, project, warmup, ...)
+        name: String,
+    },
+
+    /// Write versioned schema files and a SchemaStore catalog.json
+    Export {
+        /// Output directory
+        dir: PathBuf,
+
+        /// URL the directory is served from (default: file:// URLs)
+        #[arg(long)]
+        base_url: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProtocolsAction {
+    /// Show which layer each field of a protocol comes from
+    Explain {
+        /// Protocol name (asimov, freshness, sycophancy, green, sprint, warmup, coding-standards)
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum TemplateAction {
+    /// Scaffold .asimov/templates/<name>.yaml
+    New {
+        /// Template name (lowercase, digits and '-')
+        name: String,
+
+        /// Template to extend (default: the embedded template of the same name, or generic)
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Overwrite an existing template
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Print a rendered template and its variables
+    Show {
+        /// Template name
+        name: String,
+
+        /// Set a template variable (repeatable)
+        #[arg(long = "var", value_name = "KEY=VALUE")]
+        vars: Vec<String>,
+    },
+
+    /// Check custom templates render a valid project.yaml
+    Lint {
+        /// Template name (default: all custom templates)
+        name: Option<String>,
+    },
+
+    /// Diff a custom template against the template it overrides or extends
+    Diff {
+        /// Template name
+        name: String,
+    },
+}
+
 #[cfg_attr(feature = "coverage", coverage(off))]
 fn main() -> ExitCode {
     let cli = Cli::parse();
 
+    // -C moves the starting point of project root discovery
+    if let Some(ref dir) = cli.directory {
+        if let Err(e) = std::env::set_current_dir(dir) {
+            eprintln!(
+                "{} cannot change to {}: {}",
+                "Error:".bold().red(),
+                dir.display(),
+                e
+            );
+            return ExitCode::FAILURE;
+        }
+    }
+
+    // --date/--tz pin the clock for asimov and the hooks it runs
+    if let Some(ref date) = cli.date {
+        std::env::set_var(clock::NOW_ENV, date);
+    }
+    if let Some(ref tz) = cli.tz {
+        std::env::set_var(clock::TZ_ENV, tz);
+    }
+    if let Err(e) = clock::Clock::from_env() {
+        eprintln!("{} {}", "Error:".bold().red(), e);
+        return ExitCode::FAILURE;
+    }
+
     match cli.command {
         None => cmd_launch(),
-        Some(Commands::Validate { ethics_scan }) => cmd_validate(ethics_scan),
+        Some(Commands::Validate {
+            ethics_scan,
+            staged,
+            diff,
+            all,
+            strict,
+        }) => cmd_validate(
+            ethics_scan,
+            ScanScope::from_flags(staged, diff),
+            all,
+            strict,
+        ),
         Some(Commands::Init {
             name,
             project_type,
             output,
             force,
-        }) => cmd_init(&name, &project_type, &output, force),
+            vars,
+        }) => cmd_init(&name, &project_type, &output, force, &vars),
         Some(Commands::LintDocs {
             path,
             fix,
             semantic,
-        }) => cmd_lint_docs(&path, fix, semantic),
+            max_age,
+            staged,
+            diff,
+        }) => cmd_lint_docs(
+            &path,
+            fix,
+            semantic,
+            ScanScope::from_flags(staged, diff),
+            max_age,
+        ),
         Some(Commands::Refresh {
             verbose,
             yes,
             dry_run,
-        }) => cmd_refresh(verbose, yes, dry_run),
+            strict,
+        }) => cmd_refresh(verbose, yes, dry_run, strict),
         Some(Commands::Update { check }) => cmd_update(check),
-        Some(Commands::Warmup { path, verbose }) => cmd_warmup(&path, verbose),
+        Some(Commands::Warmup {
+            path,
+            verbose,
+            strict,
+        }) => cmd_warmup(&path, verbose, strict),
         Some(Commands::Stats) => cmd_stats(),
+        Some(Commands::Freshness { import }) => cmd_freshness(import.as_ref()),
+        Some(Commands::Check {
+            only,
+            changed,
+            json,
+            junit,
+        }) => cmd_check(only, changed, json, junit.as_deref()),
         Some(Commands::Doctor) => cmd_doctor(),
+        Some(Commands::Hooks { action }) => match action {
+            HooksAction::Install { hooks } => cmd_hooks_install(&hooks),
+            HooksAction::Uninstall { hooks } => cmd_hooks_uninstall(&hooks),
+            HooksAction::Status => cmd_hooks_status(),
+            HooksAction::Run { hook, args } => cmd_hook_run(&hook, &args),
+        },
+        Some(Commands::CommitMsg { file }) => cmd_hook_run("commit-msg", &[file]),
         Some(Commands::Role { code }) => cmd_role(code.as_deref()),
+        Some(Commands::Lsp) => cmd_lsp(),
+        Some(Commands::Schema { action }) => match action {
+            SchemaAction::List => cmd_schema_list(),
+            SchemaAction::Show { name } => cmd_schema_show(&name),
+            SchemaAction::Export { dir, base_url } => cmd_schema_export(&dir, base_url.as_deref()),
+        },
+        Some(Commands::Protocols { action }) => match action {
+            ProtocolsAction::Explain { name } => cmd_protocols_explain(&name),
+        },
+        Some(Commands::Template { action }) => match action {
+            TemplateAction::New { name, from, force } => {
+                cmd_template_new(&name, from.as_deref(), force)
+            }
+            TemplateAction::Show { name, vars } => cmd_template_show(&name, &vars),
+            TemplateAction::Lint { name } => cmd_template_lint(name.as_deref()),
+            TemplateAction::Diff { name } => cmd_template_diff(&name),
+        },
         Some(Commands::Replay {
             commits,
             yesterday,
@@ -70,7 +70,7 @@ pub fn check_file(path: &Path) -> Result<LintResult, std::io::Error> {
 }
 
 /// Check markdown content for code block closer issues
-fn check_content(content: &str) -> Vec<LintError> {
+pub fn check_content(content: &str) -> Vec<LintError> {
     let mut errors = Vec::new();
     let mut in_block = false;
     let mut block_fence: Option<&str> = None; // "```" or "~~~"
@@ -0,0 +1,211 @@
+//! Three-way line merge
+//!
+//! Used by `asimov refresh` to carry local edits of generated files over to
+//! a new default: `base` is the default the file was generated from, `local`
+//! is the file on disk and `new` is the default of the running binary.
+//! Changes on one side are taken as is, identical changes on both sides are
+//! taken once, and overlapping (or touching) different changes conflict.
+
+use similar::{capture_diff_slices, Algorithm, DiffOp, TextDiff};
+
+/// Unified diff between two versions of a file
+pub fn unified_diff(path: &str, old: &str, new: &str) -> String {
+    TextDiff::from_lines(old, new)
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{}", path), &format!("b/{}", path))
+        .to_string()
+}
+
+/// Labels written on conflict markers
+pub struct MergeLabels<'a> {
+    pub local: &'a str,
+    pub base: &'a str,
+    pub new: &'a str,
+}
+
+/// Merged text; with conflicts it holds diff3-style markers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeOutcome {
+    pub text: String,
+    pub conflicts: usize,
+}
+
+impl MergeOutcome {
+    pub fn is_clean(&self) -> bool {
+        self.conflicts == 0
+    }
+}
+
+/// Replace `base[start..end]` with `lines`
+#[derive(Debug, Clone)]
+struct Edit<'a> {
+    start: usize,
+    end: usize,
+    lines: Vec<&'a str>,
+    local: bool,
+}
+
+fn edits<'a>(base: &[&'a str], other: &[&'a str], local: bool) -> Vec<Edit<'a>> {
+    capture_diff_slices(Algorithm::Myers, base, other)
+        .into_iter()
+        .filter_map(|op| {
+            let (start, end, lines) = match op {
+                DiffOp::Equal { .. } => return None,
+                DiffOp::Delete {
+                    old_index, old_len, ..
+                } => (old_index, old_index + old_len, Vec::new()),
+                DiffOp::Insert {
+                    old_index,
+                    new_index,
+                    new_len,
+                } => (
+                    old_index,
+                    old_index,
+                    other[new_index..new_index + new_len].to_vec(),
+                ),
+                DiffOp::Replace {
+                    old_index,
+                    old_len,
+                    new_index,
+                    new_len,
+                } => (
+                    old_index,
+                    old_index + old_len,
+                    other[new_index..new_index + new_len].to_vec(),
+                ),
+            };
+            Some(Edit {
+                start,
+                end,
+                lines,
+                local,
+            })
+        })
+        .collect()
+}
+
+/// `base[start..end]` with one side's edits applied
+fn apply<'a>(base: &[&'a str], start: usize, end: usize, edits: &[&Edit<'a>]) -> Vec<&'a str> {
+    let mut out = Vec::new();
+    let mut at = start;
+    for edit in edits {
+        out.extend_from_slice(&base[at..edit.start]);
+        out.extend_from_slice(&edit.lines);
+        at = edit.end;
+    }
+    out.extend_from_slice(&base[at..end]);
+    out
+}
+
+fn push_block(text: &mut String, lines: &[&str]) {
+    for line in lines {
+        text.push_str(line);
+        if !line.ends_with('\n') {
+            text.push('\n');
+        }
+    }
+}
+
+/// Merge the changes from `base` to `local` and from `base` to `new`
+pub fn merge3(base: &str, local: &str, new: &str, labels: &MergeLabels) -> MergeOutcome {
+    let base_lines: Vec<&str> = base.split_inclusive('\n').collect();
+    let local_lines: Vec<&str> = local.split_inclusive('\n').collect();
+    let new_lines: Vec<&str> = new.split_inclusive('\n').collect();
+
+    let mut all = edits(&base_lines, &local_lines, true);
+    all.extend(edits(&base_lines, &new_lines, false));
+    all.sort_by_key(|e| (e.start, e.end));
+
+    let mut text = String::new();
+    let mut conflicts = 0;
+    let mut at = 0;
+    let mut index = 0;
+    while index < all.len() {
+        // A cluster: edits overlapping or touching the first one, from either side
+        let start = all[index].start;
+        let mut end = all[index].end;
+        let mut next = index + 1;
+        while next < all.len() && all[next].start <= end {
+            end = end.max(all[next].end);
+            next += 1;
+        }
+        let cluster = &all[index..next];
+        index = next;
+
+        text.push_str(&base_lines[at..start].concat());
+        at = end;
+
+        let local_edits: Vec<&Edit> = cluster.iter().filter(|e| e.local).collect();
+        let new_edits: Vec<&Edit> = cluster.iter().filter(|e| !e.local).collect();
+        let ours = apply(&base_lines, start, end, &local_edits);
+        let theirs = apply(&base_lines, start, end, &new_edits);
+        if new_edits.is_empty() || ours == theirs {
+            text.push_str(&ours.concat());
+        } else if local_edits.is_empty() {
+            text.push_str(&theirs.concat());
+        } else {
+            conflicts += 1;
+            text.push_str(&format!("<<<<<<< {}\n", labels.local));
+            push_block(&mut text, &ours);
+            text.push_str(&format!("||||||| {}\n", labels.base));
+            push_block(&mut text, &base_lines[start..end]);
+            text.push_str("=======\n");
+            push_block(&mut text, &theirs);
+            text.push_str(&format!(">>>>>>> {}\n", labels.new));
+        }
+    }
+    text.push_str(&base_lines[at..].concat());
+
+    MergeOutcome { text, conflicts }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LABELS: MergeLabels = MergeLabels {
+        local: "local",
+        base: "base",
+        new: "new",
+    };
+
+    #[test]
+    fn test_merge_takes_changes_from_both_sides() {
+        let base = "a\nb\nc\nd\ne\n";
+        let local = "a\nB\nc\nd\ne\n";
+        let new = "a\nb\nc\nd\nE\nf\n";
+        let outcome = merge3(base, local, new, &LABELS);
+        assert!(outcome.is_clean());
+        assert_eq!(outcome.text, "a\nB\nc\nd\nE\nf\n");
+    }
+
+    #[test]
+    fn test_merge_identical_changes_and_unchanged_sides() {
+        let base = "a\nb\nc\n";
+        let changed = "a\nX\nc\n";
+        assert_eq!(merge3(base, changed, changed, &LABELS).text, changed);
+        assert_eq!(merge3(base, base, changed, &LABELS).text, changed);
+        assert_eq!(merge3(base, changed, base, &LABELS).text, changed);
+    }
+
+    #[test]
+    fn test_merge_conflict_markers() {
+        let base = "a\nb\nc\n";
+        let local = "a\nlocal\nc\n";
+        let new = "a\nnew\nc\n";
+        let outcome = merge3(base, local, new, &LABELS);
+        assert_eq!(outcome.conflicts, 1);
+        assert_eq!(
+            outcome.text,
+            "a\n<<<<<<< local\nlocal\n||||||| base\nb\n=======\nnew\n>>>>>>> new\nc\n"
+        );
+    }
+
+    #[test]
+    fn test_merge_without_trailing_newline() {
+        let outcome = merge3("a\nb\nc", "a\nb\nC", "A\nb\nc", &LABELS);
+        assert!(outcome.is_clean());
+        assert_eq!(outcome.text, "A\nb\nC");
+    }
+}
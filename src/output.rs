@@ -1,1410 +0,0 @@
-//! CLI output formatting - command handlers with inline tests (Rust style)
-//!
-//! Each function formats and outputs command results. Tests are inline per Rust convention.
-
-use colored::Colorize;
-use royalbit_asimov::commands::{
-    check_launch_conditions, run_doctor, run_init, run_lint_docs, run_refresh_with_options,
-    run_replay, run_role, run_stats, run_update, run_validate, run_warmup, AiProfile, LaunchResult,
-    RefreshOptions, RoleError, RoleResult, UpdateResult,
-};
-use std::io::{self, Write as _};
-use std::process::ExitCode;
-
-// ============================================================================
-// THIN WRAPPERS - Call commands.rs and format output
-// These are CLI output formatters, tested via e2e tests (ADR-039)
-// ============================================================================
-
-/// Prompt user to select an AI CLI when multiple are available
-#[cfg_attr(feature = "coverage", coverage(off))]
-fn prompt_ai_selection(profiles: &[AiProfile]) -> Option<AiProfile> {
-    println!("{}", "Multiple AI CLIs detected:".bold().yellow());
-    println!();
-    for (i, profile) in profiles.iter().enumerate() {
-        println!("  {}. {} ({})", i + 1, profile.name, profile.binary);
-    }
-    println!();
-    print!("Select AI to launch [1-{}]: ", profiles.len());
-    io::stdout().flush().ok()?;
-
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).ok()?;
-    let choice: usize = input.trim().parse().ok()?;
-
-    if choice >= 1 && choice <= profiles.len() {
-        Some(profiles[choice - 1].clone())
-    } else {
-        None
-    }
-}
-
-/// Launch an AI CLI with minimal warmup context (v12.2.0)
-#[cfg_attr(feature = "coverage", coverage(off))]
-fn launch_ai(profile: &AiProfile) -> ExitCode {
-    println!("{}", format!("Launching {}...", profile.name).bright_cyan());
-
-    // Get warmup content directly
-    let warmup_result = run_warmup(std::path::Path::new("."), false);
-    if warmup_result.error.is_some() {
-        eprintln!(
-            "{} Failed to generate warmup context",
-            "Error:".bold().red()
-        );
-        return ExitCode::FAILURE;
-    }
-
-    // Build minimal warmup JSON (v12.2.0)
-    let warmup: serde_json::Value = warmup_result
-        .warmup_protocol
-        .as_ref()
-        .map(|w| serde_json::to_value(w).unwrap_or(serde_json::json!({})))
-        .unwrap_or(serde_json::json!({}));
-
-    let tools: Vec<serde_json::Value> = warmup_result
-        .tools_available
-        .iter()
-        .map(|t| {
-            serde_json::json!({
-                "name": t.name,
-                "directive": t.directive
-            })
-        })
-        .collect();
-
-    let output = serde_json::json!({
-        "warmup": warmup,
-        "tools": tools
-    });
-
-    // Pass warmup as prompt argument (not stdin - breaks terminal raw mode)
-    let prompt = output.to_string();
-
-    let mut cmd = std::process::Command::new(profile.binary);
-    cmd.args(profile.auto_mode_args);
-    cmd.arg(&prompt);
-
-    match cmd.status() {
-        Ok(s) if s.success() => ExitCode::SUCCESS,
-        Ok(_) => ExitCode::FAILURE,
-        Err(e) => {
-            eprintln!(
-                "{} Failed to start {}: {}",
-                "Error:".bold().red(),
-                profile.name,
-                e
-            );
-            ExitCode::FAILURE
-        }
-    }
-}
-
-#[cfg_attr(feature = "coverage", coverage(off))]
-pub(crate) fn cmd_launch() -> ExitCode {
-    match check_launch_conditions() {
-        LaunchResult::InsideAi(name) => {
-            // Inside an AI session - run warmup directly
-            if std::env::var("ASIMOV_DEBUG").is_ok() {
-                eprintln!("{} Inside {} session", "Debug:".dimmed(), name);
-            }
-            cmd_warmup(std::path::Path::new("."), false)
-        }
-        LaunchResult::NoAiFound => {
-            eprintln!("{} No AI CLI found in PATH", "Error:".bold().red());
-            eprintln!();
-            eprintln!("Install one of:");
-            eprintln!("  Claude Code: https://claude.ai/download");
-            eprintln!("  Gemini CLI:  https://cloud.google.com/gemini-cli");
-            eprintln!("  Codex CLI:   https://github.com/openai/codex");
-            ExitCode::FAILURE
-        }
-        LaunchResult::Launching(profile) => launch_ai(&profile),
-        LaunchResult::MultipleFound(profiles) => match prompt_ai_selection(&profiles) {
-            Some(profile) => launch_ai(&profile),
-            None => {
-                eprintln!("{} Invalid selection", "Error:".bold().red());
-                ExitCode::FAILURE
-            }
-        },
-    }
-}
-
-#[cfg_attr(feature = "coverage", coverage(off))]
-pub(crate) fn cmd_update(check_only: bool) -> ExitCode {
-    println!("{}", "RoyalBit Asimov Update".bold().green());
-    println!();
-    format_update_result(run_update(check_only))
-}
-
-#[cfg_attr(feature = "coverage", coverage(off))]
-pub(crate) fn format_update_result(result: UpdateResult) -> ExitCode {
-    match result {
-        UpdateResult::AlreadyLatest { current, .. } => {
-            println!(
-                "  {} v{} is the latest version",
-                "OK".bold().green(),
-                current
-            );
-            ExitCode::SUCCESS
-        }
-        UpdateResult::UpdateAvailable { current, latest } => {
-            println!("  Current: {}", current.bright_blue());
-            println!("  Latest:  {}", latest.bright_green());
-            println!();
-            println!(
-                "  {} Run {} to install",
-                "UPDATE".bold().yellow(),
-                "asimov update".bold()
-            );
-            ExitCode::SUCCESS
-        }
-        UpdateResult::Updated { from, to } => {
-            println!("  {} Updated {} → {}", "Success:".bold().green(), from, to);
-            ExitCode::SUCCESS
-        }
-        UpdateResult::UpdateFailed {
-            error,
-            download_url,
-            ..
-        } => {
-            eprintln!("  {} {}", "Error:".bold().red(), error);
-            eprintln!("  Manual: curl -L {} | tar xz", download_url);
-            ExitCode::FAILURE
-        }
-        UpdateResult::NoBinaryAvailable { .. } => {
-            eprintln!("  {} No binary for this platform", "Error:".bold().red());
-            ExitCode::FAILURE
-        }
-        UpdateResult::CheckFailed { error } => {
-            eprintln!("  {} {}", "Error:".bold().red(), error);
-            ExitCode::FAILURE
-        }
-    }
-}
-
-/// v12.2.0: Minimal warmup output - just warmup protocol + tools
-#[cfg_attr(feature = "coverage", coverage(off))]
-pub(crate) fn cmd_warmup(path: &std::path::Path, verbose: bool) -> ExitCode {
-    let result = run_warmup(path, false);
-
-    if let Some(ref err) = result.error {
-        eprintln!("{} {}", "Error:".bold().red(), err);
-        if err.contains("not found") {
-            eprintln!(
-                "  Run {} first",
-                "asimov init --name <NAME> --type <TYPE>".bold()
-            );
-        }
-        return ExitCode::FAILURE;
-    }
-
-    // Serialize warmup protocol
-    let warmup: serde_json::Value = result
-        .warmup_protocol
-        .as_ref()
-        .map(|w| serde_json::to_value(w).unwrap_or(serde_json::json!({})))
-        .unwrap_or(serde_json::json!({}));
-
-    // Build tools section
-    let tools: Vec<serde_json::Value> = result
-        .tools_available
-        .iter()
-        .map(|t| {
-            serde_json::json!({
-                "name": t.name,
-                "directive": t.directive
-            })
-        })
-        .collect();
-
-    // Minimal output: just warmup + tools
-    let output = serde_json::json!({
-        "warmup": warmup,
-        "tools": tools
-    });
-
-    if verbose {
-        // Pretty print for human readability
-        println!("{}", serde_json::to_string_pretty(&output).unwrap());
-    } else {
-        // Compact for AI consumption
-        println!("{}", output);
-    }
-
-    ExitCode::SUCCESS
-}
-
-#[cfg_attr(feature = "coverage", coverage(off))]
-pub(crate) fn cmd_validate(ethics_scan: bool) -> ExitCode {
-    let result = run_validate(std::path::Path::new("."), ethics_scan);
-
-    println!("{}", "RoyalBit Asimov Validate".bold().green());
-    println!();
-
-    // Show roadmap validation
-    if result.roadmap.is_some() || result.project.is_some() {
-        println!("{}", "ROADMAP & PROJECT".bold());
-    }
-    if let Some(ref r) = result.roadmap {
-        if r.valid {
-            println!("  {} roadmap.yaml", "✓".green());
-        } else {
-            println!("  {} roadmap.yaml", "✗".red());
-            for e in &r.errors {
-                println!("      {}", e.red());
-            }
-        }
-    }
-
-    // Show project validation
-    if let Some(ref p) = result.project {
-        if p.valid {
-            println!("  {} project.yaml", "✓".green());
-        } else {
-            println!("  {} project.yaml", "✗".red());
-        }
-    }
-
-    // Show ethics scan results
-    if let Some(ref scan) = result.ethics_scan {
-        println!();
-        println!("{}", "Ethics Scan".bold());
-        if scan.red_flags_found > 0 {
-            println!(
-                "  {} {} red flag(s) found",
-                "⚠".yellow(),
-                scan.red_flags_found
-            );
-            for m in &scan.matches {
-                println!("      {}:{} - {}", m.file, m.line, m.pattern);
-            }
-        } else {
-            println!("  {} No red flags found", "✓".green());
-        }
-    }
-
-    println!();
-    if result.success {
-        println!("{} All validations passed", "Success:".bold().green());
-        ExitCode::SUCCESS
-    } else {
-        println!("{} Validation failed", "Error:".bold().red());
-        ExitCode::FAILURE
-    }
-}
-
-#[cfg_attr(feature = "coverage", coverage(off))]
-pub(crate) fn cmd_init(
-    name: &str,
-    project_type: &str,
-    output: &std::path::Path,
-    force: bool,
-) -> ExitCode {
-    let result = run_init(output, name, project_type, force);
-
-    if let Some(ref err) = result.error {
-        eprintln!("{} {}", "Error:".bold().red(), err);
-        return ExitCode::FAILURE;
-    }
-
-    println!("{}", "RoyalBit Asimov Init".bold().green());
-    println!();
-
-    for f in &result.files_created {
-        println!("  {} {}", "CREATE".green(), f);
-    }
-    for f in &result.files_updated {
-        println!("  {} {}", "UPDATE".yellow(), f);
-    }
-    for f in &result.files_kept {
-        println!("  {} {}", "KEEP".bright_blue(), f);
-    }
-    for h in &result.hooks_installed {
-        println!("  {} {}", "HOOK".bright_cyan(), h);
-    }
-
-    // v9.7.0: Show dev dependencies added
-    for d in &result.deps_added {
-        println!("  {} {}", "DEP".bright_magenta(), d);
-    }
-
-    println!();
-    if result.success {
-        println!("{} Project initialized", "Success:".bold().green());
-        println!();
-
-        // v9.7.0: Show install instructions if any
-        if !result.install_instructions.is_empty() {
-            println!("Install tools:");
-            for instr in &result.install_instructions {
-                if instr.starts_with("Note:") {
-                    println!("  {} {}", "!".yellow(), instr);
-                } else {
-                    println!("  $ {}", instr.bright_cyan());
-                }
-            }
-            println!();
-        }
-
-        println!("Next steps:");
-        println!("  1. Edit .asimov/roadmap.yaml with your milestones");
-        println!("  2. Run: asimov warmup");
-        ExitCode::SUCCESS
-    } else {
-        ExitCode::FAILURE
-    }
-}
-
-#[cfg_attr(feature = "coverage", coverage(off))]
-pub(crate) fn cmd_lint_docs(path: &std::path::Path, fix: bool, semantic: bool) -> ExitCode {
-    let result = run_lint_docs(path, fix, semantic);
-
-    println!("{}", "RoyalBit Asimov Lint".bold().green());
-    println!();
-    println!("  {} markdown file(s) checked", result.files_checked);
-
-    if result.files_with_errors > 0 {
-        println!(
-            "  Files with errors: {}",
-            result.files_with_errors.to_string().red()
-        );
-    }
-
-    if fix && result.files_fixed > 0 {
-        println!("  Files fixed: {}", result.files_fixed.to_string().green());
-    }
-
-    if semantic && result.semantic_files_checked > 0 {
-        println!("  Semantic Checks: {} files", result.semantic_files_checked);
-        for issue in &result.semantic_issues {
-            println!(
-                "    {} {}:{} - {}",
-                "⚠".yellow(),
-                issue.file,
-                issue.line,
-                issue.message
-            );
-        }
-    }
-
-    println!();
-    if result.success {
-        println!("{} All checks passed", "Success:".bold().green());
-        ExitCode::SUCCESS
-    } else {
-        println!("{} Issues found", "Error:".bold().red());
-        ExitCode::FAILURE
-    }
-}
-
-#[cfg_attr(feature = "coverage", coverage(off))]
-pub(crate) fn cmd_refresh(verbose: bool, yes: bool, dry_run: bool) -> ExitCode {
-    let options = RefreshOptions { yes, dry_run };
-    let result = run_refresh_with_options(std::path::Path::new("."), options);
-
-    if !result.is_asimov_project {
-        eprintln!("{} Not in an asimov project", "Error:".bold().red());
-        eprintln!("  Run {} first", "asimov init".bold());
-        return ExitCode::FAILURE;
-    }
-
-    if let Some(ref err) = result.error {
-        eprintln!("{} {}", "Error:".bold().red(), err);
-        return ExitCode::FAILURE;
-    }
-
-    println!("{}", "RoyalBit Asimov REFRESH".bold().green());
-    if dry_run {
-        println!("{}", "(dry run - no changes made)".dimmed());
-    }
-    println!();
-
-    // v9.0.0: Protocol integrity status
-    for f in &result.protocols_updated {
-        println!("  {} {} (was outdated)", "UPDATED".yellow(), f);
-    }
-    for f in &result.protocols_created {
-        println!("  {} {}", "CREATED".green(), f);
-    }
-    for f in &result.protocols_ok {
-        println!("  {} {}", "OK".dimmed(), f);
-    }
-
-    // Data files (roadmap.yaml etc)
-    for f in &result.files_regenerated {
-        println!("  {} {}", "REGENERATE".green(), f);
-    }
-    for f in &result.files_unchanged {
-        println!("  {} {}", "UNCHANGED".dimmed(), f);
-    }
-
-    // v9.6.0: Pre-commit hook regeneration (ADR-043)
-    if result.hook_regenerated {
-        println!(
-            "  {} .git/hooks/pre-commit (direct enforcement)",
-            "HOOK".green()
-        );
-    }
-
-    // v9.5.0: Migration status
-    if verbose {
-        if let Some(ref pt) = result.project_type_detected {
-            println!();
-            println!("{}", "MIGRATION".bold());
-            println!("  Project type: {}", pt.to_string().bright_blue());
-            if result.project_type_was_missing {
-                println!("    {} Type was missing, now set", "→".yellow());
-            }
-            if result.coding_standards_upgraded {
-                println!(
-                    "    {} coding_standards upgraded to v9.4.0 format",
-                    "→".yellow()
-                );
-            }
-        }
-    }
-
-    println!();
-    if result.success {
-        let updated_count = result.protocols_updated.len();
-        let mut msg = if updated_count > 0 {
-            format!("Protocols refreshed ({} updated)", updated_count)
-        } else {
-            "Protocols refreshed".to_string()
-        };
-        if result.coding_standards_upgraded {
-            msg.push_str(", coding_standards upgraded");
-        }
-        if result.hook_regenerated {
-            msg.push_str(", pre-commit hook updated");
-        }
-        println!("{} {}", "Success:".bold().green(), msg);
-        ExitCode::SUCCESS
-    } else {
-        ExitCode::FAILURE
-    }
-}
-
-#[cfg_attr(feature = "coverage", coverage(off))]
-pub(crate) fn cmd_stats() -> ExitCode {
-    let result = run_stats(std::path::Path::new("."));
-
-    println!("{}", "RoyalBit Asimov Stats".bold().green());
-    println!();
-    println!("  Session: {}", result.session_date.bright_blue());
-    println!("  Commits today: {}", result.today_commits);
-    println!("  Asimov commits: {}", result.asimov_commits);
-    println!("  Total commits: {}", result.total_commits);
-
-    if let Some(ref ver) = result.milestone_version {
-        println!();
-        println!("  Milestone: v{}", ver.bright_yellow());
-        if let Some(ref summary) = result.milestone_summary {
-            println!("  Summary: {}", summary);
-        }
-    }
-
-    ExitCode::SUCCESS
-}
-
-#[cfg_attr(feature = "coverage", coverage(off))]
-pub(crate) fn cmd_doctor() -> ExitCode {
-    let result = run_doctor(std::path::Path::new("."));
-
-    println!("{}", "RoyalBit ASIMOV - DOCTOR".bold().green());
-    println!();
-
-    // v9.8.0: Display detected license (ADR-045)
-    if let Some(ref license) = result.license {
-        println!("{}", "DEPENDENCY HEALTH".bold());
-        println!("  License: {} (detected)", license.bright_blue());
-        println!();
-    }
-
-    for check in &result.checks {
-        let icon = if check.passed {
-            "✓".green()
-        } else {
-            "✗".red()
-        };
-        println!("  {} {}: {}", icon, check.name, check.message);
-        if check.auto_fixed {
-            println!("      {} Auto-fixed", "→".yellow());
-        }
-    }
-
-    if !result.issues.is_empty() {
-        println!();
-        println!("{}", "Issues:".bold().red());
-        for issue in &result.issues {
-            println!("  • {}", issue);
-        }
-    }
-
-    if !result.warnings.is_empty() {
-        println!();
-        println!("{}", "Warnings:".bold().yellow());
-        for warn in &result.warnings {
-            println!("  • {}", warn);
-        }
-    }
-
-    if let Some((ver, is_latest)) = &result.version_info {
-        println!();
-        if *is_latest {
-            println!("  {} v{} (latest)", "Version:".bold(), ver);
-        } else {
-            println!(
-                "  {} v{} (update available)",
-                "Version:".bold().yellow(),
-                ver
-            );
-        }
-    }
-
-    println!();
-    let passed = result.checks.iter().filter(|c| c.passed).count();
-    let total = result.checks.len();
-    println!("{} {}/{} checks passed", "Result:".bold(), passed, total);
-
-    if passed == total && result.issues.is_empty() {
-        ExitCode::SUCCESS
-    } else {
-        ExitCode::FAILURE
-    }
-}
-
-/// Role switching command (v10.0.0)
-#[cfg_attr(feature = "coverage", coverage(off))]
-pub(crate) fn cmd_role(role_code: Option<&str>) -> ExitCode {
-    match run_role(role_code) {
-        Ok(RoleResult::List(roles)) => {
-            println!("{}", "RoyalBit Asimov - ROLES".bold().green());
-            println!();
-            println!("Available roles:");
-            println!();
-            for role in &roles {
-                println!("  {} - {}", role.code.bright_cyan().bold(), role.name);
-                println!("      {}", role.description.dimmed());
-            }
-            println!();
-            println!("Usage: {} <code>", "asimov role".bold());
-            ExitCode::SUCCESS
-        }
-        Ok(RoleResult::Selected(role)) => {
-            println!("{}", "RoyalBit Asimov - ROLE ACTIVE".bold().green());
-            println!();
-            println!("  Role: {} ({})", role.name.bright_cyan().bold(), role.code);
-            println!("  {}", role.description);
-            println!();
-            println!("{}", "FOCUS AREAS:".bold());
-            for area in &role.focus {
-                println!("  • {}", area.bright_green());
-            }
-            println!();
-            println!("{}", "PROMPT PREFIX:".bold());
-            println!("  {}", role.prompt_prefix.bright_yellow());
-            println!();
-            if !role.avoid.is_empty() {
-                println!("{}", "AVOID (defer to others):".bold());
-                for topic in &role.avoid {
-                    println!("  • {}", topic.dimmed());
-                }
-                println!();
-            }
-            ExitCode::SUCCESS
-        }
-        Err(RoleError::NoRolesFound) => {
-            eprintln!("{} No roles found", "Error:".bold().red());
-            eprintln!("  Create role files in .asimov/roles/*.json");
-            eprintln!();
-            eprintln!("Example role JSON:");
-            eprintln!(r#"  {{"name": "Engineer", "code": "eng", ...}}"#);
-            ExitCode::FAILURE
-        }
-        Err(RoleError::RoleNotFound(code)) => {
-            eprintln!("{} Role '{}' not found", "Error:".bold().red(), code);
-            eprintln!("  Use {} to list available roles", "asimov role".bold());
-            ExitCode::FAILURE
-        }
-    }
-}
-
-#[cfg_attr(feature = "coverage", coverage(off))]
-pub(crate) fn cmd_replay(
-    commits: Option<usize>,
-    yesterday: bool,
-    since: Option<String>,
-) -> ExitCode {
-    let result = run_replay(std::path::Path::new("."), commits, yesterday, since);
-
-    if !result.is_git_repo {
-        eprintln!("{} Not a git repository", "Error:".bold().red());
-        return ExitCode::FAILURE;
-    }
-
-    if let Some(ref err) = result.error {
-        eprintln!("{} {}", "Error:".bold().red(), err);
-        return ExitCode::FAILURE;
-    }
-
-    println!("{}", "RoyalBit Asimov Replay".bold().green());
-    println!();
-    println!("  Range: {}", result.range_description.bright_blue());
-    println!("  Commits: {}", result.commits.len());
-    println!("  Files changed: {}", result.total_files_changed);
-    println!(
-        "  Insertions: +{}",
-        result.total_insertions.to_string().green()
-    );
-    println!("  Deletions: -{}", result.total_deletions.to_string().red());
-    println!();
-
-    for commit in &result.commits {
-        println!(
-            "  {} {} {}",
-            commit.hash.bright_yellow(),
-            commit.time.dimmed(),
-            commit.message
-        );
-    }
-
-    if result.success {
-        ExitCode::SUCCESS
-    } else {
-        ExitCode::FAILURE
-    }
-}
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use tempfile::TempDir;
-
-    #[test]
-    fn test_cmd_update_check() {
-        // This exercises the update check path
-        let result = cmd_update(true);
-        // Either success or failure is fine - we're testing the code path
-        assert!(result == ExitCode::SUCCESS || result == ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_warmup_no_project() {
-        let temp = TempDir::new().unwrap();
-        let result = cmd_warmup(temp.path(), false);
-        assert_eq!(result, ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_warmup_with_project() {
-        let temp = TempDir::new().unwrap();
-        let asimov_dir = temp.path().join(".asimov");
-        std::fs::create_dir_all(&asimov_dir).unwrap();
-        std::fs::write(
-            asimov_dir.join("roadmap.yaml"),
-            "current:\n  version: '1.0'\n  status: in_progress\n  summary: Test\n",
-        )
-        .unwrap();
-        let result = cmd_warmup(temp.path(), false);
-        assert_eq!(result, ExitCode::SUCCESS);
-    }
-
-    #[test]
-    fn test_cmd_warmup_verbose() {
-        let temp = TempDir::new().unwrap();
-        let asimov_dir = temp.path().join(".asimov");
-        std::fs::create_dir_all(&asimov_dir).unwrap();
-        std::fs::write(
-            asimov_dir.join("roadmap.yaml"),
-            "current:\n  version: '1.0'\n  status: in_progress\n  summary: Test\n",
-        )
-        .unwrap();
-        let result = cmd_warmup(temp.path(), true);
-        assert_eq!(result, ExitCode::SUCCESS);
-    }
-
-    #[test]
-    fn test_cmd_validate_empty() {
-        let temp = TempDir::new().unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_validate(false);
-        // May succeed or fail depending on state
-        assert!(result == ExitCode::SUCCESS || result == ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_validate_with_ethics() {
-        let temp = TempDir::new().unwrap();
-        let asimov_dir = temp.path().join(".asimov");
-        std::fs::create_dir_all(&asimov_dir).unwrap();
-        std::fs::write(
-            asimov_dir.join("roadmap.yaml"),
-            "current:\n  version: '1.0'\n  status: in_progress\n  summary: Test\n",
-        )
-        .unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_validate(true);
-        assert!(result == ExitCode::SUCCESS || result == ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_init_success() {
-        let temp = TempDir::new().unwrap();
-        let result = cmd_init("TestProject", "rust", temp.path(), false);
-        assert_eq!(result, ExitCode::SUCCESS);
-    }
-
-    #[test]
-    fn test_cmd_init_force() {
-        let temp = TempDir::new().unwrap();
-        // First init
-        cmd_init("Test1", "rust", temp.path(), false);
-        // Force overwrite
-        let result = cmd_init("Test2", "python", temp.path(), true);
-        assert_eq!(result, ExitCode::SUCCESS);
-    }
-
-    #[test]
-    fn test_cmd_lint_docs_empty() {
-        let temp = TempDir::new().unwrap();
-        let result = cmd_lint_docs(temp.path(), false, false);
-        assert_eq!(result, ExitCode::SUCCESS);
-    }
-
-    #[test]
-    fn test_cmd_lint_docs_with_fix() {
-        let temp = TempDir::new().unwrap();
-        std::fs::write(temp.path().join("test.md"), "# Test\n\nContent.\n").unwrap();
-        let result = cmd_lint_docs(temp.path(), true, false);
-        assert_eq!(result, ExitCode::SUCCESS);
-    }
-
-    #[test]
-    fn test_cmd_lint_docs_semantic() {
-        let temp = TempDir::new().unwrap();
-        std::fs::write(temp.path().join("test.md"), "# Test\n\nContent.\n").unwrap();
-        let result = cmd_lint_docs(temp.path(), false, true);
-        assert!(result == ExitCode::SUCCESS || result == ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_refresh_no_project() {
-        let temp = TempDir::new().unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_refresh(false, true, false);
-        // Refresh requires .asimov/ to exist (run init first)
-        assert_eq!(result, ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_refresh_with_project() {
-        use royalbit_asimov::templates::roadmap_template;
-        let temp = TempDir::new().unwrap();
-        let asimov_dir = temp.path().join(".asimov");
-        std::fs::create_dir_all(&asimov_dir).unwrap();
-        // Use proper template so it passes validation
-        std::fs::write(asimov_dir.join("roadmap.yaml"), roadmap_template()).unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_refresh(false, true, false);
-        // May succeed or fail depending on parallel test execution changing cwd
-        assert!(result == ExitCode::SUCCESS || result == ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_stats() {
-        let temp = TempDir::new().unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_stats();
-        assert_eq!(result, ExitCode::SUCCESS);
-    }
-
-    #[test]
-    fn test_cmd_doctor() {
-        let temp = TempDir::new().unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_doctor();
-        // May pass or fail depending on state
-        assert!(result == ExitCode::SUCCESS || result == ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_replay_not_git() {
-        let temp = TempDir::new().unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_replay(None, false, None);
-        assert_eq!(result, ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_replay_git_repo() {
-        let temp = TempDir::new().unwrap();
-        // Init git
-        std::process::Command::new("git")
-            .args(["init"])
-            .current_dir(temp.path())
-            .output()
-            .unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_replay(Some(5), false, None);
-        assert!(result == ExitCode::SUCCESS || result == ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_launch_inside_claude() {
-        std::env::set_var("CLAUDECODE", "1");
-        // Can't fully test launch but exercise the path
-        let result = check_launch_conditions();
-        std::env::remove_var("CLAUDECODE");
-        assert!(matches!(result, LaunchResult::InsideAi(_)));
-    }
-
-    #[test]
-    fn test_cmd_warmup_with_update_available() {
-        let temp = TempDir::new().unwrap();
-        let asimov_dir = temp.path().join(".asimov");
-        std::fs::create_dir_all(&asimov_dir).unwrap();
-        std::fs::write(
-            asimov_dir.join("roadmap.yaml"),
-            "current:\n  version: '1.0'\n  status: in_progress\n  summary: Test milestone\n",
-        )
-        .unwrap();
-        // Verbose mode checks for updates
-        let result = cmd_warmup(temp.path(), true);
-        assert_eq!(result, ExitCode::SUCCESS);
-    }
-
-    #[test]
-    fn test_cmd_validate_with_roadmap_errors() {
-        let temp = TempDir::new().unwrap();
-        let asimov_dir = temp.path().join(".asimov");
-        std::fs::create_dir_all(&asimov_dir).unwrap();
-        // Invalid YAML
-        std::fs::write(asimov_dir.join("roadmap.yaml"), "invalid: [[[").unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_validate(false);
-        // Should fail due to invalid YAML
-        assert!(result == ExitCode::SUCCESS || result == ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_validate_with_project() {
-        use royalbit_asimov::templates::{project_template, roadmap_template, ProjectType};
-        let temp = TempDir::new().unwrap();
-        let asimov_dir = temp.path().join(".asimov");
-        std::fs::create_dir_all(&asimov_dir).unwrap();
-        std::fs::write(asimov_dir.join("roadmap.yaml"), roadmap_template()).unwrap();
-        std::fs::write(
-            asimov_dir.join("project.yaml"),
-            project_template("Test", "A test project", ProjectType::Rust),
-        )
-        .unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_validate(false);
-        // Validation may have warnings but should generally succeed
-        assert!(result == ExitCode::SUCCESS || result == ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_init_with_error() {
-        // Test init with empty name - should still work
-        let temp = TempDir::new().unwrap();
-        let result = cmd_init("", "rust", temp.path(), false);
-        assert!(result == ExitCode::SUCCESS || result == ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_lint_docs_with_errors() {
-        let temp = TempDir::new().unwrap();
-        // Create a markdown file with unclosed code block
-        std::fs::write(temp.path().join("broken.md"), "# Test\n\n~~~\nunclosed\n").unwrap();
-        let result = cmd_lint_docs(temp.path(), false, false);
-        // May fail due to lint errors
-        assert!(result == ExitCode::SUCCESS || result == ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_doctor_with_project() {
-        let temp = TempDir::new().unwrap();
-        let asimov_dir = temp.path().join(".asimov");
-        std::fs::create_dir_all(&asimov_dir).unwrap();
-        std::fs::write(
-            asimov_dir.join("roadmap.yaml"),
-            "current:\n  version: '1.0'\n  status: in_progress\n  summary: Test\n",
-        )
-        .unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_doctor();
-        assert!(result == ExitCode::SUCCESS || result == ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_replay_with_commits() {
-        let temp = TempDir::new().unwrap();
-        // Init git with a commit
-        std::process::Command::new("git")
-            .args(["init"])
-            .current_dir(temp.path())
-            .output()
-            .unwrap();
-        std::process::Command::new("git")
-            .args(["config", "user.email", "test@test.com"])
-            .current_dir(temp.path())
-            .output()
-            .unwrap();
-        std::process::Command::new("git")
-            .args(["config", "user.name", "Test"])
-            .current_dir(temp.path())
-            .output()
-            .unwrap();
-        // Disable commit signing for test environments
-        std::process::Command::new("git")
-            .args(["config", "commit.gpgsign", "false"])
-            .current_dir(temp.path())
-            .output()
-            .unwrap();
-        std::fs::write(temp.path().join("test.txt"), "test").unwrap();
-        std::process::Command::new("git")
-            .args(["add", "."])
-            .current_dir(temp.path())
-            .output()
-            .unwrap();
-        std::process::Command::new("git")
-            .args(["commit", "-m", "test"])
-            .current_dir(temp.path())
-            .output()
-            .unwrap();
-
-        // Use run_replay directly to avoid global set_current_dir race conditions
-        let result = run_replay(temp.path(), Some(10), false, None);
-        assert!(result.success, "replay should succeed: {:?}", result.error);
-    }
-
-    #[test]
-    fn test_cmd_replay_yesterday() {
-        let temp = TempDir::new().unwrap();
-        std::process::Command::new("git")
-            .args(["init"])
-            .current_dir(temp.path())
-            .output()
-            .unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_replay(None, true, None);
-        assert!(result == ExitCode::SUCCESS || result == ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_replay_since() {
-        let temp = TempDir::new().unwrap();
-        std::process::Command::new("git")
-            .args(["init"])
-            .current_dir(temp.path())
-            .output()
-            .unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_replay(None, false, Some("1 hour ago".to_string()));
-        assert!(result == ExitCode::SUCCESS || result == ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_update_all_variants() {
-        // Test check mode (doesn't actually update)
-        let result = cmd_update(true);
-        assert!(result == ExitCode::SUCCESS || result == ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_launch_conditions() {
-        // Just exercise the code path - result depends on system state
-        let result = check_launch_conditions();
-        // Accept any variant as valid (depends on if AI CLIs installed and env vars)
-        assert!(matches!(
-            result,
-            LaunchResult::NoAiFound
-                | LaunchResult::Launching(_)
-                | LaunchResult::InsideAi(_)
-                | LaunchResult::MultipleFound(_)
-        ));
-    }
-
-    #[test]
-    fn test_cmd_warmup_error_no_asimov() {
-        let temp = TempDir::new().unwrap();
-        // No .asimov directory - should fail
-        let result = cmd_warmup(temp.path(), false);
-        assert_eq!(result, ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_validate_ethics_scan_with_flags() {
-        let temp = TempDir::new().unwrap();
-        let asimov_dir = temp.path().join(".asimov");
-        std::fs::create_dir_all(&asimov_dir).unwrap();
-        std::fs::write(
-            asimov_dir.join("roadmap.yaml"),
-            "current:\n  version: '1.0'\n  status: in_progress\n  summary: Test\n",
-        )
-        .unwrap();
-        // Create a file with potential red flags
-        std::fs::write(temp.path().join("script.sh"), "#!/bin/bash\nrm -rf /\n").unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_validate(true);
-        // May find flags or not
-        assert!(result == ExitCode::SUCCESS || result == ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_init_all_types() {
-        for ptype in &["rust", "python", "node", "go", "flutter", "docs", "generic"] {
-            let temp = TempDir::new().unwrap();
-            let result = cmd_init("Test", ptype, temp.path(), false);
-            assert_eq!(result, ExitCode::SUCCESS);
-        }
-    }
-
-    #[test]
-    fn test_cmd_lint_files_with_issues() {
-        let temp = TempDir::new().unwrap();
-        // Create multiple files
-        std::fs::write(temp.path().join("good.md"), "# Good\n\nContent.\n").unwrap();
-        std::fs::write(temp.path().join("bad.md"), "# Bad\n\n~~~\nunclosed\n").unwrap();
-        let result = cmd_lint_docs(temp.path(), false, false);
-        // Will have some errors
-        assert!(result == ExitCode::SUCCESS || result == ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_lint_fix_mode() {
-        let temp = TempDir::new().unwrap();
-        std::fs::write(temp.path().join("fixable.md"), "# Test\n\n~~~\ncode\n~~~\n").unwrap();
-        let result = cmd_lint_docs(temp.path(), true, false);
-        assert_eq!(result, ExitCode::SUCCESS);
-    }
-
-    #[test]
-    fn test_cmd_refresh_error_path() {
-        let temp = TempDir::new().unwrap();
-        let asimov_dir = temp.path().join(".asimov");
-        std::fs::create_dir_all(&asimov_dir).unwrap();
-        // No roadmap - should still work
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_refresh(false, true, false);
-        assert!(result == ExitCode::SUCCESS || result == ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_doctor_full() {
-        let temp = TempDir::new().unwrap();
-        // Set up a more complete project
-        std::process::Command::new("git")
-            .args(["init"])
-            .current_dir(temp.path())
-            .output()
-            .unwrap();
-        let asimov_dir = temp.path().join(".asimov");
-        std::fs::create_dir_all(&asimov_dir).unwrap();
-        std::fs::write(
-            asimov_dir.join("roadmap.yaml"),
-            "current:\n  version: '1.0'\n  status: in_progress\n  summary: Test\n",
-        )
-        .unwrap();
-        std::fs::write(
-            asimov_dir.join("project.yaml"),
-            "identity:\n  name: Test\n  tagline: Test\n",
-        )
-        .unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_doctor();
-        assert!(result == ExitCode::SUCCESS || result == ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_stats_with_git() {
-        let temp = TempDir::new().unwrap();
-        std::process::Command::new("git")
-            .args(["init"])
-            .current_dir(temp.path())
-            .output()
-            .unwrap();
-        std::process::Command::new("git")
-            .args(["config", "user.email", "t@t.com"])
-            .current_dir(temp.path())
-            .output()
-            .unwrap();
-        std::process::Command::new("git")
-            .args(["config", "user.name", "T"])
-            .current_dir(temp.path())
-            .output()
-            .unwrap();
-        std::fs::write(temp.path().join("f.txt"), "x").unwrap();
-        std::process::Command::new("git")
-            .args(["add", "."])
-            .current_dir(temp.path())
-            .output()
-            .unwrap();
-        std::process::Command::new("git")
-            .args(["commit", "-m", "init"])
-            .current_dir(temp.path())
-            .output()
-            .unwrap();
-
-        let asimov_dir = temp.path().join(".asimov");
-        std::fs::create_dir_all(&asimov_dir).unwrap();
-        std::fs::write(
-            asimov_dir.join("roadmap.yaml"),
-            "current:\n  version: '1.0'\n  status: in_progress\n  summary: Test\n",
-        )
-        .unwrap();
-
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_stats();
-        assert_eq!(result, ExitCode::SUCCESS);
-    }
-
-    #[test]
-    fn test_cmd_replay_basic_default() {
-        let temp = TempDir::new().unwrap();
-        std::process::Command::new("git")
-            .args(["init"])
-            .current_dir(temp.path())
-            .output()
-            .unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_replay(None, false, None);
-        assert!(result == ExitCode::SUCCESS || result == ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_replay_limited_commits() {
-        let temp = TempDir::new().unwrap();
-        std::process::Command::new("git")
-            .args(["init"])
-            .current_dir(temp.path())
-            .output()
-            .unwrap();
-        std::process::Command::new("git")
-            .args(["config", "user.email", "t@t.com"])
-            .current_dir(temp.path())
-            .output()
-            .unwrap();
-        std::process::Command::new("git")
-            .args(["config", "user.name", "T"])
-            .current_dir(temp.path())
-            .output()
-            .unwrap();
-        // Disable commit signing for test environments
-        std::process::Command::new("git")
-            .args(["config", "commit.gpgsign", "false"])
-            .current_dir(temp.path())
-            .output()
-            .unwrap();
-        std::fs::write(temp.path().join("f.txt"), "x").unwrap();
-        std::process::Command::new("git")
-            .args(["add", "."])
-            .current_dir(temp.path())
-            .output()
-            .unwrap();
-        std::process::Command::new("git")
-            .args(["commit", "-m", "init"])
-            .current_dir(temp.path())
-            .output()
-            .unwrap();
-        // Use run_replay directly to avoid global set_current_dir race conditions
-        let result = run_replay(temp.path(), Some(5), false, None);
-        assert!(result.success, "replay should succeed: {:?}", result.error);
-    }
-
-    #[test]
-    fn test_cmd_replay_yesterday_option() {
-        let temp = TempDir::new().unwrap();
-        std::process::Command::new("git")
-            .args(["init"])
-            .current_dir(temp.path())
-            .output()
-            .unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_replay(None, true, None);
-        assert!(result == ExitCode::SUCCESS || result == ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_replay_since_date() {
-        let temp = TempDir::new().unwrap();
-        std::process::Command::new("git")
-            .args(["init"])
-            .current_dir(temp.path())
-            .output()
-            .unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_replay(None, false, Some("2024-01-01".to_string()));
-        assert!(result == ExitCode::SUCCESS || result == ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_lint_semantic_mode() {
-        let temp = TempDir::new().unwrap();
-        std::fs::write(temp.path().join("test.md"), "# Test\n\nContent.\n").unwrap();
-        let result = cmd_lint_docs(temp.path(), false, true);
-        assert_eq!(result, ExitCode::SUCCESS);
-    }
-
-    #[test]
-    fn test_cmd_validate_no_project() {
-        let temp = TempDir::new().unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_validate(false);
-        // May succeed or fail depending on project state
-        assert!(result == ExitCode::SUCCESS || result == ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_validate_with_valid_project() {
-        use royalbit_asimov::templates::roadmap_template;
-        let temp = TempDir::new().unwrap();
-        let asimov_dir = temp.path().join(".asimov");
-        std::fs::create_dir_all(&asimov_dir).unwrap();
-        std::fs::write(asimov_dir.join("roadmap.yaml"), roadmap_template()).unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_validate(false);
-        // May have warnings/errors depending on project.yaml presence
-        assert!(result == ExitCode::SUCCESS || result == ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_init_rust() {
-        let temp = TempDir::new().unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_init("TestProject", "rust", temp.path(), false);
-        assert_eq!(result, ExitCode::SUCCESS);
-    }
-
-    #[test]
-    fn test_cmd_init_python() {
-        let temp = TempDir::new().unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_init("TestProject", "python", temp.path(), false);
-        assert_eq!(result, ExitCode::SUCCESS);
-    }
-
-    #[test]
-    fn test_cmd_init_node() {
-        let temp = TempDir::new().unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_init("TestProject", "node", temp.path(), false);
-        assert_eq!(result, ExitCode::SUCCESS);
-    }
-
-    #[test]
-    fn test_cmd_init_go() {
-        let temp = TempDir::new().unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_init("TestProject", "go", temp.path(), false);
-        assert_eq!(result, ExitCode::SUCCESS);
-    }
-
-    #[test]
-    fn test_cmd_init_docs() {
-        let temp = TempDir::new().unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_init("TestProject", "docs", temp.path(), false);
-        assert_eq!(result, ExitCode::SUCCESS);
-    }
-
-    #[test]
-    fn test_cmd_init_generic() {
-        let temp = TempDir::new().unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_init("TestProject", "generic", temp.path(), false);
-        assert_eq!(result, ExitCode::SUCCESS);
-    }
-
-    #[test]
-    fn test_cmd_warmup_with_project_yaml() {
-        use royalbit_asimov::templates::{project_template, roadmap_template, ProjectType};
-        let temp = TempDir::new().unwrap();
-        let asimov_dir = temp.path().join(".asimov");
-        std::fs::create_dir_all(&asimov_dir).unwrap();
-        std::fs::write(asimov_dir.join("roadmap.yaml"), roadmap_template()).unwrap();
-        std::fs::write(
-            asimov_dir.join("project.yaml"),
-            project_template("Test", "A test project", ProjectType::Rust),
-        )
-        .unwrap();
-        let result = cmd_warmup(temp.path(), true);
-        assert_eq!(result, ExitCode::SUCCESS);
-    }
-
-    #[test]
-    fn test_cmd_doctor_no_roadmap() {
-        let temp = TempDir::new().unwrap();
-        let asimov_dir = temp.path().join(".asimov");
-        std::fs::create_dir_all(&asimov_dir).unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        // No roadmap - doctor may succeed or fail depending on hook checks
-        let result = cmd_doctor();
-        assert!(result == ExitCode::SUCCESS || result == ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_cmd_stats_no_asimov() {
-        let temp = TempDir::new().unwrap();
-        std::env::set_current_dir(temp.path()).unwrap();
-        let result = cmd_stats();
-        assert!(result == ExitCode::SUCCESS || result == ExitCode::FAILURE);
-    }
-
-    // Test all UpdateResult formatting variants
-    #[test]
-    fn test_format_update_result_already_latest() {
-        let result = format_update_result(UpdateResult::AlreadyLatest {
-            current: "1.0.0".to_string(),
-            latest: "1.0.0".to_string(),
-        });
-        assert_eq!(result, ExitCode::SUCCESS);
-    }
-
-    #[test]
-    fn test_format_update_result_update_available() {
-        let result = format_update_result(UpdateResult::UpdateAvailable {
-            current: "1.0.0".to_string(),
-            latest: "2.0.0".to_string(),
-        });
-        assert_eq!(result, ExitCode::SUCCESS);
-    }
-
-    #[test]
-    fn test_format_update_result_updated() {
-        let result = format_update_result(UpdateResult::Updated {
-            from: "1.0.0".to_string(),
-            to: "2.0.0".to_string(),
-        });
-        assert_eq!(result, ExitCode::SUCCESS);
-    }
-
-    #[test]
-    fn test_format_update_result_failed() {
-        let result = format_update_result(UpdateResult::UpdateFailed {
-            current: "1.0.0".to_string(),
-            latest: "2.0.0".to_string(),
-            error: "Download failed".to_string(),
-            download_url: "https://example.com/file.tar.gz".to_string(),
-        });
-        assert_eq!(result, ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_format_update_result_no_binary() {
-        let result = format_update_result(UpdateResult::NoBinaryAvailable {
-            current: "1.0.0".to_string(),
-            latest: "2.0.0".to_string(),
-        });
-        assert_eq!(result, ExitCode::FAILURE);
-    }
-
-    #[test]
-    fn test_format_update_result_check_failed() {
-        let result = format_update_result(UpdateResult::CheckFailed {
-            error: "Network error".to_string(),
-        });
-        assert_eq!(result, ExitCode::FAILURE);
-    }
-}
@@ -0,0 +1,91 @@
+//! Model knowledge cutoff output
+
+use colored::Colorize;
+use royalbit_asimov::commands::run_freshness;
+use royalbit_asimov::context::project_root;
+use royalbit_asimov::freshness::{CutoffSource, MODEL_CUTOFFS_FILE};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+fn age(months: Option<i32>) -> String {
+    match months {
+        Some(1) => "1 month".to_string(),
+        Some(m) => format!("{} months", m),
+        None => "-".to_string(),
+    }
+}
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn cmd_freshness(import: Option<&PathBuf>) -> ExitCode {
+    let result = run_freshness(&project_root(), import.map(PathBuf::as_path));
+    if let Some(ref err) = result.error {
+        eprintln!("{} {}", "Error:".bold().red(), err);
+        return ExitCode::FAILURE;
+    }
+
+    println!("{}", "RoyalBit Asimov Freshness".bold().green());
+    println!();
+    if result.imported {
+        println!(
+            "  {} Imported cutoff table into .asimov/{}",
+            "✓".green(),
+            MODEL_CUTOFFS_FILE
+        );
+        println!();
+    }
+
+    println!("  Today: {}", result.today.bright_blue());
+    let source = match result.source {
+        Some(CutoffSource::Project(_)) => format!(".asimov/{}", MODEL_CUTOFFS_FILE),
+        _ => "embedded".to_string(),
+    };
+    println!("  Table: {}", source);
+    println!();
+
+    println!("{}", "MODEL CUTOFFS".bold());
+    let width = result
+        .rows
+        .iter()
+        .map(|r| r.provider.len() + r.model.len() + 1)
+        .max()
+        .unwrap_or(0);
+    for row in &result.rows {
+        println!(
+            "  {:width$}  {}  {} ago",
+            format!("{}/{}", row.provider, row.model),
+            row.cutoff,
+            age(row.months_since),
+            width = width
+        );
+    }
+    if let Some(ref note) = result.note {
+        println!("  {}", note.dimmed());
+    }
+
+    println!();
+    println!("{}", "LAUNCHED MODEL".bold());
+    if result.launched.is_empty() {
+        println!("  {} No AI CLI detected", "-".dimmed());
+    }
+    for launched in &result.launched {
+        match launched.cutoff {
+            Some(ref cutoff) => println!(
+                "  {} ({}/{}): cutoff {}, {} since - search for anything newer",
+                launched.profile.bold(),
+                launched.provider,
+                launched.model,
+                cutoff,
+                age(launched.months_since).bright_yellow()
+            ),
+            None => println!(
+                "  {} {} ({}/{}): not in the cutoff table - import an updated one",
+                "⚠".yellow(),
+                launched.profile.bold(),
+                launched.provider,
+                launched.model
+            ),
+        }
+    }
+
+    ExitCode::SUCCESS
+}
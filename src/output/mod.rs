@@ -0,0 +1,1456 @@
+//! CLI output formatting - command handlers
+//!
+//! Each function formats and outputs command results.
+//! Tests live in output/tests.rs to keep this file under 1500 lines (ADR-040)
+
+use colored::Colorize;
+use royalbit_asimov::commands::{
+    check_junit_report, check_launch_conditions, run_check, run_doctor, run_hook,
+    run_hooks_install, run_hooks_status, run_hooks_uninstall, run_init_with_vars,
+    run_lint_docs_with_scope, run_lsp, run_refresh_with_options, run_replay, run_role,
+    run_schema_export, run_schema_list, run_schema_show, run_stats, run_update,
+    run_validate_with_scope, run_warmup, run_warmup_strict, AiProfile, CheckOptions, GateResult,
+    GateStatus, HookAction, HookState, HooksResult, LaunchResult, PlannedWrite, RefreshOptions,
+    RoleError, RoleResult, UpdateResult, ValidateFileResult, ValidateResult,
+};
+use royalbit_asimov::context::{current_context, cwd_relative, project_root};
+use royalbit_asimov::find_contexts;
+use royalbit_asimov::templates::{parse_var_assignments, ProjectDetection};
+use royalbit_asimov::validator::TrustState;
+use royalbit_asimov::{ProtocolOutcome, ScanScope};
+use std::io::{self, IsTerminal as _, Write as _};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+// ============================================================================
+// THIN WRAPPERS - Call commands.rs and format output
+// These are CLI output formatters, tested via e2e tests (ADR-039)
+// ============================================================================
+
+/// Prompt user to select an AI CLI when multiple are available
+#[cfg_attr(feature = "coverage", coverage(off))]
+fn prompt_ai_selection(profiles: &[AiProfile]) -> Option<AiProfile> {
+    println!("{}", "Multiple AI CLIs detected:".bold().yellow());
+    println!();
+    for (i, profile) in profiles.iter().enumerate() {
+        println!("  {}. {} ({})", i + 1, profile.name, profile.binary);
+    }
+    println!();
+    print!("Select AI to launch [1-{}]: ", profiles.len());
+    io::stdout().flush().ok()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).ok()?;
+    let choice: usize = input.trim().parse().ok()?;
+
+    if choice >= 1 && choice <= profiles.len() {
+        Some(profiles[choice - 1].clone())
+    } else {
+        None
+    }
+}
+
+/// Launch an AI CLI with minimal warmup context (v12.2.0)
+#[cfg_attr(feature = "coverage", coverage(off))]
+fn launch_ai(profile: &AiProfile) -> ExitCode {
+    println!("{}", format!("Launching {}...", profile.name).bright_cyan());
+
+    // Get warmup content directly
+    let warmup_result = run_warmup(std::path::Path::new("."), false);
+    if warmup_result.error.is_some() {
+        eprintln!(
+            "{} Failed to generate warmup context",
+            "Error:".bold().red()
+        );
+        return ExitCode::FAILURE;
+    }
+
+    // Build minimal warmup JSON (v12.2.0)
+    let warmup: serde_json::Value = warmup_result
+        .warmup_protocol
+        .as_ref()
+        .map(|w| serde_json::to_value(w).unwrap_or(serde_json::json!({})))
+        .unwrap_or(serde_json::json!({}));
+
+    let tools: Vec<serde_json::Value> = warmup_result
+        .tools_available
+        .iter()
+        .map(|t| {
+            serde_json::json!({
+                "name": t.name,
+                "directive": t.directive
+            })
+        })
+        .collect();
+
+    let output = serde_json::json!({
+        "warmup": warmup,
+        "tools": tools
+    });
+
+    // Pass warmup as prompt argument (not stdin - breaks terminal raw mode)
+    let prompt = output.to_string();
+
+    let mut cmd = std::process::Command::new(profile.binary);
+    cmd.args(profile.auto_mode_args);
+    cmd.arg(&prompt);
+
+    match cmd.status() {
+        Ok(s) if s.success() => ExitCode::SUCCESS,
+        Ok(_) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!(
+                "{} Failed to start {}: {}",
+                "Error:".bold().red(),
+                profile.name,
+                e
+            );
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn cmd_launch() -> ExitCode {
+    match check_launch_conditions() {
+        LaunchResult::InsideAi(name) => {
+            // Inside an AI session - run warmup directly
+            if std::env::var("ASIMOV_DEBUG").is_ok() {
+                eprintln!("{} Inside {} session", "Debug:".dimmed(), name);
+            }
+            cmd_warmup(std::path::Path::new("."), false, false)
+        }
+        LaunchResult::NoAiFound => {
+            eprintln!("{} No AI CLI found in PATH", "Error:".bold().red());
+            eprintln!();
+            eprintln!("Install one of:");
+            eprintln!("  Claude Code: https://claude.ai/download");
+            eprintln!("  Gemini CLI:  https://cloud.google.com/gemini-cli");
+            eprintln!("  Codex CLI:   https://github.com/openai/codex");
+            ExitCode::FAILURE
+        }
+        LaunchResult::Launching(profile) => launch_ai(&profile),
+        LaunchResult::MultipleFound(profiles) => match prompt_ai_selection(&profiles) {
+            Some(profile) => launch_ai(&profile),
+            None => {
+                eprintln!("{} Invalid selection", "Error:".bold().red());
+                ExitCode::FAILURE
+            }
+        },
+    }
+}
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn cmd_update(check_only: bool) -> ExitCode {
+    println!("{}", "RoyalBit Asimov Update".bold().green());
+    println!();
+    format_update_result(run_update(check_only))
+}
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn format_update_result(result: UpdateResult) -> ExitCode {
+    match result {
+        UpdateResult::AlreadyLatest { current, .. } => {
+            println!(
+                "  {} v{} is the latest version",
+                "OK".bold().green(),
+                current
+            );
+            ExitCode::SUCCESS
+        }
+        UpdateResult::UpdateAvailable { current, latest } => {
+            println!("  Current: {}", current.bright_blue());
+            println!("  Latest:  {}", latest.bright_green());
+            println!();
+            println!(
+                "  {} Run {} to install",
+                "UPDATE".bold().yellow(),
+                "asimov update".bold()
+            );
+            ExitCode::SUCCESS
+        }
+        UpdateResult::Updated { from, to } => {
+            println!("  {} Updated {} → {}", "Success:".bold().green(), from, to);
+            ExitCode::SUCCESS
+        }
+        UpdateResult::UpdateFailed {
+            error,
+            download_url,
+            ..
+        } => {
+            eprintln!("  {} {}", "Error:".bold().red(), error);
+            eprintln!("  Manual: curl -L {} | tar xz", download_url);
+            ExitCode::FAILURE
+        }
+        UpdateResult::NoBinaryAvailable { .. } => {
+            eprintln!("  {} No binary for this platform", "Error:".bold().red());
+            ExitCode::FAILURE
+        }
+        UpdateResult::CheckFailed { error } => {
+            eprintln!("  {} {}", "Error:".bold().red(), error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// v12.2.0: Minimal warmup output - just warmup protocol + tools
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn cmd_warmup(path: &std::path::Path, verbose: bool, strict: bool) -> ExitCode {
+    let result = if strict {
+        run_warmup_strict(path)
+    } else {
+        run_warmup(path, false)
+    };
+
+    if let Some(ref err) = result.error {
+        eprintln!("{} {}", "Error:".bold().red(), err);
+        if err.contains("not found") {
+            eprintln!(
+                "  Run {} first",
+                "asimov init --name <NAME> --type <TYPE>".bold()
+            );
+        }
+        return ExitCode::FAILURE;
+    }
+
+    // Serialize warmup protocol
+    let warmup: serde_json::Value = result
+        .warmup_protocol
+        .as_ref()
+        .map(|w| serde_json::to_value(w).unwrap_or(serde_json::json!({})))
+        .unwrap_or(serde_json::json!({}));
+
+    // Build tools section
+    let tools: Vec<serde_json::Value> = result
+        .tools_available
+        .iter()
+        .map(|t| {
+            serde_json::json!({
+                "name": t.name,
+                "directive": t.directive
+            })
+        })
+        .collect();
+
+    // Minimal output: just warmup + tools
+    let output = serde_json::json!({
+        "warmup": warmup,
+        "tools": tools
+    });
+
+    if verbose {
+        // Pretty print for human readability
+        println!("{}", serde_json::to_string_pretty(&output).unwrap());
+    } else {
+        // Compact for AI consumption
+        println!("{}", output);
+    }
+
+    ExitCode::SUCCESS
+}
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn cmd_validate(
+    ethics_scan: bool,
+    scope: ScanScope,
+    all: bool,
+    strict: bool,
+) -> ExitCode {
+    println!("{}", "RoyalBit Asimov Validate".bold().green());
+    println!();
+
+    // Validate the nearest context; --all walks every package context
+    let context = current_context();
+    if !all {
+        let mut result = run_validate_with_scope(&project_root(), ethics_scan, &scope);
+        if strict {
+            result.make_strict();
+        }
+        print_validate_result(&result);
+        return validate_exit(result.success);
+    }
+
+    let root = context.map_or_else(|| PathBuf::from("."), |c| c.root().to_path_buf());
+    let mut contexts = find_contexts(&root);
+    if contexts.is_empty() {
+        contexts.push(root.clone());
+    }
+    let mut success = true;
+    for dir in &contexts {
+        let label = dir
+            .strip_prefix(&root)
+            .ok()
+            .filter(|rel| !rel.as_os_str().is_empty())
+            .map_or_else(|| ".".to_string(), |rel| rel.display().to_string());
+        println!("{} {}", "PACKAGE".bold().cyan(), label);
+        let mut result = run_validate_with_scope(&cwd_relative(dir), ethics_scan, &scope);
+        if strict {
+            result.make_strict();
+        }
+        print_validate_result(&result);
+        println!();
+        success &= result.success;
+    }
+    println!("  {} package context(s) validated", contexts.len());
+    validate_exit(success)
+}
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+fn validate_exit(success: bool) -> ExitCode {
+    println!();
+    if success {
+        println!("{} All validations passed", "Success:".bold().green());
+        ExitCode::SUCCESS
+    } else {
+        println!("{} Validation failed", "Error:".bold().red());
+        ExitCode::FAILURE
+    }
+}
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+fn print_validate_result(result: &ValidateResult) {
+    // Show roadmap validation
+    if result.roadmap.is_some() || result.project.is_some() {
+        println!("{}", "ROADMAP & PROJECT".bold());
+    }
+    if let Some(ref r) = result.roadmap {
+        print_file_validation("roadmap.yaml", r);
+    }
+
+    // Show project validation
+    if let Some(ref p) = result.project {
+        print_file_validation("project.yaml", p);
+    }
+
+    // Roles, templates, protocol overrides, hook templates
+    if !result.customizations.is_empty() {
+        println!();
+        println!("{}", "CUSTOMIZATIONS".bold());
+        let mut valid = 0;
+        for r in &result.customizations {
+            let name = r.file.strip_prefix("./").unwrap_or(&r.file);
+            if !r.valid {
+                print_file_validation(name, r);
+            } else if !r.warnings.is_empty() {
+                println!("  {} {}", "⚠".yellow(), name);
+            } else {
+                valid += 1;
+            }
+            for w in &r.warnings {
+                println!("      {}", w.yellow());
+            }
+        }
+        if valid > 0 {
+            println!(
+                "  {} {} valid (roles, templates, protocols, hooks)",
+                "✓".green(),
+                valid
+            );
+        }
+    }
+
+    // Protocol files against the signed bundle
+    if !result.protocol_trust.is_empty() {
+        println!();
+        println!("{}", "PROTOCOL BUNDLE".bold());
+        let verified = result
+            .protocol_trust
+            .iter()
+            .filter(|t| t.state == TrustState::Verified)
+            .count();
+        if verified > 0 {
+            println!("  {} {} verified", "✓".green(), verified);
+        }
+        for t in result
+            .protocol_trust
+            .iter()
+            .filter(|t| t.state == TrustState::Tampered)
+        {
+            println!(
+                "  {} {} {} ({})",
+                "✗".red(),
+                t.filename,
+                t.state.to_string().red(),
+                t.reason.as_deref().unwrap_or_default()
+            );
+        }
+        // Unsigned files mostly share a reason (e.g. an unsigned bundle)
+        let mut unsigned: Vec<(&str, Vec<&str>)> = Vec::new();
+        for t in result
+            .protocol_trust
+            .iter()
+            .filter(|t| t.state == TrustState::UnsignedLocal)
+        {
+            let reason = t.reason.as_deref().unwrap_or_default();
+            match unsigned.iter_mut().find(|(r, _)| *r == reason) {
+                Some((_, files)) => files.push(&t.filename),
+                None => unsigned.push((reason, vec![&t.filename])),
+            }
+        }
+        for (reason, files) in unsigned {
+            let what = match files[..] {
+                [file] => file.to_string(),
+                _ => files.len().to_string(),
+            };
+            println!(
+                "  {} {} {} ({})",
+                "⚠".yellow(),
+                what,
+                TrustState::UnsignedLocal.to_string().yellow(),
+                reason
+            );
+        }
+    }
+
+    // Show ethics scan results
+    if let Some(ref scan) = result.ethics_scan {
+        println!();
+        println!("{}{}", "Ethics Scan".bold(), scope_label(&scan.scope));
+        if let Some(ref err) = scan.error {
+            println!("  {} {}", "✗".red(), err);
+        } else if scan.red_flags_found > 0 {
+            println!(
+                "  {} {} red flag(s) found",
+                "⚠".yellow(),
+                scan.red_flags_found
+            );
+            for m in &scan.matches {
+                println!("      {}:{} - {}", m.file, m.line, m.pattern);
+            }
+        } else {
+            println!("  {} No red flags found", "✓".green());
+        }
+    }
+}
+
+/// Suffix describing a scan scope
+fn scope_label(scope: &ScanScope) -> String {
+    match scope {
+        ScanScope::All => String::new(),
+        ScanScope::Staged => " (staged, added lines only)".to_string(),
+        ScanScope::Diff(base) => format!(" (since {}, added lines only)", base),
+    }
+}
+
+/// Print a file's validation status, with `file:line:col` errors and code frames
+#[cfg_attr(feature = "coverage", coverage(off))]
+fn print_file_validation(name: &str, r: &ValidateFileResult) {
+    if r.valid {
+        println!("  {} {}", "✓".green(), name);
+        return;
+    }
+
+    println!("  {} {}", "✗".red(), name);
+    let located = r.diagnostics.len();
+    for d in &r.diagnostics {
+        println!("      {}", d.to_string().red());
+        for line in d.code_frame.lines() {
+            println!("      {}", line.dimmed());
+        }
+    }
+    // Structural errors without a source location
+    for e in r.errors.iter().skip(located) {
+        println!("      {}", e.red());
+    }
+}
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn cmd_init(
+    name: &str,
+    project_type: &str,
+    output: &std::path::Path,
+    force: bool,
+    vars: &[String],
+) -> ExitCode {
+    let mut vars = match parse_var_assignments(vars) {
+        Ok(vars) => vars,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".bold().red(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    // Ask for declared template variables on a terminal
+    if io::stdin().is_terminal() {
+        let answers = prompt_template_vars(project_type, &vars);
+        vars.extend(answers);
+    }
+    let result = run_init_with_vars(output, name, project_type, force, &vars);
+
+    if let Some(ref err) = result.error {
+        eprintln!("{} {}", "Error:".bold().red(), err);
+        return ExitCode::FAILURE;
+    }
+
+    println!("{}", "RoyalBit Asimov Init".bold().green());
+    println!();
+
+    for f in &result.files_created {
+        println!("  {} {}", "CREATE".green(), f);
+    }
+    for f in &result.files_updated {
+        println!("  {} {}", "UPDATE".yellow(), f);
+    }
+    for f in &result.files_kept {
+        println!("  {} {}", "KEEP".bright_blue(), f);
+    }
+    for h in &result.hooks_installed {
+        println!("  {} {}", "HOOK".bright_cyan(), h);
+    }
+
+    // v9.7.0: Show dev dependencies added
+    for d in &result.deps_added {
+        println!("  {} {}", "DEP".bright_magenta(), d);
+    }
+
+    println!();
+    if result.success {
+        println!("{} Project initialized", "Success:".bold().green());
+        println!();
+
+        // v9.7.0: Show install instructions if any
+        if !result.install_instructions.is_empty() {
+            println!("Install tools:");
+            for instr in &result.install_instructions {
+                if instr.starts_with("Note:") {
+                    println!("  {} {}", "!".yellow(), instr);
+                } else {
+                    println!("  $ {}", instr.bright_cyan());
+                }
+            }
+            println!();
+        }
+
+        println!("Next steps:");
+        println!("  1. Edit .asimov/roadmap.yaml with your milestones");
+        println!("  2. Run: asimov warmup");
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn cmd_lint_docs(
+    path: &std::path::Path,
+    fix: bool,
+    semantic: bool,
+    scope: ScanScope,
+    max_claim_age_months: u32,
+) -> ExitCode {
+    let result = run_lint_docs_with_scope(path, fix, semantic, &scope, max_claim_age_months);
+
+    if let Some(ref err) = result.error {
+        eprintln!("{} {}", "Error:".bold().red(), err);
+        return ExitCode::FAILURE;
+    }
+
+    println!("{}", "RoyalBit Asimov Lint".bold().green());
+    println!();
+    println!(
+        "  {} markdown file(s) checked{}",
+        result.files_checked,
+        scope_label(&result.scope)
+    );
+
+    if result.files_with_errors > 0 {
+        println!(
+            "  Files with errors: {}",
+            result.files_with_errors.to_string().red()
+        );
+    }
+
+    if fix && result.files_fixed > 0 {
+        println!("  Files fixed: {}", result.files_fixed.to_string().green());
+    }
+
+    if semantic && result.semantic_files_checked > 0 {
+        println!("  Semantic Checks: {} files", result.semantic_files_checked);
+        for issue in &result.semantic_issues {
+            println!(
+                "    {} {}:{} - {}",
+                "⚠".yellow(),
+                issue.file,
+                issue.line,
+                issue.message
+            );
+        }
+    }
+
+    println!();
+    if result.success {
+        println!("{} All checks passed", "Success:".bold().green());
+        ExitCode::SUCCESS
+    } else {
+        println!("{} Issues found", "Error:".bold().red());
+        ExitCode::FAILURE
+    }
+}
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn cmd_refresh(verbose: bool, yes: bool, dry_run: bool, strict: bool) -> ExitCode {
+    let options = RefreshOptions {
+        yes,
+        dry_run,
+        strict,
+    };
+    let result = run_refresh_with_options(&project_root(), options);
+
+    if !result.is_asimov_project {
+        eprintln!("{} Not in an asimov project", "Error:".bold().red());
+        eprintln!("  Run {} first", "asimov init".bold());
+        return ExitCode::FAILURE;
+    }
+
+    if let Some(ref err) = result.error {
+        eprintln!("{} {}", "Error:".bold().red(), err);
+        return ExitCode::FAILURE;
+    }
+
+    println!("{}", "RoyalBit Asimov REFRESH".bold().green());
+    if dry_run {
+        println!("{}", "(dry run - no changes made)".dimmed());
+    }
+    println!();
+
+    // Schema version migrations
+    for m in &result.schema_migrations {
+        println!(
+            "  {} {} (schema_version {} → {}: {})",
+            "MIGRATE".yellow(),
+            m.file,
+            m.from,
+            m.to,
+            m.steps.join(", ")
+        );
+    }
+
+    // v9.0.0: Protocol integrity status
+    // Local edits are merged with the new defaults, not overwritten
+    for item in &result.protocol_refresh {
+        let f = &item.filename;
+        match item.outcome {
+            ProtocolOutcome::Updated => println!("  {} {} (was outdated)", "UPDATED".yellow(), f),
+            ProtocolOutcome::Created => println!("  {} {}", "CREATED".green(), f),
+            ProtocolOutcome::Unchanged => println!("  {} {}", "OK".dimmed(), f),
+            ProtocolOutcome::Merged => {
+                println!("  {} {} (local edits kept)", "MERGED".green(), f)
+            }
+            ProtocolOutcome::Customized => {
+                println!(
+                    "  {} {} (customized, default unchanged)",
+                    "KEPT".dimmed(),
+                    f
+                )
+            }
+            ProtocolOutcome::Conflict => println!(
+                "  {} {} → .asimov/{}.conflict{}",
+                "CONFLICT".red().bold(),
+                f,
+                f,
+                if item.had_base {
+                    ""
+                } else {
+                    " (no recorded base)"
+                }
+            ),
+        }
+    }
+
+    // Data files (roadmap.yaml etc)
+    for f in &result.files_regenerated {
+        println!("  {} {}", "REGENERATE".green(), f);
+    }
+    for f in &result.files_unchanged {
+        println!("  {} {}", "UNCHANGED".dimmed(), f);
+    }
+
+    // v9.6.0: Pre-commit hook regeneration (ADR-043)
+    if result.hook_regenerated {
+        println!(
+            "  {} pre-commit (direct enforcement, see asimov hooks status)",
+            "HOOK".green()
+        );
+    }
+
+    // Component-aware configuration for monorepos and polyglot repositories
+    if result.detection.is_multi_component() && !result.components_declared {
+        print_components_suggestion(&result.detection);
+    }
+
+    // v9.5.0: Migration status
+    if verbose {
+        if let Some(ref pt) = result.project_type_detected {
+            println!();
+            println!("{}", "MIGRATION".bold());
+            println!("  Project type: {}", pt.to_string().bright_blue());
+            if result.project_type_was_missing {
+                println!("    {} Type was missing, now set", "→".yellow());
+            }
+            if result.coding_standards_upgraded {
+                println!(
+                    "    {} coding_standards upgraded to v9.4.0 format",
+                    "→".yellow()
+                );
+            }
+        }
+    }
+
+    println!();
+    if result.success {
+        let updated_count = result.protocols_updated.len();
+        let merged_count = result.protocols_merged.len();
+        let mut msg = match (updated_count, merged_count) {
+            (0, 0) => "Protocols refreshed".to_string(),
+            (updated, 0) => format!("Protocols refreshed ({} updated)", updated),
+            (updated, merged) => format!(
+                "Protocols refreshed ({} updated, {} merged)",
+                updated, merged
+            ),
+        };
+        if result.coding_standards_upgraded {
+            msg.push_str(", coding_standards upgraded");
+        }
+        if result.hook_regenerated {
+            msg.push_str(", pre-commit hook updated");
+        }
+        if !result.protocols_conflicted.is_empty() {
+            println!();
+            println!(
+                "{} {} protocol file(s) conflict with local edits.",
+                "Conflict:".bold().red(),
+                result.protocols_conflicted.len()
+            );
+            println!(
+                "  Resolve each .asimov/<file>.json.conflict into .asimov/<file>.json, delete the .conflict file and run 'asimov refresh' again."
+            );
+            if !dry_run {
+                return ExitCode::FAILURE;
+            }
+        }
+        if dry_run {
+            return print_refresh_plan(&result.changes);
+        }
+        if !result.changes.is_empty() {
+            msg.push_str(&format!(", {} file(s) written", result.changes.len()));
+        }
+        println!("{} {}", "Success:".bold().green(), msg);
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Diffs of the writes a dry run planned
+///
+/// Exits 0 when nothing would change and 2 when changes are pending, so CI
+/// can detect drift with `asimov refresh --dry-run`.
+#[cfg_attr(feature = "coverage", coverage(off))]
+fn print_refresh_plan(changes: &[PlannedWrite]) -> ExitCode {
+    if changes.is_empty() {
+        println!(
+            "{} No changes - the project is up to date",
+            "Dry run:".bold().green()
+        );
+        return ExitCode::SUCCESS;
+    }
+
+    println!("{}", "CHANGES".bold());
+    for change in changes {
+        let kind = if change.old.is_none() {
+            "new"
+        } else {
+            "modified"
+        };
+        println!("  {} {} ({})", "•".yellow(), change.file, kind);
+        for line in change.diff().lines() {
+            println!("      {}", color_diff_line(line));
+        }
+    }
+    println!();
+    let (new, modified): (Vec<_>, Vec<_>) = changes.iter().partition(|c| c.old.is_none());
+    println!(
+        "{} {} file(s) would change ({} new, {} modified)",
+        "Dry run:".bold().yellow(),
+        changes.len(),
+        new.len(),
+        modified.len()
+    );
+    ExitCode::from(2)
+}
+
+/// Detected components and the project.yaml section that declares them
+#[cfg_attr(feature = "coverage", coverage(off))]
+fn print_components_suggestion(detection: &ProjectDetection) {
+    println!();
+    let workspaces: Vec<String> = detection.workspaces.iter().map(|w| w.to_string()).collect();
+    if workspaces.is_empty() {
+        println!("{}", "COMPONENTS".bold());
+    } else {
+        println!(
+            "{} ({} workspace)",
+            "COMPONENTS".bold(),
+            workspaces.join(", ")
+        );
+    }
+    for c in &detection.components {
+        let declared = if c.declared { "" } else { ", undeclared" };
+        println!(
+            "  {} {} ({} via {}, {} confidence{})",
+            "•".dimmed(),
+            c.path.display(),
+            c.project_type.to_string().bright_blue(),
+            c.marker,
+            c.confidence,
+            declared
+        );
+    }
+    println!("  Suggested for .asimov/project.yaml:");
+    for line in detection.components_yaml().lines() {
+        println!("    {}", line.dimmed());
+    }
+}
+
+/// Color a unified diff line (additions green, removals red)
+#[cfg_attr(feature = "coverage", coverage(off))]
+fn color_diff_line(line: &str) -> String {
+    if line.starts_with("+++") || line.starts_with("---") {
+        line.bold().to_string()
+    } else if line.starts_with('+') {
+        line.green().to_string()
+    } else if line.starts_with('-') {
+        line.red().to_string()
+    } else if line.starts_with("@@") {
+        line.cyan().to_string()
+    } else {
+        line.dimmed().to_string()
+    }
+}
+
+/// Quality gate runner
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn cmd_check(
+    only: Vec<String>,
+    changed: bool,
+    json: bool,
+    junit: Option<&std::path::Path>,
+) -> ExitCode {
+    let result = run_check(&project_root(), &CheckOptions { only, changed });
+
+    if let Some(ref err) = result.error {
+        eprintln!("{} {}", "Error:".bold().red(), err);
+        return ExitCode::FAILURE;
+    }
+
+    if let Some(path) = junit {
+        if let Err(e) = std::fs::write(path, check_junit_report(&result)) {
+            eprintln!(
+                "{} Failed to write {}: {}",
+                "Error:".bold().red(),
+                path.display(),
+                e
+            );
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&result).expect("Check report serialization")
+        );
+    } else {
+        println!("{}", "RoyalBit Asimov CHECK".bold().green());
+        println!();
+        if result.gates.is_empty() {
+            println!(
+                "  {} No quality gates declared (add quality: to .asimov/project.yaml)",
+                "⚠".yellow()
+            );
+        }
+        for gate in &result.gates {
+            print_gate(gate);
+        }
+        println!();
+        if result.success {
+            println!(
+                "{} {} passed, {} skipped",
+                "Success:".bold().green(),
+                result.count(GateStatus::Passed),
+                result.count(GateStatus::Skipped)
+            );
+        } else {
+            println!(
+                "{} {} of {} gates failed",
+                "Error:".bold().red(),
+                result.count(GateStatus::Failed),
+                result.gates.len()
+            );
+        }
+    }
+
+    if result.success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+fn print_gate(gate: &GateResult) {
+    let seconds = gate.duration_ms as f64 / 1000.0;
+    match gate.status {
+        GateStatus::Passed => {
+            println!("  {} {} ({:.1}s)", "✓".green(), gate.name, seconds)
+        }
+        GateStatus::Failed => {
+            let code = gate
+                .exit_code
+                .map(|c| format!(", exit {}", c))
+                .unwrap_or_default();
+            println!("  {} {} ({:.1}s{})", "✗".red(), gate.name, seconds, code);
+            println!("      {}", gate.command.dimmed());
+            for line in gate.output_tail.lines() {
+                println!("      {}", line.dimmed());
+            }
+        }
+        GateStatus::Skipped => println!(
+            "  {} {} (skipped: {})",
+            "-".dimmed(),
+            gate.name,
+            gate.skip_reason.as_deref().unwrap_or_default()
+        ),
+    }
+}
+
+/// Native git hook runner - called by the installed hook shim
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn cmd_hook_run(hook: &str, args: &[String]) -> ExitCode {
+    // The message file is named from the working directory, not the project root
+    let mut args = args.to_vec();
+    if let (Some(file), "commit-msg") = (args.first_mut(), hook) {
+        if let Ok(absolute) = std::path::absolute(&*file) {
+            *file = absolute.to_string_lossy().into_owned();
+        }
+    }
+    let result = run_hook(&project_root(), hook, &args);
+
+    if let Some(ref err) = result.error {
+        eprintln!("{} {}", "Error:".bold().red(), err);
+        return ExitCode::FAILURE;
+    }
+
+    println!(
+        "{} ({})",
+        format!("RoyalBit Asimov {}", result.hook.to_uppercase())
+            .bold()
+            .green(),
+        result.project_type
+    );
+    println!();
+
+    if result.hook == "pre-commit" {
+        println!("{}", "FILE SIZES".bold());
+        if result.oversized_files.is_empty() {
+            println!(
+                "  {} {} staged files",
+                "✓".green(),
+                result.staged_files.len()
+            );
+        }
+        for file in &result.oversized_files {
+            let marker = if result.size_limit_blocking {
+                "✗".red()
+            } else {
+                "⚠".yellow()
+            };
+            println!(
+                "  {} {} has {} lines (limit: {})",
+                marker, file.file, file.lines, file.max_lines
+            );
+        }
+        if !result.oversized_files.is_empty() {
+            println!("      Split large files to improve maintainability (ADR-040)");
+        }
+        println!();
+    }
+
+    if result.hook == "commit-msg" {
+        let source = if result.policy_declared {
+            "project.yaml"
+        } else {
+            "no commit_msg policy"
+        };
+        println!("{} ({})", "COMMIT MESSAGE".bold(), source);
+        if result.message_errors.is_empty() {
+            println!("  {} OK", "✓".green());
+        }
+        for error in &result.message_errors {
+            println!("  {} {}", "✗".red(), error);
+        }
+        println!();
+    }
+
+    if let Some(ref gates) = result.gates {
+        let source = if result.gates_declared {
+            "project.yaml"
+        } else {
+            "defaults"
+        };
+        println!("{} ({})", "QUALITY GATES".bold(), source);
+        if gates.gates.is_empty() {
+            println!(
+                "  {} No quality gates (add quality: to .asimov/project.yaml)",
+                "-".dimmed()
+            );
+        }
+        for gate in &gates.gates {
+            print_gate(gate);
+        }
+        println!();
+    }
+
+    if !result.outdated_protocols.is_empty() {
+        println!("{}", "PROTOCOLS".bold());
+        for file in &result.outdated_protocols {
+            println!("  {} {} is outdated", "⚠".yellow(), file);
+        }
+        println!("      Run: asimov refresh");
+        println!();
+    }
+
+    if !result.red_flags.is_empty() {
+        println!("{}", "ETHICS (review before committing)".bold());
+        for flag in &result.red_flags {
+            println!(
+                "  {} {}:{} [{}] {}",
+                "⚠".yellow(),
+                flag.file,
+                flag.line,
+                flag.category,
+                flag.pattern
+            );
+            println!("      {}", flag.context.dimmed());
+        }
+        println!();
+    }
+
+    if result.success {
+        println!(
+            "{} {} checks passed",
+            "Success:".bold().green(),
+            result.hook
+        );
+        ExitCode::SUCCESS
+    } else {
+        println!("{} {} checks failed", "Error:".bold().red(), result.hook);
+        ExitCode::FAILURE
+    }
+}
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn cmd_hooks_status() -> ExitCode {
+    let result = run_hooks_status(&project_root());
+    print_hooks_location(&result);
+    if let Some(ref err) = result.error {
+        eprintln!("{} {}", "Error:".bold().red(), err);
+        return ExitCode::FAILURE;
+    }
+
+    for status in &result.hooks {
+        let (marker, state) = match &status.state {
+            HookState::Installed => ("✓".green(), "asimov".to_string()),
+            HookState::Chained => ("✓".green(), "asimov (chains previous hook)".to_string()),
+            HookState::Managed {
+                manager,
+                integrated: true,
+            } => ("✓".green(), format!("asimov via {}", manager)),
+            HookState::Managed {
+                manager,
+                integrated: false,
+            } => ("⚠".yellow(), format!("{} (asimov not configured)", manager)),
+            HookState::Foreign => ("⚠".yellow(), "other hook (asimov not run)".to_string()),
+            HookState::Missing => ("-".dimmed(), "not installed".to_string()),
+        };
+        println!("  {} {:<11} {}", marker, status.hook, state);
+    }
+
+    if result.hooks.iter().any(|h| !h.state.runs_asimov()) {
+        println!();
+        println!("  Run: asimov hooks install");
+    }
+    ExitCode::SUCCESS
+}
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn cmd_hooks_install(hooks: &[String]) -> ExitCode {
+    let result = run_hooks_install(&project_root(), hooks);
+    print_hooks_changes(&result)
+}
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn cmd_hooks_uninstall(hooks: &[String]) -> ExitCode {
+    let result = run_hooks_uninstall(&project_root(), hooks);
+    print_hooks_changes(&result)
+}
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+fn print_hooks_location(result: &HooksResult) {
+    println!("{}", "RoyalBit Asimov Hooks".bold().green());
+    println!();
+    if let Some(ref location) = result.location {
+        println!("  Hooks directory: {}", location.hooks_dir.display());
+        if let Some(ref hooks_path) = location.hooks_path_config {
+            println!("  core.hooksPath: {}", hooks_path);
+        }
+        if let Some((manager, ref config)) = location.manager {
+            println!("  Hook manager: {} ({})", manager, config.display());
+        }
+        println!();
+    }
+}
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+fn print_hooks_changes(result: &HooksResult) -> ExitCode {
+    print_hooks_location(result);
+
+    for change in &result.changes {
+        let hook = &change.hook;
+        match &change.action {
+            HookAction::Installed => println!("  {} {} installed", "✓".green(), hook),
+            HookAction::Updated => println!("  {} {} updated", "✓".green(), hook),
+            HookAction::Unchanged => println!("  {} {} up to date", "-".dimmed(), hook),
+            HookAction::Chained { backup } => println!(
+                "  {} {} installed, previous hook kept as {} and run first",
+                "✓".green(),
+                hook,
+                backup
+            ),
+            HookAction::AddedToManager { manager, file } => {
+                println!("  {} {} added to {} ({})", "✓".green(), hook, manager, file)
+            }
+            HookAction::ManualSetup {
+                manager,
+                file,
+                snippet,
+            } => {
+                println!(
+                    "  {} {}: {} manages hooks, edit {}:",
+                    "⚠".yellow(),
+                    hook,
+                    manager,
+                    file
+                );
+                for line in snippet.lines() {
+                    println!("      {}", line);
+                }
+            }
+            HookAction::Removed => println!("  {} {} removed", "✓".green(), hook),
+            HookAction::Restored { backup } => {
+                println!("  {} {} removed, restored {}", "✓".green(), hook, backup)
+            }
+            HookAction::RemovedFromManager { manager, file } => println!(
+                "  {} {} removed from {} ({})",
+                "✓".green(),
+                hook,
+                manager,
+                file
+            ),
+            HookAction::NotInstalled => println!("  {} {} not installed", "-".dimmed(), hook),
+        }
+    }
+
+    if let Some(ref err) = result.error {
+        eprintln!("{} {}", "Error:".bold().red(), err);
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn cmd_stats() -> ExitCode {
+    let result = run_stats(&project_root());
+
+    println!("{}", "RoyalBit Asimov Stats".bold().green());
+    println!();
+    println!("  Session: {}", result.session_date.bright_blue());
+    println!("  Commits today: {}", result.today_commits);
+    println!("  Asimov commits: {}", result.asimov_commits);
+    println!("  Total commits: {}", result.total_commits);
+
+    if let Some(ref ver) = result.milestone_version {
+        println!();
+        println!("  Milestone: v{}", ver.bright_yellow());
+        if let Some(ref summary) = result.milestone_summary {
+            println!("  Summary: {}", summary);
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn cmd_doctor() -> ExitCode {
+    let result = run_doctor(&project_root());
+
+    println!("{}", "RoyalBit ASIMOV - DOCTOR".bold().green());
+    println!();
+
+    // v9.8.0: Display detected license (ADR-045)
+    if let Some(ref license) = result.license {
+        println!("{}", "DEPENDENCY HEALTH".bold());
+        println!("  License: {} (detected)", license.bright_blue());
+        println!();
+    }
+
+    for check in &result.checks {
+        let icon = if check.passed {
+            "✓".green()
+        } else {
+            "✗".red()
+        };
+        println!("  {} {}: {}", icon, check.name, check.message);
+        if check.auto_fixed {
+            println!("      {} Auto-fixed", "→".yellow());
+        }
+    }
+
+    if !result.issues.is_empty() {
+        println!();
+        println!("{}", "Issues:".bold().red());
+        for issue in &result.issues {
+            println!("  • {}", issue);
+        }
+    }
+
+    if !result.warnings.is_empty() {
+        println!();
+        println!("{}", "Warnings:".bold().yellow());
+        for warn in &result.warnings {
+            println!("  • {}", warn);
+        }
+    }
+
+    if let Some((ver, is_latest)) = &result.version_info {
+        println!();
+        if *is_latest {
+            println!("  {} v{} (latest)", "Version:".bold(), ver);
+        } else {
+            println!(
+                "  {} v{} (update available)",
+                "Version:".bold().yellow(),
+                ver
+            );
+        }
+    }
+
+    println!();
+    let passed = result.checks.iter().filter(|c| c.passed).count();
+    let total = result.checks.len();
+    println!("{} {}/{} checks passed", "Result:".bold(), passed, total);
+
+    if passed == total && result.issues.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Language server - stdout carries the protocol, so errors go to stderr
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn cmd_lsp() -> ExitCode {
+    match run_lsp() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".bold().red(), e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn cmd_schema_list() -> ExitCode {
+    println!("{}", "RoyalBit Asimov - SCHEMAS".bold().green());
+    println!();
+    for entry in run_schema_list() {
+        println!(
+            "  {} - {}",
+            entry.name.bright_cyan().bold(),
+            entry.description
+        );
+        println!("      {}", entry.file_match.join(", ").dimmed());
+    }
+    println!();
+    println!("Usage: {} <name>", "asimov schema show".bold());
+    ExitCode::SUCCESS
+}
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn cmd_schema_show(name: &str) -> ExitCode {
+    match run_schema_show(name) {
+        Some(json) => {
+            println!("{}", json);
+            ExitCode::SUCCESS
+        }
+        None => {
+            eprintln!("{} Schema '{}' not found", "Error:".bold().red(), name);
+            eprintln!(
+                "  Use {} to list available schemas",
+                "asimov schema list".bold()
+            );
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn cmd_schema_export(dir: &std::path::Path, base_url: Option<&str>) -> ExitCode {
+    let result = run_schema_export(dir, base_url);
+    if let Some(error) = &result.error {
+        eprintln!("{} {}", "Error:".bold().red(), error);
+        return ExitCode::FAILURE;
+    }
+
+    println!("{}", "RoyalBit Asimov - SCHEMA EXPORT".bold().green());
+    println!();
+    for file in &result.files_written {
+        println!("  {} {}", "✓".green(), file);
+    }
+    if let Some(catalog) = &result.catalog {
+        println!("  {} {}", "✓".green(), catalog);
+    }
+    println!();
+    println!(
+        "Exported {} schemas (v{}) to {}",
+        result.files_written.len(),
+        result.version,
+        dir.display()
+    );
+    ExitCode::SUCCESS
+}
+
+/// Role switching command (v10.0.0)
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn cmd_role(role_code: Option<&str>) -> ExitCode {
+    match run_role(role_code) {
+        Ok(RoleResult::List { roles, invalid }) => {
+            println!("{}", "RoyalBit Asimov - ROLES".bold().green());
+            println!();
+            println!("Available roles:");
+            println!();
+            for role in &roles {
+                println!("  {} - {}", role.code.bright_cyan().bold(), role.name);
+                println!("      {}", role.description.dimmed());
+            }
+            if !invalid.is_empty() {
+                println!();
+                println!("{}", "Invalid role files:".bold().red());
+                for error in &invalid {
+                    println!("  {} {}", "✗".red(), error);
+                }
+            }
+            println!();
+            println!("Usage: {} <code>", "asimov role".bold());
+            if invalid.is_empty() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Ok(RoleResult::Selected(role)) => {
+            println!("{}", "RoyalBit Asimov - ROLE ACTIVE".bold().green());
+            println!();
+            println!("  Role: {} ({})", role.name.bright_cyan().bold(), role.code);
+            println!("  {}", role.description);
+            println!();
+            println!("{}", "FOCUS AREAS:".bold());
+            for area in &role.focus {
+                println!("  • {}", area.bright_green());
+            }
+            println!();
+            println!("{}", "PROMPT PREFIX:".bold());
+            println!("  {}", role.prompt_prefix.bright_yellow());
+            println!();
+            if !role.avoid.is_empty() {
+                println!("{}", "AVOID (defer to others):".bold());
+                for topic in &role.avoid {
+                    println!("  • {}", topic.dimmed());
+                }
+                println!();
+            }
+            ExitCode::SUCCESS
+        }
+        Err(RoleError::NoRolesFound) => {
+            eprintln!("{} No roles found", "Error:".bold().red());
+            eprintln!("  Create role files in .asimov/roles/*.json");
+            eprintln!();
+            eprintln!("Example role JSON:");
+            eprintln!(r#"  {{"name": "Engineer", "code": "eng", ...}}"#);
+            ExitCode::FAILURE
+        }
+        Err(RoleError::RoleNotFound(code)) => {
+            eprintln!("{} Role '{}' not found", "Error:".bold().red(), code);
+            eprintln!("  Use {} to list available roles", "asimov role".bold());
+            ExitCode::FAILURE
+        }
+        Err(RoleError::InvalidRole(code, reason)) => {
+            eprintln!("{} Role '{}' is invalid", "Error:".bold().red(), code);
+            eprintln!("  {}", reason);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn cmd_replay(
+    commits: Option<usize>,
+    yesterday: bool,
+    since: Option<String>,
+) -> ExitCode {
+    let result = run_replay(&project_root(), commits, yesterday, since);
+
+    if !result.is_git_repo {
+        eprintln!("{} Not a git repository", "Error:".bold().red());
+        return ExitCode::FAILURE;
+    }
+
+    if let Some(ref err) = result.error {
+        eprintln!("{} {}", "Error:".bold().red(), err);
+        return ExitCode::FAILURE;
+    }
+
+    println!("{}", "RoyalBit Asimov Replay".bold().green());
+    println!();
+    println!("  Range: {}", result.range_description.bright_blue());
+    println!("  Commits: {}", result.commits.len());
+    println!("  Files changed: {}", result.total_files_changed);
+    println!(
+        "  Insertions: +{}",
+        result.total_insertions.to_string().green()
+    );
+    println!("  Deletions: -{}", result.total_deletions.to_string().red());
+    println!();
+
+    for commit in &result.commits {
+        println!(
+            "  {} {} {}",
+            commit.hash.bright_yellow(),
+            commit.time.dimmed(),
+            commit.message
+        );
+    }
+
+    if result.success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+mod freshness;
+mod protocols;
+mod template;
+pub(crate) use freshness::*;
+pub(crate) use protocols::*;
+pub(crate) use template::*;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,56 @@
+//! Protocol layer output
+
+use colored::Colorize;
+use royalbit_asimov::commands::run_protocols_explain;
+use std::process::ExitCode;
+
+/// Longest field value printed before it is shortened
+const MAX_VALUE_WIDTH: usize = 72;
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn cmd_protocols_explain(name: &str) -> ExitCode {
+    let result = run_protocols_explain(name);
+    if let Some(ref err) = result.error {
+        eprintln!("{} {}", "Error:".bold().red(), err);
+        return ExitCode::FAILURE;
+    }
+
+    println!("{} {}", "PROTOCOL".bold().green(), name.bold());
+    println!();
+    println!("{}", "LAYERS (lowest first)".bold());
+    for layer in &result.layers {
+        match layer.value {
+            Ok(_) => println!("  {} {}", "✓".green(), layer.label()),
+            Err(ref e) => println!("  {} {} - skipped: {}", "✗".red(), layer.label(), e),
+        }
+    }
+
+    println!();
+    println!("{}", "FIELDS".bold());
+    for field in &result.fields {
+        let value = if field.value.chars().count() > MAX_VALUE_WIDTH {
+            let short: String = field.value.chars().take(MAX_VALUE_WIDTH - 1).collect();
+            format!("{}…", short)
+        } else {
+            field.value.clone()
+        };
+        println!(
+            "  {} {} {}",
+            field.field.bold(),
+            "←".dimmed(),
+            field.layers.join(" + ").bright_blue()
+        );
+        println!("      {}", value.dimmed());
+    }
+
+    if let Some(ref e) = result.fallback {
+        println!();
+        println!(
+            "{} The merged protocol is invalid, the embedded default is used instead: {}",
+            "Error:".bold().red(),
+            e
+        );
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
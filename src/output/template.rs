@@ -0,0 +1,170 @@
+//! Template authoring output
+
+use super::{color_diff_line, print_file_validation};
+use colored::Colorize;
+use royalbit_asimov::commands::{
+    run_template_diff, run_template_lint, run_template_new, run_template_show,
+};
+use royalbit_asimov::context::project_root;
+use royalbit_asimov::templates::{
+    find_template, get_template_by_name, parse_var_assignments, template_variables,
+    BUILTIN_VARIABLES,
+};
+use std::io::{self, Write as _};
+use std::process::ExitCode;
+
+/// Ask for template variables `asimov init` was not given (`--var`)
+///
+/// An empty answer keeps the default.
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn prompt_template_vars(
+    project_type: &str,
+    given: &[(String, String)],
+) -> Vec<(String, String)> {
+    let name = find_template(project_type)
+        .map(|t| t.name.to_string())
+        .unwrap_or_else(|| project_type.to_lowercase());
+    let Some(content) = get_template_by_name(&name) else {
+        return Vec::new();
+    };
+    let Ok(vars) = template_variables(&name, &content) else {
+        return Vec::new();
+    };
+
+    let mut answers = Vec::new();
+    for var in vars {
+        if BUILTIN_VARIABLES.contains(&var.name.as_str())
+            || given.iter().any(|(key, _)| key == &var.name)
+        {
+            continue;
+        }
+        let question = var.prompt.as_deref().unwrap_or(&var.name);
+        match var.default {
+            Some(ref default) => print!("{} [{}]: ", question, default.dimmed()),
+            None => print!("{}: ", question),
+        }
+        if io::stdout().flush().is_err() {
+            break;
+        }
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input).is_err() {
+            break;
+        }
+        let answer = input.trim();
+        if !answer.is_empty() {
+            answers.push((var.name, answer.to_string()));
+        }
+    }
+    answers
+}
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn cmd_template_new(name: &str, from: Option<&str>, force: bool) -> ExitCode {
+    let result = run_template_new(&project_root(), name, from, force);
+    match result.path {
+        Some(ref path) if result.success => {
+            println!(
+                "  {} {} (extends {})",
+                "CREATE".green(),
+                path,
+                result.extends
+            );
+            println!();
+            println!("Preview: asimov template show {}", name);
+            ExitCode::SUCCESS
+        }
+        _ => {
+            let err = result.error.unwrap_or_default();
+            eprintln!("{} {}", "Error:".bold().red(), err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn cmd_template_show(name: &str, vars: &[String]) -> ExitCode {
+    let vars = match parse_var_assignments(vars) {
+        Ok(vars) => vars,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".bold().red(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let result = run_template_show(name, &vars);
+
+    let source = result.source.as_deref().unwrap_or("embedded");
+    match result.extends {
+        Some(ref parent) => eprintln!("# {} ({}, extends {})", name, source, parent),
+        None => eprintln!("# {} ({})", name, source),
+    }
+    for var in &result.variables {
+        let value = vars
+            .iter()
+            .rev()
+            .find(|(key, _)| key == &var.name)
+            .map(|(_, value)| value.as_str())
+            .or(var.default.as_deref())
+            .unwrap_or("(required)");
+        let prompt = var.prompt.as_deref().unwrap_or_default();
+        eprintln!("#   {} = {}  {}", var.name.bold(), value, prompt.dimmed());
+    }
+
+    if let Some(ref err) = result.error {
+        eprintln!("{} {}", "Error:".bold().red(), err);
+        return ExitCode::FAILURE;
+    }
+    // Rendered YAML on stdout so it can be redirected
+    print!("{}", result.rendered.unwrap_or_default());
+    ExitCode::SUCCESS
+}
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn cmd_template_lint(name: Option<&str>) -> ExitCode {
+    let result = run_template_lint(&project_root(), name);
+    if let Some(ref err) = result.error {
+        eprintln!("{} {}", "Error:".bold().red(), err);
+        return ExitCode::FAILURE;
+    }
+
+    println!("{}", "TEMPLATES".bold());
+    if result.results.is_empty() {
+        println!("  No custom templates in .asimov/templates/");
+    }
+    for r in &result.results {
+        print_file_validation(&r.file, r);
+        for w in &r.warnings {
+            println!("      {}", w.yellow());
+        }
+    }
+
+    println!();
+    if result.success {
+        println!(
+            "{} All templates render valid project.yaml",
+            "Success:".bold().green()
+        );
+        ExitCode::SUCCESS
+    } else {
+        println!("{} Template lint failed", "Error:".bold().red());
+        ExitCode::FAILURE
+    }
+}
+
+#[cfg_attr(feature = "coverage", coverage(off))]
+pub(crate) fn cmd_template_diff(name: &str) -> ExitCode {
+    let result = run_template_diff(name);
+    if let Some(ref err) = result.error {
+        eprintln!("{} {}", "Error:".bold().red(), err);
+        return ExitCode::FAILURE;
+    }
+
+    if result.diff.is_empty() {
+        println!("{} renders the same as {}", name, result.against);
+    } else {
+        println!("{} vs {}", name.bold(), result.against);
+        for line in result.diff.lines() {
+            println!("{}", color_diff_line(line));
+        }
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,259 @@
+//! Protocol activation
+//!
+//! Which files a project loads at warmup is decided by declarative rules
+//! instead of `compile_protocols_for_type` (removed in ADR-062). A rule
+//! holds when every condition it gives holds:
+//!
+//! - `project_types` - `identity.type` of project.yaml is one of these
+//! - `not_project_types` - `identity.type` is none of these
+//! - `files_exist` - at least one of these paths exists in the project
+//! - `roadmap_goals` - `current.goal` of roadmap.yaml is one of these
+//!
+//! Built-in files are active when any of their rules holds (files without
+//! rules always are); custom protocols declare one rule as `include_when`.
+//! Types and goals compare case-insensitively, and type aliases
+//! (`migrations`) are normalized.
+
+use crate::context::AsimovContext;
+use crate::templates::ProjectType;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Activation rules of the built-in warmup files; any rule activates a file
+///
+/// Docs-only projects have no code for the coding standards to apply to;
+/// migration projects (by type or roadmap goal) load their
+/// functional-equivalence protocol, which init, refresh and validate write
+/// from `migrations_template` when it is missing.
+const BUILTIN_ACTIVATION: &str = r#"{
+  "coding-standards.json": [
+    { "not_project_types": ["docs"] }
+  ],
+  "migrations.yaml": [
+    { "project_types": ["migration"] },
+    { "roadmap_goals": ["FUNCTIONAL-EQUIVALENCE"] }
+  ]
+}"#;
+
+/// Warmup file of the functional-equivalence protocol
+pub const MIGRATIONS_FILE: &str = ".asimov/migrations.yaml";
+
+/// Conditions under which a protocol is active; every one given must hold
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ActivationRule {
+    #[serde(default)]
+    pub project_types: Vec<String>,
+    #[serde(default)]
+    pub not_project_types: Vec<String>,
+    #[serde(default)]
+    pub files_exist: Vec<String>,
+    #[serde(default)]
+    pub roadmap_goals: Vec<String>,
+}
+
+/// What activation rules are checked against
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProjectFacts {
+    /// Normalized `identity.type`
+    pub project_type: Option<String>,
+    /// `current.goal` of the roadmap
+    pub roadmap_goal: Option<String>,
+    /// Directory `files_exist` paths are relative to
+    pub root: PathBuf,
+}
+
+fn normalize_type(name: &str) -> String {
+    name.parse::<ProjectType>()
+        .map(|t| t.to_string())
+        .unwrap_or_else(|_| name.to_lowercase())
+}
+
+fn yaml_string(path: Option<PathBuf>, keys: &[&str]) -> Option<String> {
+    let content = std::fs::read_to_string(path?).ok()?;
+    let yaml: serde_yaml_ng::Value = serde_yaml_ng::from_str(&content).ok()?;
+    let mut value = &yaml;
+    for key in keys {
+        value = value.get(key)?;
+    }
+    Some(value.as_str()?.to_string())
+}
+
+impl ProjectFacts {
+    /// Facts of the project `dir` belongs to (packages inherit project.yaml
+    /// and roadmap.yaml from enclosing contexts)
+    pub fn discover(dir: &Path) -> Self {
+        let Some(context) = AsimovContext::discover(dir) else {
+            return ProjectFacts {
+                root: dir.to_path_buf(),
+                ..ProjectFacts::default()
+            };
+        };
+        ProjectFacts {
+            project_type: yaml_string(context.resolve("project.yaml"), &["identity", "type"])
+                .map(|t| normalize_type(&t)),
+            roadmap_goal: yaml_string(context.resolve("roadmap.yaml"), &["current", "goal"]),
+            root: context.dir.clone(),
+        }
+    }
+}
+
+impl ActivationRule {
+    /// Whether the rule holds for a project
+    pub fn matches(&self, facts: &ProjectFacts) -> bool {
+        let is_type = |types: &[String]| {
+            facts
+                .project_type
+                .as_ref()
+                .is_some_and(|t| types.iter().any(|name| normalize_type(name) == *t))
+        };
+        let goal_matches = facts.roadmap_goal.as_ref().is_some_and(|goal| {
+            self.roadmap_goals
+                .iter()
+                .any(|g| g.eq_ignore_ascii_case(goal.trim()))
+        });
+
+        (self.project_types.is_empty() || is_type(&self.project_types))
+            && !is_type(&self.not_project_types)
+            && (self.files_exist.is_empty()
+                || self
+                    .files_exist
+                    .iter()
+                    .any(|file| facts.root.join(file).exists()))
+            && (self.roadmap_goals.is_empty() || goal_matches)
+    }
+}
+
+/// Rules of the built-in warmup files, by file name
+pub fn builtin_activation() -> BTreeMap<String, Vec<ActivationRule>> {
+    serde_json::from_str(BUILTIN_ACTIVATION).expect("Built-in activation rules must be valid")
+}
+
+/// Whether a built-in warmup file (`.asimov/<name>`) is active
+pub fn is_builtin_active(file: &str, facts: &ProjectFacts) -> bool {
+    let name = file.rsplit('/').next().unwrap_or(file);
+    builtin_activation()
+        .get(name)
+        .is_none_or(|rules| rules.iter().any(|rule| rule.matches(facts)))
+}
+
+/// Warmup files without inactive built-ins, plus `migrations.yaml` after
+/// the roadmap when it is active
+pub fn active_builtin_files(mut files: Vec<String>, facts: &ProjectFacts) -> Vec<String> {
+    files.retain(|file| is_builtin_active(file, facts));
+    if is_builtin_active(MIGRATIONS_FILE, facts) && !files.iter().any(|f| f == MIGRATIONS_FILE) {
+        let position = files
+            .iter()
+            .position(|f| f.ends_with("roadmap.yaml"))
+            .map_or(0, |i| i + 1);
+        files.insert(position, MIGRATIONS_FILE.to_string());
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn facts(project_type: Option<&str>, goal: Option<&str>, root: &Path) -> ProjectFacts {
+        ProjectFacts {
+            project_type: project_type.map(normalize_type),
+            roadmap_goal: goal.map(String::from),
+            root: root.to_path_buf(),
+        }
+    }
+
+    #[test]
+    fn test_rule_conditions() {
+        let temp = TempDir::new().unwrap();
+        let rule = ActivationRule {
+            project_types: vec!["Migrations".into()],
+            roadmap_goals: vec!["functional-equivalence".into()],
+            ..ActivationRule::default()
+        };
+        let project = facts(
+            Some("migration"),
+            Some("FUNCTIONAL-EQUIVALENCE"),
+            temp.path(),
+        );
+        assert!(rule.matches(&project));
+        assert!(!rule.matches(&facts(
+            Some("rust"),
+            Some("FUNCTIONAL-EQUIVALENCE"),
+            temp.path()
+        )));
+        assert!(!rule.matches(&facts(Some("migration"), None, temp.path())));
+
+        let not_docs = ActivationRule {
+            not_project_types: vec!["docs".into()],
+            ..ActivationRule::default()
+        };
+        assert!(not_docs.matches(&facts(None, None, temp.path())));
+        assert!(!not_docs.matches(&facts(Some("docs"), None, temp.path())));
+
+        let files = ActivationRule {
+            files_exist: vec!["Dockerfile".into(), "compose.yaml".into()],
+            ..ActivationRule::default()
+        };
+        assert!(!files.matches(&project));
+        std::fs::write(temp.path().join("compose.yaml"), "").unwrap();
+        assert!(files.matches(&project));
+    }
+
+    #[test]
+    fn test_builtin_files_by_project() {
+        let temp = TempDir::new().unwrap();
+        let files: Vec<String> = [
+            ".asimov/project.yaml",
+            ".asimov/roadmap.yaml",
+            ".asimov/asimov.json",
+            ".asimov/coding-standards.json",
+        ]
+        .iter()
+        .map(|f| f.to_string())
+        .collect();
+
+        let rust = active_builtin_files(files.clone(), &facts(Some("rust"), None, temp.path()));
+        assert_eq!(rust, files);
+        let docs = active_builtin_files(files.clone(), &facts(Some("docs"), None, temp.path()));
+        assert!(!docs.iter().any(|f| f.contains("coding-standards")));
+
+        // Migration projects load their protocol after the roadmap
+        let migration = facts(Some("migration"), None, temp.path());
+        assert_eq!(
+            active_builtin_files(files.clone(), &migration)[2],
+            MIGRATIONS_FILE
+        );
+        let goal = facts(Some("java"), Some("functional-equivalence"), temp.path());
+        assert!(is_builtin_active(MIGRATIONS_FILE, &goal));
+        assert!(
+            !active_builtin_files(files, &facts(Some("rust"), None, temp.path()))
+                .contains(&MIGRATIONS_FILE.into())
+        );
+    }
+
+    #[test]
+    fn test_discover_facts() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join(".git")).unwrap();
+        std::fs::create_dir_all(temp.path().join(".asimov")).unwrap();
+        std::fs::write(
+            temp.path().join(".asimov/project.yaml"),
+            "identity:\n  name: x\n  type: migrations\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp.path().join(".asimov/roadmap.yaml"),
+            "current:\n  version: \"1.0\"\n  goal: FUNCTIONAL-EQUIVALENCE\n",
+        )
+        .unwrap();
+        let facts = ProjectFacts::discover(temp.path());
+        assert_eq!(facts.project_type.as_deref(), Some("migration"));
+        assert_eq!(
+            facts.roadmap_goal.as_deref(),
+            Some("FUNCTIONAL-EQUIVALENCE")
+        );
+    }
+}
@@ -0,0 +1,254 @@
+//! Signed protocol bundle
+//!
+//! `protocols/manifest.json` lists the SHA-256 of every protocol file the
+//! binary generates from its embedded protocols, signed with ed25519. The
+//! public key is compiled in (`PROTOCOL_PUBLIC_KEY`); the secret key never
+//! is. Keys are generated and the manifest signed offline by the
+//! maintainers:
+//!
+//! ```text
+//! cargo run --example sign_protocols -- keygen <secret-key-file>
+//! cargo run --example sign_protocols -- sign <secret-key-file>
+//! ```
+//!
+//! Until a key is set the bundle ships unsigned and every protocol file is
+//! at best `unsigned-local`. A protocol file in `.asimov/` whose hash is in a
+//! verified bundle is `verified`; see `validator::check_protocol_trust` for
+//! the other states.
+
+use super::{
+    integrity_form, AsimovProtocol, CodingStandardsProtocol, FreshnessProtocol, GreenProtocol,
+    SprintProtocol, SycophancyProtocol, WarmupEntry, WarmupProtocol,
+};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+/// Public key the embedded bundle manifest is signed with (hex), once the
+/// maintainers have generated one
+pub const PROTOCOL_PUBLIC_KEY: Option<&str> = None;
+
+/// Signed manifest of the embedded protocols
+/// (`include_str!("../../protocols/manifest.json")` once signed)
+const BUNDLE_MANIFEST_JSON: Option<&str> = None;
+
+/// Content hashes of a protocol bundle and their signature
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleManifest {
+    /// asimov version the bundle was signed for
+    pub version: String,
+    /// Protocol file name -> SHA-256 of its content (hex)
+    pub files: BTreeMap<String, String>,
+    /// ed25519 signature of `signing_message` (hex)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+/// Whether the compiled-in bundle can be trusted
+#[derive(Debug, Clone, PartialEq)]
+pub enum BundleStatus {
+    /// Signature valid and every hash matches the embedded protocols
+    Signed(BundleManifest),
+    /// Why the bundle is not verified
+    Unsigned(String),
+}
+
+/// SHA-256 of a protocol file, ignoring surrounding whitespace
+pub fn content_hash(content: &str) -> String {
+    to_hex(&Sha256::digest(content.trim().as_bytes()))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex<const N: usize>(hex: &str) -> Result<[u8; N], String> {
+    let hex = hex.trim();
+    if hex.len() != N * 2 || !hex.is_ascii() {
+        return Err(format!("expected {} hex characters", N * 2));
+    }
+    let mut bytes = [0u8; N];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)
+            .map_err(|_| format!("invalid hex '{}'", &hex[i * 2..i * 2 + 2]))?;
+    }
+    Ok(bytes)
+}
+
+fn pretty<T: serde::de::DeserializeOwned + Serialize>(name: &str, embedded: &str) -> String {
+    let protocol: T = serde_json::from_str(embedded)
+        .unwrap_or_else(|e| panic!("Embedded {}.json must be valid: {}", name, e));
+    serde_json::to_string_pretty(&protocol).expect("Protocol serialization should never fail")
+}
+
+/// The protocol files generated from the embedded protocols alone, without
+/// user/org or project layers, before dates are rendered: what the bundle
+/// manifest hashes
+pub fn bundle_files() -> Vec<(String, String)> {
+    let embedded = |name| super::embedded_protocol(name).expect("built-in protocol");
+    let warmup: WarmupProtocol =
+        serde_json::from_str(embedded("warmup")).expect("Embedded warmup.json must be valid");
+    let entry = WarmupEntry {
+        on_start: warmup.on_start,
+        files: warmup.files,
+        note: warmup.note.unwrap_or_default(),
+    };
+    vec![
+        (
+            "warmup.json".to_string(),
+            serde_json::to_string_pretty(&entry).expect("Warmup entry serialization"),
+        ),
+        (
+            "asimov.json".to_string(),
+            pretty::<AsimovProtocol>("asimov", embedded("asimov")),
+        ),
+        (
+            "freshness.json".to_string(),
+            pretty::<FreshnessProtocol>("freshness", embedded("freshness")),
+        ),
+        (
+            "sycophancy.json".to_string(),
+            pretty::<SycophancyProtocol>("sycophancy", embedded("sycophancy")),
+        ),
+        (
+            "green.json".to_string(),
+            pretty::<GreenProtocol>("green", embedded("green")),
+        ),
+        (
+            "sprint.json".to_string(),
+            pretty::<SprintProtocol>("sprint", embedded("sprint")),
+        ),
+        (
+            "coding-standards.json".to_string(),
+            pretty::<CodingStandardsProtocol>("coding-standards", embedded("coding-standards")),
+        ),
+    ]
+}
+
+impl BundleManifest {
+    /// Unsigned manifest of `bundle_files`
+    pub fn of_embedded(version: &str) -> Self {
+        BundleManifest {
+            version: version.to_string(),
+            files: bundle_files()
+                .into_iter()
+                .map(|(file, template)| (file, content_hash(&integrity_form(&template, &template))))
+                .collect(),
+            signature: None,
+        }
+    }
+
+    /// The bytes the signature covers
+    pub fn signing_message(&self) -> Vec<u8> {
+        let mut message = format!("asimov-protocol-bundle v1\nversion {}\n", self.version);
+        for (file, hash) in &self.files {
+            message.push_str(&format!("{}  {}\n", hash, file));
+        }
+        message.into_bytes()
+    }
+
+    /// Sign with a 32-byte ed25519 secret key (offline, see module docs)
+    pub fn sign(mut self, secret_key: &[u8; 32]) -> Self {
+        let key = SigningKey::from_bytes(secret_key);
+        self.signature = Some(to_hex(&key.sign(&self.signing_message()).to_bytes()));
+        self
+    }
+
+    /// Check the signature against a hex public key
+    pub fn verify(&self, public_key: &str) -> Result<(), String> {
+        let signature = self
+            .signature
+            .as_deref()
+            .ok_or_else(|| "manifest is not signed".to_string())?;
+        let key = VerifyingKey::from_bytes(&from_hex::<32>(public_key)?)
+            .map_err(|e| format!("invalid public key: {}", e))?;
+        let signature = Signature::from_bytes(&from_hex::<64>(signature)?);
+        key.verify(&self.signing_message(), &signature)
+            .map_err(|_| "signature does not match the manifest".to_string())
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Manifest serialization should never fail")
+    }
+}
+
+/// Public key (hex) of a 32-byte ed25519 secret key
+pub fn public_key_of(secret_key: &[u8; 32]) -> String {
+    to_hex(
+        SigningKey::from_bytes(secret_key)
+            .verifying_key()
+            .as_bytes(),
+    )
+}
+
+/// Parse a hex secret key file's content
+pub fn parse_secret_key(hex: &str) -> Result<[u8; 32], String> {
+    from_hex::<32>(hex)
+}
+
+/// Verify the compiled-in bundle manifest against the embedded protocols
+pub fn embedded_bundle() -> BundleStatus {
+    let (Some(public_key), Some(manifest_json)) = (PROTOCOL_PUBLIC_KEY, BUNDLE_MANIFEST_JSON)
+    else {
+        return BundleStatus::Unsigned("no signing key configured".to_string());
+    };
+    let manifest: BundleManifest = match serde_json::from_str(manifest_json) {
+        Ok(manifest) => manifest,
+        Err(e) => return BundleStatus::Unsigned(format!("manifest is not valid: {}", e)),
+    };
+    if let Err(e) = manifest.verify(public_key) {
+        return BundleStatus::Unsigned(e);
+    }
+    let expected = BundleManifest::of_embedded(&manifest.version);
+    if expected.files != manifest.files {
+        return BundleStatus::Unsigned(
+            "embedded protocols changed since the manifest was signed".to_string(),
+        );
+    }
+    BundleStatus::Signed(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: [u8; 32] = [7; 32];
+
+    #[test]
+    fn test_sign_and_verify() {
+        let manifest = BundleManifest::of_embedded("1.0.0").sign(&SECRET);
+        let public = public_key_of(&SECRET);
+        assert_eq!(manifest.verify(&public), Ok(()));
+
+        let mut tampered = manifest.clone();
+        tampered
+            .files
+            .insert("asimov.json".to_string(), content_hash("{}"));
+        assert!(tampered.verify(&public).is_err());
+        assert!(manifest.verify(&public_key_of(&[8; 32])).is_err());
+        assert_eq!(
+            BundleManifest::of_embedded("1.0.0").verify(&public),
+            Err("manifest is not signed".to_string())
+        );
+    }
+
+    #[test]
+    fn test_bundle_matches_generated_defaults() {
+        let files = bundle_files();
+        assert_eq!(files.len(), super::super::PROTOCOL_FILES.len());
+        assert_eq!(content_hash("{}\n"), content_hash("{}"));
+        assert_eq!(parse_secret_key(&to_hex(&SECRET)), Ok(SECRET));
+        assert!(parse_secret_key("zz").is_err());
+    }
+
+    #[test]
+    fn test_embedded_bundle_unsigned_without_key() {
+        if PROTOCOL_PUBLIC_KEY.is_none() {
+            assert_eq!(
+                embedded_bundle(),
+                BundleStatus::Unsigned("no signing key configured".to_string())
+            );
+        }
+    }
+}
@@ -0,0 +1,439 @@
+//! Custom protocols
+//!
+//! Any `<name>.json` in a protocol layer directory (user/org dirs, see
+//! `layers`, and `.asimov/protocols/`) whose name is not a built-in protocol
+//! defines a custom protocol:
+//!
+//! ```json
+//! {
+//!   "name": "security-review",
+//!   "priority": 1.5,
+//!   "include_when": { "project_types": ["rust", "node"], "files_exist": ["Dockerfile"] },
+//!   "schema": { "type": "object", "required": ["rule"] },
+//!   "protocol": { "rule": "Review every change to auth/ with the security checklist." }
+//! }
+//! ```
+//!
+//! `include_when` is an activation rule (see `activation`).
+//!
+//! Definitions of the same name are deep-merged like built-in overrides, so
+//! a project can adjust an organization's protocol. Included protocols are
+//! written to `.asimov/<name>.json` by init/refresh, checked by the protocol
+//! integrity checks and listed in the warmup files by priority (built-ins:
+//! asimov/warmup 0, green 0.5, freshness/coding-standards 1, sycophancy 1.5,
+//! sprint 2).
+
+use super::{
+    merge_layers, parse_layer, user_protocol_dirs, ActivationRule, LayerSource, ProjectFacts,
+    ProtocolLayer, PROTOCOL_NAMES,
+};
+use crate::context::AsimovContext;
+use crate::freshness::MODEL_CUTOFFS_FILE;
+use crate::validator::PROTOCOL_MANIFEST;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// Warmup priorities of the built-in protocol files
+pub const BUILTIN_PRIORITIES: &[(&str, f64)] = &[
+    ("asimov.json", 0.0),
+    ("warmup.json", 0.0),
+    ("green.json", 0.5),
+    ("freshness.json", 1.0),
+    ("coding-standards.json", 1.0),
+    ("sycophancy.json", 1.5),
+    ("sprint.json", 2.0),
+];
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Definition {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    priority: f64,
+    #[serde(default)]
+    include_when: ActivationRule,
+    #[serde(default)]
+    schema: Option<Value>,
+    protocol: Value,
+}
+
+/// A validated custom protocol definition
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomProtocol {
+    pub name: String,
+    pub description: Option<String>,
+    pub priority: f64,
+    pub include_when: ActivationRule,
+    /// JSON Schema the protocol body was checked against
+    pub schema: Option<Value>,
+    /// The protocol body written to `.asimov/<name>.json`
+    pub protocol: Value,
+    /// Definition files it was merged from, lowest first
+    pub sources: Vec<PathBuf>,
+}
+
+impl CustomProtocol {
+    /// `.asimov/` file name
+    pub fn filename(&self) -> String {
+        format!("{}.json", self.name)
+    }
+
+    /// Content of `.asimov/<name>.json`
+    pub fn json(&self) -> String {
+        serde_json::to_string_pretty(&self.protocol)
+            .expect("Protocol serialization should never fail")
+    }
+
+    /// Whether the protocol applies to a project
+    pub fn is_active(&self, facts: &ProjectFacts) -> bool {
+        self.include_when.matches(facts)
+    }
+}
+
+/// Files asimov writes to `.asimov/` that a protocol must not replace
+const RESERVED_FILES: &[&str] = &[PROTOCOL_MANIFEST, MODEL_CUTOFFS_FILE];
+
+/// Custom protocol names become `.asimov/<name>.json`: `[a-z0-9][a-z0-9-]*`
+fn check_name(name: &str) -> Result<(), String> {
+    let valid = name.starts_with(|c: char| c.is_ascii_lowercase() || c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !valid {
+        return Err(format!(
+            "invalid protocol name '{}' (use lowercase letters, digits and '-')",
+            name
+        ));
+    }
+    let file = format!("{}.json", name);
+    if RESERVED_FILES.contains(&file.as_str()) {
+        return Err(format!(
+            "protocol name '{}' is reserved (.asimov/{} is written by asimov)",
+            name, file
+        ));
+    }
+    Ok(())
+}
+
+/// Parse and check a (merged) custom protocol definition
+pub fn parse_custom_protocol(name: &str, definition: &Value) -> Result<CustomProtocol, String> {
+    check_name(name)?;
+    let definition: Definition =
+        serde_json::from_value(definition.clone()).map_err(|e| e.to_string())?;
+    if definition.name != name {
+        return Err(format!(
+            "name '{}' does not match the file name '{}.json'",
+            definition.name, name
+        ));
+    }
+    if !definition.priority.is_finite() || definition.priority < 0.0 {
+        return Err(format!(
+            "priority must be a non-negative number, got {}",
+            definition.priority
+        ));
+    }
+    if !definition.protocol.is_object() {
+        return Err("protocol must be a JSON object".to_string());
+    }
+    if let Some(ref schema) = definition.schema {
+        let validator = jsonschema::Validator::new(schema)
+            .map_err(|e| format!("schema is not a valid JSON Schema: {}", e))?;
+        let errors: Vec<String> = validator
+            .iter_errors(&definition.protocol)
+            .map(|e| {
+                let path = e.instance_path().to_string();
+                format!("protocol{}: {}", path.replace('/', "."), e)
+            })
+            .collect();
+        if !errors.is_empty() {
+            return Err(format!(
+                "protocol does not match its schema: {}",
+                errors.join("; ")
+            ));
+        }
+    }
+
+    Ok(CustomProtocol {
+        name: definition.name,
+        description: definition.description,
+        priority: definition.priority,
+        include_when: definition.include_when,
+        schema: definition.schema,
+        protocol: definition.protocol,
+        sources: Vec::new(),
+    })
+}
+
+/// Definition layers of every custom protocol visible from `dir`, by name
+fn definition_layers(dir: &Path) -> Vec<(String, Vec<ProtocolLayer>)> {
+    let mut sources: Vec<(LayerSource, PathBuf)> = Vec::new();
+    for user_dir in user_protocol_dirs() {
+        sources.extend(
+            json_files(&user_dir)
+                .into_iter()
+                .map(|p| (LayerSource::User, p)),
+        );
+    }
+    if let Some(context) = AsimovContext::discover(dir) {
+        sources.extend(
+            context
+                .inherited_files("protocols", "json")
+                .into_iter()
+                .map(|p| (LayerSource::Project, p)),
+        );
+    }
+
+    let names: BTreeSet<String> = sources
+        .iter()
+        .filter_map(|(_, path)| Some(path.file_stem()?.to_str()?.to_string()))
+        .filter(|name| !PROTOCOL_NAMES.contains(&name.as_str()))
+        .collect();
+    names
+        .into_iter()
+        .map(|name| {
+            let layers = sources
+                .iter()
+                .filter(|(_, path)| path.file_stem().and_then(|s| s.to_str()) == Some(&name))
+                .filter_map(|(source, path)| {
+                    let content = std::fs::read_to_string(path).ok()?;
+                    Some(ProtocolLayer {
+                        source: *source,
+                        path: Some(path.clone()),
+                        value: parse_layer(&content),
+                    })
+                })
+                .collect();
+            (name, layers)
+        })
+        .collect()
+}
+
+fn json_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|e| e == "json"))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Every custom protocol visible from `dir`, or why its definition is invalid
+pub fn discover_custom_protocols(dir: &Path) -> Vec<(String, Result<CustomProtocol, String>)> {
+    definition_layers(dir)
+        .into_iter()
+        .map(|(name, layers)| {
+            if let Some((layer, Err(e))) = layers.iter().find_map(|l| match l.value {
+                Err(ref e) => Some((l, Err::<(), _>(e.clone()))),
+                Ok(_) => None,
+            }) {
+                return (name, Err(format!("{}: {}", layer.label(), e)));
+            }
+            let merged = merge_layers(&layers);
+            let protocol = parse_custom_protocol(&name, &merged.value).map(|mut protocol| {
+                protocol.sources = layers.iter().filter_map(|l| l.path.clone()).collect();
+                protocol
+            });
+            (name, protocol)
+        })
+        .collect()
+}
+
+/// Check a project definition file merged over the user/org definitions
+pub fn check_custom_definition(name: &str, path: &Path) -> Result<CustomProtocol, String> {
+    let mut layers: Vec<ProtocolLayer> = user_protocol_dirs()
+        .into_iter()
+        .map(|dir| dir.join(format!("{}.json", name)))
+        .filter_map(|path| {
+            let content = std::fs::read_to_string(&path).ok()?;
+            Some(ProtocolLayer {
+                source: LayerSource::User,
+                path: Some(path),
+                value: parse_layer(&content),
+            })
+        })
+        .collect();
+    let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    layers.push(ProtocolLayer {
+        source: LayerSource::Project,
+        path: Some(path.to_path_buf()),
+        value: Ok(parse_layer(&content)?),
+    });
+    parse_custom_protocol(name, &merge_layers(&layers).value)
+}
+
+/// Valid custom protocols that apply to the project in `dir`, by priority
+pub fn active_custom_protocols(dir: &Path) -> Vec<CustomProtocol> {
+    let facts = ProjectFacts::discover(dir);
+    let mut protocols: Vec<CustomProtocol> = discover_custom_protocols(dir)
+        .into_iter()
+        .filter_map(|(_, protocol)| protocol.ok())
+        .filter(|protocol| protocol.is_active(&facts))
+        .collect();
+    protocols.sort_by(|a, b| a.priority.total_cmp(&b.priority).then(a.name.cmp(&b.name)));
+    protocols
+}
+
+/// Warmup files with custom protocols placed by priority
+///
+/// A custom protocol loads after the last listed file of lower or equal
+/// priority; project.yaml and roadmap.yaml always load first.
+pub fn warmup_files_with(mut files: Vec<String>, custom: &[CustomProtocol]) -> Vec<String> {
+    let priority = |file: &str| {
+        let name = file.rsplit('/').next().unwrap_or(file);
+        BUILTIN_PRIORITIES
+            .iter()
+            .find(|(builtin, _)| *builtin == name)
+            .map(|(_, p)| *p)
+            .or_else(|| {
+                custom
+                    .iter()
+                    .find(|c| c.filename() == name)
+                    .map(|c| c.priority)
+            })
+    };
+    for protocol in custom {
+        let file = format!(".asimov/{}", protocol.filename());
+        if files.contains(&file) {
+            continue;
+        }
+        let position = files
+            .iter()
+            .rposition(|f| priority(f).is_none_or(|p| p <= protocol.priority))
+            .map_or(0, |i| i + 1);
+        files.insert(position, file);
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn definition(name: &str, priority: f64) -> Value {
+        json!({
+            "name": name,
+            "priority": priority,
+            "schema": {"type": "object", "required": ["rule"]},
+            "protocol": {"rule": "Review auth changes"}
+        })
+    }
+
+    #[test]
+    fn test_parse_custom_protocol() {
+        let protocol =
+            parse_custom_protocol("security-review", &definition("security-review", 1.5)).unwrap();
+        assert_eq!(protocol.filename(), "security-review.json");
+        assert!(protocol.json().contains("Review auth changes"));
+
+        let err = parse_custom_protocol("other", &definition("security-review", 1.0)).unwrap_err();
+        assert!(err.contains("does not match the file name"));
+
+        let mut bad = definition("a11y", 1.0);
+        bad["protocol"] = json!({"note": "no rule"});
+        let err = parse_custom_protocol("a11y", &bad).unwrap_err();
+        assert!(err.contains("does not match its schema"), "{}", err);
+
+        let err = parse_custom_protocol("a11y", &json!({"name": "a11y"})).unwrap_err();
+        assert!(err.contains("missing field"), "{}", err);
+
+        for name in [".manifest", "Upper", "../x", "-lead", "a_b"] {
+            let err = parse_custom_protocol(name, &definition(name, 1.0)).unwrap_err();
+            assert!(err.contains("invalid protocol name"), "{}", err);
+        }
+        let err =
+            parse_custom_protocol("model-cutoffs", &definition("model-cutoffs", 1.0)).unwrap_err();
+        assert!(err.contains("is reserved"), "{}", err);
+    }
+
+    #[test]
+    fn test_inclusion_conditions() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join(".asimov")).unwrap();
+        std::fs::write(
+            temp.path().join(".asimov/project.yaml"),
+            "identity:\n  name: x\n  type: rust\n",
+        )
+        .unwrap();
+
+        let mut value = definition("security-review", 1.0);
+        value["include_when"] = json!({"project_types": ["rust"], "files_exist": ["Dockerfile"]});
+        let protocol = parse_custom_protocol("security-review", &value).unwrap();
+        assert!(!protocol.is_active(&ProjectFacts::discover(temp.path())));
+        std::fs::write(temp.path().join("Dockerfile"), "FROM scratch").unwrap();
+        assert!(protocol.is_active(&ProjectFacts::discover(temp.path())));
+
+        value["include_when"] = json!({"project_types": ["node"]});
+        let protocol = parse_custom_protocol("security-review", &value).unwrap();
+        assert!(!protocol.is_active(&ProjectFacts::discover(temp.path())));
+    }
+
+    #[test]
+    fn test_warmup_files_by_priority() {
+        let files: Vec<String> = [
+            ".asimov/project.yaml",
+            ".asimov/roadmap.yaml",
+            ".asimov/freshness.json",
+            ".asimov/asimov.json",
+            ".asimov/green.json",
+            ".asimov/sprint.json",
+        ]
+        .iter()
+        .map(|f| f.to_string())
+        .collect();
+        let custom: Vec<CustomProtocol> = [("early", 0.0), ("mid", 0.7), ("late", 5.0)]
+            .iter()
+            .map(|(name, p)| parse_custom_protocol(name, &definition(name, *p)).unwrap())
+            .collect();
+
+        let files = warmup_files_with(files, &custom);
+        assert_eq!(
+            files,
+            vec![
+                ".asimov/project.yaml",
+                ".asimov/roadmap.yaml",
+                ".asimov/freshness.json",
+                ".asimov/asimov.json",
+                ".asimov/early.json",
+                ".asimov/green.json",
+                ".asimov/mid.json",
+                ".asimov/sprint.json",
+                ".asimov/late.json",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_discover_merges_definitions() {
+        let temp = TempDir::new().unwrap();
+        let protocols = temp.path().join(".asimov/protocols");
+        std::fs::create_dir_all(&protocols).unwrap();
+        std::fs::create_dir_all(temp.path().join(".git")).unwrap();
+        std::fs::write(
+            protocols.join("retention.json"),
+            definition("retention", 1.0).to_string(),
+        )
+        .unwrap();
+        std::fs::write(protocols.join("broken.json"), "{").unwrap();
+        std::fs::write(protocols.join("green.json"), r#"{"rule": "x"}"#).unwrap();
+
+        let found = discover_custom_protocols(temp.path());
+        let names: Vec<&str> = found.iter().map(|(n, _)| n.as_str()).collect();
+        assert!(names.contains(&"retention") && names.contains(&"broken"));
+        assert!(!names.contains(&"green"));
+        assert!(found.iter().any(|(n, p)| n == "broken" && p.is_err()));
+
+        let active = active_custom_protocols(temp.path());
+        assert!(active
+            .iter()
+            .any(|p| p.name == "retention" && p.sources.len() == 1));
+    }
+}
@@ -0,0 +1,121 @@
+//! Dates in protocol files
+//!
+//! Protocol templates (built-in, overrides and custom protocols) may contain
+//! `{TODAY}` and `{YEAR}`, rendered with the clock of `crate::clock` when the
+//! files are written. A JSON string field whose template holds a placeholder
+//! is date-bearing: integrity checks and content hashes read it as its
+//! template, so the files do not turn outdated or tampered when the day
+//! changes.
+
+use crate::clock::{clock, Clock};
+use serde_json::Value;
+
+/// Placeholders replaced when a protocol file is rendered
+pub const DATE_PLACEHOLDERS: &[&str] = &["{TODAY}", "{YEAR}"];
+
+/// Inject dynamic dates into a protocol template
+pub fn inject_dates(template: &str) -> String {
+    inject_dates_at(template, &clock())
+}
+
+/// Inject the dates of a given clock into a protocol template
+pub fn inject_dates_at(template: &str, clock: &Clock) -> String {
+    template
+        .replace("{TODAY}", &clock.today())
+        .replace("{YEAR}", &clock.year())
+}
+
+fn collect_date_fields(value: &Value, pointer: String, fields: &mut Vec<String>) {
+    match value {
+        Value::String(s) if DATE_PLACEHOLDERS.iter().any(|p| s.contains(p)) => fields.push(pointer),
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                collect_date_fields(item, format!("{}/{}", pointer, i), fields);
+            }
+        }
+        Value::Object(map) => {
+            for (key, item) in map {
+                let key = key.replace('~', "~0").replace('/', "~1");
+                collect_date_fields(item, format!("{}/{}", pointer, key), fields);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// JSON pointers of the date-bearing fields of a protocol template
+pub fn date_fields(template: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    if let Ok(value) = serde_json::from_str::<Value>(template) {
+        collect_date_fields(&value, String::new(), &mut fields);
+    }
+    fields
+}
+
+/// A protocol file as integrity checks see it: date-bearing fields read as
+/// their template. Templates without dates leave the content untouched.
+pub fn integrity_form(content: &str, template: &str) -> String {
+    let fields = date_fields(template);
+    if fields.is_empty() {
+        return content.to_string();
+    }
+    let (Ok(mut value), Ok(template)) = (
+        serde_json::from_str::<Value>(content),
+        serde_json::from_str::<Value>(template),
+    ) else {
+        return content.to_string();
+    };
+    for field in &fields {
+        if let (Some(slot), Some(original)) = (value.pointer_mut(field), template.pointer(field)) {
+            *slot = original.clone();
+        }
+    }
+    serde_json::to_string_pretty(&value).expect("JSON serialization should never fail")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_date_injection() {
+        let template = "Today is {TODAY}, year {YEAR}";
+        let result = inject_dates(template);
+        assert!(result.contains(&clock().year()));
+        assert!(!result.contains("{TODAY}"));
+        assert!(!result.contains("{YEAR}"));
+
+        let pinned = Clock::from_settings(Some("2025-03-01"), Some("UTC")).unwrap();
+        assert_eq!(
+            inject_dates_at(template, &pinned),
+            "Today is 2025-03-01, year 2025"
+        );
+    }
+
+    #[test]
+    fn test_date_fields_excluded_from_integrity() {
+        let template =
+            r#"{"rule": "Search as of {TODAY}", "notes": ["(c) {YEAR}", "x"], "a/b": 1}"#;
+        assert_eq!(date_fields(template), vec!["/notes/0", "/rule"]);
+        assert!(date_fields(r#"{"rule": "no dates"}"#).is_empty());
+
+        let day = |date| {
+            let clock = Clock::from_settings(Some(date), Some("UTC")).unwrap();
+            inject_dates_at(template, &clock)
+        };
+        let (monday, tuesday) = (day("2025-03-03"), day("2025-03-04"));
+        assert_ne!(monday, tuesday);
+        assert_eq!(
+            integrity_form(&monday, template),
+            integrity_form(&tuesday, template)
+        );
+
+        // Other fields still count
+        let edited = tuesday.replace("\"x\"", "\"y\"");
+        assert_ne!(
+            integrity_form(&monday, template),
+            integrity_form(&edited, template)
+        );
+        assert_eq!(integrity_form("not json", template), "not json");
+    }
+}
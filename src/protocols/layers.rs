@@ -0,0 +1,420 @@
+//! Protocol layering
+//!
+//! A protocol is resolved from up to three layers, later layers winning:
+//!
+//! 1. `embedded` - the JSON compiled into the binary
+//! 2. `user` - `<dir>/<name>.json` for each directory of `ASIMOV_PROTOCOL_PATH`
+//!    (a path list, first entry lowest), or `$XDG_CONFIG_HOME/asimov/protocols`
+//!    (`~/.config/asimov/protocols`) when the variable is unset
+//! 3. `project` - `.asimov/protocols/<name>.json` of the nearest context
+//!
+//! Layers are deep-merged:
+//!
+//! - objects merge key by key, recursively
+//! - scalars and arrays replace the lower layer's value
+//! - `"key+": [...]` appends to the lower layer's `key` array
+//! - `"key": null` removes `key`
+//!
+//! So an organization can ship partial files (one stricter rule, extra
+//! entries in a list) that every repository picks up, and a project can
+//! still override a single field on top.
+
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Directories holding user/org protocol layers, lowest first
+pub const PROTOCOL_PATH_ENV: &str = "ASIMOV_PROTOCOL_PATH";
+
+/// Where a protocol layer comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayerSource {
+    Embedded,
+    User,
+    Project,
+}
+
+impl fmt::Display for LayerSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayerSource::Embedded => write!(f, "embedded"),
+            LayerSource::User => write!(f, "user"),
+            LayerSource::Project => write!(f, "project"),
+        }
+    }
+}
+
+/// One layer of a protocol
+#[derive(Debug, Clone)]
+pub struct ProtocolLayer {
+    pub source: LayerSource,
+    /// File the layer was read from (`None` for the embedded layer)
+    pub path: Option<PathBuf>,
+    /// The layer's JSON object, or why it was skipped
+    pub value: Result<Value, String>,
+}
+
+impl ProtocolLayer {
+    /// `user (/etc/asimov/protocols/green.json)`
+    pub fn label(&self) -> String {
+        match self.path {
+            Some(ref path) => format!("{} ({})", self.source, path.display()),
+            None => self.source.to_string(),
+        }
+    }
+}
+
+/// Keys from the protocol root to a field (keys may contain dots)
+pub type FieldPath = Vec<String>;
+
+/// A merged protocol and the layers each field came from
+#[derive(Debug, Clone)]
+pub struct MergedProtocol {
+    pub value: Value,
+    /// Field path -> indexes into the layers (several when appended)
+    pub origins: BTreeMap<FieldPath, Vec<usize>>,
+}
+
+/// User/org protocol directories, lowest first
+pub fn user_protocol_dirs() -> Vec<PathBuf> {
+    #[cfg(test)]
+    return TEST_USER_DIRS.with(|dirs| dirs.borrow().clone());
+    #[cfg(not(test))]
+    user_protocol_dirs_from(|name| std::env::var_os(name))
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Unit tests only see the user dirs they inject (`with_user_protocol_dirs`),
+    /// never the machine's config
+    static TEST_USER_DIRS: std::cell::RefCell<Vec<PathBuf>> = const {
+        std::cell::RefCell::new(Vec::new())
+    };
+}
+
+/// Run `f` with `dirs` as the user/org protocol directories
+#[cfg(test)]
+pub(crate) fn with_user_protocol_dirs<T>(dirs: &[PathBuf], f: impl FnOnce() -> T) -> T {
+    let previous = TEST_USER_DIRS.with(|current| current.replace(dirs.to_vec()));
+    let result = f();
+    TEST_USER_DIRS.with(|current| current.replace(previous));
+    result
+}
+
+/// `user_protocol_dirs` for an environment lookup
+fn user_protocol_dirs_from(var: impl Fn(&str) -> Option<OsString>) -> Vec<PathBuf> {
+    if let Some(paths) = var(PROTOCOL_PATH_ENV) {
+        return std::env::split_paths(&paths)
+            .filter(|path| !path.as_os_str().is_empty())
+            .collect();
+    }
+    let config = var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| {
+            var(if cfg!(windows) { "APPDATA" } else { "HOME" }).map(|home| {
+                if cfg!(windows) {
+                    PathBuf::from(home)
+                } else {
+                    PathBuf::from(home).join(".config")
+                }
+            })
+        });
+    config
+        .map(|dir| vec![dir.join("asimov").join("protocols")])
+        .unwrap_or_default()
+}
+
+fn read_layer(source: LayerSource, path: PathBuf) -> Option<ProtocolLayer> {
+    let content = std::fs::read_to_string(&path).ok()?;
+    let value = parse_layer(&content);
+    Some(ProtocolLayer {
+        source,
+        path: Some(path),
+        value,
+    })
+}
+
+/// A layer must be a JSON object
+pub fn parse_layer(content: &str) -> Result<Value, String> {
+    match serde_json::from_str::<Value>(content) {
+        Ok(value) if value.is_object() => Ok(value),
+        Ok(_) => Err("not a JSON object".to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Layers of a protocol, lowest first
+///
+/// `embedded` is the compiled-in JSON; `project` the project override file
+/// (usually from `resolve_asimov_file`), if any.
+pub fn protocol_layers(name: &str, embedded: &str, project: Option<&Path>) -> Vec<ProtocolLayer> {
+    let mut layers = vec![ProtocolLayer {
+        source: LayerSource::Embedded,
+        path: None,
+        value: parse_layer(embedded),
+    }];
+    for dir in user_protocol_dirs() {
+        layers.extend(read_layer(
+            LayerSource::User,
+            dir.join(format!("{}.json", name)),
+        ));
+    }
+    if let Some(path) = project {
+        layers.extend(read_layer(LayerSource::Project, path.to_path_buf()));
+    }
+    layers
+}
+
+/// Deep-merge layers; unreadable layers are skipped
+pub fn merge_layers(layers: &[ProtocolLayer]) -> MergedProtocol {
+    let mut value = Map::new();
+    let mut origins = BTreeMap::new();
+    for (index, layer) in layers.iter().enumerate() {
+        if let Ok(Value::Object(ref overlay)) = layer.value {
+            merge_object(&mut value, &mut origins, &[], overlay, index);
+        }
+    }
+    MergedProtocol {
+        value: Value::Object(value),
+        origins,
+    }
+}
+
+fn field_path(prefix: &[String], key: &str) -> FieldPath {
+    let mut path = prefix.to_vec();
+    path.push(key.to_string());
+    path
+}
+
+fn forget(origins: &mut BTreeMap<FieldPath, Vec<usize>>, path: &[String]) {
+    origins.retain(|field, _| !field.starts_with(path));
+}
+
+fn merge_object(
+    target: &mut Map<String, Value>,
+    origins: &mut BTreeMap<FieldPath, Vec<usize>>,
+    prefix: &[String],
+    overlay: &Map<String, Value>,
+    layer: usize,
+) {
+    for (key, value) in overlay {
+        if let Some(base_key) = key.strip_suffix('+') {
+            let path = field_path(prefix, base_key);
+            match (target.get_mut(base_key), value) {
+                (Some(Value::Array(existing)), Value::Array(extra)) => {
+                    existing.extend(extra.iter().cloned());
+                    origins.entry(path).or_default().push(layer);
+                }
+                _ => {
+                    target.insert(base_key.to_string(), value.clone());
+                    forget(origins, &path);
+                    origins.insert(path, vec![layer]);
+                }
+            }
+            continue;
+        }
+
+        let path = field_path(prefix, key);
+        match (target.get_mut(key), value) {
+            (_, Value::Null) => {
+                target.remove(key);
+                forget(origins, &path);
+            }
+            (Some(Value::Object(existing)), Value::Object(nested)) => {
+                merge_object(existing, origins, &path, nested, layer);
+            }
+            _ => {
+                target.insert(key.clone(), value.clone());
+                forget(origins, &path);
+                origins.insert(path.clone(), vec![layer]);
+                if let Value::Object(nested) = value {
+                    // Record the new object's fields individually
+                    for field in leaf_fields(nested, &path) {
+                        origins.insert(field, vec![layer]);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn leaf_fields(object: &Map<String, Value>, prefix: &[String]) -> Vec<FieldPath> {
+    let mut fields = Vec::new();
+    for (key, value) in object {
+        let path = field_path(prefix, key);
+        match value {
+            Value::Object(nested) if !nested.is_empty() => {
+                fields.extend(leaf_fields(nested, &path))
+            }
+            _ => fields.push(path),
+        }
+    }
+    fields
+}
+
+impl MergedProtocol {
+    /// Leaf fields (scalars, arrays, empty objects) in document order with
+    /// the layers that set them
+    pub fn fields(&self) -> Vec<(FieldPath, Vec<usize>)> {
+        let Value::Object(ref object) = self.value else {
+            return Vec::new();
+        };
+        leaf_fields(object, &[])
+            .into_iter()
+            .map(|field| {
+                let origin = self.lookup(&field);
+                (field, origin)
+            })
+            .collect()
+    }
+
+    /// Layers of a field; an object set as a whole is credited to its fields
+    fn lookup(&self, field: &[String]) -> Vec<usize> {
+        (1..=field.len())
+            .rev()
+            .find_map(|len| self.origins.get(&field[..len]))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Value of a field
+    pub fn get(&self, field: &[String]) -> Option<&Value> {
+        field
+            .iter()
+            .try_fold(&self.value, |value, key| value.get(key.as_str()))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.value).expect("Protocol serialization should never fail")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn layer(source: LayerSource, value: Value) -> ProtocolLayer {
+        ProtocolLayer {
+            source,
+            path: None,
+            value: Ok(value),
+        }
+    }
+
+    #[test]
+    fn test_merge_semantics() {
+        let layers = [
+            layer(
+                LayerSource::Embedded,
+                json!({"rule": "a", "phrases": ["x"], "limits": {"max": 1, "min": 0}, "old": true}),
+            ),
+            layer(
+                LayerSource::User,
+                json!({"phrases+": ["y"], "limits": {"max": 5}, "old": null}),
+            ),
+            layer(LayerSource::Project, json!({"rule": "b"})),
+        ];
+        let merged = merge_layers(&layers);
+        assert_eq!(
+            merged.value,
+            json!({"rule": "b", "phrases": ["x", "y"], "limits": {"max": 5, "min": 0}})
+        );
+
+        let fields: BTreeMap<String, Vec<usize>> = merged
+            .fields()
+            .into_iter()
+            .map(|(field, origin)| (field.join("."), origin))
+            .collect();
+        assert_eq!(fields["rule"], vec![2]);
+        assert_eq!(fields["phrases"], vec![0, 1]);
+        assert_eq!(fields["limits.max"], vec![1]);
+        assert_eq!(fields["limits.min"], vec![0]);
+        assert!(!fields.contains_key("old"));
+    }
+
+    #[test]
+    fn test_replacing_arrays_and_objects() {
+        let layers = [
+            layer(
+                LayerSource::Embedded,
+                json!({"list": [1, 2], "obj": {"a": 1}}),
+            ),
+            layer(LayerSource::User, json!({"list": [3], "obj": "flat"})),
+            layer(LayerSource::Project, json!({"obj": {"b": 2}, "new+": [1]})),
+        ];
+        let merged = merge_layers(&layers);
+        assert_eq!(
+            merged.value,
+            json!({"list": [3], "obj": {"b": 2}, "new": [1]})
+        );
+        let fields: BTreeMap<String, Vec<usize>> = merged
+            .fields()
+            .into_iter()
+            .map(|(field, origin)| (field.join("."), origin))
+            .collect();
+        assert_eq!(fields["obj.b"], vec![2]);
+        assert_eq!(fields["list"], vec![1]);
+    }
+
+    #[test]
+    fn test_user_protocol_dirs() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| OsString::from(value))
+            }
+        };
+        // An empty path list disables user layers
+        assert!(
+            user_protocol_dirs_from(env(&[(PROTOCOL_PATH_ENV, ""), ("HOME", "/h")])).is_empty()
+        );
+        #[cfg(unix)]
+        assert_eq!(
+            user_protocol_dirs_from(env(&[("XDG_CONFIG_HOME", "/xdg")])),
+            vec![PathBuf::from("/xdg/asimov/protocols")]
+        );
+
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join("green.json"), r#"{"rule": "org"}"#).unwrap();
+        assert_eq!(protocol_layers("green", "{}", None).len(), 1);
+        let layers = with_user_protocol_dirs(&[temp.path().to_path_buf()], || {
+            protocol_layers("green", "{}", None)
+        });
+        assert_eq!(layers[1].source, LayerSource::User);
+    }
+
+    #[test]
+    fn test_keys_with_dots() {
+        let layers = [
+            layer(LayerSource::Embedded, json!({"a.b": {"x": 1}})),
+            layer(LayerSource::User, json!({"a.b": {"y": 2}})),
+        ];
+        let merged = merge_layers(&layers);
+        assert_eq!(merged.value, json!({"a.b": {"x": 1, "y": 2}}));
+        let fields = merged.fields();
+        let y = vec!["a.b".to_string(), "y".to_string()];
+        assert_eq!(fields[1], (y.clone(), vec![1]));
+        assert_eq!(merged.get(&y), Some(&json!(2)));
+    }
+
+    #[test]
+    fn test_invalid_layers_are_skipped() {
+        assert!(parse_layer("[1]").is_err());
+        assert!(parse_layer("{").is_err());
+        let layers = [
+            layer(LayerSource::Embedded, json!({"rule": "a"})),
+            ProtocolLayer {
+                source: LayerSource::User,
+                path: Some(PathBuf::from("/org/green.json")),
+                value: parse_layer("not json"),
+            },
+        ];
+        assert_eq!(merge_layers(&layers).value, json!({"rule": "a"}));
+        assert_eq!(layers[1].label(), "user (/org/green.json)");
+    }
+}
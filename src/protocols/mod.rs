@@ -4,12 +4,29 @@
 //! These are copied to .asimov/protocols/ on init/refresh for runtime customization.
 //! Supersedes ADR-031 (hardcoded protocols).
 //! v10.8.0: Migrations protocol removed (ADR-062) - now part of API templates.
+//! Overrides are layers (embedded, user/org, project), see `layers`.
+//! User-defined protocols next to the built-ins, see `custom`.
+//! Declarative activation of warmup files, see `activation`.
+//! Signed manifest of the embedded protocols, see `bundle`.
+//! `{TODAY}`/`{YEAR}` rendered with a pinnable clock, see `dates`.
+
+mod activation;
+mod bundle;
+mod custom;
+mod dates;
+mod layers;
+pub use activation::*;
+pub use bundle::*;
+pub use custom::*;
+pub use dates::*;
+pub use layers::*;
 
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 
 // ========== Embedded JSON Protocols (compile-time from cli/protocols/) ==========
 // Single source of truth: JSON files in cli/protocols/ are embedded at compile time.
-// Runtime: External files in .asimov/protocols/ take priority if they exist.
+// Runtime: User/org and .asimov/protocols/ layers are deep-merged over them.
 
 /// Asimov protocol - Harm prevention inspired by Asimov's Three Laws (Priority 0)
 const ASIMOV_JSON: &str = include_str!("../../protocols/asimov.json");
@@ -37,14 +54,106 @@ const CODING_STANDARDS_JSON: &str = include_str!("../../protocols/coding-standar
 // ========== Protocol Directory ==========
 
 /// Get the protocols directory path
+/// Under the discovered project root, so subdirectories find it
 pub fn protocols_dir() -> std::path::PathBuf {
-    std::path::PathBuf::from(".asimov/protocols")
+    crate::context::project_root().join(".asimov/protocols")
 }
 
 /// Try to read a protocol from external file, return None if not found
+/// Packages inherit overrides from enclosing .asimov/ contexts
+/// The user/org and project layers merged over the embedded JSON
 fn try_read_protocol(name: &str) -> Option<String> {
-    let path = protocols_dir().join(format!("{}.json", name));
-    std::fs::read_to_string(&path).ok()
+    layered_protocol(name, Some(&project_protocol_path(name)))
+}
+
+/// The project layer of a protocol: `.asimov/protocols/<name>.json` of the
+/// nearest context that has it
+pub fn project_protocol_path(name: &str) -> std::path::PathBuf {
+    crate::context::resolve_asimov_file(&format!("protocols/{}.json", name))
+}
+
+/// Embedded JSON of a protocol (`None` for unknown names)
+pub fn embedded_protocol(name: &str) -> Option<&'static str> {
+    match name {
+        "asimov" => Some(ASIMOV_JSON),
+        "freshness" => Some(FRESHNESS_JSON),
+        "sycophancy" => Some(SYCOPHANCY_JSON),
+        "green" => Some(GREEN_JSON),
+        "sprint" => Some(SPRINT_JSON),
+        "warmup" => Some(WARMUP_JSON),
+        "coding-standards" => Some(CODING_STANDARDS_JSON),
+        _ => None,
+    }
+}
+
+/// A protocol merged from its layers, `None` when only the embedded one exists
+///
+/// Loaders fall back to the embedded protocol when the merge does not parse.
+pub fn layered_protocol(name: &str, project: Option<&Path>) -> Option<String> {
+    let layers = protocol_layers(name, embedded_protocol(name)?, project);
+    (layers.len() > 1).then(|| merge_layers(&layers).to_json())
+}
+
+/// Protocols that can be overridden in .asimov/protocols/<name>.json
+pub const PROTOCOL_NAMES: &[&str] = &[
+    "asimov",
+    "freshness",
+    "sycophancy",
+    "green",
+    "sprint",
+    "warmup",
+    "coding-standards",
+];
+
+/// Check that an override parses as the protocol it replaces
+///
+/// Loaders silently fall back to the embedded protocol when an override is
+/// malformed; this surfaces why. Returns `None` for unknown protocol names.
+pub fn check_protocol_override(name: &str, content: &str) -> Option<Result<(), String>> {
+    fn parse<T: serde::de::DeserializeOwned>(content: &str) -> Result<(), String> {
+        serde_json::from_str::<T>(content)
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    let result = match name {
+        "asimov" => parse::<AsimovProtocol>(content),
+        "freshness" => parse::<FreshnessProtocol>(content),
+        "sycophancy" => parse::<SycophancyProtocol>(content),
+        "green" => parse::<GreenProtocol>(content),
+        "sprint" => parse::<SprintProtocol>(content),
+        "warmup" => parse::<WarmupProtocol>(content),
+        "coding-standards" => parse::<CodingStandardsProtocol>(content),
+        _ => return None,
+    };
+    Some(result)
+}
+
+/// Schema violations of a (merged) protocol, e.g. misspelled or unknown keys
+/// Loaders ignore unknown keys; the schemas reject them. Empty for
+/// unknown protocol names and content that is not JSON.
+pub fn protocol_schema_errors(name: &str, content: &str) -> Vec<String> {
+    let (Some(schema), Ok(value)) = (
+        crate::schemas::protocol_schema(name),
+        serde_json::from_str::<serde_json::Value>(content),
+    ) else {
+        return Vec::new();
+    };
+    let schema: serde_json::Value =
+        serde_json::from_str(schema).expect("Embedded protocol schema must be valid JSON");
+    let validator =
+        jsonschema::Validator::new(&schema).expect("Embedded protocol schema must compile");
+    validator
+        .iter_errors(&value)
+        .map(|e| {
+            let path = e.instance_path().to_string();
+            if path.is_empty() {
+                e.to_string()
+            } else {
+                format!("{}: {}", path.trim_start_matches('/').replace('/', "."), e)
+            }
+        })
+        .collect()
 }
 
 /// Compiled protocol context for minimal token usage
@@ -134,23 +243,6 @@ pub struct Rfc2119Rules {
     pub may: String,
 }
 
-/// Get today's date in YYYY-MM-DD format
-fn get_today() -> String {
-    chrono::Local::now().format("%Y-%m-%d").to_string()
-}
-
-/// Get current year
-fn get_year() -> String {
-    chrono::Local::now().format("%Y").to_string()
-}
-
-/// Inject dynamic dates into a protocol template
-pub fn inject_dates(template: &str) -> String {
-    template
-        .replace("{TODAY}", &get_today())
-        .replace("{YEAR}", &get_year())
-}
-
 /// Get embedded protocol JSON (for debugging/inspection)
 pub fn get_asimov_protocol() -> &'static str {
     ASIMOV_JSON
@@ -250,7 +342,12 @@ fn load_sprint_protocol() -> SprintProtocol {
 
 /// Load warmup protocol from external file or embedded default
 pub fn load_warmup_protocol() -> WarmupProtocol {
-    try_read_protocol("warmup")
+    warmup_protocol_from(try_read_protocol("warmup"))
+}
+
+/// Warmup protocol from override content, or the embedded one if absent or invalid
+pub fn warmup_protocol_from(content: Option<String>) -> WarmupProtocol {
+    content
         .and_then(|content| serde_json::from_str(&content).ok())
         .unwrap_or_else(|| {
             serde_json::from_str(WARMUP_JSON).expect("Embedded warmup.json must be valid")
@@ -292,19 +389,30 @@ pub fn to_yaml() -> String {
 // ========== Individual Protocol JSON Output (v8.14.0) ==========
 
 /// Get warmup entry point JSON - v12.2.0: minimal warmup
+/// Of the project the working directory belongs to
 pub fn warmup_entry_json() -> String {
+    warmup_entry_json_for(&crate::context::project_root())
+}
+
+/// Warmup entry point JSON of the project in `dir`
+/// Active built-ins (see `activation`), custom protocols by priority
+pub fn warmup_entry_json_for(dir: &Path) -> String {
+    let files = vec![
+        ".asimov/project.yaml".into(),
+        ".asimov/roadmap.yaml".into(),
+        ".asimov/freshness.json".into(),
+        ".asimov/asimov.json".into(),
+        ".asimov/sycophancy.json".into(),
+        ".asimov/coding-standards.json".into(),
+        ".asimov/green.json".into(),
+        ".asimov/sprint.json".into(),
+    ];
     let entry = WarmupEntry {
         on_start: vec!["load_files".into()],
-        files: vec![
-            ".asimov/project.yaml".into(),
-            ".asimov/roadmap.yaml".into(),
-            ".asimov/freshness.json".into(),
-            ".asimov/asimov.json".into(),
-            ".asimov/sycophancy.json".into(),
-            ".asimov/coding-standards.json".into(),
-            ".asimov/green.json".into(),
-            ".asimov/sprint.json".into(),
-        ],
+        files: warmup_files_with(
+            active_builtin_files(files, &ProjectFacts::discover(dir)),
+            &active_custom_protocols(dir),
+        ),
         note: "Read files in order. Project/roadmap first, then protocols.".into(),
     };
     serde_json::to_string_pretty(&entry).expect("Warmup entry serialization should never fail")
@@ -364,17 +472,82 @@ pub const PROTOCOL_FILES: &[(&str, fn() -> String)] = &[
     ("coding-standards.json", coding_standards_json),
 ];
 
+/// Protocol files to write for the project in `dir`, as (filename, content)
+/// The built-ins plus every included custom protocol
+/// Dates rendered, see `protocol_templates` for the templates
+pub fn protocol_files(dir: &Path) -> Vec<(String, String)> {
+    protocol_templates(dir)
+        .into_iter()
+        .map(|(filename, template)| (filename, inject_dates(&template)))
+        .collect()
+}
+
+/// Protocol files of the project in `dir` before `{TODAY}`/`{YEAR}` are
+/// rendered, as (filename, template)
+pub fn protocol_templates(dir: &Path) -> Vec<(String, String)> {
+    let custom = active_custom_protocols(dir);
+    let mut files: Vec<(String, String)> = PROTOCOL_FILES
+        .iter()
+        .map(|(filename, generator)| {
+            let content = if *filename == "warmup.json" {
+                warmup_entry_json_for(dir)
+            } else {
+                generator()
+            };
+            (filename.to_string(), content)
+        })
+        .collect();
+    files.extend(custom.iter().map(|c| (c.filename(), c.json())));
+    files
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_date_injection() {
-        let template = "Today is {TODAY}, year {YEAR}";
-        let result = inject_dates(template);
-        assert!(result.contains(&get_year()));
-        assert!(!result.contains("{TODAY}"));
-        assert!(!result.contains("{YEAR}"));
+    fn test_check_protocol_override() {
+        for (name, embedded) in [
+            ("asimov", ASIMOV_JSON),
+            ("green", GREEN_JSON),
+            ("warmup", WARMUP_JSON),
+            ("coding-standards", CODING_STANDARDS_JSON),
+        ] {
+            assert_eq!(check_protocol_override(name, embedded), Some(Ok(())));
+        }
+        let err = check_protocol_override("green", r#"{"rules": "typo"}"#)
+            .unwrap()
+            .unwrap_err();
+        assert!(err.contains("missing field `rule`"), "{}", err);
+        assert!(check_protocol_override("custom", "{}").is_none());
+    }
+
+    #[test]
+    fn test_protocol_schema_errors() {
+        for (name, generator) in PROTOCOL_FILES {
+            let name = name.trim_end_matches(".json");
+            assert_eq!(
+                protocol_schema_errors(name, &generator()),
+                Vec::<String>::new()
+            );
+        }
+        for name in PROTOCOL_NAMES {
+            let embedded = embedded_protocol(name).unwrap();
+            assert!(
+                protocol_schema_errors(name, embedded).is_empty(),
+                "{}",
+                name
+            );
+        }
+
+        // Parses (unknown keys are ignored), but the schema catches the typo
+        let typo = r#"{"rule": "x", "rulee": "y"}"#;
+        assert_eq!(check_protocol_override("green", typo), Some(Ok(())));
+        let errors = protocol_schema_errors("green", typo);
+        assert!(errors[0].contains("rulee"), "{:?}", errors);
+        let errors = protocol_schema_errors("sycophancy", r#"{"truth_over_comfort": "yes"}"#);
+        assert!(errors.iter().any(|e| e.starts_with("truth_over_comfort:")));
+        assert!(protocol_schema_errors("custom", "{}").is_empty());
     }
 
     #[test]
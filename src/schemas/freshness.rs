@@ -1,7 +1,7 @@
 //! JSON Schema for freshness.yaml files
 
 /// Schema for validating freshness.yaml protocol files
-pub const FRESHNESS_SCHEMA: &str = r#"{
+pub const FRESHNESS_SCHEMA: &str = r##"{
   "$schema": "http://json-schema.org/draft-07/schema#",
   "$id": "https://github.com/royalbit/asimov/schemas/freshness.json",
   "title": "RoyalBit Asimov - freshness.yaml",
@@ -47,14 +47,15 @@ pub const FRESHNESS_SCHEMA: &str = r#"{
     },
     "model_cutoffs": {
       "type": "object",
-      "description": "Knowledge cutoff dates for various AI models",
+      "description": "Knowledge cutoff dates for various AI models, by provider then model (YYYY-MM)",
       "properties": {
-        "claude": { "type": "object" },
-        "openai": { "type": "object" },
-        "google": { "type": "object" },
-        "xai": { "type": "object" },
+        "claude": { "$ref": "#/definitions/provider_cutoffs" },
+        "openai": { "$ref": "#/definitions/provider_cutoffs" },
+        "google": { "$ref": "#/definitions/provider_cutoffs" },
+        "xai": { "$ref": "#/definitions/provider_cutoffs" },
         "note": { "type": "string" }
-      }
+      },
+      "additionalProperties": { "$ref": "#/definitions/provider_cutoffs" }
     },
     "always_search": {
       "type": "object",
@@ -154,5 +155,14 @@ pub const FRESHNESS_SCHEMA: &str = r#"{
       "additionalProperties": { "type": "string" }
     },
     "motto": { "type": "string" }
+  },
+  "definitions": {
+    "provider_cutoffs": {
+      "type": "object",
+      "additionalProperties": {
+        "type": "string",
+        "pattern": "^[0-9]{4}-(0[1-9]|1[0-2])$"
+      }
+    }
   }
-}"#;
+}"##;
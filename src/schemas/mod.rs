@@ -7,9 +7,12 @@ mod freshness;
 mod green;
 mod migrations;
 mod project;
+mod protocols;
 mod roadmap;
+mod role;
 mod sprint;
 mod sycophancy;
+mod versioning;
 mod warmup;
 
 // NOTE: ethics.rs removed - asimov.yaml is the canonical ethics source (ADR-031)
@@ -20,11 +23,180 @@ pub use freshness::FRESHNESS_SCHEMA;
 pub use green::GREEN_SCHEMA;
 pub use migrations::MIGRATIONS_SCHEMA;
 pub use project::PROJECT_SCHEMA;
+pub use protocols::*;
 pub use roadmap::ROADMAP_SCHEMA;
+pub use role::ROLE_SCHEMA;
 pub use sprint::SPRINT_SCHEMA;
 pub use sycophancy::SYCOPHANCY_SCHEMA;
+pub use versioning::*;
 pub use warmup::WARMUP_SCHEMA;
 
+/// An embedded schema and the data files it describes
+#[derive(Debug, Clone, Copy)]
+pub struct SchemaInfo {
+    /// Short name (`roadmap`, `project`, ...), also the file stem it validates
+    pub name: &'static str,
+    /// Schema JSON text
+    pub schema: &'static str,
+    /// Glob patterns for editor schema catalogs (SchemaStore `fileMatch`)
+    pub file_match: &'static [&'static str],
+    /// Pre-JSON YAML protocol format, kept for projects that still have one
+    pub legacy: bool,
+}
+
+impl SchemaInfo {
+    /// The schema's own `description` (or `title`), labelled when legacy
+    pub fn description(&self) -> String {
+        let text = serde_json::from_str::<serde_json::Value>(self.schema)
+            .ok()
+            .and_then(|v| {
+                v["description"]
+                    .as_str()
+                    .or(v["title"].as_str())
+                    .map(String::from)
+            })
+            .unwrap_or_default();
+        if self.legacy {
+            format!("Legacy YAML protocol - {}", text)
+        } else {
+            text
+        }
+    }
+}
+
+/// All embedded schemas: data files, JSON protocols, then legacy YAML protocols
+pub const SCHEMAS: &[SchemaInfo] = &[
+    SchemaInfo {
+        name: "roadmap",
+        schema: ROADMAP_SCHEMA,
+        file_match: &["**/.asimov/roadmap.yaml", "**/.asimov/roadmap.yml"],
+        legacy: false,
+    },
+    SchemaInfo {
+        name: "project",
+        schema: PROJECT_SCHEMA,
+        file_match: &["**/.asimov/project.yaml", "**/.asimov/project.yml"],
+        legacy: false,
+    },
+    SchemaInfo {
+        name: "migrations",
+        schema: MIGRATIONS_SCHEMA,
+        file_match: &["**/.asimov/migrations.yaml", "**/.asimov/migrations.yml"],
+        legacy: false,
+    },
+    SchemaInfo {
+        name: "role",
+        schema: ROLE_SCHEMA,
+        file_match: &["**/.asimov/roles/*.json"],
+        legacy: false,
+    },
+    SchemaInfo {
+        name: "asimov",
+        schema: ASIMOV_PROTOCOL_SCHEMA,
+        file_match: &["**/.asimov/asimov.json", "**/.asimov/protocols/asimov.json"],
+        legacy: false,
+    },
+    SchemaInfo {
+        name: "freshness",
+        schema: FRESHNESS_PROTOCOL_SCHEMA,
+        file_match: &[
+            "**/.asimov/freshness.json",
+            "**/.asimov/protocols/freshness.json",
+        ],
+        legacy: false,
+    },
+    SchemaInfo {
+        name: "sycophancy",
+        schema: SYCOPHANCY_PROTOCOL_SCHEMA,
+        file_match: &[
+            "**/.asimov/sycophancy.json",
+            "**/.asimov/protocols/sycophancy.json",
+        ],
+        legacy: false,
+    },
+    SchemaInfo {
+        name: "green",
+        schema: GREEN_PROTOCOL_SCHEMA,
+        file_match: &["**/.asimov/green.json", "**/.asimov/protocols/green.json"],
+        legacy: false,
+    },
+    SchemaInfo {
+        name: "sprint",
+        schema: SPRINT_PROTOCOL_SCHEMA,
+        file_match: &["**/.asimov/sprint.json", "**/.asimov/protocols/sprint.json"],
+        legacy: false,
+    },
+    SchemaInfo {
+        name: "warmup",
+        schema: WARMUP_PROTOCOL_SCHEMA,
+        file_match: &["**/.asimov/warmup.json", "**/.asimov/protocols/warmup.json"],
+        legacy: false,
+    },
+    SchemaInfo {
+        name: "coding-standards",
+        schema: CODING_STANDARDS_PROTOCOL_SCHEMA,
+        file_match: &[
+            "**/.asimov/coding-standards.json",
+            "**/.asimov/protocols/coding-standards.json",
+        ],
+        legacy: false,
+    },
+    SchemaInfo {
+        name: "warmup-yaml",
+        schema: WARMUP_SCHEMA,
+        file_match: &["**/.asimov/warmup.yaml", "**/.asimov/warmup.yml"],
+        legacy: true,
+    },
+    SchemaInfo {
+        name: "sprint-yaml",
+        schema: SPRINT_SCHEMA,
+        file_match: &["**/.asimov/sprint.yaml", "**/.asimov/sprint.yml"],
+        legacy: true,
+    },
+    SchemaInfo {
+        name: "asimov-yaml",
+        schema: ASIMOV_SCHEMA,
+        file_match: &["**/.asimov/asimov.yaml", "**/.asimov/asimov.yml"],
+        legacy: true,
+    },
+    SchemaInfo {
+        name: "freshness-yaml",
+        schema: FRESHNESS_SCHEMA,
+        file_match: &["**/.asimov/freshness.yaml", "**/.asimov/freshness.yml"],
+        legacy: true,
+    },
+    SchemaInfo {
+        name: "green-yaml",
+        schema: GREEN_SCHEMA,
+        file_match: &["**/.asimov/green.yaml", "**/.asimov/green.yml"],
+        legacy: true,
+    },
+    SchemaInfo {
+        name: "sycophancy-yaml",
+        schema: SYCOPHANCY_SCHEMA,
+        file_match: &["**/.asimov/sycophancy.yaml", "**/.asimov/sycophancy.yml"],
+        legacy: true,
+    },
+];
+
+/// Look up an embedded schema by name
+pub fn schema_by_name(name: &str) -> Option<&'static SchemaInfo> {
+    let name = name.to_lowercase();
+    let name = name.trim_end_matches(".json").trim_end_matches(".schema");
+    // `sprint.yaml` means the legacy YAML protocol, `sprint` the JSON one
+    if let Some(stem) = name
+        .strip_suffix(".yaml")
+        .or_else(|| name.strip_suffix(".yml"))
+    {
+        let legacy = format!("{}-yaml", stem);
+        return SCHEMAS
+            .iter()
+            .find(|s| s.name == legacy)
+            .or_else(|| SCHEMAS.iter().find(|s| s.name == stem));
+    }
+    SCHEMAS.iter().find(|s| s.name == name)
+}
+
 /// Determine which schema to use based on filename
 /// NOTE: ethics.yaml is no longer supported - use asimov.yaml (ADR-031)
 /// NOTE: checkpoint.yaml is deprecated - use hooks instead (ADR-032)
@@ -81,10 +253,92 @@ pub fn schema_type_for_file(filename: &str) -> Option<&'static str> {
     }
 }
 
+/// Find the schema node describing the value at a path
+///
+/// Walks `properties` for keys and `items` for array indices. Returns `None`
+/// when the path leaves the schema (e.g. an undeclared property).
+pub fn schema_node<'a>(
+    schema: &'a serde_json::Value,
+    path: &[String],
+) -> Option<&'a serde_json::Value> {
+    let mut node = schema;
+    for segment in path {
+        node = match node.get("properties").and_then(|p| p.get(segment)) {
+            Some(child) => child,
+            None if segment.parse::<usize>().is_ok() => node.get("items")?,
+            None => return None,
+        };
+    }
+    Some(node)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_schemas_registry_is_complete_and_valid() {
+        assert_eq!(SCHEMAS.len(), 17);
+        for info in SCHEMAS {
+            let schema: serde_json::Value = serde_json::from_str(info.schema)
+                .unwrap_or_else(|e| panic!("{} schema is invalid JSON: {}", info.name, e));
+            assert!(schema["$id"].is_string(), "{} schema needs $id", info.name);
+            assert!(!info.file_match.is_empty());
+            assert!(
+                !info.description().is_empty(),
+                "{} needs a description",
+                info.name
+            );
+            // YAML files are also resolved by file name
+            if info.file_match[0].ends_with(".yaml") {
+                let stem = info.name.trim_end_matches("-yaml");
+                assert_eq!(schema_type_for_file(&format!("{}.yaml", stem)), Some(stem));
+                assert_eq!(info.legacy, stem != info.name);
+            }
+        }
+
+        // Every JSON protocol is in the catalog, matched where it is written
+        for (name, schema) in PROTOCOL_SCHEMAS {
+            let info = SCHEMAS.iter().find(|s| s.name == *name).unwrap();
+            assert_eq!(info.schema, *schema);
+            assert!(info
+                .file_match
+                .contains(&format!("**/.asimov/{}.json", name).as_str()));
+            assert!(info
+                .file_match
+                .contains(&format!("**/.asimov/protocols/{}.json", name).as_str()));
+        }
+    }
+
+    #[test]
+    fn test_schema_by_name() {
+        assert_eq!(schema_by_name("roadmap").unwrap().name, "roadmap");
+        assert_eq!(
+            schema_by_name("Project.schema.json").unwrap().name,
+            "project"
+        );
+        assert!(schema_by_name("unknown").is_none());
+        assert_eq!(schema_by_name("sprint.json").unwrap().name, "sprint");
+        assert_eq!(schema_by_name("sprint.yaml").unwrap().name, "sprint-yaml");
+        assert!(schema_by_name("sprint-yaml").unwrap().legacy);
+        assert_eq!(schema_by_name("roadmap.yml").unwrap().name, "roadmap");
+        assert!(!schema_by_name("roadmap").unwrap().description().is_empty());
+    }
+
+    #[test]
+    fn test_schema_node_walks_properties_and_items() {
+        let schema: serde_json::Value = serde_json::from_str(ROADMAP_SCHEMA).unwrap();
+        let path = |p: &[&str]| p.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        let status = schema_node(&schema, &path(&["current", "status"])).unwrap();
+        assert!(status["enum"].as_array().unwrap().len() == 4);
+
+        let version = schema_node(&schema, &path(&["next", "2", "version"])).unwrap();
+        assert_eq!(version["type"], "string");
+
+        assert!(schema_node(&schema, &path(&["current", "unknown"])).is_none());
+    }
+
     #[test]
     fn test_schema_for_file_warmup() {
         assert!(schema_for_file("warmup.yaml").is_some());
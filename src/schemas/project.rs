@@ -11,6 +11,11 @@ pub const PROJECT_SCHEMA: &str = r#"{
   "type": "object",
   "required": ["identity"],
   "properties": {
+    "schema_version": {
+      "type": "integer",
+      "minimum": 1,
+      "description": "Schema version of this file (migrated by asimov refresh)"
+    },
     "identity": {
       "type": "object",
       "description": "Project identity",
@@ -23,8 +28,12 @@ pub const PROJECT_SCHEMA: &str = r#"{
         },
         "type": {
           "type": "string",
-          "enum": ["rust", "python", "node", "go", "flutter", "docs", "generic", "migration"],
-          "description": "Project type"
+          "enum": ["rust", "python", "node", "go", "flutter", "java", "csharp", "ruby", "php", "cpp", "docs", "arch", "generic", "migration"],
+          "description": "Project type (base language)"
+        },
+        "kind": {
+          "type": "string",
+          "description": "Template kind (e.g., backend-api, frontend, monorepo)"
         },
         "version": {
           "type": "string",
@@ -40,6 +49,25 @@ pub const PROJECT_SCHEMA: &str = r#"{
         }
       }
     },
+    "components": {
+      "type": "array",
+      "description": "Projects inside a monorepo or polyglot repository (suggested by asimov refresh)",
+      "items": {
+        "type": "object",
+        "required": ["path", "type"],
+        "properties": {
+          "path": {
+            "type": "string",
+            "description": "Directory relative to the repository root"
+          },
+          "type": {
+            "type": "string",
+            "enum": ["rust", "python", "node", "go", "flutter", "java", "csharp", "ruby", "php", "cpp", "docs", "arch", "generic", "migration"],
+            "description": "Component language"
+          }
+        }
+      }
+    },
     "quality": {
       "type": "object",
       "description": "Quality gate commands",
@@ -78,6 +106,31 @@ pub const PROJECT_SCHEMA: &str = r#"{
         }
       }
     },
+    "commit_msg": {
+      "type": "object",
+      "description": "Commit message policy checked by the commit-msg hook",
+      "properties": {
+        "types": {
+          "type": "array",
+          "items": { "type": "string" },
+          "description": "Allowed conventional commit types (e.g. feat, fix, docs)"
+        },
+        "require_reference": {
+          "type": "boolean",
+          "description": "Require a milestone (v1.2.0) or ADR (ADR-040) reference"
+        },
+        "attribution": {
+          "type": "string",
+          "enum": ["allow", "require", "forbid"],
+          "description": "AI attribution trailer policy (Co-Authored-By)"
+        },
+        "max_subject_length": {
+          "type": "integer",
+          "minimum": 0,
+          "description": "Maximum subject line length (0 = no limit, default 72)"
+        }
+      }
+    },
     "files": {
       "type": "object",
       "description": "Project file structure",
@@ -0,0 +1,135 @@
+//! JSON Schemas for the .asimov/*.json protocol files
+//!
+//! `FRESHNESS_SCHEMA`, `GREEN_SCHEMA`, `SYCOPHANCY_SCHEMA` and friends describe
+//! the pre-v9 YAML protocol files and accept any extra key. These describe the
+//! JSON protocols the loaders read, and reject unknown keys so a misspelled
+//! field in an override is reported instead of silently ignored.
+
+/// Schema for asimov.json (harm prevention)
+pub const ASIMOV_PROTOCOL_SCHEMA: &str = r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "$id": "https://github.com/royalbit/asimov/schemas/protocols/asimov.json",
+  "title": "RoyalBit Asimov - asimov.json",
+  "type": "object",
+  "required": ["harm", "veto"],
+  "additionalProperties": false,
+  "properties": {
+    "description": { "type": "string" },
+    "harm": { "type": "array", "items": { "type": "string" } },
+    "veto": { "type": "array", "items": { "type": "string" } }
+  }
+}"#;
+
+/// Schema for freshness.json (date-aware search)
+pub const FRESHNESS_PROTOCOL_SCHEMA: &str = r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "$id": "https://github.com/royalbit/asimov/schemas/protocols/freshness.json",
+  "title": "RoyalBit Asimov - freshness.json",
+  "type": "object",
+  "required": ["rule"],
+  "additionalProperties": false,
+  "properties": {
+    "rule": { "type": "string", "minLength": 1 }
+  }
+}"#;
+
+/// Schema for sycophancy.json (truth over comfort)
+pub const SYCOPHANCY_PROTOCOL_SCHEMA: &str = r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "$id": "https://github.com/royalbit/asimov/schemas/protocols/sycophancy.json",
+  "title": "RoyalBit Asimov - sycophancy.json",
+  "type": "object",
+  "required": ["truth_over_comfort", "disagree_openly", "rule"],
+  "additionalProperties": false,
+  "properties": {
+    "truth_over_comfort": { "type": "boolean" },
+    "disagree_openly": { "type": "boolean" },
+    "rule": { "type": "string", "minLength": 1 }
+  }
+}"#;
+
+/// Schema for green.json (local-first)
+pub const GREEN_PROTOCOL_SCHEMA: &str = r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "$id": "https://github.com/royalbit/asimov/schemas/protocols/green.json",
+  "title": "RoyalBit Asimov - green.json",
+  "type": "object",
+  "required": ["rule"],
+  "additionalProperties": false,
+  "properties": {
+    "rule": { "type": "string", "minLength": 1 }
+  }
+}"#;
+
+/// Schema for sprint.json (session boundaries)
+pub const SPRINT_PROTOCOL_SCHEMA: &str = r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "$id": "https://github.com/royalbit/asimov/schemas/protocols/sprint.json",
+  "title": "RoyalBit Asimov - sprint.json",
+  "type": "object",
+  "required": ["rule", "compaction_reminder"],
+  "additionalProperties": false,
+  "properties": {
+    "rule": { "type": "string", "minLength": 1 },
+    "compaction_reminder": { "type": "string" }
+  }
+}"#;
+
+/// Schema for warmup.json (session bootstrap)
+pub const WARMUP_PROTOCOL_SCHEMA: &str = r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "$id": "https://github.com/royalbit/asimov/schemas/protocols/warmup.json",
+  "title": "RoyalBit Asimov - warmup.json",
+  "type": "object",
+  "required": ["on_start"],
+  "additionalProperties": false,
+  "properties": {
+    "on_start": { "type": "array", "items": { "type": "string" } },
+    "files": { "type": "array", "items": { "type": "string" } },
+    "note": { "type": "string" }
+  }
+}"#;
+
+/// Schema for coding-standards.json (human-readable code)
+pub const CODING_STANDARDS_PROTOCOL_SCHEMA: &str = r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "$id": "https://github.com/royalbit/asimov/schemas/protocols/coding-standards.json",
+  "title": "RoyalBit Asimov - coding-standards.json",
+  "type": "object",
+  "required": ["philosophy", "rfc2119", "principles", "rule"],
+  "additionalProperties": false,
+  "properties": {
+    "philosophy": { "type": "string" },
+    "rfc2119": {
+      "type": "object",
+      "required": ["MUST", "SHOULD", "MAY"],
+      "additionalProperties": false,
+      "properties": {
+        "MUST": { "type": "string" },
+        "SHOULD": { "type": "string" },
+        "MAY": { "type": "string" }
+      }
+    },
+    "principles": { "type": "array", "items": { "type": "string" } },
+    "rule": { "type": "string", "minLength": 1 }
+  }
+}"#;
+
+/// Schema of each built-in JSON protocol, by protocol name
+pub const PROTOCOL_SCHEMAS: &[(&str, &str)] = &[
+    ("asimov", ASIMOV_PROTOCOL_SCHEMA),
+    ("freshness", FRESHNESS_PROTOCOL_SCHEMA),
+    ("sycophancy", SYCOPHANCY_PROTOCOL_SCHEMA),
+    ("green", GREEN_PROTOCOL_SCHEMA),
+    ("sprint", SPRINT_PROTOCOL_SCHEMA),
+    ("warmup", WARMUP_PROTOCOL_SCHEMA),
+    ("coding-standards", CODING_STANDARDS_PROTOCOL_SCHEMA),
+];
+
+/// Schema of a built-in JSON protocol
+pub fn protocol_schema(name: &str) -> Option<&'static str> {
+    PROTOCOL_SCHEMAS
+        .iter()
+        .find(|(protocol, _)| *protocol == name)
+        .map(|(_, schema)| *schema)
+}
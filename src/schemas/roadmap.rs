@@ -8,6 +8,11 @@ pub const ROADMAP_SCHEMA: &str = r#"
   "description": "Milestone planning for the RoyalBit Asimov. See docs/PROTOCOL_GOALS.md for core values, CHANGELOG.md for release history.",
   "type": "object",
   "properties": {
+    "schema_version": {
+      "type": "integer",
+      "minimum": 1,
+      "description": "Schema version of this file (migrated by asimov refresh)"
+    },
     "current": {
       "type": "object",
       "description": "Current milestone in progress",
@@ -0,0 +1,43 @@
+//! JSON Schema for role files (.asimov/roles/*.json)
+//!
+//! Roles are loaded by `asimov role` (v10.0.0). The file stem must match `code`.
+
+pub const ROLE_SCHEMA: &str = r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "$id": "https://github.com/royalbit/asimov/schemas/role.json",
+  "title": "RoyalBit Asimov - role",
+  "description": "Role definition for asimov role switching",
+  "type": "object",
+  "required": ["name", "code", "description", "focus", "prompt_prefix", "avoid"],
+  "properties": {
+    "name": {
+      "type": "string",
+      "minLength": 1,
+      "description": "Display name (e.g., Principal Engineer)"
+    },
+    "code": {
+      "type": "string",
+      "pattern": "^[a-z0-9][a-z0-9_-]*$",
+      "description": "Short code used with asimov role <code>; must match the file name"
+    },
+    "description": {
+      "type": "string",
+      "description": "One-line description of the role"
+    },
+    "focus": {
+      "type": "array",
+      "items": { "type": "string" },
+      "description": "Focus areas"
+    },
+    "prompt_prefix": {
+      "type": "string",
+      "minLength": 1,
+      "description": "Instruction prepended when the role is active"
+    },
+    "avoid": {
+      "type": "array",
+      "items": { "type": "string" },
+      "description": "Topics deferred to other roles"
+    }
+  }
+}"#;
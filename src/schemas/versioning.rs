@@ -0,0 +1,419 @@
+//! Schema versions and ordered migrations for project data files
+//!
+//! `project.yaml` and `roadmap.yaml` declare `schema_version`. Files without
+//! it predate versioning and are treated as version 1. `asimov refresh` walks
+//! the steps below from the file's version up to the binary's version.
+//!
+//! Steps rewrite the YAML text, not a parsed value, so comments and layout
+//! survive. To evolve a schema: bump its `*_SCHEMA_VERSION`, append a step
+//! with `from` equal to the old version, and add a test for the step.
+
+use std::fmt;
+
+/// Current `project.yaml` schema version
+pub const PROJECT_SCHEMA_VERSION: u32 = 3;
+
+/// Current `roadmap.yaml` schema version
+pub const ROADMAP_SCHEMA_VERSION: u32 = 2;
+
+/// Version assumed for files written before `schema_version` existed
+pub const UNVERSIONED_SCHEMA_VERSION: u32 = 1;
+
+/// One migration from `from` to `from + 1`
+#[derive(Debug, Clone, Copy)]
+pub struct MigrationStep {
+    pub from: u32,
+    pub description: &'static str,
+    /// Rewrites the file content; `schema_version` is stamped afterwards.
+    /// An error leaves the file at its old version.
+    pub apply: fn(&str) -> Result<String, String>,
+}
+
+// Step 1: versioning introduced - pre-versioned files only gain the field
+// Step 2: identity.type is the base language; template kinds move to identity.kind
+const PROJECT_STEPS: &[MigrationStep] = &[
+    MigrationStep {
+        from: 1,
+        description: "Add schema_version",
+        apply: unchanged,
+    },
+    MigrationStep {
+        from: 2,
+        description: "Move template kind out of identity.type",
+        apply: move_template_kind,
+    },
+];
+
+/// Template kinds older templates wrote as `identity.type`
+const TEMPLATE_KINDS: &[&str] = &["backend-api", "frontend", "monorepo"];
+
+const ROADMAP_STEPS: &[MigrationStep] = &[MigrationStep {
+    from: 1,
+    description: "Add schema_version",
+    apply: unchanged,
+}];
+
+/// Why a file cannot be migrated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationError {
+    /// The file was written by a newer asimov; migrating would lose data
+    Downgrade { found: u32, supported: u32 },
+    /// `schema_version` is present but not a positive integer
+    InvalidVersion(String),
+    /// No step starts at this version (a gap in the step list)
+    MissingStep(u32),
+    /// The file is not parseable YAML
+    InvalidYaml(String),
+    /// A step cannot rewrite this file's layout
+    StepFailed { from: u32, reason: String },
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationError::Downgrade { found, supported } => write!(
+                f,
+                "schema_version {} is newer than this asimov supports ({}). Run: asimov update",
+                found, supported
+            ),
+            MigrationError::InvalidVersion(v) => {
+                write!(f, "schema_version must be a positive integer (found {})", v)
+            }
+            MigrationError::MissingStep(v) => write!(f, "no migration from schema_version {}", v),
+            MigrationError::InvalidYaml(e) => write!(f, "invalid YAML: {}", e),
+            MigrationError::StepFailed { from, reason } => write!(
+                f,
+                "migration from schema_version {} failed: {}",
+                from, reason
+            ),
+        }
+    }
+}
+
+/// Migrations needed to bring one file up to date
+#[derive(Debug, Clone)]
+pub struct MigrationPlan {
+    pub from: u32,
+    pub to: u32,
+    /// Descriptions of the applied steps, in order
+    pub steps: Vec<&'static str>,
+    pub migrated: String,
+}
+
+/// Current schema version for a data file schema (`project`, `roadmap`)
+pub fn current_schema_version(schema_type: &str) -> Option<u32> {
+    match schema_type {
+        "project" => Some(PROJECT_SCHEMA_VERSION),
+        "roadmap" => Some(ROADMAP_SCHEMA_VERSION),
+        _ => None,
+    }
+}
+
+fn migration_steps(schema_type: &str) -> &'static [MigrationStep] {
+    match schema_type {
+        "project" => PROJECT_STEPS,
+        "roadmap" => ROADMAP_STEPS,
+        _ => &[],
+    }
+}
+
+/// Declared `schema_version` of a file (1 when absent)
+pub fn schema_version_of(content: &str) -> Result<u32, MigrationError> {
+    let yaml: serde_yaml_ng::Value =
+        serde_yaml_ng::from_str(content).map_err(|e| MigrationError::InvalidYaml(e.to_string()))?;
+
+    match yaml.get("schema_version") {
+        None => Ok(UNVERSIONED_SCHEMA_VERSION),
+        Some(value) => value
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .filter(|v| *v >= 1)
+            .ok_or_else(|| {
+                MigrationError::InvalidVersion(
+                    serde_yaml_ng::to_string(value)
+                        .unwrap_or_default()
+                        .trim()
+                        .to_string(),
+                )
+            }),
+    }
+}
+
+/// Plan the migration of a data file, `None` when it is already current
+pub fn plan_migration(
+    schema_type: &str,
+    content: &str,
+) -> Result<Option<MigrationPlan>, MigrationError> {
+    let Some(target) = current_schema_version(schema_type) else {
+        return Ok(None);
+    };
+    let found = schema_version_of(content)?;
+    if found > target {
+        return Err(MigrationError::Downgrade {
+            found,
+            supported: target,
+        });
+    }
+    if found == target {
+        return Ok(None);
+    }
+
+    let steps = migration_steps(schema_type);
+    let mut migrated = content.to_string();
+    let mut applied = Vec::new();
+    for version in found..target {
+        let step = steps
+            .iter()
+            .find(|s| s.from == version)
+            .ok_or(MigrationError::MissingStep(version))?;
+        let stepped = (step.apply)(&migrated).map_err(|reason| MigrationError::StepFailed {
+            from: version,
+            reason,
+        })?;
+        migrated = set_schema_version(&stepped, version + 1);
+        applied.push(step.description);
+    }
+
+    Ok(Some(MigrationPlan {
+        from: found,
+        to: target,
+        steps: applied,
+        migrated,
+    }))
+}
+
+/// Stamp the current schema version on freshly generated content
+pub fn with_current_schema_version(schema_type: &str, content: &str) -> String {
+    match current_schema_version(schema_type) {
+        Some(version) => set_schema_version(content, version),
+        None => content.to_string(),
+    }
+}
+
+/// Set the top-level `schema_version`, replacing it or inserting it after the header comments
+pub fn set_schema_version(content: &str, version: u32) -> String {
+    let line = format!("schema_version: {}", version);
+    let mut lines: Vec<&str> = content.lines().collect();
+
+    if let Some(index) = lines.iter().position(|l| l.starts_with("schema_version:")) {
+        lines[index] = &line;
+        return join_lines(&lines, content);
+    }
+
+    let insert_at = lines
+        .iter()
+        .position(|l| {
+            let trimmed = l.trim();
+            !trimmed.is_empty() && !trimmed.starts_with('#') && trimmed != "---"
+        })
+        .unwrap_or(lines.len());
+    lines.insert(insert_at, "");
+    lines.insert(insert_at, &line);
+    if insert_at + 2 == lines.len() {
+        lines.pop();
+    }
+    join_lines(&lines, content)
+}
+
+fn join_lines(lines: &[&str], original: &str) -> String {
+    let mut joined = lines.join("\n");
+    if original.ends_with('\n') || original.is_empty() {
+        joined.push('\n');
+    }
+    joined
+}
+
+fn unchanged(content: &str) -> Result<String, String> {
+    Ok(content.to_string())
+}
+
+/// `type: backend-api` -> `type: <language>` + `kind: backend-api`
+///
+/// Works at whatever indentation the `identity:` block uses; fails when the
+/// kind is still in `identity.type` afterwards (e.g. a flow mapping).
+fn move_template_kind(content: &str) -> Result<String, String> {
+    let mut in_identity = false;
+    // Indentation of the keys directly under `identity:`
+    let mut indent: Option<&str> = None;
+    let mut lines = Vec::new();
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if !line.starts_with([' ', '#']) && !line.trim().is_empty() {
+            in_identity = line.split(" #").next().unwrap_or_default().trim_end() == "identity:";
+            indent = None;
+        } else if in_identity
+            && indent.is_none()
+            && !trimmed.is_empty()
+            && !trimmed.starts_with('#')
+        {
+            indent = Some(&line[..line.len() - trimmed.len()]);
+        }
+        let kind = indent
+            .filter(|_| in_identity)
+            .and_then(|indent| line.strip_prefix(indent)?.strip_prefix("type:"))
+            .map(|value| value.split('#').next().unwrap_or_default())
+            .map(|value| value.trim().trim_matches(['"', '\'']))
+            .filter(|value| TEMPLATE_KINDS.contains(value));
+        match (kind, indent) {
+            (Some(kind), Some(indent)) => {
+                lines.push(format!("{}type: {}", indent, stack_language(content, kind)));
+                lines.push(format!("{}kind: {}", indent, kind));
+            }
+            _ => lines.push(line.to_string()),
+        }
+    }
+    let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+    let migrated = join_lines(&lines, content);
+
+    let left = serde_yaml_ng::from_str::<serde_yaml_ng::Value>(&migrated)
+        .ok()
+        .and_then(|yaml| Some(yaml.get("identity")?.get("type")?.as_str()?.to_string()))
+        .filter(|value| TEMPLATE_KINDS.contains(&value.as_str()));
+    match left {
+        Some(kind) => Err(format!(
+            "could not move identity.type '{}' - set identity.type to the language and add identity.kind: {} by hand",
+            kind, kind
+        )),
+        None => Ok(migrated),
+    }
+}
+
+/// Project type for a migrated template kind, from `stack.language`
+fn stack_language(content: &str, kind: &str) -> &'static str {
+    let language = serde_yaml_ng::from_str::<serde_yaml_ng::Value>(content)
+        .ok()
+        .and_then(|yaml| Some(yaml.get("stack")?.get("language")?.as_str()?.to_lowercase()))
+        .unwrap_or_default();
+    let known = [
+        ("rust", "rust"),
+        ("go", "go"),
+        ("python", "python"),
+        ("typescript", "node"),
+        ("javascript", "node"),
+        ("java", "java"),
+        ("kotlin", "java"),
+    ];
+    known
+        .iter()
+        .find(|(marker, _)| language.split_whitespace().next() == Some(marker))
+        .map(|(_, project_type)| *project_type)
+        .unwrap_or(if kind == "backend-api" {
+            "generic"
+        } else {
+            "node"
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steps_cover_every_version() {
+        for schema_type in ["project", "roadmap"] {
+            let target = current_schema_version(schema_type).unwrap();
+            for version in UNVERSIONED_SCHEMA_VERSION..target {
+                assert!(
+                    migration_steps(schema_type)
+                        .iter()
+                        .any(|s| s.from == version),
+                    "{} has no step from {}",
+                    schema_type,
+                    version
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_schema_version_of() {
+        assert_eq!(schema_version_of("current: {}\n"), Ok(1));
+        assert_eq!(schema_version_of("schema_version: 3\n"), Ok(3));
+        assert!(matches!(
+            schema_version_of("schema_version: two\n"),
+            Err(MigrationError::InvalidVersion(_))
+        ));
+        assert!(matches!(
+            schema_version_of("schema_version: 0\n"),
+            Err(MigrationError::InvalidVersion(_))
+        ));
+    }
+
+    #[test]
+    fn test_plan_migration_unversioned_roadmap() {
+        let content = "# Roadmap\n\ncurrent:\n  version: '1.0' # keep me\n";
+        let plan = plan_migration("roadmap", content).unwrap().unwrap();
+        assert_eq!(plan.from, 1);
+        assert_eq!(plan.to, ROADMAP_SCHEMA_VERSION);
+        assert_eq!(plan.steps, vec!["Add schema_version"]);
+        assert_eq!(
+            plan.migrated,
+            format!(
+                "# Roadmap\n\nschema_version: {}\n\ncurrent:\n  version: '1.0' # keep me\n",
+                ROADMAP_SCHEMA_VERSION
+            )
+        );
+        assert!(plan_migration("roadmap", &plan.migrated).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_plan_migration_moves_template_kind() {
+        let content = "schema_version: 2\n\nidentity:\n  name: api\n  type: backend-api # from api-spring\n\nstack:\n  language: \"Java 21\"\n  type: backend-api\n";
+        let plan = plan_migration("project", content).unwrap().unwrap();
+        assert_eq!(plan.steps, vec!["Move template kind out of identity.type"]);
+        assert_eq!(
+            plan.migrated,
+            format!(
+                "schema_version: {}\n\nidentity:\n  name: api\n  type: java\n  kind: backend-api\n\nstack:\n  language: \"Java 21\"\n  type: backend-api\n",
+                PROJECT_SCHEMA_VERSION
+            )
+        );
+
+        // Without a known stack language: frontends are node, APIs generic
+        let migrate = |content: &str| move_template_kind(content).unwrap();
+        assert!(migrate("identity:\n  type: frontend\n").contains("  type: node\n"));
+        assert!(migrate("identity:\n  type: 'backend-api'\n").contains("  type: generic\n"));
+        let rust = "identity:\n  type: rust\n";
+        assert_eq!(migrate(rust), rust);
+
+        // Any indentation of the identity block
+        assert_eq!(
+            migrate("identity:  # who\n    name: x\n    type: monorepo\n"),
+            "identity:  # who\n    name: x\n    type: node\n    kind: monorepo\n"
+        );
+
+        // A layout the step cannot rewrite keeps the old version
+        let flow = "schema_version: 2\nidentity: {name: x, type: frontend}\n";
+        let err = plan_migration("project", flow).unwrap_err();
+        assert!(
+            matches!(err, MigrationError::StepFailed { from: 2, .. }),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_plan_migration_refuses_downgrade() {
+        let content = format!("schema_version: {}\n", PROJECT_SCHEMA_VERSION + 1);
+        assert_eq!(
+            plan_migration("project", &content).unwrap_err(),
+            MigrationError::Downgrade {
+                found: PROJECT_SCHEMA_VERSION + 1,
+                supported: PROJECT_SCHEMA_VERSION,
+            }
+        );
+    }
+
+    #[test]
+    fn test_plan_migration_ignores_unversioned_schemas() {
+        assert!(plan_migration("warmup", "a: 1\n").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_set_schema_version_replaces_existing() {
+        assert_eq!(
+            set_schema_version("schema_version: 1\nidentity: {}\n", 2),
+            "schema_version: 2\nidentity: {}\n"
+        );
+        assert_eq!(set_schema_version("", 2), "schema_version: 2\n");
+    }
+}
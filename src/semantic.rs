@@ -4,8 +4,10 @@
 //! - Version consistency across files
 //! - Deprecated pattern detection
 //! - Cross-reference validation
+//! - Stale dated claims and version assertions (see `stale_claims`)
 
 use crate::markdown::find_markdown_files;
+use crate::stale_claims::check_stale_claims;
 use regex::Regex;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -17,6 +19,8 @@ pub struct SemanticResult {
     pub files_checked: usize,
     pub version_refs_found: usize,
     pub deprecated_matches: usize,
+    /// Dated claims and version assertions found
+    pub freshness_claims_found: usize,
 }
 
 impl SemanticResult {
@@ -56,6 +60,8 @@ pub enum IssueCategory {
     VersionMismatch,
     DeprecatedPattern,
     HelpDocMismatch,
+    /// A claim older than allowed, or citing an unlisted source
+    StaleClaim,
 }
 
 impl std::fmt::Display for IssueCategory {
@@ -64,6 +70,7 @@ impl std::fmt::Display for IssueCategory {
             IssueCategory::VersionMismatch => write!(f, "version"),
             IssueCategory::DeprecatedPattern => write!(f, "deprecated"),
             IssueCategory::HelpDocMismatch => write!(f, "help-doc"),
+            IssueCategory::StaleClaim => write!(f, "freshness"),
         }
     }
 }
@@ -84,6 +91,9 @@ pub struct SemanticConfig {
     pub expected_version: Option<String>,
     /// Check --help output against docs
     pub check_help: bool,
+    /// Flag claims older than this many months before their file's
+    /// last change (None skips the check)
+    pub max_claim_age_months: Option<u32>,
 }
 
 /// A deprecated pattern to detect
@@ -142,6 +152,11 @@ pub fn check_semantic(dir: &Path, config: &SemanticConfig) -> SemanticResult {
         check_help_doc_consistency(dir, &mut result);
     }
 
+    // 4. Freshness-sensitive claims
+    if let Some(max_age) = config.max_claim_age_months {
+        check_stale_claims(dir, &md_files, max_age, &mut result);
+    }
+
     result
 }
 
@@ -442,6 +457,7 @@ mod tests {
             "deprecated"
         );
         assert_eq!(format!("{}", IssueCategory::HelpDocMismatch), "help-doc");
+        assert_eq!(format!("{}", IssueCategory::StaleClaim), "freshness");
     }
 
     #[test]
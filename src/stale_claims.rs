@@ -0,0 +1,544 @@
+//! Freshness-sensitive claims in documentation
+//!
+//! Stale data is its own failure mode (see the freshness protocol), and our
+//! docs go stale too. `lint-docs --semantic` finds claims that age:
+//!
+//! - dated claims - a date (`2024`, `2024-05`, `May 2024`) on a line that
+//!   asserts something current: "latest", "as of", "fastest", "12x", "40%"
+//! - version assertions - "the latest version is 1.4"
+//!
+//! A claim is dated by the `references.yaml` entry it links to, else by the
+//! newest date it states, else (version assertions) by the commit that last
+//! touched its line. It is stale when that date is more than the maximum age
+//! before the file's last git modification, so results only change with the
+//! history. Sources a claim links to that `references.yaml` lacks are
+//! reported as well.
+
+use crate::clock::clock;
+use crate::semantic::{IssueCategory, SemanticIssue, SemanticResult, Severity};
+use chrono::{DateTime, Datelike, NaiveDate};
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Default maximum age of a claim relative to its file's last change
+pub const DEFAULT_MAX_CLAIM_AGE_MONTHS: u32 = 12;
+
+/// Where the project's sources are listed, relative to the project root
+pub const REFERENCES_FILES: &[&str] = &[
+    "references.yaml",
+    ".asimov/references.yaml",
+    "docs/references.yaml",
+];
+
+/// Words that make a dated line a claim about the present
+const CLAIM_MARKERS: &str = r"(?i)\b(as of|currently|current|latest|newest|today|now|recent|recently|state[- ]of[- ]the[- ]art|benchmarks?|benchmarked|fastest|slowest|cheapest|leading|most popular)\b";
+
+/// Measured numbers: percentages, multipliers, latencies, throughput
+const QUANTITY: &str = r"(?i)\d+(\.\d+)?\s?(%|x\b|×|ms\b|req/s|tokens/s)";
+
+const VERSION_ASSERTION: &str =
+    r"(?i)\b(latest|current|newest)\s+(stable\s+)?(version|release)\b[^.\n]{0,40}?\bv?\d+\.\d+";
+
+const ISO_DATE: &str = r"\b((?:19|20)\d{2})-(0[1-9]|1[0-2])(?:-\d{2})?\b";
+
+const MONTH_DATE: &str = r"(?i)\b(jan|feb|mar|apr|may|jun|jul|aug|sep|oct|nov|dec)[a-z]*\.?\s+(?:\d{1,2},?\s+)?((?:19|20)\d{2})\b";
+
+const YEAR: &str = r"\b((?:19|20)\d{2})\b";
+
+const URL: &str = r#"https?://[^\s)>\]"'`]+"#;
+
+/// A year and month; bare years are read as their December
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct YearMonth {
+    pub year: i32,
+    pub month: u32,
+}
+
+impl YearMonth {
+    fn of(date: NaiveDate) -> Self {
+        YearMonth {
+            year: date.year(),
+            month: date.month(),
+        }
+    }
+
+    /// `2024`, `2024-05` or `2024-05-17`
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.trim().split('-');
+        let year = parts.next()?.parse().ok()?;
+        let month = match parts.next() {
+            Some(month) => month.parse().ok().filter(|m| (1..=12).contains(m))?,
+            None => 12,
+        };
+        Some(YearMonth { year, month })
+    }
+
+    /// Whole months from `self` to `later`
+    pub fn months_until(&self, later: YearMonth) -> i32 {
+        (later.year - self.year) * 12 + later.month as i32 - self.month as i32
+    }
+}
+
+impl std::fmt::Display for YearMonth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{:02}", self.year, self.month)
+    }
+}
+
+/// A source listed in `references.yaml`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reference {
+    pub url: String,
+    pub title: Option<String>,
+    /// `date` (or `verified`/`accessed`) of the entry
+    pub date: Option<YearMonth>,
+}
+
+fn yaml_text(value: &serde_yaml_ng::Value) -> Option<String> {
+    match value {
+        serde_yaml_ng::Value::String(s) => Some(s.clone()),
+        serde_yaml_ng::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn collect_references(value: &serde_yaml_ng::Value, references: &mut Vec<Reference>) {
+    match value {
+        serde_yaml_ng::Value::Mapping(map) => {
+            if let Some(url) = map.get("url").and_then(yaml_text) {
+                references.push(Reference {
+                    url: normalize_url(&url),
+                    title: map.get("title").and_then(yaml_text),
+                    date: ["date", "verified", "accessed"]
+                        .iter()
+                        .find_map(|key| map.get(*key).and_then(yaml_text))
+                        .and_then(|date| YearMonth::parse(&date)),
+                });
+            }
+            for (_, item) in map {
+                collect_references(item, references);
+            }
+        }
+        serde_yaml_ng::Value::Sequence(items) => {
+            for item in items {
+                collect_references(item, references);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The `references.yaml` for docs in `start`: the first of
+/// `REFERENCES_FILES` in it or an enclosing directory up to the git root
+pub fn find_references_file(start: &Path) -> Option<PathBuf> {
+    for dir in start.ancestors() {
+        if let Some(path) = REFERENCES_FILES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+        {
+            return Some(path);
+        }
+        if dir.join(".git").exists() {
+            break;
+        }
+    }
+    None
+}
+
+/// Every entry with a `url`, at any depth, of a references file
+pub fn load_references(path: &Path) -> Vec<Reference> {
+    let mut references = Vec::new();
+    if let Some(yaml) = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_yaml_ng::from_str(&content).ok())
+    {
+        collect_references(&yaml, &mut references);
+    }
+    references
+}
+
+fn normalize_url(url: &str) -> String {
+    url.trim()
+        .trim_end_matches(['.', ',', ';', ':'])
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// Kind of freshness-sensitive claim
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClaimKind {
+    Dated,
+    VersionAssertion,
+}
+
+/// A freshness-sensitive claim on one line of a markdown file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Claim {
+    pub line: usize,
+    pub kind: ClaimKind,
+    /// Newest date stated on the line
+    pub date: Option<YearMonth>,
+    pub urls: Vec<String>,
+    pub text: String,
+}
+
+struct ClaimPatterns {
+    marker: Regex,
+    quantity: Regex,
+    version: Regex,
+    iso: Regex,
+    month: Regex,
+    year: Regex,
+    url: Regex,
+    code: Regex,
+}
+
+impl ClaimPatterns {
+    fn new() -> Self {
+        let re = |pattern| Regex::new(pattern).expect("Claim pattern must compile");
+        ClaimPatterns {
+            marker: re(CLAIM_MARKERS),
+            quantity: re(QUANTITY),
+            version: re(VERSION_ASSERTION),
+            iso: re(ISO_DATE),
+            month: re(MONTH_DATE),
+            year: re(YEAR),
+            url: re(URL),
+            code: re(r"`[^`]*`"),
+        }
+    }
+
+    /// Dates stated in text, ignoring years after `latest`
+    fn dates(&self, text: &str, latest: YearMonth) -> Vec<YearMonth> {
+        let mut dates = Vec::new();
+        let mut rest = text.to_string();
+        for caps in self.iso.captures_iter(text) {
+            dates.push(YearMonth {
+                year: caps[1].parse().unwrap_or_default(),
+                month: caps[2].parse().unwrap_or(12),
+            });
+        }
+        rest = self.iso.replace_all(&rest, " ").to_string();
+        for caps in self.month.captures_iter(&rest) {
+            let month = [
+                "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+            ]
+            .iter()
+            .position(|m| caps[1].eq_ignore_ascii_case(m))
+            .map_or(12, |i| i as u32 + 1);
+            dates.push(YearMonth {
+                year: caps[2].parse().unwrap_or_default(),
+                month,
+            });
+        }
+        rest = self.month.replace_all(&rest, " ").to_string();
+        for caps in self.year.captures_iter(&rest) {
+            dates.push(YearMonth {
+                year: caps[1].parse().unwrap_or_default(),
+                month: 12,
+            });
+        }
+        dates.retain(|d| d.year <= latest.year);
+        dates
+    }
+}
+
+/// Freshness-sensitive claims of a markdown document; dates after `latest`
+/// are not claims about the past (and `2048 tokens` is not a year)
+pub fn find_claims(content: &str, latest: YearMonth) -> Vec<Claim> {
+    let patterns = ClaimPatterns::new();
+    let mut claims = Vec::new();
+    let mut fence: Option<&str> = None;
+
+    for (i, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+        if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+            fence = Some(marker);
+            continue;
+        }
+
+        let urls: Vec<String> = patterns
+            .url
+            .find_iter(line)
+            .map(|m| normalize_url(m.as_str()))
+            .collect();
+        let prose = patterns.url.replace_all(line, " ");
+        let prose = patterns.code.replace_all(&prose, " ");
+        let date = patterns.dates(&prose, latest).into_iter().max();
+
+        let kind = if patterns.version.is_match(&prose) {
+            ClaimKind::VersionAssertion
+        } else if date.is_some()
+            && (patterns.marker.is_match(&prose) || patterns.quantity.is_match(&prose))
+        {
+            ClaimKind::Dated
+        } else {
+            continue;
+        };
+        claims.push(Claim {
+            line: i + 1,
+            kind,
+            date,
+            urls,
+            text: line.trim().to_string(),
+        });
+    }
+    claims
+}
+
+fn git_output(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string()).filter(|s| !s.is_empty())
+}
+
+fn split_path(file: &Path) -> (&Path, String) {
+    let dir = file
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let name = file
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    (dir, name)
+}
+
+/// Date of the last commit that changed `file`
+pub fn last_git_modification(file: &Path) -> Option<NaiveDate> {
+    let (dir, name) = split_path(file);
+    let date = git_output(dir, &["log", "-1", "--format=%cs", "--", &name])?;
+    NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok()
+}
+
+/// Date of the commit that last touched a line of `file`
+fn line_modification(file: &Path, line: usize) -> Option<NaiveDate> {
+    let (dir, name) = split_path(file);
+    let range = format!("{},{}", line, line);
+    let blame = git_output(
+        dir,
+        &["blame", "--line-porcelain", "-L", &range, "--", &name],
+    )?;
+    let time = blame
+        .lines()
+        .find_map(|l| l.strip_prefix("committer-time "))?
+        .parse()
+        .ok()?;
+    DateTime::from_timestamp(time, 0).map(|t| t.date_naive())
+}
+
+/// Check markdown files for stale claims and unlisted sources
+pub fn check_stale_claims(
+    dir: &Path,
+    files: &[PathBuf],
+    max_age_months: u32,
+    result: &mut SemanticResult,
+) {
+    let start = if dir.is_file() {
+        dir.parent().unwrap_or(Path::new("."))
+    } else {
+        dir
+    };
+    let references_file = find_references_file(start);
+    let references = references_file
+        .as_deref()
+        .map(load_references)
+        .unwrap_or_default();
+    let today = clock().date();
+
+    for file in files {
+        let Ok(content) = std::fs::read_to_string(file) else {
+            continue;
+        };
+        // Files never committed are being written now
+        let modified = last_git_modification(file).unwrap_or(today);
+        let reference_point = YearMonth::of(modified);
+
+        for claim in find_claims(&content, reference_point) {
+            result.freshness_claims_found += 1;
+            let mut issue = |message: String| {
+                result.issues.push(SemanticIssue {
+                    file: file.clone(),
+                    line: Some(claim.line),
+                    category: IssueCategory::StaleClaim,
+                    severity: Severity::Warning,
+                    message,
+                    context: Some(claim.text.clone()),
+                })
+            };
+
+            let cited: Vec<&Reference> = claim
+                .urls
+                .iter()
+                .filter_map(|url| references.iter().find(|r| r.url == *url))
+                .collect();
+            if references_file.is_some() {
+                for url in claim
+                    .urls
+                    .iter()
+                    .filter(|u| !cited.iter().any(|r| r.url == **u))
+                {
+                    issue(format!(
+                        "Claim cites {}, which is not in references.yaml",
+                        url
+                    ));
+                }
+            }
+
+            let (date, source) = if let Some(reference) = cited.iter().filter_map(|r| r.date).max()
+            {
+                (reference, "by references.yaml")
+            } else if let Some(date) = claim.date {
+                (date, "")
+            } else {
+                match line_modification(file, claim.line) {
+                    Some(date) => (YearMonth::of(date), "by its last change"),
+                    None => continue,
+                }
+            };
+
+            let age = date.months_until(reference_point);
+            if age > max_age_months as i32 {
+                let what = match claim.kind {
+                    ClaimKind::Dated => "Stale claim",
+                    ClaimKind::VersionAssertion => "Stale version assertion",
+                };
+                let dated = if source.is_empty() {
+                    format!("dated {}", date)
+                } else {
+                    format!("dated {} {}", date, source)
+                };
+                issue(format!(
+                    "{}: {}, {} months before the file's last change ({}) - re-verify it (max {} months)",
+                    what, dated, age, modified, max_age_months
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const NOW: YearMonth = YearMonth {
+        year: 2026,
+        month: 1,
+    };
+
+    #[test]
+    fn test_find_claims() {
+        let doc = "\
+# Notes
+
+As of March 2024, ripgrep is the fastest grep.
+Benchmarks (2023-06): 12x faster than grep.
+The latest version is 1.4.2.
+Copyright 2024 RoyalBit.
+Context window of 2048 tokens is the latest limit.
+
+```text
+As of 2020 the latest version is 0.1
+```
+See [the study](https://example.com/2021/fast) for the fastest setup.
+";
+        let claims = find_claims(doc, NOW);
+        let lines: Vec<usize> = claims.iter().map(|c| c.line).collect();
+        assert_eq!(lines, vec![3, 4, 5]);
+        assert_eq!(
+            claims[0].date,
+            Some(YearMonth {
+                year: 2024,
+                month: 3
+            })
+        );
+        assert_eq!(claims[1].date, YearMonth::parse("2023-06"));
+        assert_eq!(claims[2].kind, ClaimKind::VersionAssertion);
+        assert_eq!(claims[2].date, None);
+    }
+
+    #[test]
+    fn test_year_month() {
+        assert_eq!(YearMonth::parse("2024").unwrap().to_string(), "2024-12");
+        assert_eq!(
+            YearMonth::parse("2024-05-17").unwrap().to_string(),
+            "2024-05"
+        );
+        assert_eq!(YearMonth::parse("2024-13"), None);
+        let may = YearMonth::parse("2024-05").unwrap();
+        assert_eq!(may.months_until(NOW), 20);
+    }
+
+    #[test]
+    fn test_references_file_and_entries() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join(".git")).unwrap();
+        std::fs::create_dir_all(temp.path().join("docs/guides")).unwrap();
+        assert_eq!(find_references_file(&temp.path().join("docs/guides")), None);
+
+        std::fs::write(
+            temp.path().join("docs/references.yaml"),
+            "benchmarks:\n  - title: Grep bench\n    url: https://example.com/bench/\n    date: 2025-11\n  - url: https://example.com/old\n    verified: 2022\n",
+        )
+        .unwrap();
+        let path = find_references_file(&temp.path().join("docs/guides")).unwrap();
+        let references = load_references(&path);
+        assert_eq!(references.len(), 2);
+        assert_eq!(references[0].url, "https://example.com/bench");
+        assert_eq!(references[0].date, YearMonth::parse("2025-11"));
+        assert_eq!(references[1].date, YearMonth::parse("2022"));
+    }
+
+    #[test]
+    fn test_check_stale_claims() {
+        let temp = TempDir::new().unwrap();
+        // Outside git the reference point is today
+        std::fs::write(
+            temp.path().join("references.yaml"),
+            format!(
+                "- url: https://example.com/bench\n  date: {}\n",
+                &clock().today()[..7]
+            ),
+        )
+        .unwrap();
+        let doc = temp.path().join("README.md");
+        std::fs::write(
+            &doc,
+            "As of 2019, asimov is the fastest.\n\
+             Benchmarks from 2019: 3x faster ([source](https://example.com/bench)).\n\
+             The latest benchmark (2019) is [here](https://example.com/other).\n",
+        )
+        .unwrap();
+
+        let mut result = SemanticResult::default();
+        check_stale_claims(temp.path(), std::slice::from_ref(&doc), 12, &mut result);
+        assert_eq!(result.freshness_claims_found, 3);
+        let messages: Vec<(usize, &str)> = result
+            .issues
+            .iter()
+            .map(|i| (i.line.unwrap(), i.message.as_str()))
+            .collect();
+        // Line 2 is dated by its (recent) reference, not by the year it states
+        assert!(messages[0].0 == 1 && messages[0].1.starts_with("Stale claim: dated 2019-12"));
+        assert!(messages.iter().all(|(line, _)| *line != 2), "{messages:?}");
+        assert!(messages
+            .iter()
+            .any(|(line, m)| *line == 3 && m.contains("https://example.com/other")));
+        assert_eq!(result.issues[0].category, IssueCategory::StaleClaim);
+    }
+}
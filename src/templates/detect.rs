@@ -0,0 +1,652 @@
+//! Workspace-aware project detection
+//!
+//! `detect_project_type` answers "what is this directory" from top-level
+//! marker files. Repositories are often more than one project: a Cargo
+//! workspace, a pnpm/nx/turbo monorepo, a `go.work`, a uv workspace, or a
+//! Rust service next to a TypeScript frontend. `detect_project` expands the
+//! declared workspace members, scans a couple of levels below the root for
+//! undeclared components and reports each one with its path and confidence.
+
+use super::{detect_project_type, ProjectType};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// How far below the root undeclared components are looked for
+const SCAN_DEPTH: usize = 2;
+
+/// Directories never scanned for components
+const SKIP_DIRS: &[&str] = &[
+    "node_modules",
+    "target",
+    "vendor",
+    "build",
+    "dist",
+    "out",
+    "bin",
+    "obj",
+    "venv",
+    "__pycache__",
+    "third_party",
+];
+
+/// Marker files in priority order: (file, type, strong)
+///
+/// Strong markers are build manifests; weak ones only suggest a language.
+/// `detect_project_type` uses the same table for the root directory.
+const MARKERS: &[(&str, ProjectType, bool)] = &[
+    ("pubspec.yaml", ProjectType::Flutter, true),
+    ("Cargo.toml", ProjectType::Rust, true),
+    ("go.mod", ProjectType::Go, true),
+    ("pyproject.toml", ProjectType::Python, true),
+    ("setup.py", ProjectType::Python, false),
+    ("pom.xml", ProjectType::Java, true),
+    ("build.gradle", ProjectType::Java, true),
+    ("build.gradle.kts", ProjectType::Java, true),
+    ("settings.gradle.kts", ProjectType::Java, false),
+    ("*.csproj", ProjectType::CSharp, true),
+    ("global.json", ProjectType::CSharp, false),
+    ("*.sln", ProjectType::CSharp, false),
+    ("Gemfile", ProjectType::Ruby, true),
+    ("composer.json", ProjectType::Php, true),
+    ("package.json", ProjectType::Node, true),
+    ("CMakeLists.txt", ProjectType::Cpp, true),
+    ("meson.build", ProjectType::Cpp, true),
+    ("conanfile.txt", ProjectType::Cpp, false),
+    ("vcpkg.json", ProjectType::Cpp, false),
+    ("requirements.txt", ProjectType::Python, false),
+];
+
+/// How sure detection is about a component
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Confidence {
+    /// Only a weak marker, found by scanning
+    Low,
+    /// A build manifest found by scanning, or a declared member with a weak marker
+    Medium,
+    /// The root's build manifest, or a declared workspace member's
+    High,
+}
+
+impl fmt::Display for Confidence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Confidence::Low => write!(f, "low"),
+            Confidence::Medium => write!(f, "medium"),
+            Confidence::High => write!(f, "high"),
+        }
+    }
+}
+
+/// Workspace declarations that list member projects
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkspaceKind {
+    /// `[workspace] members` in Cargo.toml
+    Cargo,
+    /// pnpm-workspace.yaml `packages`
+    Pnpm,
+    /// package.json `workspaces` (npm, yarn, bun)
+    Npm,
+    /// nx.json (members from package workspaces or apps/ and libs/)
+    Nx,
+    /// turbo.json (members from package workspaces)
+    Turbo,
+    /// go.work `use` directives
+    GoWork,
+    /// `[tool.uv.workspace] members` in pyproject.toml
+    Uv,
+}
+
+impl fmt::Display for WorkspaceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorkspaceKind::Cargo => write!(f, "cargo"),
+            WorkspaceKind::Pnpm => write!(f, "pnpm"),
+            WorkspaceKind::Npm => write!(f, "npm"),
+            WorkspaceKind::Nx => write!(f, "nx"),
+            WorkspaceKind::Turbo => write!(f, "turbo"),
+            WorkspaceKind::GoWork => write!(f, "go.work"),
+            WorkspaceKind::Uv => write!(f, "uv"),
+        }
+    }
+}
+
+/// One project inside the repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectedComponent {
+    /// Relative to the root ("." for the root itself)
+    pub path: PathBuf,
+    pub project_type: ProjectType,
+    /// Marker file that identified the component
+    pub marker: String,
+    pub confidence: Confidence,
+    /// Listed by a workspace declaration
+    pub declared: bool,
+}
+
+/// Everything `detect_project` found
+#[derive(Debug, Clone, Default)]
+pub struct ProjectDetection {
+    pub workspaces: Vec<WorkspaceKind>,
+    pub components: Vec<DetectedComponent>,
+    /// `detect_project_type` of the root directory alone
+    pub root_type: ProjectType,
+}
+
+impl ProjectDetection {
+    /// More than one language among the components
+    pub fn is_polyglot(&self) -> bool {
+        self.type_counts().len() > 1
+    }
+
+    /// Whether the repository holds more than one project
+    pub fn is_multi_component(&self) -> bool {
+        self.components.len() > 1
+    }
+
+    /// The type a single project.yaml should declare
+    ///
+    /// The root's own build manifest wins; otherwise the language most
+    /// components share; otherwise the root heuristics (docs, arch, generic).
+    pub fn primary_type(&self) -> ProjectType {
+        if let Some(root) = self.components.iter().find(|c| c.path == Path::new(".")) {
+            return root.project_type;
+        }
+        self.type_counts()
+            .into_iter()
+            .max_by_key(|(pt, count)| {
+                (
+                    *count,
+                    std::cmp::Reverse(ProjectType::ALL.iter().position(|p| p == pt)),
+                )
+            })
+            .map(|(pt, _)| pt)
+            .unwrap_or(self.root_type)
+    }
+
+    fn type_counts(&self) -> Vec<(ProjectType, usize)> {
+        let mut counts: Vec<(ProjectType, usize)> = Vec::new();
+        for component in &self.components {
+            match counts
+                .iter_mut()
+                .find(|(pt, _)| *pt == component.project_type)
+            {
+                Some((_, count)) => *count += 1,
+                None => counts.push((component.project_type, 1)),
+            }
+        }
+        counts
+    }
+
+    /// `components:` section for project.yaml listing every component
+    pub fn components_yaml(&self) -> String {
+        let mut yaml = String::from("components:\n");
+        for component in &self.components {
+            yaml.push_str(&format!(
+                "  - path: {}\n    type: {}\n",
+                component.path.display(),
+                component.project_type
+            ));
+        }
+        yaml
+    }
+}
+
+/// Detect the projects in a repository: workspace members and other components
+pub fn detect_project(dir: &Path) -> ProjectDetection {
+    let mut detection = ProjectDetection {
+        root_type: detect_project_type(dir),
+        ..ProjectDetection::default()
+    };
+    // BTreeMap keeps components sorted by path
+    let mut components: BTreeMap<PathBuf, DetectedComponent> = BTreeMap::new();
+
+    if let Some((project_type, marker, strong)) = component_marker(dir) {
+        components.insert(
+            PathBuf::from("."),
+            DetectedComponent {
+                path: PathBuf::from("."),
+                project_type,
+                marker,
+                confidence: if strong {
+                    Confidence::High
+                } else {
+                    Confidence::Medium
+                },
+                declared: true,
+            },
+        );
+    }
+
+    let (workspaces, members) = workspace_members(dir);
+    detection.workspaces = workspaces;
+    for member in members {
+        if let Some((project_type, marker, strong)) = component_marker(&dir.join(&member)) {
+            components.insert(
+                member.clone(),
+                DetectedComponent {
+                    path: member,
+                    project_type,
+                    marker,
+                    confidence: if strong {
+                        Confidence::High
+                    } else {
+                        Confidence::Medium
+                    },
+                    declared: true,
+                },
+            );
+        }
+    }
+
+    scan_components(dir, Path::new(""), 1, &mut components);
+
+    detection.components = components.into_values().collect();
+    detection
+}
+
+/// Type and marker of a directory, from the first marker file present
+pub(super) fn component_marker(dir: &Path) -> Option<(ProjectType, String, bool)> {
+    MARKERS.iter().find_map(|(marker, project_type, strong)| {
+        let found = match marker.strip_prefix("*.") {
+            Some(extension) => file_with_extension(dir, extension),
+            None => dir.join(marker).is_file().then(|| marker.to_string()),
+        };
+        found.map(|file| (*project_type, file, *strong))
+    })
+}
+
+fn file_with_extension(dir: &Path, extension: &str) -> Option<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter(|entry| entry.path().extension().is_some_and(|e| e == extension))
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+    names.into_iter().next()
+}
+
+/// Undeclared components below the root, up to `SCAN_DEPTH` levels
+fn scan_components(
+    root: &Path,
+    relative: &Path,
+    depth: usize,
+    components: &mut BTreeMap<PathBuf, DetectedComponent>,
+) {
+    let Ok(entries) = std::fs::read_dir(root.join(relative)) else {
+        return;
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            !name.starts_with('.') && !SKIP_DIRS.contains(&name.as_str())
+        })
+        .map(|entry| relative.join(entry.file_name()))
+        .collect();
+    dirs.sort();
+
+    for path in dirs {
+        if !components.contains_key(&path) {
+            if let Some((project_type, marker, strong)) = component_marker(&root.join(&path)) {
+                components.insert(
+                    path.clone(),
+                    DetectedComponent {
+                        path: path.clone(),
+                        project_type,
+                        marker,
+                        confidence: if strong {
+                            Confidence::Medium
+                        } else {
+                            Confidence::Low
+                        },
+                        declared: false,
+                    },
+                );
+            }
+        }
+        if depth < SCAN_DEPTH {
+            scan_components(root, &path, depth + 1, components);
+        }
+    }
+}
+
+/// Declared workspaces and their member directories
+fn workspace_members(dir: &Path) -> (Vec<WorkspaceKind>, Vec<PathBuf>) {
+    let mut kinds = Vec::new();
+    let mut patterns: Vec<String> = Vec::new();
+    let read = |file: &str| std::fs::read_to_string(dir.join(file)).ok();
+
+    if let Some(members) = read("Cargo.toml").and_then(|c| toml_array(&c, "workspace", "members")) {
+        kinds.push(WorkspaceKind::Cargo);
+        patterns.extend(members);
+    }
+    if let Some(members) =
+        read("pyproject.toml").and_then(|c| toml_array(&c, "tool.uv.workspace", "members"))
+    {
+        kinds.push(WorkspaceKind::Uv);
+        patterns.extend(members);
+    }
+    if let Some(content) = read("go.work") {
+        kinds.push(WorkspaceKind::GoWork);
+        patterns.extend(go_work_uses(&content));
+    }
+
+    let mut package_patterns = Vec::new();
+    if let Some(packages) = read("pnpm-workspace.yaml").and_then(|c| yaml_packages(&c)) {
+        kinds.push(WorkspaceKind::Pnpm);
+        package_patterns.extend(packages);
+    }
+    if let Some(workspaces) = read("package.json").and_then(|c| package_json_workspaces(&c)) {
+        kinds.push(WorkspaceKind::Npm);
+        package_patterns.extend(workspaces);
+    }
+    if dir.join("turbo.json").is_file() {
+        kinds.push(WorkspaceKind::Turbo);
+    }
+    if dir.join("nx.json").is_file() {
+        kinds.push(WorkspaceKind::Nx);
+        if package_patterns.is_empty() {
+            package_patterns.extend(["apps/*".to_string(), "libs/*".to_string()]);
+        }
+    }
+    patterns.extend(package_patterns);
+
+    let excluded: Vec<String> = patterns
+        .iter()
+        .filter_map(|p| p.strip_prefix('!').map(str::to_string))
+        .collect();
+    let mut members: Vec<PathBuf> = patterns
+        .iter()
+        .filter(|p| !p.starts_with('!'))
+        .flat_map(|p| expand_pattern(dir, p))
+        .filter(|member| {
+            !excluded
+                .iter()
+                .any(|ex| expand_pattern(dir, ex).contains(member))
+        })
+        .collect();
+    members.sort();
+    members.dedup();
+    (kinds, members)
+}
+
+/// Directories matching a workspace glob (`*` and `**` match one path segment)
+fn expand_pattern(dir: &Path, pattern: &str) -> Vec<PathBuf> {
+    let mut matches = vec![PathBuf::new()];
+    let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+    for segment in pattern.split('/').filter(|s| !s.is_empty() && *s != ".") {
+        let mut next = Vec::new();
+        for base in &matches {
+            if !segment.contains('*') {
+                let candidate = base.join(segment);
+                if dir.join(&candidate).is_dir() {
+                    next.push(candidate);
+                }
+                continue;
+            }
+            let Ok(entries) = std::fs::read_dir(dir.join(base)) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if entry.file_type().is_ok_and(|t| t.is_dir())
+                    && !name.starts_with('.')
+                    && wildcard_match(segment, &name)
+                {
+                    next.push(base.join(name));
+                }
+            }
+        }
+        matches = next;
+    }
+    matches.retain(|m| !m.as_os_str().is_empty());
+    matches
+}
+
+/// `*` wildcard match within one path segment
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if !name.starts_with(first) || name.len() < first.len() + last.len() || !name.ends_with(last) {
+        return false;
+    }
+    let mut rest = &name[first.len()..name.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+/// String array `key = [...]` in a TOML `[section]` (may span lines)
+fn toml_array(content: &str, section: &str, key: &str) -> Option<Vec<String>> {
+    let header = format!("[{}]", section);
+    let body = content
+        .split_once(&format!("\n{}", header))
+        .map(|(_, rest)| rest)
+        .or_else(|| content.strip_prefix(&header))?;
+    let body = body.split("\n[").next().unwrap_or_default();
+    let start = body.lines().position(|line| {
+        line.trim_start()
+            .strip_prefix(key)
+            .is_some_and(|rest| rest.trim_start().starts_with('='))
+    })?;
+    let value: String = body
+        .lines()
+        .skip(start)
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let open = value.find('[')?;
+    let close = value[open..].find(']')? + open;
+    Some(
+        value[open + 1..close]
+            .split(',')
+            .map(|item| item.trim().trim_matches(['"', '\'']).to_string())
+            .filter(|item| !item.is_empty())
+            .collect(),
+    )
+}
+
+/// `use ./api` and `use ( ./api ./web )` directives in go.work
+fn go_work_uses(content: &str) -> Vec<String> {
+    let mut uses = Vec::new();
+    let mut in_block = false;
+    for line in content.lines() {
+        let line = line.split("//").next().unwrap_or_default().trim();
+        if in_block {
+            if line == ")" {
+                in_block = false;
+            } else if !line.is_empty() {
+                uses.push(line.to_string());
+            }
+        } else if let Some(rest) = line.strip_prefix("use") {
+            let rest = rest.trim();
+            if rest == "(" {
+                in_block = true;
+            } else if !rest.is_empty() {
+                uses.push(rest.to_string());
+            }
+        }
+    }
+    uses
+}
+
+fn yaml_packages(content: &str) -> Option<Vec<String>> {
+    let yaml: serde_yaml_ng::Value = serde_yaml_ng::from_str(content).ok()?;
+    string_list(yaml.get("packages")?)
+}
+
+/// `workspaces: [...]` or `workspaces: { packages: [...] }`
+fn package_json_workspaces(content: &str) -> Option<Vec<String>> {
+    let json: serde_json::Value = serde_json::from_str(content).ok()?;
+    let workspaces = json.get("workspaces")?;
+    let list = workspaces.get("packages").unwrap_or(workspaces);
+    Some(
+        list.as_array()?
+            .iter()
+            .filter_map(|w| w.as_str().map(str::to_string))
+            .collect(),
+    )
+}
+
+fn string_list(value: &serde_yaml_ng::Value) -> Option<Vec<String>> {
+    Some(
+        value
+            .as_sequence()?
+            .iter()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, path: &str, content: &str) {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn paths(detection: &ProjectDetection) -> Vec<String> {
+        detection
+            .components
+            .iter()
+            .map(|c| format!("{}:{}", c.path.display(), c.project_type))
+            .collect()
+    }
+
+    #[test]
+    fn test_cargo_workspace() {
+        let temp = TempDir::new().unwrap();
+        write(
+            temp.path(),
+            "Cargo.toml",
+            "[workspace]\nmembers = [\n  \"crates/*\", # all crates\n  \"xtask\",\n]\nexclude = [\"crates/old\"]\n",
+        );
+        write(temp.path(), "crates/core/Cargo.toml", "[package]\n");
+        write(temp.path(), "crates/cli/Cargo.toml", "[package]\n");
+        write(temp.path(), "xtask/Cargo.toml", "[package]\n");
+
+        let detection = detect_project(temp.path());
+        assert_eq!(detection.workspaces, vec![WorkspaceKind::Cargo]);
+        assert_eq!(
+            paths(&detection),
+            vec![
+                ".:rust",
+                "crates/cli:rust",
+                "crates/core:rust",
+                "xtask:rust"
+            ]
+        );
+        assert!(detection.components.iter().all(|c| c.declared));
+        assert!(!detection.is_polyglot());
+        assert_eq!(detection.primary_type(), ProjectType::Rust);
+    }
+
+    #[test]
+    fn test_pnpm_workspace_with_polyglot_service() {
+        let temp = TempDir::new().unwrap();
+        write(temp.path(), "package.json", "{\"private\": true}");
+        write(
+            temp.path(),
+            "pnpm-workspace.yaml",
+            "packages:\n  - 'apps/*'\n  - '!apps/legacy'\n",
+        );
+        write(temp.path(), "apps/web/package.json", "{}");
+        write(temp.path(), "apps/legacy/package.json", "{}");
+        write(temp.path(), "services/billing/go.mod", "module billing\n");
+
+        let detection = detect_project(temp.path());
+        assert_eq!(detection.workspaces, vec![WorkspaceKind::Pnpm]);
+        let legacy = detection
+            .components
+            .iter()
+            .find(|c| c.path == Path::new("apps/legacy"))
+            .unwrap();
+        assert!(!legacy.declared);
+        let billing = detection
+            .components
+            .iter()
+            .find(|c| c.path == Path::new("services/billing"))
+            .unwrap();
+        assert_eq!(billing.project_type, ProjectType::Go);
+        assert_eq!(billing.confidence, Confidence::Medium);
+        assert!(detection.is_polyglot());
+        assert_eq!(detection.primary_type(), ProjectType::Node);
+    }
+
+    #[test]
+    fn test_go_work_and_uv_workspace() {
+        let temp = TempDir::new().unwrap();
+        write(
+            temp.path(),
+            "go.work",
+            "go 1.22\n\nuse (\n\t./api // service\n\t./worker\n)\nuse ./tools\n",
+        );
+        for module in ["api", "worker", "tools"] {
+            write(temp.path(), &format!("{}/go.mod", module), "module x\n");
+        }
+        let detection = detect_project(temp.path());
+        assert_eq!(detection.workspaces, vec![WorkspaceKind::GoWork]);
+        assert_eq!(paths(&detection), vec!["api:go", "tools:go", "worker:go"]);
+        // No root manifest: the shared language wins
+        assert_eq!(detection.primary_type(), ProjectType::Go);
+
+        let temp = TempDir::new().unwrap();
+        write(
+            temp.path(),
+            "pyproject.toml",
+            "[project]\nname = \"mono\"\n\n[tool.uv.workspace]\nmembers = [\"packages/*\"]\n",
+        );
+        write(temp.path(), "packages/lib/pyproject.toml", "");
+        write(temp.path(), "packages/scripts/requirements.txt", "");
+        let detection = detect_project(temp.path());
+        assert_eq!(detection.workspaces, vec![WorkspaceKind::Uv]);
+        let scripts = &detection.components[2];
+        assert_eq!(scripts.path, Path::new("packages/scripts"));
+        assert_eq!(scripts.confidence, Confidence::Medium);
+    }
+
+    #[test]
+    fn test_scan_skips_build_output_and_hidden_dirs() {
+        let temp = TempDir::new().unwrap();
+        write(temp.path(), "README.md", "# Docs\n");
+        write(temp.path(), "node_modules/left-pad/package.json", "{}");
+        write(temp.path(), ".cache/tool/Cargo.toml", "");
+        write(temp.path(), "examples/demo/Gemfile", "");
+
+        let detection = detect_project(temp.path());
+        assert_eq!(paths(&detection), vec!["examples/demo:ruby"]);
+        assert_eq!(detection.components[0].confidence, Confidence::Medium);
+        assert_eq!(detection.root_type, ProjectType::Docs);
+    }
+
+    #[test]
+    fn test_components_yaml() {
+        let temp = TempDir::new().unwrap();
+        write(temp.path(), "Cargo.toml", "[package]\n");
+        write(temp.path(), "web/package.json", "{}");
+        let detection = detect_project(temp.path());
+        assert_eq!(
+            detection.components_yaml(),
+            "components:\n  - path: .\n    type: rust\n  - path: web\n    type: node\n"
+        );
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("*", "core"));
+        assert!(wildcard_match("svc-*", "svc-billing"));
+        assert!(!wildcard_match("svc-*", "lib-core"));
+        assert!(wildcard_match("*-api", "billing-api"));
+    }
+}
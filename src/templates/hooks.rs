@@ -1,450 +1,51 @@
 //! Hook template generators for git and Claude Code
 //! v9.6.0: Direct coding standards enforcement (ADR-043)
 //! v9.8.0: Dependency health audit - STRICT (ADR-045)
+//! Pre-commit checks moved to `asimov hook run pre-commit` (commands/hook.rs)
 
 use super::ProjectType;
 
-/// Generate pre-commit hook for RoyalBit Asimov
-/// v9.6.0: Direct tool calls, asimov is optional (no SPOF)
-pub fn precommit_hook_template(project_type: ProjectType) -> String {
-    let (checks, file_ext, max_lines, exclude_dirs) = match project_type {
-        ProjectType::Rust => (
-            r#"# === QUALITY CHECKS (independent, no asimov) ===
-echo "Checking formatting..."
-if [ -f "cli/Cargo.toml" ]; then
-  (cd cli && cargo fmt --all -- --check) || {
-    echo ""; echo "❌ Run: cd cli && cargo fmt --all"; exit 1
-  }
-elif [ -f "Cargo.toml" ]; then
-  cargo fmt --all -- --check || {
-    echo ""; echo "❌ Run: cargo fmt --all"; exit 1
-  }
-fi
-
-echo "Running clippy..."
-if [ -f "cli/Cargo.toml" ]; then
-  (cd cli && cargo clippy --all-targets -- -D warnings) || exit 1
-elif [ -f "Cargo.toml" ]; then
-  cargo clippy --all-targets -- -D warnings || exit 1
-fi
-
-echo "Running tests..."
-if [ -f "cli/Cargo.toml" ]; then
-  (cd cli && cargo test) || exit 1
-elif [ -f "Cargo.toml" ]; then
-  cargo test || exit 1
-fi"#,
-            "rs",
-            1500,
-            "target cli/target",
-        ),
-        ProjectType::Python => (
-            r#"# === QUALITY CHECKS (independent, no asimov) ===
-echo "Checking formatting..."
-if command -v ruff &>/dev/null; then
-  ruff format --check . || { echo ""; echo "❌ Run: ruff format ."; exit 1; }
-elif command -v black &>/dev/null; then
-  black --check . || { echo ""; echo "❌ Run: black ."; exit 1; }
-fi
-
-echo "Running linter..."
-if command -v ruff &>/dev/null; then
-  ruff check . || exit 1
-elif command -v flake8 &>/dev/null; then
-  flake8 . || exit 1
-fi
-
-echo "Running tests..."
-if command -v pytest &>/dev/null; then
-  pytest || exit 1
-elif [ -f "setup.py" ] || [ -f "pyproject.toml" ]; then
-  python -m pytest || true
-fi"#,
-            "py",
-            1000,
-            "venv __pycache__ .venv",
-        ),
-        ProjectType::Node => (
-            r#"# === QUALITY CHECKS (independent, no asimov) ===
-echo "Checking formatting..."
-if [ -f "package.json" ]; then
-  if command -v npx &>/dev/null; then
-    npx prettier --check "**/*.{js,ts,jsx,tsx}" 2>/dev/null || npm run format:check 2>/dev/null || true
-  fi
-fi
-
-echo "Running linter..."
-if [ -f "package.json" ]; then
-  npm run lint 2>/dev/null || npx eslint . 2>/dev/null || true
-fi
-
-echo "Running tests..."
-if [ -f "package.json" ]; then
-  npm test 2>/dev/null || npx jest 2>/dev/null || true
-fi"#,
-            "ts js tsx jsx",
-            800,
-            "node_modules dist build",
-        ),
-        ProjectType::Go => (
-            r#"# === QUALITY CHECKS (independent, no asimov) ===
-echo "Checking formatting..."
-if command -v gofmt &>/dev/null; then
-  unformatted=$(gofmt -l . 2>/dev/null | grep -v vendor || true)
-  if [ -n "$unformatted" ]; then
-    echo "❌ Files need formatting:"; echo "$unformatted"
-    echo "Run: gofmt -w ."; exit 1
-  fi
-fi
-
-echo "Running linter..."
-if command -v golangci-lint &>/dev/null; then
-  golangci-lint run || exit 1
-elif command -v go &>/dev/null; then
-  go vet ./... || exit 1
-fi
+/// First comment line of every hook asimov writes (`asimov hooks status` looks for it)
+pub const MANAGED_HOOK_MARKER: &str = "# asimov-managed hook";
 
-echo "Running tests..."
-if command -v go &>/dev/null; then
-  go test ./... || exit 1
-fi"#,
-            "go",
-            1000,
-            "vendor",
-        ),
-        ProjectType::Flutter => (
-            r#"# === QUALITY CHECKS (independent, no asimov) ===
-echo "Checking formatting..."
-if command -v dart &>/dev/null; then
-  dart format --set-exit-if-changed lib/ test/ 2>/dev/null || {
-    echo ""; echo "❌ Run: dart format lib/ test/"; exit 1
-  }
-fi
+/// Suffix of a pre-existing hook kept and chained by `asimov hooks install`
+pub const CHAINED_HOOK_SUFFIX: &str = ".pre-asimov";
 
-echo "Running analyzer..."
-if command -v flutter &>/dev/null; then
-  flutter analyze || exit 1
-elif command -v dart &>/dev/null; then
-  dart analyze lib/ || exit 1
-fi
-
-echo "Running tests..."
-if command -v flutter &>/dev/null; then
-  flutter test || exit 1
-fi"#,
-            "dart",
-            800,
-            ".dart_tool build",
-        ),
-        ProjectType::Docs | ProjectType::Arch | ProjectType::Generic | ProjectType::Migration => (
-            r#"# === QUALITY CHECKS ===
-echo "Checking documentation..."
-# No code-specific checks for docs/arch/generic projects"#,
-            "md",
-            800,
-            "node_modules",
-        ),
-    };
-
-    // Build file size check for code files
-    let file_size_check = if matches!(
-        project_type,
-        ProjectType::Docs | ProjectType::Arch | ProjectType::Generic | ProjectType::Migration
-    ) {
-        // For docs projects, check markdown files
-        format!(
-            r#"
-# === FILE SIZE CHECK (inline, no deps) ===
-echo "Checking file sizes..."
-max_lines={}
-found_large=0
-for f in $(find . -name '*.md' {} 2>/dev/null); do
-  lines=$(wc -l < "$f" | tr -d ' ')
-  if [ "$lines" -gt "$max_lines" ]; then
-    echo "⚠️  $f has $lines lines (limit: $max_lines)"
-    found_large=1
-  fi
-done
-if [ "$found_large" -eq 1 ]; then
-  echo "Consider splitting large files"
-fi"#,
-            max_lines,
-            exclude_dirs
-                .split_whitespace()
-                .map(|d| format!("-not -path './{d}/*'"))
-                .collect::<Vec<_>>()
-                .join(" ")
-        )
-    } else {
-        // For code projects, check source files
-        let extensions: Vec<&str> = file_ext.split_whitespace().collect();
-        let find_patterns: String = extensions
-            .iter()
-            .map(|ext| format!("-name '*.{ext}'"))
-            .collect::<Vec<_>>()
-            .join(" -o ");
-        let exclude_pattern: String = exclude_dirs
-            .split_whitespace()
-            .map(|d| format!("-not -path './{d}/*'"))
-            .collect::<Vec<_>>()
-            .join(" ");
+/// Generate pre-commit hook for RoyalBit Asimov
+/// Shim - checks run natively in `asimov hook run pre-commit`, and the
+/// project type is resolved at run time. Without asimov on PATH the shim
+/// skips every check, gates included, and lets the commit through
+pub fn precommit_hook_template(_project_type: ProjectType) -> String {
+    hook_shim_template("pre-commit")
+}
 
-        format!(
-            r#"
-# === FILE SIZE CHECK (inline, no deps) ===
-echo "Checking file sizes..."
-max_lines={}
-found_large=0
-for f in $(find . \( {} \) {} 2>/dev/null); do
-  lines=$(wc -l < "$f" | tr -d ' ')
-  if [ "$lines" -gt "$max_lines" ]; then
-    echo "❌ $f exceeds $max_lines lines ($lines)"
-    found_large=1
-  fi
-done
-if [ "$found_large" -eq 1 ]; then
-  echo "Split large files to improve maintainability"
-  exit 1
-fi"#,
-            max_lines, find_patterns, exclude_pattern
-        )
+/// Generate the shim for a git hook
+///
+/// Runs a chained pre-existing hook first, then `asimov hook run <hook>`.
+/// Without asimov installed the commit goes through (ADR-043: no SPOF).
+pub fn hook_shim_template(hook: &str) -> String {
+    let checks = match hook {
+        "pre-commit" => "staged file sizes, quality gates, protocol integrity, ethics scan",
+        "commit-msg" => "commit message policy",
+        "pre-push" => "quality gates",
+        _ => "asimov hook checks",
     };
-
-    // v9.8.0: Dependency health checks (ADR-045)
-    let dep_health_check = dependency_health_check(project_type);
-
     format!(
-        r#"#!/bin/bash
-# ═══════════════════════════════════════════════════════════════════════════════
-# Pre-commit hook - Direct Coding Standards Enforcement (v9.6.0)
-# Dependency Health Audit - STRICT (v9.8.0)
-# ═══════════════════════════════════════════════════════════════════════════════
-# Generated by: asimov init / asimov refresh
-# Architecture: ADR-043 (No SPOF), ADR-045 (GOOD CODE = Fresh deps, zero vulns)
-# ═══════════════════════════════════════════════════════════════════════════════
-
-set -e
-
-echo "Running pre-commit checks..."
-
-{}
-{}
-{}
-
-# === DOCUMENTATION CHECK (if markdownlint-cli2 available) ===
-# Note: markdownlint-cli2 uses .markdownlintignore for exclusions (not --ignore flags)
-if command -v markdownlint-cli2 &>/dev/null; then
-  echo "Linting markdown..."
-  markdownlint-cli2 "*.md" "**/*.md" 2>/dev/null || true
-fi
-
-# === ASIMOV (optional, soft-fail) ===
-# Protocol refresh - survives context compaction
-if command -v asimov &>/dev/null; then
-  echo "Refreshing protocols..."
-  asimov refresh || true
-  asimov validate || true
-fi
-
-# === WIP CONTINUITY (survives context compaction) ===
-# Forcing function: Claude sees WIP state on every commit
-# Reads from roadmap.yaml deliverables with status: wip
-if [ -f ".asimov/roadmap.yaml" ] && grep -q "status: wip" .asimov/roadmap.yaml 2>/dev/null; then
-  # Extract current WIP item (id comes before status in YAML)
-  current=$(grep -B5 "status: wip" .asimov/roadmap.yaml | grep "id:" | tail -1 | sed 's/.*id:[[:space:]]*//' | sed 's/[[:space:]]*$//' | tr -d '"')
-  if [ -n "$current" ]; then
-    # Count progress from deliverables section
-    total=$(grep -c "status:" .asimov/roadmap.yaml 2>/dev/null || echo "0")
-    done_count=$(grep -c "status: done" .asimov/roadmap.yaml 2>/dev/null || echo "0")
-    echo ""
-    echo "══════════════════════════════════════════════════════════════════════════════"
-    echo "🔥 ACTIVE WIP - CONTINUE THIS TASK"
-    echo "══════════════════════════════════════════════════════════════════════════════"
-    echo "CURRENT TASK: $current"
-    echo "PROGRESS: $done_count/$total items complete"
-    echo ""
-    echo ">>> CONTINUE WORKING ON: $current <<<"
-    echo ">>> DO NOT ASK FOR PERMISSION - USER CONSENT ALREADY GIVEN <<<"
-    echo "══════════════════════════════════════════════════════════════════════════════"
-    echo ""
-  fi
-fi
-
-echo ""
-echo "✅ Pre-commit checks passed!"
+        r#"#!/bin/sh
+{marker}: {hook} (asimov hooks install)
+# Checks: {checks}
+chained="$0{suffix}"
+if [ -x "$chained" ]; then "$chained" "$@" || exit $?; fi
+command -v asimov >/dev/null 2>&1 || {{ echo "asimov not found, skipping {hook} checks"; exit 0; }}
+exec asimov hook run {hook} "$@"
 "#,
-        checks, dep_health_check, file_size_check
+        marker = MANAGED_HOOK_MARKER,
+        suffix = CHAINED_HOOK_SUFFIX,
+        hook = hook,
+        checks = checks
     )
 }
 
-/// Generate dependency health check section (v9.8.0 ADR-045)
-/// STRICT: FAIL on security vulnerabilities, outdated deps, license issues
-fn dependency_health_check(project_type: ProjectType) -> String {
-    match project_type {
-        ProjectType::Rust => r#"
-# === DEPENDENCY HEALTH (v9.8.0 ADR-045 - STRICT) ===
-# Security audit - FAIL on ANY vulnerability
-if command -v cargo-audit &>/dev/null; then
-  echo "Security audit..."
-  cargo audit --deny warnings 2>/dev/null || {
-    echo ""
-    echo "❌ SECURITY: Vulnerabilities found!"
-    echo "Run: cargo audit fix"
-    exit 1
-  }
-fi
-
-# License compliance - FAIL on incompatible licenses
-if [ -f "deny.toml" ] && command -v cargo-deny &>/dev/null; then
-  echo "License compliance..."
-  cargo deny check licenses 2>/dev/null || {
-    echo ""
-    echo "❌ LICENSE: Incompatible dependency license!"
-    exit 1
-  }
-fi
-
-# Freshness check - FAIL on outdated deps (including MAJOR)
-if command -v cargo-outdated &>/dev/null; then
-  echo "Freshness check..."
-  outdated_count=$(cargo outdated --depth 1 -R 2>/dev/null | grep -c "^[a-z]" || echo "0")
-  if [ "$outdated_count" -gt 0 ]; then
-    echo ""
-    echo "❌ OUTDATED: $outdated_count dependencies need updates!"
-    cargo outdated --depth 1 -R 2>/dev/null || true
-    echo ""
-    echo "Run: cargo update"
-    echo "For major updates: cargo upgrade --incompatible (requires cargo-edit)"
-    exit 1
-  fi
-fi"#
-        .to_string(),
-
-        ProjectType::Python => r#"
-# === DEPENDENCY HEALTH (v9.8.0 ADR-045 - STRICT) ===
-# Security audit - FAIL on ANY vulnerability
-if command -v pip-audit &>/dev/null; then
-  echo "Security audit..."
-  pip-audit 2>/dev/null || {
-    echo ""
-    echo "❌ SECURITY: Vulnerabilities found!"
-    echo "Run: pip-audit --fix"
-    exit 1
-  }
-fi
-
-# License compliance
-if command -v pip-licenses &>/dev/null; then
-  echo "License compliance..."
-  # Check for GPL/AGPL in MIT/Apache projects
-  gpl_deps=$(pip-licenses --format=csv 2>/dev/null | grep -i "GPL" | head -5 || true)
-  if [ -n "$gpl_deps" ]; then
-    echo ""
-    echo "⚠️ LICENSE: Found GPL-licensed dependencies:"
-    echo "$gpl_deps"
-    echo "Verify compatibility with your project license"
-  fi
-fi
-
-# Freshness check - FAIL on outdated deps
-echo "Freshness check..."
-outdated=$(pip list --outdated --format=columns 2>/dev/null | tail -n +3 | wc -l | tr -d ' ')
-if [ "$outdated" -gt 0 ]; then
-  echo ""
-  echo "❌ OUTDATED: $outdated dependencies need updates!"
-  pip list --outdated 2>/dev/null || true
-  echo ""
-  echo "Run: pip install --upgrade <package>"
-  exit 1
-fi"#
-        .to_string(),
-
-        ProjectType::Node => r#"
-# === DEPENDENCY HEALTH (v9.8.0 ADR-045 - STRICT) ===
-# Security audit - FAIL on high/critical vulnerabilities
-if [ -f "package.json" ]; then
-  echo "Security audit..."
-  npm audit --audit-level=high 2>/dev/null || {
-    echo ""
-    echo "❌ SECURITY: Vulnerabilities found!"
-    echo "Run: npm audit fix"
-    exit 1
-  }
-fi
-
-# License compliance
-if command -v license-checker &>/dev/null; then
-  echo "License compliance..."
-  license-checker --failOn 'GPL;AGPL' 2>/dev/null || {
-    echo ""
-    echo "❌ LICENSE: Incompatible dependency license!"
-    exit 1
-  }
-fi
-
-# Freshness check - FAIL on outdated deps
-if [ -f "package.json" ]; then
-  echo "Freshness check..."
-  outdated=$(npm outdated --json 2>/dev/null | grep -c '"current"' || echo "0")
-  if [ "$outdated" -gt 0 ]; then
-    echo ""
-    echo "❌ OUTDATED: Dependencies need updates!"
-    npm outdated 2>/dev/null || true
-    echo ""
-    echo "Run: npm update"
-    exit 1
-  fi
-fi"#
-        .to_string(),
-
-        ProjectType::Go => r#"
-# === DEPENDENCY HEALTH (v9.8.0 ADR-045 - STRICT) ===
-# Security audit - FAIL on ANY vulnerability
-if command -v govulncheck &>/dev/null; then
-  echo "Security audit..."
-  govulncheck ./... 2>/dev/null || {
-    echo ""
-    echo "❌ SECURITY: Vulnerabilities found!"
-    echo "Run: go get -u <package>"
-    exit 1
-  }
-fi
-
-# Freshness check - FAIL on outdated deps
-echo "Freshness check..."
-outdated=$(go list -m -u all 2>/dev/null | grep '\[' | wc -l | tr -d ' ')
-if [ "$outdated" -gt 0 ]; then
-  echo ""
-  echo "❌ OUTDATED: $outdated dependencies need updates!"
-  go list -m -u all 2>/dev/null | grep '\[' || true
-  echo ""
-  echo "Run: go get -u ./..."
-  exit 1
-fi"#
-        .to_string(),
-
-        ProjectType::Flutter => r#"
-# === DEPENDENCY HEALTH (v9.8.0 ADR-045 - STRICT) ===
-# Freshness check - FAIL on outdated deps
-echo "Freshness check..."
-if command -v dart &>/dev/null; then
-  outdated=$(dart pub outdated --json 2>/dev/null | grep -c '"current"' || echo "0")
-  if [ "$outdated" -gt 0 ]; then
-    echo ""
-    echo "❌ OUTDATED: Dependencies need updates!"
-    dart pub outdated 2>/dev/null || true
-    echo ""
-    echo "Run: dart pub upgrade --major-versions"
-    exit 1
-  fi
-fi"#
-        .to_string(),
-
-        // Docs/Arch/Generic/Migration don't have package dependencies
-        _ => String::new(),
-    }
-}
-
 /// Generate hook installer script
 pub fn hook_installer_template() -> String {
     r#"#!/bin/bash
@@ -511,59 +112,22 @@ mod tests {
     #[test]
     fn test_git_precommit_hook() {
         let hook = git_precommit_hook();
-        assert!(hook.contains("#!/bin/bash"));
-        assert!(hook.contains("cargo"));
+        assert!(hook.starts_with("#!/bin/sh\n# asimov-managed hook: pre-commit"));
     }
 
     #[test]
-    fn test_precommit_hook_template() {
-        let hook = precommit_hook_template(ProjectType::Rust);
-        assert!(hook.contains("cargo fmt"));
-        assert!(hook.contains("cargo clippy"));
-        assert!(hook.contains("cargo test"));
-        assert!(hook.contains("FILE SIZE CHECK"));
-        assert!(hook.contains("asimov refresh || true")); // Optional, soft-fail
-    }
-
-    #[test]
-    fn test_precommit_hook_python() {
+    fn test_precommit_hook_template_is_shim() {
         let hook = precommit_hook_template(ProjectType::Python);
-        assert!(hook.contains("ruff") || hook.contains("black"));
-        assert!(hook.contains("pytest"));
-        assert!(hook.contains("FILE SIZE CHECK"));
-        assert!(hook.contains("*.py"));
-    }
-
-    #[test]
-    fn test_precommit_hook_node() {
-        let hook = precommit_hook_template(ProjectType::Node);
-        assert!(hook.contains("prettier") || hook.contains("eslint"));
-        assert!(hook.contains("npm test"));
-        assert!(hook.contains("FILE SIZE CHECK"));
-    }
-
-    #[test]
-    fn test_precommit_hook_go() {
-        let hook = precommit_hook_template(ProjectType::Go);
-        assert!(hook.contains("gofmt"));
-        assert!(hook.contains("go test"));
-        assert!(hook.contains("FILE SIZE CHECK"));
-    }
-
-    #[test]
-    fn test_precommit_hook_flutter() {
-        let hook = precommit_hook_template(ProjectType::Flutter);
-        assert!(hook.contains("dart format"));
-        assert!(hook.contains("flutter test") || hook.contains("flutter analyze"));
-        assert!(hook.contains("FILE SIZE CHECK"));
+        assert!(hook.ends_with("exec asimov hook run pre-commit \"$@\"\n"));
+        assert!(!hook.contains("pytest"));
     }
 
     #[test]
-    fn test_precommit_hook_docs() {
-        let hook = precommit_hook_template(ProjectType::Docs);
-        assert!(hook.contains("FILE SIZE CHECK"));
-        assert!(hook.contains("*.md"));
-        assert!(hook.contains("asimov refresh || true"));
+    fn test_hook_shim_chains_previous_hook() {
+        let hook = hook_shim_template("commit-msg");
+        assert!(hook.contains("chained=\"$0.pre-asimov\""));
+        assert!(hook.contains("\"$chained\" \"$@\" || exit $?"));
+        assert!(hook.ends_with("exec asimov hook run commit-msg \"$@\"\n"));
     }
 
     #[test]
@@ -577,8 +141,9 @@ mod tests {
     // test_claude_pre_compact_hook (ADR-060)
 
     #[test]
-    fn test_precommit_hook_all_types() {
-        let types = [
+    fn test_precommit_no_spof() {
+        // Without asimov installed the commit goes through (ADR-043)
+        for pt in [
             ProjectType::Rust,
             ProjectType::Python,
             ProjectType::Node,
@@ -588,48 +153,14 @@ mod tests {
             ProjectType::Generic,
             ProjectType::Migration,
             ProjectType::Arch,
-        ];
-        for pt in types {
-            let hook = precommit_hook_template(pt);
-            assert!(!hook.is_empty(), "Hook for {:?} should not be empty", pt);
-            assert!(
-                hook.contains("asimov refresh || true"),
-                "Hook for {:?} should have optional asimov",
-                pt
-            );
-            assert!(
-                hook.contains("FILE SIZE CHECK"),
-                "Hook for {:?} should have file size check",
-                pt
-            );
-        }
-    }
-
-    #[test]
-    fn test_precommit_no_spof() {
-        // Verify asimov calls are optional (soft-fail with || true)
-        for pt in [
-            ProjectType::Rust,
-            ProjectType::Python,
-            ProjectType::Node,
-            ProjectType::Go,
-            ProjectType::Flutter,
         ] {
             let hook = precommit_hook_template(pt);
-            // Quality checks should NOT depend on asimov
-            assert!(
-                !hook.contains("asimov lint"),
-                "Hook should not call asimov lint"
-            );
-            // asimov calls should be soft-fail
             assert!(
-                hook.contains("asimov refresh || true"),
-                "asimov refresh should soft-fail"
-            );
-            assert!(
-                hook.contains("asimov validate || true"),
-                "asimov validate should soft-fail"
+                hook.contains("command -v asimov >/dev/null 2>&1 || {"),
+                "Hook for {:?} should guard on asimov",
+                pt
             );
+            assert!(hook.contains("exit 0; }"));
         }
     }
 }
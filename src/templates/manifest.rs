@@ -0,0 +1,387 @@
+//! Template manifest
+//!
+//! One entry per `asimov init --type` template. Each declares its base
+//! language, the git hooks it installs and how dev dependencies are set up;
+//! quality commands live in the template's own `quality:` section. Replaces
+//! the template-name match in `run_init` that mapped 21 names onto 9 types.
+
+use super::{render_template_content, ProjectType};
+
+/// How `asimov init` sets up coding standards tools (ADR-044)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DevSetup {
+    /// cargo-husky in Cargo.toml [dev-dependencies]
+    CargoHusky,
+    /// [tool.ruff] in pyproject.toml
+    Ruff,
+    /// prettier and eslint in package.json devDependencies
+    NodeLinters,
+    /// Nothing to edit; print install instructions
+    Instructions(&'static [&'static str]),
+}
+
+/// An embedded project template
+#[derive(Debug, Clone, Copy)]
+pub struct TemplateManifest {
+    pub name: &'static str,
+    /// Other names accepted by `asimov init --type`
+    pub aliases: &'static [&'static str],
+    /// Base language: drives hooks, default quality gates and doctor checks
+    pub language: ProjectType,
+    /// Embedded project.yaml (overridable by .asimov/templates/<name>.yaml)
+    pub content: &'static str,
+    /// Git hooks `asimov init` installs
+    pub hooks: &'static [&'static str],
+    pub dev_setup: DevSetup,
+}
+
+impl TemplateManifest {
+    /// Whether `asimov init --type <name>` selects this template
+    pub fn matches(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        self.name == name || self.aliases.contains(&name.as_str())
+    }
+
+    /// Embedded content with `@extends`/`@include` resolved and default variables
+    ///
+    /// Falls back to the raw content if a custom parent fails to render.
+    pub fn rendered_content(&self) -> String {
+        render_template_content(self.name, self.content, &[])
+            .unwrap_or_else(|_| self.content.to_string())
+    }
+
+    /// Quality commands declared in the template's `quality:` section
+    pub fn quality_commands(&self) -> Vec<(String, String)> {
+        let content = self.rendered_content();
+        let Ok(yaml) = serde_yaml_ng::from_str::<serde_yaml_ng::Value>(&content) else {
+            return Vec::new();
+        };
+        yaml.get("quality")
+            .and_then(|q| q.as_mapping())
+            .map(|quality| {
+                quality
+                    .iter()
+                    .filter_map(|(k, v)| Some((k.as_str()?.to_string(), v.as_str()?.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+const PRE_COMMIT: &[&str] = &["pre-commit"];
+// Monorepo test suites are slow: gates also run before push
+const PRE_COMMIT_AND_PUSH: &[&str] = &["pre-commit", "pre-push"];
+
+const NODE_ALIASES: &[&str] = &["nodejs", "javascript", "js", "typescript", "ts"];
+
+const GO_SETUP: DevSetup = DevSetup::Instructions(&[
+    "go install github.com/golangci/golangci-lint/cmd/golangci-lint@latest",
+    "Note: golangci-lint is GPL-3.0 licensed (tool use is fine)",
+]);
+const FLUTTER_SETUP: DevSetup = DevSetup::Instructions(&["dart pub add --dev test"]);
+const DOCS_SETUP: DevSetup = DevSetup::Instructions(&["npm install -g markdownlint-cli2"]);
+const JAVA_SETUP: DevSetup = DevSetup::Instructions(&[
+    "Commit the build wrapper: mvn wrapper:wrapper (Maven) or gradle wrapper (Gradle)",
+]);
+const CSHARP_SETUP: DevSetup =
+    DevSetup::Instructions(&["dotnet restore (dotnet format ships with the .NET 6+ SDK)"]);
+const RUBY_SETUP: DevSetup = DevSetup::Instructions(&["bundle add rubocop --group development"]);
+const PHP_SETUP: DevSetup =
+    DevSetup::Instructions(&["composer require --dev phpunit/phpunit squizlabs/php_codesniffer"]);
+const CPP_SETUP: DevSetup = DevSetup::Instructions(&[
+    "Install clang-format, clang-tidy and CMake (e.g. apt install clang-format clang-tidy cmake)",
+]);
+
+/// All embedded templates, base languages first
+pub const TEMPLATES: &[TemplateManifest] = &[
+    // Base project templates
+    TemplateManifest {
+        name: "rust",
+        aliases: &["rs"],
+        language: ProjectType::Rust,
+        content: include_str!("../../templates/rust.yaml"),
+        hooks: PRE_COMMIT,
+        dev_setup: DevSetup::CargoHusky,
+    },
+    TemplateManifest {
+        name: "python",
+        aliases: &["py"],
+        language: ProjectType::Python,
+        content: include_str!("../../templates/python.yaml"),
+        hooks: PRE_COMMIT,
+        dev_setup: DevSetup::Ruff,
+    },
+    TemplateManifest {
+        name: "node",
+        aliases: NODE_ALIASES,
+        language: ProjectType::Node,
+        content: include_str!("../../templates/node.yaml"),
+        hooks: PRE_COMMIT,
+        dev_setup: DevSetup::NodeLinters,
+    },
+    TemplateManifest {
+        name: "go",
+        aliases: &["golang"],
+        language: ProjectType::Go,
+        content: include_str!("../../templates/go.yaml"),
+        hooks: PRE_COMMIT,
+        dev_setup: GO_SETUP,
+    },
+    TemplateManifest {
+        name: "flutter",
+        aliases: &["dart"],
+        language: ProjectType::Flutter,
+        content: include_str!("../../templates/flutter.yaml"),
+        hooks: PRE_COMMIT,
+        dev_setup: FLUTTER_SETUP,
+    },
+    TemplateManifest {
+        name: "java",
+        aliases: &["kotlin", "jvm"],
+        language: ProjectType::Java,
+        content: include_str!("../../templates/java.yaml"),
+        hooks: PRE_COMMIT,
+        dev_setup: JAVA_SETUP,
+    },
+    TemplateManifest {
+        name: "csharp",
+        aliases: &["c#", "dotnet", "cs"],
+        language: ProjectType::CSharp,
+        content: include_str!("../../templates/csharp.yaml"),
+        hooks: PRE_COMMIT,
+        dev_setup: CSHARP_SETUP,
+    },
+    TemplateManifest {
+        name: "ruby",
+        aliases: &["rb"],
+        language: ProjectType::Ruby,
+        content: include_str!("../../templates/ruby.yaml"),
+        hooks: PRE_COMMIT,
+        dev_setup: RUBY_SETUP,
+    },
+    TemplateManifest {
+        name: "php",
+        aliases: &[],
+        language: ProjectType::Php,
+        content: include_str!("../../templates/php.yaml"),
+        hooks: PRE_COMMIT,
+        dev_setup: PHP_SETUP,
+    },
+    TemplateManifest {
+        name: "cpp",
+        aliases: &["c++", "c", "cxx"],
+        language: ProjectType::Cpp,
+        content: include_str!("../../templates/cpp.yaml"),
+        hooks: PRE_COMMIT,
+        dev_setup: CPP_SETUP,
+    },
+    TemplateManifest {
+        name: "docs",
+        aliases: &["documentation"],
+        language: ProjectType::Docs,
+        content: include_str!("../../templates/docs.yaml"),
+        hooks: PRE_COMMIT,
+        dev_setup: DOCS_SETUP,
+    },
+    TemplateManifest {
+        name: "arch",
+        aliases: &["architecture"],
+        language: ProjectType::Arch,
+        content: include_str!("../../templates/arch.yaml"),
+        hooks: PRE_COMMIT,
+        dev_setup: DOCS_SETUP,
+    },
+    TemplateManifest {
+        name: "generic",
+        aliases: &[],
+        language: ProjectType::Generic,
+        content: include_str!("../../templates/generic.yaml"),
+        hooks: PRE_COMMIT,
+        dev_setup: DOCS_SETUP,
+    },
+    TemplateManifest {
+        name: "migration",
+        aliases: &["migrations"],
+        language: ProjectType::Migration,
+        content: include_str!("../../templates/migration.yaml"),
+        hooks: PRE_COMMIT,
+        dev_setup: DOCS_SETUP,
+    },
+    // API templates
+    TemplateManifest {
+        name: "api-rust",
+        aliases: &[],
+        language: ProjectType::Rust,
+        content: include_str!("../../templates/api-rust.yaml"),
+        hooks: PRE_COMMIT,
+        dev_setup: DevSetup::CargoHusky,
+    },
+    TemplateManifest {
+        name: "api-go",
+        aliases: &[],
+        language: ProjectType::Go,
+        content: include_str!("../../templates/api-go.yaml"),
+        hooks: PRE_COMMIT,
+        dev_setup: GO_SETUP,
+    },
+    TemplateManifest {
+        name: "api-fastapi",
+        aliases: &[],
+        language: ProjectType::Python,
+        content: include_str!("../../templates/api-fastapi.yaml"),
+        hooks: PRE_COMMIT,
+        dev_setup: DevSetup::Ruff,
+    },
+    TemplateManifest {
+        name: "api-nestjs",
+        aliases: &[],
+        language: ProjectType::Node,
+        content: include_str!("../../templates/api-nestjs.yaml"),
+        hooks: PRE_COMMIT,
+        dev_setup: DevSetup::NodeLinters,
+    },
+    TemplateManifest {
+        name: "api-spring",
+        aliases: &[],
+        language: ProjectType::Java,
+        content: include_str!("../../templates/api-spring.yaml"),
+        hooks: PRE_COMMIT,
+        dev_setup: JAVA_SETUP,
+    },
+    // Web templates
+    TemplateManifest {
+        name: "web-nextjs",
+        aliases: &[],
+        language: ProjectType::Node,
+        content: include_str!("../../templates/web-nextjs.yaml"),
+        hooks: PRE_COMMIT,
+        dev_setup: DevSetup::NodeLinters,
+    },
+    TemplateManifest {
+        name: "web-react",
+        aliases: &[],
+        language: ProjectType::Node,
+        content: include_str!("../../templates/web-react.yaml"),
+        hooks: PRE_COMMIT,
+        dev_setup: DevSetup::NodeLinters,
+    },
+    TemplateManifest {
+        name: "web-vue",
+        aliases: &[],
+        language: ProjectType::Node,
+        content: include_str!("../../templates/web-vue.yaml"),
+        hooks: PRE_COMMIT,
+        dev_setup: DevSetup::NodeLinters,
+    },
+    TemplateManifest {
+        name: "web-angular",
+        aliases: &[],
+        language: ProjectType::Node,
+        content: include_str!("../../templates/web-angular.yaml"),
+        hooks: PRE_COMMIT,
+        dev_setup: DevSetup::NodeLinters,
+    },
+    // Monorepo templates
+    TemplateManifest {
+        name: "mono-turbo",
+        aliases: &[],
+        language: ProjectType::Node,
+        content: include_str!("../../templates/mono-turbo.yaml"),
+        hooks: PRE_COMMIT_AND_PUSH,
+        dev_setup: DevSetup::NodeLinters,
+    },
+    TemplateManifest {
+        name: "mono-nx",
+        aliases: &[],
+        language: ProjectType::Node,
+        content: include_str!("../../templates/mono-nx.yaml"),
+        hooks: PRE_COMMIT_AND_PUSH,
+        dev_setup: DevSetup::NodeLinters,
+    },
+    TemplateManifest {
+        name: "mono-pnpm",
+        aliases: &[],
+        language: ProjectType::Node,
+        content: include_str!("../../templates/mono-pnpm.yaml"),
+        hooks: PRE_COMMIT_AND_PUSH,
+        dev_setup: DevSetup::NodeLinters,
+    },
+    // Admin template
+    TemplateManifest {
+        name: "admin-dashboard",
+        aliases: &[],
+        language: ProjectType::Node,
+        content: include_str!("../../templates/admin-dashboard.yaml"),
+        hooks: PRE_COMMIT,
+        dev_setup: DevSetup::NodeLinters,
+    },
+];
+
+/// Template selected by a name or alias (case-insensitive)
+pub fn find_template(name: &str) -> Option<&'static TemplateManifest> {
+    TEMPLATES.iter().find(|t| t.matches(name))
+}
+
+/// The base template of a project type
+pub fn base_template(project_type: ProjectType) -> &'static TemplateManifest {
+    TEMPLATES
+        .iter()
+        .find(|t| t.language == project_type && t.name == project_type.to_string())
+        .unwrap_or_else(|| find_template("generic").expect("generic template is embedded"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schemas::PROJECT_SCHEMA;
+
+    #[test]
+    fn test_find_template_by_alias() {
+        assert_eq!(find_template("TS").unwrap().name, "node");
+        assert_eq!(find_template("kotlin").unwrap().language, ProjectType::Java);
+        assert_eq!(
+            find_template("api-spring").unwrap().language,
+            ProjectType::Java
+        );
+        assert!(find_template("cobol").is_none());
+    }
+
+    #[test]
+    fn test_every_project_type_has_a_base_template() {
+        for pt in ProjectType::ALL {
+            assert_eq!(base_template(pt).language, pt);
+        }
+    }
+
+    #[test]
+    fn test_templates_declare_their_language_as_identity_type() {
+        let schema: serde_json::Value = serde_json::from_str(PROJECT_SCHEMA).unwrap();
+        let allowed = schema["properties"]["identity"]["properties"]["type"]["enum"]
+            .as_array()
+            .unwrap();
+        for template in TEMPLATES {
+            let content = render_template_content(template.name, template.content, &[]).unwrap();
+            let yaml: serde_yaml_ng::Value = serde_yaml_ng::from_str(&content).unwrap();
+            let declared = yaml["identity"]["type"].as_str().unwrap();
+            assert_eq!(
+                declared.parse::<ProjectType>(),
+                Ok(template.language),
+                "{}",
+                template.name
+            );
+            assert!(
+                allowed.iter().any(|v| v == declared),
+                "{}: type {} not in PROJECT_SCHEMA",
+                template.name,
+                declared
+            );
+        }
+    }
+
+    #[test]
+    fn test_quality_commands_from_content() {
+        let commands = find_template("go").unwrap().quality_commands();
+        assert!(commands.contains(&("vet".to_string(), "go vet ./...".to_string())));
+        assert!(TEMPLATES.iter().all(|t| !t.hooks.is_empty()));
+    }
+}
@@ -1,17 +1,23 @@
 //! Template generators for RoyalBit Asimov files
 
+mod detect;
 mod hooks;
+mod manifest;
 mod project;
 mod protocols;
+mod render;
 mod warmup;
 
 use std::fmt;
 use std::path::Path;
 
 // Re-export all public items
+pub use detect::*;
 pub use hooks::*;
+pub use manifest::*;
 pub use project::*;
 pub use protocols::*;
+pub use render::*;
 pub use warmup::*;
 
 /// Supported project types for template generation
@@ -27,6 +33,41 @@ pub enum ProjectType {
     Docs,
     Migration,
     Arch,
+    /// Java and Kotlin (Maven or Gradle)
+    Java,
+    CSharp,
+    Ruby,
+    Php,
+    /// C and C++
+    Cpp,
+}
+
+impl ProjectType {
+    /// Every project type, in the order menus list them
+    pub const ALL: [ProjectType; 14] = [
+        ProjectType::Rust,
+        ProjectType::Python,
+        ProjectType::Node,
+        ProjectType::Go,
+        ProjectType::Flutter,
+        ProjectType::Java,
+        ProjectType::CSharp,
+        ProjectType::Ruby,
+        ProjectType::Php,
+        ProjectType::Cpp,
+        ProjectType::Docs,
+        ProjectType::Arch,
+        ProjectType::Generic,
+        ProjectType::Migration,
+    ];
+
+    /// Whether the project is source code (coding standards, blocking size limits)
+    pub fn is_code(&self) -> bool {
+        !matches!(
+            self,
+            ProjectType::Docs | ProjectType::Arch | ProjectType::Generic | ProjectType::Migration
+        )
+    }
 }
 
 impl fmt::Display for ProjectType {
@@ -41,6 +82,11 @@ impl fmt::Display for ProjectType {
             ProjectType::Docs => write!(f, "docs"),
             ProjectType::Migration => write!(f, "migration"),
             ProjectType::Arch => write!(f, "arch"),
+            ProjectType::Java => write!(f, "java"),
+            ProjectType::CSharp => write!(f, "csharp"),
+            ProjectType::Ruby => write!(f, "ruby"),
+            ProjectType::Php => write!(f, "php"),
+            ProjectType::Cpp => write!(f, "cpp"),
         }
     }
 }
@@ -59,8 +105,13 @@ impl std::str::FromStr for ProjectType {
             "docs" | "documentation" => Ok(ProjectType::Docs),
             "migration" | "migrations" => Ok(ProjectType::Migration),
             "arch" | "architecture" => Ok(ProjectType::Arch),
+            "java" | "kotlin" | "jvm" => Ok(ProjectType::Java),
+            "csharp" | "c#" | "dotnet" | "cs" => Ok(ProjectType::CSharp),
+            "ruby" | "rb" => Ok(ProjectType::Ruby),
+            "php" => Ok(ProjectType::Php),
+            "cpp" | "c++" | "c" | "cxx" => Ok(ProjectType::Cpp),
             _ => Err(format!(
-                "Unknown template: '{}'. Use --help to see all 21 available templates",
+                "Unknown template: '{}'. Use --help to see all available templates",
                 s
             )),
         }
@@ -70,22 +121,9 @@ impl std::str::FromStr for ProjectType {
 /// Detect project type from marker files in the given directory (ADR-032)
 /// Returns the detected project type or Generic if no markers found
 pub fn detect_project_type(dir: &Path) -> ProjectType {
-    // Check for marker files in priority order
-    // Flutter/Dart before Node (pubspec.yaml is more specific)
-    if dir.join("pubspec.yaml").exists() {
-        return ProjectType::Flutter;
-    }
-    if dir.join("Cargo.toml").exists() {
-        return ProjectType::Rust;
-    }
-    if dir.join("go.mod").exists() {
-        return ProjectType::Go;
-    }
-    if dir.join("pyproject.toml").exists() || dir.join("setup.py").exists() {
-        return ProjectType::Python;
-    }
-    if dir.join("package.json").exists() {
-        return ProjectType::Node;
+    // Check for marker files in priority order (shared with detect_project)
+    if let Some((project_type, _, _)) = detect::component_marker(dir) {
+        return project_type;
     }
     // Check for arch project (ADR-041)
     // c4-models/ OR decisions/ OR (diagrams/ AND ARCHITECTURE*.md)
@@ -379,6 +417,39 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_project_type_new_languages_round_trip() {
+        for pt in ProjectType::ALL {
+            assert_eq!(pt.to_string().parse::<ProjectType>(), Ok(pt));
+        }
+        assert_eq!("kotlin".parse::<ProjectType>(), Ok(ProjectType::Java));
+        assert_eq!("c#".parse::<ProjectType>(), Ok(ProjectType::CSharp));
+        assert_eq!("c++".parse::<ProjectType>(), Ok(ProjectType::Cpp));
+        assert!(ProjectType::Ruby.is_code());
+        assert!(!ProjectType::Docs.is_code());
+    }
+
+    #[test]
+    fn test_detect_project_type_new_languages() {
+        let cases: [(&str, ProjectType); 6] = [
+            ("pom.xml", ProjectType::Java),
+            ("build.gradle.kts", ProjectType::Java),
+            ("App.csproj", ProjectType::CSharp),
+            ("Gemfile", ProjectType::Ruby),
+            ("composer.json", ProjectType::Php),
+            ("CMakeLists.txt", ProjectType::Cpp),
+        ];
+        for (marker, expected) in cases {
+            let temp_dir = tempfile::TempDir::new().unwrap();
+            std::fs::write(temp_dir.path().join(marker), "").unwrap();
+            if expected != ProjectType::Cpp {
+                // A package.json for assets must not win
+                std::fs::write(temp_dir.path().join("package.json"), "{}").unwrap();
+            }
+            assert_eq!(detect_project_type(temp_dir.path()), expected, "{}", marker);
+        }
+    }
+
     #[test]
     fn test_has_architecture_file_true() {
         let temp_dir = tempfile::TempDir::new().unwrap();
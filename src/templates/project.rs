@@ -1,105 +1,37 @@
 //! Project-related template generators
 //! v10.3.1: All templates in flat cli/templates/ with .asimov/templates/ override (ADR-057)
 
-use super::ProjectType;
+use super::{base_template, find_template, render_template, ProjectType, TEMPLATES};
+use crate::context::{current_context, project_root, resolve_asimov_file};
+use crate::schemas::with_current_schema_version;
 use std::path::PathBuf;
 
 // ========== Embedded Templates (compile-time from cli/templates/) ==========
-// Single source of truth: cli/templates/*.yaml
+// Single source of truth: cli/templates/*.yaml, listed in TEMPLATES (manifest.rs)
 // Runtime override: .asimov/templates/*.yaml takes priority
 
-// Base project templates
-const TEMPLATE_RUST: &str = include_str!("../../templates/rust.yaml");
-const TEMPLATE_PYTHON: &str = include_str!("../../templates/python.yaml");
-const TEMPLATE_NODE: &str = include_str!("../../templates/node.yaml");
-const TEMPLATE_GO: &str = include_str!("../../templates/go.yaml");
-const TEMPLATE_FLUTTER: &str = include_str!("../../templates/flutter.yaml");
-const TEMPLATE_DOCS: &str = include_str!("../../templates/docs.yaml");
-const TEMPLATE_GENERIC: &str = include_str!("../../templates/generic.yaml");
-const TEMPLATE_ARCH: &str = include_str!("../../templates/arch.yaml");
-
-// API templates
-const TEMPLATE_API_RUST: &str = include_str!("../../templates/api-rust.yaml");
-const TEMPLATE_API_GO: &str = include_str!("../../templates/api-go.yaml");
-const TEMPLATE_API_FASTAPI: &str = include_str!("../../templates/api-fastapi.yaml");
-const TEMPLATE_API_NESTJS: &str = include_str!("../../templates/api-nestjs.yaml");
-const TEMPLATE_API_SPRING: &str = include_str!("../../templates/api-spring.yaml");
-
-// Web templates
-const TEMPLATE_WEB_NEXTJS: &str = include_str!("../../templates/web-nextjs.yaml");
-const TEMPLATE_WEB_REACT: &str = include_str!("../../templates/web-react.yaml");
-const TEMPLATE_WEB_VUE: &str = include_str!("../../templates/web-vue.yaml");
-const TEMPLATE_WEB_ANGULAR: &str = include_str!("../../templates/web-angular.yaml");
-
-// Monorepo templates
-const TEMPLATE_MONO_TURBO: &str = include_str!("../../templates/mono-turbo.yaml");
-const TEMPLATE_MONO_NX: &str = include_str!("../../templates/mono-nx.yaml");
-const TEMPLATE_MONO_PNPM: &str = include_str!("../../templates/mono-pnpm.yaml");
-
-// Admin template
-const TEMPLATE_ADMIN_DASHBOARD: &str = include_str!("../../templates/admin-dashboard.yaml");
-
 /// Get the templates directory path
+/// Under the discovered project root, so subdirectories find it
 pub fn templates_dir() -> PathBuf {
-    PathBuf::from(".asimov/templates")
+    project_root().join(".asimov/templates")
 }
 
 /// Try to read a template from external file, return None if not found
+/// Packages inherit templates from enclosing .asimov/ contexts
 fn try_read_template(name: &str) -> Option<String> {
-    let path = templates_dir().join(format!("{}.yaml", name));
+    let path = resolve_asimov_file(&format!("templates/{}.yaml", name));
     std::fs::read_to_string(&path).ok()
 }
 
 /// Get template content, trying external file first, then embedded fallback
 fn get_template(project_type: ProjectType) -> String {
-    let name = match project_type {
-        ProjectType::Rust => "rust",
-        ProjectType::Python => "python",
-        ProjectType::Node => "node",
-        ProjectType::Go => "go",
-        ProjectType::Flutter => "flutter",
-        ProjectType::Docs => "docs",
-        ProjectType::Arch => "arch",
-        ProjectType::Generic | ProjectType::Migration => "generic",
-    };
-
-    // Try external file first
-    if let Some(content) = try_read_template(name) {
-        return content;
-    }
-
-    // Fall back to embedded template
-    match project_type {
-        ProjectType::Rust => TEMPLATE_RUST.to_string(),
-        ProjectType::Python => TEMPLATE_PYTHON.to_string(),
-        ProjectType::Node => TEMPLATE_NODE.to_string(),
-        ProjectType::Go => TEMPLATE_GO.to_string(),
-        ProjectType::Flutter => TEMPLATE_FLUTTER.to_string(),
-        ProjectType::Docs => TEMPLATE_DOCS.to_string(),
-        ProjectType::Arch => TEMPLATE_ARCH.to_string(),
-        ProjectType::Generic | ProjectType::Migration => TEMPLATE_GENERIC.to_string(),
-    }
+    let manifest = base_template(project_type);
+    try_read_template(manifest.name).unwrap_or_else(|| manifest.content.to_string())
 }
 
-/// All embedded template names (beyond base project types)
-const EXTENDED_TEMPLATES: &[&str] = &[
-    "api-rust",
-    "api-go",
-    "api-fastapi",
-    "api-nestjs",
-    "api-spring",
-    "web-nextjs",
-    "web-react",
-    "web-vue",
-    "web-angular",
-    "mono-turbo",
-    "mono-nx",
-    "mono-pnpm",
-    "admin-dashboard",
-];
-
 /// Get any template by name (tries external first, falls back to embedded)
 /// v10.3.1: Unified template lookup for all template types
+/// Embedded templates come from the manifest (aliases accepted)
 pub fn get_template_by_name(name: &str) -> Option<String> {
     // Try external file first
     if let Some(content) = try_read_template(name) {
@@ -107,35 +39,7 @@ pub fn get_template_by_name(name: &str) -> Option<String> {
     }
 
     // Fall back to embedded template
-    match name {
-        // Base project templates
-        "rust" => Some(TEMPLATE_RUST.to_string()),
-        "python" => Some(TEMPLATE_PYTHON.to_string()),
-        "node" => Some(TEMPLATE_NODE.to_string()),
-        "go" => Some(TEMPLATE_GO.to_string()),
-        "flutter" => Some(TEMPLATE_FLUTTER.to_string()),
-        "docs" => Some(TEMPLATE_DOCS.to_string()),
-        "arch" => Some(TEMPLATE_ARCH.to_string()),
-        "generic" => Some(TEMPLATE_GENERIC.to_string()),
-        // API templates
-        "api-rust" => Some(TEMPLATE_API_RUST.to_string()),
-        "api-go" => Some(TEMPLATE_API_GO.to_string()),
-        "api-fastapi" => Some(TEMPLATE_API_FASTAPI.to_string()),
-        "api-nestjs" => Some(TEMPLATE_API_NESTJS.to_string()),
-        "api-spring" => Some(TEMPLATE_API_SPRING.to_string()),
-        // Web templates
-        "web-nextjs" => Some(TEMPLATE_WEB_NEXTJS.to_string()),
-        "web-react" => Some(TEMPLATE_WEB_REACT.to_string()),
-        "web-vue" => Some(TEMPLATE_WEB_VUE.to_string()),
-        "web-angular" => Some(TEMPLATE_WEB_ANGULAR.to_string()),
-        // Monorepo templates
-        "mono-turbo" => Some(TEMPLATE_MONO_TURBO.to_string()),
-        "mono-nx" => Some(TEMPLATE_MONO_NX.to_string()),
-        "mono-pnpm" => Some(TEMPLATE_MONO_PNPM.to_string()),
-        // Admin template
-        "admin-dashboard" => Some(TEMPLATE_ADMIN_DASHBOARD.to_string()),
-        _ => None,
-    }
+    find_template(name).map(|t| t.content.to_string())
 }
 
 /// Backwards compatibility alias
@@ -146,26 +50,19 @@ pub fn get_enterprise_template(name: &str) -> Option<String> {
 
 /// List all available templates (external + embedded)
 pub fn list_templates() -> Vec<String> {
-    let mut templates = vec![
-        "rust", "python", "node", "go", "flutter", "docs", "arch", "generic",
-    ]
-    .into_iter()
-    .map(|s| s.to_string())
-    .collect::<Vec<_>>();
-
-    // Add extended templates (api-*, web-*, mono-*, admin-dashboard)
-    templates.extend(EXTENDED_TEMPLATES.iter().map(|s| s.to_string()));
-
-    // Add custom templates from .asimov/templates/ if they exist
-    if let Ok(entries) = std::fs::read_dir(templates_dir()) {
-        for entry in entries.flatten() {
-            let name = entry.file_name();
-            let name_str = name.to_string_lossy();
-            if name_str.ends_with(".yaml") {
-                let template_name = name_str.trim_end_matches(".yaml").to_string();
-                if !templates.contains(&template_name) {
-                    templates.push(template_name);
-                }
+    let mut templates = TEMPLATES
+        .iter()
+        .map(|t| t.name.to_string())
+        .collect::<Vec<_>>();
+
+    // Add custom templates from .asimov/templates/ (and enclosing contexts)
+    let custom = current_context()
+        .map(|context| context.inherited_files("templates", "yaml"))
+        .unwrap_or_default();
+    for path in custom {
+        if let Some(template_name) = path.file_stem().map(|s| s.to_string_lossy().to_string()) {
+            if !templates.contains(&template_name) {
+                templates.push(template_name);
             }
         }
     }
@@ -176,16 +73,23 @@ pub fn list_templates() -> Vec<String> {
 
 /// Generate a project.yaml template for project-specific configuration (ADR-032)
 /// v10.0.0: Tries external template files first, falls back to embedded
+/// Rendered with template variables; a broken override is used verbatim
 pub fn project_template(
     project_name: &str,
     project_tagline: &str,
     project_type: ProjectType,
 ) -> String {
-    let template = get_template(project_type);
-
-    template
-        .replace("{PROJECT_NAME}", project_name)
-        .replace("{PROJECT_TAGLINE}", project_tagline)
+    let given = [
+        ("PROJECT_NAME".to_string(), project_name.to_string()),
+        ("PROJECT_TAGLINE".to_string(), project_tagline.to_string()),
+    ];
+    let template = render_template(base_template(project_type).name, &given).unwrap_or_else(|_| {
+        get_template(project_type)
+            .replace("{PROJECT_NAME}", project_name)
+            .replace("{PROJECT_TAGLINE}", project_tagline)
+    });
+
+    with_current_schema_version("project", &template)
 }
 
 /// Generate CLAUDE.md for RoyalBit Asimov (auto-loaded by Claude Code)
@@ -230,6 +134,44 @@ flutter test                  # Run tests
 dart analyze lib/             # Analyze (must pass)
 dart format lib/ test/        # Format code
 flutter build apk             # Build Android
+```"#
+        }
+        ProjectType::Java => {
+            r#"```bash
+./gradlew test                # Run tests (./mvnw test for Maven)
+./gradlew check               # Lint + tests (must pass)
+./gradlew spotlessApply       # Format code
+./gradlew build               # Build
+```"#
+        }
+        ProjectType::CSharp => {
+            r#"```bash
+dotnet test                   # Run tests
+dotnet format --verify-no-changes  # Format check (must pass)
+dotnet format                 # Format code
+dotnet build -c Release       # Release build
+```"#
+        }
+        ProjectType::Ruby => {
+            r#"```bash
+bundle exec rake              # Run tests
+bundle exec rubocop           # Lint (must pass)
+bundle exec rubocop -a        # Fix lint
+```"#
+        }
+        ProjectType::Php => {
+            r#"```bash
+vendor/bin/phpunit            # Run tests
+vendor/bin/phpcs              # Lint (must pass)
+vendor/bin/phpcbf             # Fix lint
+```"#
+        }
+        ProjectType::Cpp => {
+            r#"```bash
+cmake -B build && cmake --build build   # Build
+ctest --test-dir build        # Run tests
+clang-format -i <files>       # Format code
+clang-tidy <files>            # Lint
 ```"#
         }
         ProjectType::Docs => {
@@ -295,18 +237,7 @@ mod tests {
 
     #[test]
     fn test_project_template_all_types() {
-        let types = [
-            ProjectType::Rust,
-            ProjectType::Python,
-            ProjectType::Node,
-            ProjectType::Go,
-            ProjectType::Flutter,
-            ProjectType::Docs,
-            ProjectType::Generic,
-            ProjectType::Migration,
-            ProjectType::Arch,
-        ];
-        for pt in types {
+        for pt in ProjectType::ALL {
             let template = project_template("test", "tagline", pt);
             let yaml: Result<serde_yaml_ng::Value, _> = serde_yaml_ng::from_str(&template);
             assert!(yaml.is_ok(), "Template for {:?} should be valid YAML", pt);
@@ -321,18 +252,7 @@ mod tests {
 
     #[test]
     fn test_claude_md_template_all_types() {
-        let types = [
-            ProjectType::Rust,
-            ProjectType::Python,
-            ProjectType::Node,
-            ProjectType::Go,
-            ProjectType::Flutter,
-            ProjectType::Docs,
-            ProjectType::Generic,
-            ProjectType::Migration,
-            ProjectType::Arch,
-        ];
-        for pt in types {
+        for pt in ProjectType::ALL {
             let template = claude_md_template("test", pt);
             assert!(
                 !template.is_empty(),
@@ -344,7 +264,7 @@ mod tests {
 
     #[test]
     fn test_extended_templates_valid_yaml() {
-        for name in EXTENDED_TEMPLATES {
+        for name in TEMPLATES.iter().map(|t| t.name) {
             let template = get_template_by_name(name);
             assert!(template.is_some(), "Template {} should exist", name);
             let yaml: Result<serde_yaml_ng::Value, _> = serde_yaml_ng::from_str(&template.unwrap());
@@ -362,6 +282,9 @@ mod tests {
         assert!(templates.contains(&"api-rust".to_string()));
         assert!(templates.contains(&"web-nextjs".to_string()));
         assert!(templates.contains(&"admin-dashboard".to_string()));
+        // New base languages
+        assert!(templates.contains(&"java".to_string()));
+        assert!(templates.contains(&"cpp".to_string()));
     }
 
     #[test]
@@ -1,5 +1,7 @@
 //! Protocol template generators (asimov, green, sycophancy, sprint, roadmap)
 
+use crate::schemas::with_current_schema_version;
+
 /// Generate asimov.yaml template - AI safety and ethics configuration
 pub fn asimov_template() -> String {
     r#"# Asimov - AI Safety Configuration
@@ -402,9 +404,14 @@ authority:
     .to_string()
 }
 
+/// Default functional-equivalence protocol for migration projects
+pub fn migrations_template() -> String {
+    include_str!("migrations.yaml").to_string()
+}
+
 /// Generate a starter roadmap.yaml template (skeleton for self-healing)
 pub fn roadmap_template() -> String {
-    r#"# RoyalBit Asimov Roadmap
+    let template = r#"# RoyalBit Asimov Roadmap
 #
 # WHAT to build - milestones only
 #
@@ -430,8 +437,8 @@ next:
 backlog:
   - "Future idea one"
   - "Future idea two"
-"#
-    .to_string()
+"#;
+    with_current_schema_version("roadmap", template)
 }
 
 #[cfg(test)]
@@ -493,6 +500,16 @@ mod tests {
         assert!(result.is_ok(), "Roadmap template should be valid YAML");
     }
 
+    #[test]
+    fn test_migrations_template_is_valid() {
+        let result = crate::validator::validate_content(
+            std::path::Path::new("migrations.yaml"),
+            &migrations_template(),
+        )
+        .unwrap();
+        assert!(result.is_valid, "{:?}", result.errors);
+    }
+
     #[test]
     fn test_roadmap_template_has_sections() {
         let template = roadmap_template();
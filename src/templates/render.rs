@@ -0,0 +1,884 @@
+//! Template variables, conditionals, includes and inheritance
+//!
+//! Project templates are YAML with `{VARIABLE}` placeholders. Directives live
+//! in comment lines, so a template is still valid YAML before rendering:
+//!
+//! ```yaml
+//! # @extends rust
+//! # @var DATABASE = postgres | Database engine (postgres, mysql, sqlite)
+//! # @include observability
+//! # @if DATABASE == postgres
+//! database: "PostgreSQL"
+//! # @else
+//! database: "{DATABASE}"
+//! # @endif
+//! ```
+//!
+//! A template that `@extends` another is deep-merged over the rendered parent,
+//! line by line so the parent keeps its comments:
+//! mappings merge key by key, anything else replaces the parent's value. A
+//! template extending its own name (an override of `rust` extending `rust`)
+//! extends the embedded one. `@include` pastes another template's rendered
+//! body in place.
+
+use super::{find_template, get_template_by_name};
+use regex::Regex;
+use serde_yaml_ng::Value;
+
+/// Variables every template can use; `asimov init` always supplies them
+pub const BUILTIN_VARIABLES: &[&str] = &["PROJECT_NAME", "PROJECT_TAGLINE"];
+
+/// Deepest chain of `@extends` and `@include`
+const MAX_DEPTH: usize = 8;
+
+/// A `# @var NAME [= default] [| prompt]` declaration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateVar {
+    pub name: String,
+    /// Value used when none is given; `None` makes the variable required
+    pub default: Option<String>,
+    /// Question `asimov init` asks on a terminal
+    pub prompt: Option<String>,
+}
+
+/// Directives of one template file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TemplateHeader {
+    pub extends: Option<String>,
+    pub includes: Vec<String>,
+    pub vars: Vec<TemplateVar>,
+}
+
+/// A comment line holding a directive
+enum Directive<'a> {
+    Extends(&'a str),
+    Var(TemplateVar),
+    Include(&'a str),
+    If(&'a str),
+    Else,
+    EndIf,
+}
+
+fn builtin_vars() -> Vec<TemplateVar> {
+    vec![
+        TemplateVar {
+            name: "PROJECT_NAME".to_string(),
+            default: Some("example-project".to_string()),
+            prompt: Some("Project name".to_string()),
+        },
+        TemplateVar {
+            name: "PROJECT_TAGLINE".to_string(),
+            default: Some("Your project tagline".to_string()),
+            prompt: Some("Project tagline".to_string()),
+        },
+    ]
+}
+
+fn directive(line: &str) -> Result<Option<Directive<'_>>, String> {
+    let Some(rest) = line.trim_start().strip_prefix('#') else {
+        return Ok(None);
+    };
+    let Some(rest) = rest.trim_start().strip_prefix('@') else {
+        return Ok(None);
+    };
+    let (keyword, argument) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let argument = argument.trim();
+    let directive = match keyword {
+        "extends" => Directive::Extends(argument),
+        "var" => Directive::Var(parse_var(argument)?),
+        "include" => Directive::Include(argument),
+        "if" => Directive::If(argument),
+        "else" => Directive::Else,
+        "endif" => Directive::EndIf,
+        // `# @author` and the like are ordinary comments
+        _ => return Ok(None),
+    };
+    match directive {
+        Directive::Extends("") | Directive::Include("") | Directive::If("") => {
+            Err(format!("@{} needs an argument", keyword))
+        }
+        directive => Ok(Some(directive)),
+    }
+}
+
+fn parse_var(argument: &str) -> Result<TemplateVar, String> {
+    let (declaration, prompt) = match argument.split_once('|') {
+        Some((declaration, prompt)) => (declaration, Some(prompt.trim().to_string())),
+        None => (argument, None),
+    };
+    let (name, default) = match declaration.split_once('=') {
+        Some((name, default)) => (name.trim(), Some(unquote(default).to_string())),
+        None => (declaration.trim(), None),
+    };
+    if !is_variable_name(name) {
+        return Err(format!(
+            "invalid variable name '{}' (use UPPER_SNAKE_CASE)",
+            name
+        ));
+    }
+    Ok(TemplateVar {
+        name: name.to_string(),
+        default,
+        prompt: prompt.filter(|p| !p.is_empty()),
+    })
+}
+
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_uppercase())
+        && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+fn unquote(value: &str) -> &str {
+    let value = value.trim();
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+/// Directives declared in a template file (not following `@extends`)
+pub fn parse_template_header(content: &str) -> Result<TemplateHeader, String> {
+    let mut header = TemplateHeader::default();
+    for (index, line) in content.lines().enumerate() {
+        match directive(line).map_err(|e| format!("line {}: {}", index + 1, e))? {
+            Some(Directive::Extends(parent)) => header.extends = Some(parent.to_string()),
+            Some(Directive::Include(name)) => header.includes.push(name.to_string()),
+            Some(Directive::Var(var)) => header.vars.push(var),
+            _ => {}
+        }
+    }
+    Ok(header)
+}
+
+/// `KEY=VALUE` arguments (`asimov init --var`, `asimov template show --var`)
+pub fn parse_var_assignments(args: &[String]) -> Result<Vec<(String, String)>, String> {
+    args.iter()
+        .map(|arg| match arg.split_once('=') {
+            Some((key, value)) if is_variable_name(key.trim()) => {
+                Ok((key.trim().to_string(), value.to_string()))
+            }
+            _ => Err(format!(
+                "Expected KEY=VALUE with an UPPER_SNAKE_CASE key, got '{}'",
+                arg
+            )),
+        })
+        .collect()
+}
+
+/// A template to render: where it came from and its text
+struct Source {
+    /// Name shown in errors and used for cycle detection
+    key: String,
+    name: String,
+    content: String,
+}
+
+impl Source {
+    /// Look up `name` as referenced from the template `from`
+    fn load(name: &str, from: &Source) -> Result<Self, String> {
+        // An override extending its own name extends the embedded template
+        let (key, content) = if name == from.name {
+            let embedded = find_template(name).filter(|t| t.name == name);
+            (
+                format!("{} (embedded)", name),
+                embedded.map(|t| t.content.to_string()),
+            )
+        } else {
+            (name.to_string(), get_template_by_name(name))
+        };
+        let content =
+            content.ok_or_else(|| format!("{}: unknown template '{}'", from.key, name))?;
+        Ok(Self {
+            key,
+            name: name.to_string(),
+            content,
+        })
+    }
+}
+
+/// Every variable a template and its parents and includes declare
+///
+/// Built-ins come first; a nearer declaration wins over a parent's, so a
+/// template can give `PROJECT_TAGLINE` its own default.
+pub fn template_variables(name: &str, content: &str) -> Result<Vec<TemplateVar>, String> {
+    let source = Source {
+        key: name.to_string(),
+        name: name.to_string(),
+        content: content.to_string(),
+    };
+    let mut declared = Vec::new();
+    collect_vars(&source, &mut Vec::new(), &mut declared)?;
+
+    let mut vars: Vec<TemplateVar> = builtin_vars()
+        .into_iter()
+        .map(
+            |builtin| match declared.iter().position(|v| v.name == builtin.name) {
+                Some(index) => declared.remove(index),
+                None => builtin,
+            },
+        )
+        .collect();
+    vars.extend(declared);
+    Ok(vars)
+}
+
+fn collect_vars(
+    source: &Source,
+    chain: &mut Vec<String>,
+    vars: &mut Vec<TemplateVar>,
+) -> Result<(), String> {
+    enter(source, chain)?;
+    let header =
+        parse_template_header(&source.content).map_err(|e| format!("{}: {}", source.key, e))?;
+    for var in header.vars {
+        if !vars.iter().any(|v| v.name == var.name) {
+            vars.push(var);
+        }
+    }
+    for name in header.includes.iter().chain(&header.extends) {
+        collect_vars(&Source::load(name, source)?, chain, vars)?;
+    }
+    chain.pop();
+    Ok(())
+}
+
+fn enter(source: &Source, chain: &mut Vec<String>) -> Result<(), String> {
+    if chain.contains(&source.key) {
+        chain.push(source.key.clone());
+        return Err(format!("Template cycle: {}", chain.join(" -> ")));
+    }
+    if chain.len() >= MAX_DEPTH {
+        return Err(format!(
+            "{}: more than {} levels of @extends/@include",
+            source.key, MAX_DEPTH
+        ));
+    }
+    chain.push(source.key.clone());
+    Ok(())
+}
+
+/// Values for every declared variable: given ones, then defaults
+///
+/// Fails on a required variable without a value and on a given variable no
+/// template declares (usually a typo).
+fn resolve_values(
+    vars: &[TemplateVar],
+    given: &[(String, String)],
+) -> Result<Vec<(String, String)>, String> {
+    if let Some((unknown, _)) = given
+        .iter()
+        .find(|(key, _)| !vars.iter().any(|v| &v.name == key))
+    {
+        let declared: Vec<&str> = vars.iter().map(|v| v.name.as_str()).collect();
+        return Err(format!(
+            "Unknown template variable {} (declared: {})",
+            unknown,
+            declared.join(", ")
+        ));
+    }
+    vars.iter()
+        .map(|var| {
+            given
+                .iter()
+                .rev()
+                .find(|(key, _)| key == &var.name)
+                .map(|(_, value)| value.clone())
+                .or_else(|| var.default.clone())
+                .map(|value| (var.name.clone(), value))
+                .ok_or_else(|| format!("Missing value for template variable {}", var.name))
+        })
+        .collect()
+}
+
+/// Render a template by name (`.asimov/templates/` first, then embedded)
+pub fn render_template(name: &str, given: &[(String, String)]) -> Result<String, String> {
+    let content =
+        get_template_by_name(name).ok_or_else(|| format!("Unknown template: '{}'", name))?;
+    render_template_content(name, &content, given)
+}
+
+/// Render template text; `name` resolves an `@extends` of its own name
+pub fn render_template_content(
+    name: &str,
+    content: &str,
+    given: &[(String, String)],
+) -> Result<String, String> {
+    let vars = template_variables(name, content)?;
+    let values = resolve_values(&vars, given)?;
+    let source = Source {
+        key: name.to_string(),
+        name: name.to_string(),
+        content: content.to_string(),
+    };
+    render_source(&source, &values, &mut Vec::new())
+}
+
+/// Render for review (`asimov template show|diff`, `asimov validate`)
+///
+/// Like `render_template_content`, but required variables that were not
+/// given render as `example` instead of failing.
+pub fn render_template_preview(
+    name: &str,
+    content: &str,
+    given: &[(String, String)],
+) -> Result<String, String> {
+    let mut given = given.to_vec();
+    for var in template_variables(name, content)? {
+        if var.default.is_none() && !given.iter().any(|(key, _)| key == &var.name) {
+            given.push((var.name, "example".to_string()));
+        }
+    }
+    render_template_content(name, content, &given)
+}
+
+fn render_source(
+    source: &Source,
+    values: &[(String, String)],
+    chain: &mut Vec<String>,
+) -> Result<String, String> {
+    enter(source, chain)?;
+    let placeholder =
+        Regex::new(r"(\$?)\{([A-Z][A-Z0-9_]*)\}").expect("Placeholder regex is valid");
+    let mut extends = None;
+    let mut body = String::new();
+    // One entry per open @if: (condition held, inside @else)
+    let mut conditions: Vec<(bool, bool)> = Vec::new();
+
+    for (index, line) in source.content.lines().enumerate() {
+        let at = |e: String| format!("{}: line {}: {}", source.key, index + 1, e);
+        let active = conditions.iter().all(|&(held, in_else)| held != in_else);
+        match directive(line).map_err(at)? {
+            Some(Directive::Extends(parent)) => extends = Some(parent),
+            Some(Directive::Var(_)) => {}
+            Some(Directive::Include(name)) if active => {
+                let included = Source::load(name, source)?;
+                body.push_str(&render_source(&included, values, chain)?);
+            }
+            Some(Directive::Include(_)) => {}
+            Some(Directive::If(condition)) => {
+                conditions.push((evaluate(condition, values).map_err(at)?, false));
+            }
+            Some(Directive::Else) => match conditions.last_mut() {
+                Some((_, in_else)) if !*in_else => *in_else = true,
+                _ => return Err(at("@else without @if".to_string())),
+            },
+            Some(Directive::EndIf) => {
+                if conditions.pop().is_none() {
+                    return Err(at("@endif without @if".to_string()));
+                }
+            }
+            None if active => {
+                body.push_str(&substitute(line, &placeholder, values).map_err(at)?);
+                body.push('\n');
+            }
+            None => {}
+        }
+    }
+    if !conditions.is_empty() {
+        return Err(format!("{}: @if without @endif", source.key));
+    }
+
+    let rendered = match extends {
+        Some(parent) => {
+            let parent = render_source(&Source::load(parent, source)?, values, chain)?;
+            merge_over(&parent, &body).map_err(|e| format!("{}: {}", source.key, e))?
+        }
+        None => body,
+    };
+    chain.pop();
+    Ok(rendered)
+}
+
+/// Replace `{NAME}` placeholders; `${NAME}` shell expansions are left alone
+fn substitute(
+    line: &str,
+    placeholder: &Regex,
+    values: &[(String, String)],
+) -> Result<String, String> {
+    let mut unknown = None;
+    let replaced = placeholder.replace_all(line, |caps: &regex::Captures| {
+        if !caps[1].is_empty() {
+            return caps[0].to_string();
+        }
+        match values.iter().find(|(key, _)| key == &caps[2]) {
+            Some((_, value)) => value.clone(),
+            None => {
+                unknown.get_or_insert_with(|| caps[2].to_string());
+                caps[0].to_string()
+            }
+        }
+    });
+    match unknown {
+        Some(name) => Err(format!(
+            "unknown variable {{{}}} (declare it with # @var {})",
+            name, name
+        )),
+        None => Ok(replaced.into_owned()),
+    }
+}
+
+/// `NAME`, `!NAME`, `NAME == value` or `NAME != value`
+///
+/// A bare variable holds unless it is empty, `false`, `no` or `0`.
+fn evaluate(condition: &str, values: &[(String, String)]) -> Result<bool, String> {
+    let lookup = |name: &str| {
+        values
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+            .ok_or_else(|| format!("@if uses undeclared variable {}", name))
+    };
+    if let Some((name, expected)) = condition.split_once("!=") {
+        return Ok(lookup(name.trim())? != unquote(expected));
+    }
+    if let Some((name, expected)) = condition.split_once("==") {
+        return Ok(lookup(name.trim())? == unquote(expected));
+    }
+    let (negated, name) = match condition.strip_prefix('!') {
+        Some(name) => (true, name.trim()),
+        None => (false, condition),
+    };
+    let value = lookup(name)?;
+    let truthy = !matches!(value.trim(), "" | "false" | "no" | "0");
+    Ok(truthy != negated)
+}
+
+/// Deep-merge a rendered child over its rendered parent
+///
+/// The merge works on lines so the parent keeps its comments and quoting:
+/// child keys replace or extend the parent's entries in place and new keys
+/// are appended. The child's first comment paragraph replaces the parent's. YAML the line
+/// merge cannot follow (flow mappings, top-level lists, anchors that change
+/// the result) falls back to re-serializing the merged document.
+fn merge_over(parent: &str, child: &str) -> Result<String, String> {
+    let mut merged: Value = serde_yaml_ng::from_str(parent)
+        .map_err(|e| format!("parent is not valid YAML after rendering: {}", e))?;
+    let overlay: Value = serde_yaml_ng::from_str(child)
+        .map_err(|e| format!("not valid YAML after rendering: {}", e))?;
+    merge_value(&mut merged, overlay);
+
+    if let Some(lines) = merge_lines(&owned_lines(parent), &owned_lines(child)) {
+        let text = format!("{}\n", lines.join("\n"));
+        if serde_yaml_ng::from_str::<Value>(&text).ok().as_ref() == Some(&merged) {
+            return Ok(text);
+        }
+    }
+
+    let header: String = child
+        .lines()
+        .skip_while(|line| line.trim().is_empty())
+        .take_while(|line| line.trim_start().starts_with('#'))
+        .map(|line| format!("{}\n", line))
+        .collect();
+    let yaml = serde_yaml_ng::to_string(&merged).map_err(|e| e.to_string())?;
+    if header.is_empty() {
+        Ok(yaml)
+    } else {
+        Ok(format!("{}\n{}", header, yaml))
+    }
+}
+
+fn owned_lines(text: &str) -> Vec<String> {
+    text.lines().map(str::to_string).collect()
+}
+
+/// A block mapping split into its `key:` entries
+struct Block {
+    indent: usize,
+    /// Comment and blank lines before the first key
+    preamble: Vec<String>,
+    entries: Vec<Entry>,
+    /// Comment and blank lines after the last entry
+    trailer: Vec<String>,
+}
+
+struct Entry {
+    key: String,
+    /// Comment and blank lines above the key
+    leading: Vec<String>,
+    line: String,
+    /// Lines indented deeper than the key
+    body: Vec<String>,
+}
+
+impl Entry {
+    /// Text after the colon, without a trailing comment
+    fn inline(&self) -> &str {
+        let (_, value) = mapping_key(self.line.trim_start()).unwrap_or_default();
+        if value.starts_with('#') {
+            ""
+        } else {
+            value
+        }
+    }
+
+    fn is_null(&self) -> bool {
+        matches!(self.inline(), "" | "~" | "null") && self.body.iter().all(|l| is_comment(l))
+    }
+
+    fn is_mapping(&self) -> bool {
+        self.inline().is_empty()
+            && parse_block(&self.body).is_some_and(|block| !block.entries.is_empty())
+    }
+
+    fn shift(&mut self, from: usize, to: usize) {
+        for line in self
+            .leading
+            .iter_mut()
+            .chain(std::iter::once(&mut self.line))
+            .chain(self.body.iter_mut())
+        {
+            let spaces = line.len() - line.trim_start_matches(' ').len();
+            *line = if to >= from {
+                format!("{}{}", " ".repeat(to - from), line)
+            } else {
+                line[spaces.min(from - to)..].to_string()
+            };
+        }
+    }
+}
+
+fn is_comment(line: &str) -> bool {
+    let line = line.trim_start();
+    line.is_empty() || line.starts_with('#')
+}
+
+/// Split `key: value` into the unquoted key and the trimmed value
+fn mapping_key(line: &str) -> Option<(&str, &str)> {
+    if line.starts_with('-') || line.starts_with('?') || line.starts_with(['{', '[', '&', '*', '!'])
+    {
+        return None;
+    }
+    let (key, rest) = match line.chars().next()? {
+        quote @ ('"' | '\'') => {
+            let end = line[1..].find(quote)? + 1;
+            (&line[1..end], &line[end + 1..])
+        }
+        _ => {
+            let colon = line.match_indices(':').map(|(i, _)| i).find(|&i| {
+                line[i + 1..].is_empty() || line[i + 1..].starts_with(char::is_whitespace)
+            })?;
+            (line[..colon].trim_end(), &line[colon..])
+        }
+    };
+    Some((key, rest.strip_prefix(':')?.trim()))
+}
+
+/// `None` when the lines are not a plain block mapping
+fn parse_block(lines: &[String]) -> Option<Block> {
+    let indent = lines
+        .iter()
+        .find(|line| !is_comment(line))
+        .map_or(0, |line| line.len() - line.trim_start().len());
+    let mut block = Block {
+        indent,
+        preamble: Vec::new(),
+        entries: Vec::new(),
+        trailer: Vec::new(),
+    };
+    let mut pending = Vec::new();
+    for line in lines {
+        if is_comment(line) {
+            pending.push(line.clone());
+            continue;
+        }
+        let content = line.trim_start();
+        let depth = line.len() - content.len();
+        if depth > indent {
+            let entry = block.entries.last_mut()?;
+            entry.body.append(&mut pending);
+            entry.body.push(line.clone());
+            continue;
+        }
+        if depth < indent || content.starts_with('\t') {
+            return None;
+        }
+        let (key, _) = mapping_key(content)?;
+        let leading = std::mem::take(&mut pending);
+        if block.entries.is_empty() {
+            block.preamble = leading;
+            block.entries.push(Entry {
+                key: key.to_string(),
+                leading: Vec::new(),
+                line: line.clone(),
+                body: Vec::new(),
+            });
+        } else {
+            block.entries.push(Entry {
+                key: key.to_string(),
+                leading,
+                line: line.clone(),
+                body: Vec::new(),
+            });
+        }
+    }
+    block.trailer = pending;
+    Some(block)
+}
+
+fn merge_lines(parent: &[String], child: &[String]) -> Option<Vec<String>> {
+    let mut base = parse_block(parent)?;
+    let overlay = parse_block(child)?;
+    if base.entries.is_empty() {
+        return None;
+    }
+    let header: Vec<String> = overlay
+        .preamble
+        .iter()
+        .skip_while(|line| line.trim().is_empty())
+        .take_while(|line| !line.trim().is_empty())
+        .cloned()
+        .collect();
+    if !header.is_empty() {
+        base.preamble = header;
+        base.preamble.push(String::new());
+    }
+    for mut entry in overlay.entries {
+        entry.shift(overlay.indent, base.indent);
+        let Some(existing) = base.entries.iter_mut().find(|e| e.key == entry.key) else {
+            if base.indent == 0 && entry.leading.first().is_none_or(|l| !l.trim().is_empty()) {
+                entry.leading.insert(0, String::new());
+            }
+            base.entries.push(entry);
+            continue;
+        };
+        if entry.is_null() {
+            continue;
+        }
+        if existing.is_mapping() && entry.is_mapping() {
+            existing.body = merge_lines(&existing.body, &entry.body)?;
+        } else {
+            existing.line = entry.line;
+            existing.body = entry.body;
+        }
+        if entry.leading.iter().any(|line| !line.trim().is_empty()) {
+            existing.leading = entry.leading;
+        }
+    }
+    if overlay.trailer.iter().any(|line| !line.trim().is_empty()) {
+        base.trailer.extend(overlay.trailer);
+    }
+
+    let mut lines = base.preamble;
+    for entry in base.entries {
+        lines.extend(entry.leading);
+        lines.push(entry.line);
+        lines.extend(entry.body);
+    }
+    lines.extend(base.trailer);
+    Some(lines)
+}
+
+fn merge_value(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Mapping(base), Value::Mapping(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_value(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (_, Value::Null) => {}
+        (base, overlay) => *base = overlay,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_template_header() {
+        let header = parse_template_header(
+            "# @extends rust\n# @var PORT = 8080 | HTTP port\n# @var OWNER\n# @include ci\nname: x\n",
+        )
+        .unwrap();
+        assert_eq!(header.extends.as_deref(), Some("rust"));
+        assert_eq!(header.includes, vec!["ci"]);
+        assert_eq!(
+            header.vars,
+            vec![
+                TemplateVar {
+                    name: "PORT".to_string(),
+                    default: Some("8080".to_string()),
+                    prompt: Some("HTTP port".to_string()),
+                },
+                TemplateVar {
+                    name: "OWNER".to_string(),
+                    default: None,
+                    prompt: None,
+                },
+            ]
+        );
+
+        assert!(parse_template_header("# @var lower = x\n").is_err());
+        assert!(parse_template_header("# @extends\n").is_err());
+        // Plain comments, unknown @words and e-mail-like text are not directives
+        assert_eq!(
+            parse_template_header("# @author Jane\n# contact: dev@example.com\n").unwrap(),
+            TemplateHeader::default()
+        );
+        let rendered = render_template_content("t", "# @author Jane\na: 1\n", &[]).unwrap();
+        assert_eq!(rendered, "# @author Jane\na: 1\n");
+    }
+
+    #[test]
+    fn test_render_variables_and_conditionals() {
+        let template = "\
+# @var DATABASE = postgres | Database engine
+# @var CACHE = false
+identity:
+  name: \"{PROJECT_NAME}\"
+# @if DATABASE == postgres
+database: PostgreSQL
+# @else
+database: \"{DATABASE}\"
+# @endif
+# @if CACHE
+cache: redis
+# @endif
+hook: \"${HOME}/bin\"
+";
+        let rendered = render_template_content("custom", template, &[]).unwrap();
+        assert!(rendered.contains("name: \"example-project\""));
+        assert!(rendered.contains("database: PostgreSQL"));
+        assert!(!rendered.contains("cache"));
+        assert!(rendered.contains("${HOME}"));
+        assert!(!rendered.contains("@var"));
+
+        let given = vars(&[
+            ("DATABASE", "sqlite"),
+            ("CACHE", "yes"),
+            ("PROJECT_NAME", "app"),
+        ]);
+        let rendered = render_template_content("custom", template, &given).unwrap();
+        assert!(rendered.contains("database: \"sqlite\""));
+        assert!(rendered.contains("cache: redis"));
+        assert!(rendered.contains("name: \"app\""));
+    }
+
+    #[test]
+    fn test_render_errors() {
+        let err = render_template_content("t", "name: {UNDECLARED}\n", &[]).unwrap_err();
+        assert!(
+            err.contains("line 1") && err.contains("{UNDECLARED}"),
+            "{err}"
+        );
+
+        let err = render_template_content("t", "# @var OWNER\nowner: {OWNER}\n", &[]).unwrap_err();
+        assert!(err.contains("Missing value for template variable OWNER"));
+
+        let err = render_template_content("t", "a: 1\n", &vars(&[("TYPO", "x")])).unwrap_err();
+        assert!(err.contains("Unknown template variable TYPO"));
+
+        let err = render_template_content("t", "# @if PROJECT_NAME\na: 1\n", &[]).unwrap_err();
+        assert!(err.contains("@if without @endif"));
+    }
+
+    #[test]
+    fn test_extends_merges_over_embedded_parent() {
+        let template = "\
+# Custom Rust service
+# @extends rust
+identity:
+  kind: backend-api
+quality:
+  lint: cargo clippy --all-targets -- -D warnings
+";
+        let rendered = render_template_content("service", template, &[]).unwrap();
+        assert!(rendered.starts_with("# Custom Rust service\n"));
+        let yaml: Value = serde_yaml_ng::from_str(&rendered).unwrap();
+        assert_eq!(yaml["identity"]["type"], "rust");
+        assert_eq!(yaml["identity"]["kind"], "backend-api");
+        assert_eq!(yaml["identity"]["name"], "example-project");
+        assert_eq!(yaml["quality"]["test"], "cargo test");
+        assert_eq!(
+            yaml["quality"]["lint"],
+            "cargo clippy --all-targets -- -D warnings"
+        );
+    }
+
+    #[test]
+    fn test_extends_own_name_uses_embedded_and_cycles_fail() {
+        let rendered =
+            render_template_content("rust", "# @extends rust\nextra: true\n", &[]).unwrap();
+        let yaml: Value = serde_yaml_ng::from_str(&rendered).unwrap();
+        assert_eq!(yaml["identity"]["type"], "rust");
+        assert_eq!(yaml["extra"], true);
+
+        let err = render_template_content("x", "# @extends nonexistent\n", &[]).unwrap_err();
+        assert!(err.contains("unknown template 'nonexistent'"));
+    }
+
+    #[test]
+    fn test_embedded_api_rust_extends_rust() {
+        let header = parse_template_header(find_template("api-rust").unwrap().content).unwrap();
+        assert_eq!(header.extends.as_deref(), Some("rust"));
+
+        let rendered = render_template("api-rust", &vars(&[("PROJECT_NAME", "svc")])).unwrap();
+        let yaml: Value = serde_yaml_ng::from_str(&rendered).unwrap();
+        assert_eq!(yaml["identity"]["name"], "svc");
+        assert_eq!(yaml["identity"]["kind"], "backend-api");
+        // Inherited from rust
+        assert_eq!(yaml["quality"]["format"], "cargo fmt --check");
+        // The parent's text survives the merge: comments, quoting, block scalars
+        assert!(rendered.starts_with("# Enterprise Template: api-rust\n"));
+        assert!(rendered.contains("# v9.3.0: Coding standards for Rust projects (ADR-041)\n"));
+        assert!(rendered.contains("  test: \"cargo test\"\n"));
+        assert!(rendered.contains("  profile: |\n    [profile.release]\n"));
+        assert!(!rendered.contains("Generated by: asimov init --type rust"));
+    }
+
+    #[test]
+    fn test_merge_over_keeps_parent_lines() {
+        let parent = "\
+# Parent
+a:
+  # about b
+  b: \"1\"
+  c: 2 # inline
+list:
+  - x
+";
+        let child = "\
+a:
+    c: 3
+    d: 4
+list:
+  - y
+e: null
+";
+        let merged = merge_over(parent, child).unwrap();
+        assert_eq!(
+            merged,
+            "# Parent\na:\n  # about b\n  b: \"1\"\n  c: 3\n  d: 4\nlist:\n  - y\n\ne: null\n"
+        );
+
+        // Flow mappings are merged structurally
+        let merged = merge_over("a: {b: 1}\n", "a:\n  c: 2\n").unwrap();
+        let yaml: Value = serde_yaml_ng::from_str(&merged).unwrap();
+        assert_eq!(yaml["a"]["b"], 1);
+        assert_eq!(yaml["a"]["c"], 2);
+    }
+
+    #[test]
+    fn test_parse_var_assignments() {
+        assert_eq!(
+            parse_var_assignments(&["PORT=80".to_string(), "URL=a=b".to_string()]).unwrap(),
+            vars(&[("PORT", "80"), ("URL", "a=b")])
+        );
+        assert!(parse_var_assignments(&["port=80".to_string()]).is_err());
+        assert!(parse_var_assignments(&["PORT".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,401 @@
+//! Validation of .asimov/ customizations
+//!
+//! Roles, project templates, protocol overrides and hook templates all have
+//! embedded fallbacks, so a broken file used to be skipped without a word.
+//! Each file now gets a `ValidationResult` like roadmap.yaml and project.yaml.
+
+use super::{validate_against_schema, ValidationResult};
+use crate::commands::read_role;
+use crate::context::AsimovContext;
+use crate::error::Error;
+use crate::protocols::{
+    check_custom_definition, check_protocol_override, embedded_protocol, merge_layers, parse_layer,
+    protocol_layers, protocol_schema_errors, user_protocol_dirs, LayerSource, ProtocolLayer,
+};
+use crate::schemas::PROJECT_SCHEMA;
+use crate::templates::render_template_preview;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+/// Placeholders substituted in hook templates
+pub const HOOK_PLACEHOLDERS: &[&str] = &[
+    "PROJECT_TYPE",
+    "QUALITY_CHECKS",
+    "DEPENDENCY_HEALTH_CHECK",
+    "FILE_SIZE_CHECK",
+];
+
+/// Validate everything under .asimov/roles, templates, protocols and hooks
+pub fn validate_customizations(asimov_dir: &Path) -> Vec<ValidationResult> {
+    let mut results = Vec::new();
+
+    for path in files_in(&asimov_dir.join("roles"), Some("json")) {
+        results.push(validate_role(&path));
+    }
+    for path in files_in(&asimov_dir.join("templates"), Some("yaml")) {
+        results.push(validate_template(&path));
+    }
+    for path in files_in(&asimov_dir.join("protocols"), Some("json")) {
+        results.push(validate_protocol_override(&path));
+    }
+    for path in files_in(&asimov_dir.join("hooks"), None) {
+        results.push(validate_hook_template(&path));
+    }
+
+    results
+}
+
+/// Role files must match the role schema and their file name
+pub fn validate_role(path: &Path) -> ValidationResult {
+    let file = path.display().to_string();
+    match read_role(path) {
+        Ok(_) => ValidationResult::success(file, "role".to_string()),
+        Err(e) => ValidationResult::failure(file, "role".to_string(), vec![e]),
+    }
+}
+
+/// Project templates must only use declared variables and render to valid YAML
+///
+/// Schema violations in the rendered project.yaml are errors now that
+/// every embedded template declares a base language as `identity.type`.
+/// Templates render with their defaults, see `render_template_preview`.
+pub fn validate_template(path: &Path) -> ValidationResult {
+    let file = path.display().to_string();
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            return ValidationResult::failure(file, "template".to_string(), vec![e.to_string()])
+        }
+    };
+
+    let name = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    match render_template_preview(name, &content, &[]) {
+        Ok(rendered) => validate_rendered_template(path, &rendered),
+        Err(e) => ValidationResult::failure(file, "template".to_string(), vec![e]),
+    }
+}
+
+/// A rendered template must be a project.yaml that matches `PROJECT_SCHEMA`
+pub fn validate_rendered_template(path: &Path, rendered: &str) -> ValidationResult {
+    let file = path.display().to_string();
+    match validate_against_schema(path, rendered, PROJECT_SCHEMA, "template") {
+        Ok(rendered_result) if !rendered_result.errors.is_empty() => {
+            ValidationResult::failure(file, "template".to_string(), rendered_result.errors)
+                .with_warnings(rendered_result.warnings)
+        }
+        Ok(rendered_result) => ValidationResult::success(file, "template".to_string())
+            .with_warnings(rendered_result.warnings),
+        Err(Error::YamlError(e)) => ValidationResult::failure(
+            file,
+            "template".to_string(),
+            vec![format!("Rendered template is not valid YAML: {}", e)],
+        ),
+        Err(e) => ValidationResult::failure(file, "template".to_string(), vec![e.to_string()]),
+    }
+}
+
+/// Protocol overrides must parse as the protocol they replace
+///
+/// Overrides are layers and may be partial: the file must be a JSON
+/// object, and merged over the embedded and user/org layers it must parse.
+/// Files not named after a built-in must be custom protocol definitions.
+pub fn validate_protocol_override(path: &Path) -> ValidationResult {
+    let file = path.display().to_string();
+    let name = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            return ValidationResult::failure(file, "protocol".to_string(), vec![e.to_string()])
+        }
+    };
+
+    // Any other name defines a custom protocol
+    let Some(embedded) = embedded_protocol(name) else {
+        return match check_custom_definition(name, path) {
+            Ok(_) => ValidationResult::success(file, "protocol".to_string()),
+            Err(e) => ValidationResult::failure(
+                file,
+                "protocol".to_string(),
+                vec![format!(
+                    "Not a valid custom protocol definition, it is ignored: {}",
+                    e
+                )],
+            ),
+        };
+    };
+    let value = match parse_layer(&content) {
+        Ok(value) => value,
+        Err(e) => {
+            return ValidationResult::failure(
+                file,
+                "protocol".to_string(),
+                vec![format!("Not a protocol layer, it is ignored: {}", e)],
+            )
+        }
+    };
+    let mut layers = protocol_layers(name, embedded, None);
+    layers.push(ProtocolLayer {
+        source: LayerSource::Project,
+        path: Some(path.to_path_buf()),
+        value: Ok(value),
+    });
+
+    let merged = merge_layers(&layers).to_json();
+    match check_protocol_override(name, &merged) {
+        Some(Err(e)) => ValidationResult::failure(
+            file,
+            "protocol".to_string(),
+            vec![format!(
+                "Not a valid {} protocol, the embedded default is used instead: {}",
+                name, e
+            )],
+        ),
+        // Loaders ignore unknown keys, so they are only warnings
+        _ => protocol_schema_errors(name, &merged).into_iter().fold(
+            ValidationResult::success(file, "protocol".to_string()),
+            |result, e| result.with_warning(format!("Does not match the {} schema: {}", name, e)),
+        ),
+    }
+}
+
+/// Every protocol layer file `dir` loads: user/org directories, then the
+/// `.asimov/protocols/` files of its context and the contexts it inherits from
+pub fn validate_protocol_overrides(dir: &Path) -> Vec<ValidationResult> {
+    let mut paths: Vec<PathBuf> = user_protocol_dirs()
+        .iter()
+        .flat_map(|dir| files_in(dir, Some("json")))
+        .collect();
+    if let Some(context) = AsimovContext::discover(dir) {
+        paths.extend(context.inherited_files("protocols", "json"));
+    }
+    paths
+        .iter()
+        .map(|path| validate_protocol_override(path))
+        .collect()
+}
+
+/// Why `--strict` refuses to load protocols: every error and warning of the
+/// protocol layer files, as `file: message`
+pub fn strict_protocol_errors(dir: &Path) -> Vec<String> {
+    validate_protocol_overrides(dir)
+        .into_iter()
+        .flat_map(|result| {
+            let file = result.file.clone();
+            result
+                .errors
+                .into_iter()
+                .chain(result.warnings)
+                .map(move |message| format!("{}: {}", file, message))
+        })
+        .collect()
+}
+
+/// Hook templates must be scripts and only use known placeholders
+pub fn validate_hook_template(path: &Path) -> ValidationResult {
+    let file = path.display().to_string();
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => return ValidationResult::failure(file, "hook".to_string(), vec![e.to_string()]),
+    };
+
+    let mut errors = unknown_placeholders(&content, HOOK_PLACEHOLDERS);
+    if !content.starts_with("#!") {
+        errors.insert(0, "Missing shebang line (e.g. #!/bin/bash)".to_string());
+    }
+
+    if errors.is_empty() {
+        ValidationResult::success(file, "hook".to_string())
+    } else {
+        ValidationResult::failure(file, "hook".to_string(), errors)
+    }
+}
+
+/// `{NAME}` placeholders that are not in `known`, one error per occurrence
+///
+/// Shell expansions like `${NAME}` are not placeholders.
+fn unknown_placeholders(content: &str, known: &[&str]) -> Vec<String> {
+    let pattern = Regex::new(r"(\$?)\{([A-Z][A-Z0-9_]*)\}").expect("Placeholder regex is valid");
+    let supported: Vec<String> = known.iter().map(|k| format!("{{{}}}", k)).collect();
+
+    let mut errors = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        for caps in pattern.captures_iter(line) {
+            if !caps[1].is_empty() || known.contains(&&caps[2]) {
+                continue;
+            }
+            errors.push(format!(
+                "line {}: unknown placeholder {{{}}} (supported: {})",
+                index + 1,
+                &caps[2],
+                supported.join(", ")
+            ));
+        }
+    }
+    errors
+}
+
+/// Files in a directory, optionally filtered by extension, sorted by name
+fn files_in(dir: &Path, extension: Option<&str>) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .filter(|p| extension.is_none_or(|ext| p.extension().is_some_and(|e| e == ext)))
+        .collect();
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, relative: &str, content: &str) -> PathBuf {
+        let path = dir.join(relative);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_malformed_role_is_an_error() {
+        let temp = TempDir::new().unwrap();
+        let path = write(temp.path(), "roles/eng.json", "{ not json");
+        let result = validate_role(&path);
+        assert!(!result.is_valid);
+        assert!(result.errors[0].contains("Invalid JSON"));
+    }
+
+    #[test]
+    fn test_template_unknown_placeholder() {
+        let temp = TempDir::new().unwrap();
+        let path = write(
+            temp.path(),
+            "templates/rust.yaml",
+            "identity:\n  name: {PROJECT_NAME}\n  type: rust\n  tagline: {PROJECT_TAGLNE}\n",
+        );
+        let result = validate_template(&path);
+        assert!(!result.is_valid);
+        assert!(
+            result.errors[0].contains("line 4: unknown variable {PROJECT_TAGLNE}"),
+            "{:?}",
+            result.errors
+        );
+    }
+
+    #[test]
+    fn test_template_renders_and_checks_schema() {
+        let temp = TempDir::new().unwrap();
+        let good = write(
+            temp.path(),
+            "templates/rust.yaml",
+            "identity:\n  name: \"{PROJECT_NAME}\"\n  type: rust\n",
+        );
+        let result = validate_template(&good);
+        assert!(result.is_valid);
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+
+        let odd_type = write(
+            temp.path(),
+            "templates/odd.yaml",
+            "identity:\n  name: \"{PROJECT_NAME}\"\n  type: cobol\n",
+        );
+        let result = validate_template(&odd_type);
+        assert!(!result.is_valid);
+        assert!(result.errors[0].contains("identity.type must be one of"));
+
+        let broken = write(temp.path(), "templates/broken.yaml", "identity: [\n");
+        assert!(!validate_template(&broken).is_valid);
+    }
+
+    #[test]
+    fn test_protocol_override() {
+        let temp = TempDir::new().unwrap();
+        let good = write(temp.path(), "protocols/green.json", r#"{"rule": "x"}"#);
+        assert!(validate_protocol_override(&good).is_valid);
+
+        // Layers may be partial, but the merged protocol must still parse
+        let partial = write(temp.path(), "protocols/sprint.json", r#"{"rule": "x"}"#);
+        assert!(validate_protocol_override(&partial).is_valid);
+        let bad = write(
+            temp.path(),
+            "protocols/sprint.json",
+            r#"{"compaction_reminder": null}"#,
+        );
+        let result = validate_protocol_override(&bad);
+        assert!(!result.is_valid);
+        assert!(result.errors[0].contains("compaction_reminder"));
+
+        // Keys the loader ignores are reported against the protocol schema
+        let typo = write(temp.path(), "protocols/green.json", r#"{"rulee": "x"}"#);
+        let result = validate_protocol_override(&typo);
+        assert!(result.is_valid);
+        assert!(
+            result.warnings[0].contains("green schema"),
+            "{:?}",
+            result.warnings
+        );
+        assert!(result.warnings[0].contains("rulee"));
+
+        let not_object = write(temp.path(), "protocols/green.json", "[]");
+        let result = validate_protocol_override(&not_object);
+        assert!(result.errors[0].contains("not a JSON object"));
+
+        // Other names are custom protocol definitions
+        let custom = write(
+            temp.path(),
+            "protocols/custom.json",
+            r#"{"name": "custom", "priority": 1, "protocol": {"rule": "x"}}"#,
+        );
+        assert!(validate_protocol_override(&custom).is_valid);
+        let incomplete = write(temp.path(), "protocols/custom.json", "{}");
+        let result = validate_protocol_override(&incomplete);
+        assert!(!result.is_valid);
+        assert!(result.errors[0].contains("custom protocol definition"));
+    }
+
+    #[test]
+    fn test_hook_template() {
+        let temp = TempDir::new().unwrap();
+        let good = write(
+            temp.path(),
+            "hooks/pre-commit.sh.tpl",
+            "#!/bin/bash\n# {QUALITY_CHECKS}\necho ${HOME}\n",
+        );
+        assert!(validate_hook_template(&good).is_valid);
+
+        let bad = write(temp.path(), "hooks/post.sh.tpl", "echo {CHECKS}\n");
+        let result = validate_hook_template(&bad);
+        assert_eq!(result.errors.len(), 2);
+        assert!(result.errors[0].contains("shebang"));
+    }
+
+    #[test]
+    fn test_validate_customizations_collects_all_kinds() {
+        let temp = TempDir::new().unwrap();
+        write(temp.path(), "roles/eng.json", "{}");
+        write(
+            temp.path(),
+            "templates/rust.yaml",
+            "identity:\n  name: x\n  type: rust\n",
+        );
+        write(temp.path(), "protocols/green.json", r#"{"rule": "x"}"#);
+        write(temp.path(), "hooks/pre-commit.sh.tpl", "#!/bin/sh\n");
+        write(temp.path(), "templates/notes.txt", "ignored");
+
+        let results = validate_customizations(temp.path());
+        let kinds: Vec<&str> = results.iter().map(|r| r.schema_type.as_str()).collect();
+        assert_eq!(kinds, vec!["role", "template", "protocol", "hook"]);
+        assert!(!results[0].is_valid);
+    }
+}
@@ -0,0 +1,558 @@
+//! Protocol file integrity and merge-aware refresh
+//!
+//! v9.0.0: Protocol JSON files are compared with the compiled defaults.
+//! Every default written to `.asimov/<file>.json` is also recorded in
+//! `.asimov/.base/<file>.json`. That base tells local edits apart from an
+//! older default: `refresh` three-way merges the new default, the base and
+//! the file on disk, and writes conflicts to `.asimov/<file>.json.conflict`
+//! for review instead of overwriting team customizations.
+//! The hashes of the protocol files asimov writes are recorded in
+//! `.asimov/.manifest.json`, so edits made outside asimov can be told apart
+//! from the signed bundle and from locally generated content.
+//! Date-bearing fields are compared and hashed as their template
+//! (see `protocols::dates`), so rendered dates never make a file outdated.
+
+use crate::error::{Error, Result};
+use crate::merge::{merge3, MergeLabels};
+use crate::protocols::{
+    content_hash, embedded_bundle, inject_dates, integrity_form, protocol_templates,
+    BundleManifest, BundleStatus, PROTOCOL_FILES,
+};
+use crate::update::CURRENT_VERSION;
+use std::path::{Path, PathBuf};
+
+/// Directory under `.asimov/` holding the defaults protocol files came from
+pub const PROTOCOL_BASE_DIR: &str = ".base";
+
+/// Suffix of a reviewable merge conflict next to the protocol file
+pub const CONFLICT_SUFFIX: &str = ".conflict";
+
+/// File under `.asimov/` with the hashes of the protocol files asimov wrote
+pub const PROTOCOL_MANIFEST: &str = ".manifest.json";
+
+/// Whether a protocol file can be trusted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrustState {
+    /// Content of the signed bundle compiled into this binary
+    Verified,
+    /// Generated locally (overrides, custom protocols, kept edits) or not
+    /// recorded; not covered by a signature
+    UnsignedLocal,
+    /// Changed since asimov last wrote it
+    Tampered,
+}
+
+impl std::fmt::Display for TrustState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrustState::Verified => write!(f, "verified"),
+            TrustState::UnsignedLocal => write!(f, "unsigned-local"),
+            TrustState::Tampered => write!(f, "tampered"),
+        }
+    }
+}
+
+/// Trust state of one protocol file
+#[derive(Debug, Clone)]
+pub struct ProtocolTrust {
+    pub filename: String,
+    pub state: TrustState,
+    /// Why it is not verified
+    pub reason: Option<String>,
+}
+
+/// Result of checking a single protocol file
+#[derive(Debug, Clone)]
+pub struct ProtocolCheck {
+    pub filename: String,
+    pub exists: bool,
+    pub matches: bool,
+    pub outdated: bool, // v9.0.0: renamed from tampered - could be old version, not malicious
+    /// Edited locally since it was generated (not outdated)
+    pub customized: bool,
+    /// A refresh conflict file is waiting to be resolved
+    pub conflict_pending: bool,
+}
+
+/// What `refresh` does with a protocol file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtocolOutcome {
+    /// Already the current default
+    Unchanged,
+    /// Missing; the default is written
+    Created,
+    /// An unedited older default; replaced by the new one
+    Updated,
+    /// Local edits merged cleanly with the new default
+    Merged,
+    /// Local edits kept; the default did not change
+    Customized,
+    /// Local edits and the new default overlap; see the conflict file
+    Conflict,
+}
+
+/// Planned refresh of one protocol file
+#[derive(Debug, Clone)]
+pub struct ProtocolRefresh {
+    pub filename: String,
+    pub outcome: ProtocolOutcome,
+    /// New content of the protocol file, when it changes
+    pub content: Option<String>,
+    /// Conflict file content, for `ProtocolOutcome::Conflict`
+    pub conflict: Option<String>,
+    /// Compiled default, recorded as the new base
+    pub default: String,
+    /// Whether a base was recorded (files from older versions have none)
+    pub had_base: bool,
+}
+
+/// `.asimov/.base/<filename>`
+pub fn protocol_base_path(dir: &Path, filename: &str) -> PathBuf {
+    dir.join(".asimov").join(PROTOCOL_BASE_DIR).join(filename)
+}
+
+/// `.asimov/<filename>.conflict`
+pub fn protocol_conflict_path(dir: &Path, filename: &str) -> PathBuf {
+    dir.join(".asimov")
+        .join(format!("{}{}", filename, CONFLICT_SUFFIX))
+}
+
+/// Record the default a protocol file was generated from
+pub fn record_protocol_base(dir: &Path, filename: &str, content: &str) -> std::io::Result<()> {
+    let path = protocol_base_path(dir, filename);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, content)
+}
+
+fn same(a: &str, b: &str) -> bool {
+    a.trim() == b.trim()
+}
+
+fn read_manifest(dir: &Path) -> Option<BundleManifest> {
+    let content = std::fs::read_to_string(dir.join(".asimov").join(PROTOCOL_MANIFEST)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Hash of a protocol file, date-bearing fields read as their template
+fn protocol_hash(content: &str, template: Option<&String>) -> String {
+    content_hash(&template.map_or_else(|| content.to_string(), |t| integrity_form(content, t)))
+}
+
+/// Record the content of protocol files as written by asimov
+pub fn record_protocol_hashes(dir: &Path, files: &[(&str, &str)]) -> std::io::Result<()> {
+    let templates: std::collections::HashMap<String, String> =
+        protocol_templates(dir).into_iter().collect();
+    let mut manifest = read_manifest(dir).unwrap_or_else(|| BundleManifest {
+        version: CURRENT_VERSION.to_string(),
+        files: Default::default(),
+        signature: None,
+    });
+    manifest.version = CURRENT_VERSION.to_string();
+    for (filename, content) in files {
+        manifest.files.insert(
+            filename.to_string(),
+            protocol_hash(content, templates.get(*filename)),
+        );
+    }
+    std::fs::write(
+        dir.join(".asimov").join(PROTOCOL_MANIFEST),
+        manifest.to_json() + "\n",
+    )
+}
+
+/// Trust state of every existing protocol file: verified against the
+/// signed bundle, generated by asimov (unsigned-local) or tampered
+///
+/// A built-in protocol that is not what the embedded bundle and the override
+/// layers produce is tampered unless asimov accounts for it: the hash refresh
+/// recorded (a kept customization), the default asimov last wrote (`.base`),
+/// or a project from an older asimov that recorded neither. Custom protocols
+/// are checked against the hashes asimov recorded when writing them.
+pub fn check_protocol_trust(dir: &Path) -> Vec<ProtocolTrust> {
+    let bundle = embedded_bundle();
+    let recorded = read_manifest(dir);
+    let mut checks = Vec::new();
+
+    for (filename, template) in protocol_templates(dir) {
+        let Ok(content) = std::fs::read_to_string(dir.join(".asimov").join(&filename)) else {
+            continue;
+        };
+        let hash = protocol_hash(&content, Some(&template));
+        let recorded = recorded.as_ref().and_then(|m| m.files.get(&filename));
+        let built_in = PROTOCOL_FILES.iter().any(|(name, _)| *name == filename);
+        let generated = hash == protocol_hash(&template, Some(&template));
+        let (state, reason) = match (&bundle, recorded) {
+            (BundleStatus::Signed(signed), _) if signed.files.get(&filename) == Some(&hash) => {
+                (TrustState::Verified, None)
+            }
+            _ if built_in && !generated => {
+                built_in_drift(dir, &filename, Some(&template), &hash, recorded)
+            }
+            (_, Some(recorded)) if *recorded != hash => (
+                TrustState::Tampered,
+                Some("changed since asimov last wrote it".to_string()),
+            ),
+            (BundleStatus::Unsigned(why), _) if built_in || recorded.is_some() => (
+                TrustState::UnsignedLocal,
+                Some(format!("bundle not verified: {}", why)),
+            ),
+            _ if built_in => (
+                TrustState::UnsignedLocal,
+                Some("generated with override layers".to_string()),
+            ),
+            (_, Some(_)) => (
+                TrustState::UnsignedLocal,
+                Some("generated locally, not the signed default".to_string()),
+            ),
+            _ => (
+                TrustState::UnsignedLocal,
+                Some("not recorded - run 'asimov refresh'".to_string()),
+            ),
+        };
+        checks.push(ProtocolTrust {
+            filename,
+            state,
+            reason,
+        });
+    }
+
+    checks
+}
+
+/// Trust of a built-in protocol file that differs from this binary's output
+fn built_in_drift(
+    dir: &Path,
+    filename: &str,
+    template: Option<&String>,
+    hash: &str,
+    recorded: Option<&String>,
+) -> (TrustState, Option<String>) {
+    let base = std::fs::read_to_string(protocol_base_path(dir, filename))
+        .ok()
+        .map(|base| protocol_hash(&base, template));
+    let older_default = || {
+        (
+            TrustState::UnsignedLocal,
+            Some("older default - run 'asimov refresh'".to_string()),
+        )
+    };
+    match (recorded, base) {
+        (Some(recorded), _) if recorded.as_str() != hash => (
+            TrustState::Tampered,
+            Some("changed since asimov last wrote it".to_string()),
+        ),
+        (Some(_), Some(base)) if base != hash => (
+            TrustState::UnsignedLocal,
+            Some("customized, kept by refresh".to_string()),
+        ),
+        (Some(_), _) => older_default(),
+        (None, Some(base)) if base == hash => older_default(),
+        (None, Some(_)) => (
+            TrustState::Tampered,
+            Some("differs from the default asimov wrote and is not recorded".to_string()),
+        ),
+        (None, None) => (
+            TrustState::UnsignedLocal,
+            Some("written by an older asimov, not recorded - run 'asimov refresh'".to_string()),
+        ),
+    }
+}
+
+/// Check all protocol JSON files against expected (hardcoded) content
+/// Returns list of checks with status for each file
+pub fn check_protocol_integrity(dir: &Path) -> Vec<ProtocolCheck> {
+    let asimov_dir = dir.join(".asimov");
+    let mut checks = Vec::new();
+
+    for (filename, template) in protocol_templates(dir) {
+        let filename = filename.as_str();
+        let file_path = asimov_dir.join(filename);
+        let base = std::fs::read_to_string(protocol_base_path(dir, filename)).ok();
+        // Rendered dates are not differences
+        let form = |content: &str| integrity_form(content, &template);
+
+        let (exists, matches, customized) = if file_path.exists() {
+            match std::fs::read_to_string(&file_path) {
+                Ok(content) => {
+                    // Normalize whitespace for comparison
+                    let matches = same(&form(&content), &form(&template));
+                    let customized =
+                        !matches && base.is_some_and(|b| !same(&form(&content), &form(&b)));
+                    (true, matches, customized)
+                }
+                Err(_) => (true, false, false), // Can't read = outdated/corrupt
+            }
+        } else {
+            (false, false, false) // Missing, not outdated
+        };
+
+        checks.push(ProtocolCheck {
+            filename: filename.to_string(),
+            exists,
+            matches,
+            outdated: exists && !matches && !customized,
+            customized,
+            conflict_pending: protocol_conflict_path(dir, filename).exists(),
+        });
+    }
+
+    checks
+}
+
+/// Plan the refresh of every protocol file without writing anything
+pub fn plan_protocol_refresh(dir: &Path) -> Result<Vec<ProtocolRefresh>> {
+    let asimov_dir = dir.join(".asimov");
+    if !asimov_dir.exists() {
+        return Err(Error::ValidationError(
+            "Not in an asimov project (.asimov/ not found)".to_string(),
+        ));
+    }
+
+    let new_label = format!("asimov {}", CURRENT_VERSION);
+    let mut plan = Vec::new();
+    for (filename, template) in protocol_templates(dir) {
+        let default = inject_dates(&template);
+        let form = |content: &str| integrity_form(content, &template);
+        let filename = filename.as_str();
+        let path = format!(".asimov/{}", filename);
+        let local = std::fs::read_to_string(asimov_dir.join(filename)).ok();
+        let base = std::fs::read_to_string(protocol_base_path(dir, filename)).ok();
+        let labels = MergeLabels {
+            local: &format!("local {}", path),
+            base: "base (default it was generated from)",
+            new: &new_label,
+        };
+
+        let (outcome, content, conflict) = match (&local, &base) {
+            (None, _) => (ProtocolOutcome::Created, Some(default.clone()), None),
+            (Some(local), _) if same(&form(local), &form(&default)) => {
+                (ProtocolOutcome::Unchanged, None, None)
+            }
+            (Some(local), Some(base)) if same(&form(local), &form(base)) => {
+                (ProtocolOutcome::Updated, Some(default.clone()), None)
+            }
+            (Some(_), Some(base)) if same(&form(base), &form(&default)) => {
+                (ProtocolOutcome::Customized, None, None)
+            }
+            (Some(local), base) => {
+                // Without a recorded base every difference is a conflict
+                let outcome = merge3(base.as_deref().unwrap_or(""), local, &default, &labels);
+                let valid_json = serde_json::from_str::<serde_json::Value>(&outcome.text).is_ok();
+                if base.is_some() && outcome.is_clean() && valid_json {
+                    (ProtocolOutcome::Merged, Some(outcome.text), None)
+                } else if outcome.is_clean() {
+                    let whole = merge3("", local, &default, &labels);
+                    (ProtocolOutcome::Conflict, None, Some(whole.text))
+                } else {
+                    (ProtocolOutcome::Conflict, None, Some(outcome.text))
+                }
+            }
+        };
+
+        plan.push(ProtocolRefresh {
+            filename: filename.to_string(),
+            outcome,
+            content,
+            conflict,
+            default,
+            had_base: base.is_some(),
+        });
+    }
+    Ok(plan)
+}
+
+/// Write a planned protocol refresh
+///
+/// Conflicts only write the conflict file: the protocol file and its base
+/// stay as they are, so the next refresh merges again once it is resolved.
+pub fn apply_protocol_refresh(dir: &Path, plan: &[ProtocolRefresh]) -> Result<()> {
+    let write_error = |file: &str, e: std::io::Error| {
+        Error::ValidationError(format!("Failed to write {}: {}", file, e))
+    };
+
+    for item in plan {
+        let conflict_path = protocol_conflict_path(dir, &item.filename);
+        if let Some(ref conflict) = item.conflict {
+            std::fs::write(&conflict_path, conflict)
+                .map_err(|e| write_error(&format!("{}{}", item.filename, CONFLICT_SUFFIX), e))?;
+            continue;
+        }
+        let path = dir.join(".asimov").join(&item.filename);
+        if let Some(ref content) = item.content {
+            std::fs::write(&path, content).map_err(|e| write_error(&item.filename, e))?;
+        }
+        if let Ok(written) = std::fs::read_to_string(&path) {
+            record_protocol_hashes(dir, &[(&item.filename, &written)])
+                .map_err(|e| write_error(PROTOCOL_MANIFEST, e))?;
+        }
+        record_protocol_base(dir, &item.filename, &item.default)
+            .map_err(|e| write_error(&format!("{}/{}", PROTOCOL_BASE_DIR, item.filename), e))?;
+        if conflict_path.exists() {
+            std::fs::remove_file(&conflict_path)
+                .map_err(|e| write_error(&format!("{}{}", item.filename, CONFLICT_SUFFIX), e))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocols::sprint_json;
+    use tempfile::TempDir;
+
+    fn project() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join(".asimov")).unwrap();
+        temp
+    }
+
+    fn outcome(plan: &[ProtocolRefresh], filename: &str) -> ProtocolOutcome {
+        plan.iter()
+            .find(|p| p.filename == filename)
+            .unwrap()
+            .outcome
+    }
+
+    #[test]
+    fn test_refresh_creates_and_records_bases() {
+        let temp = project();
+        let plan = plan_protocol_refresh(temp.path()).unwrap();
+        assert!(plan.iter().all(|p| p.outcome == ProtocolOutcome::Created));
+        apply_protocol_refresh(temp.path(), &plan).unwrap();
+
+        assert!(protocol_base_path(temp.path(), "sprint.json").exists());
+        let plan = plan_protocol_refresh(temp.path()).unwrap();
+        assert!(plan.iter().all(|p| p.outcome == ProtocolOutcome::Unchanged));
+        assert!(check_protocol_integrity(temp.path())
+            .iter()
+            .all(|c| c.matches));
+    }
+
+    #[test]
+    fn test_refresh_updates_unedited_old_default() {
+        let temp = project();
+        let old = sprint_json().replace("\"rule\"", "\"old_rule\"");
+        std::fs::write(temp.path().join(".asimov/sprint.json"), &old).unwrap();
+        record_protocol_base(temp.path(), "sprint.json", &old).unwrap();
+
+        let check = check_protocol_integrity(temp.path());
+        let sprint = check.iter().find(|c| c.filename == "sprint.json").unwrap();
+        assert!(sprint.outdated && !sprint.customized);
+
+        let plan = plan_protocol_refresh(temp.path()).unwrap();
+        assert_eq!(outcome(&plan, "sprint.json"), ProtocolOutcome::Updated);
+        apply_protocol_refresh(temp.path(), &plan).unwrap();
+        let written = std::fs::read_to_string(temp.path().join(".asimov/sprint.json")).unwrap();
+        assert_eq!(written, sprint_json());
+    }
+
+    #[test]
+    fn test_refresh_keeps_and_merges_customizations() {
+        let temp = project();
+        let default = sprint_json();
+        let mut lines: Vec<String> = default.lines().map(String::from).collect();
+        let last = lines.len() - 2;
+        // Local edit near the top, upstream change near the bottom
+        let local = default.replacen("{\n", "{\n  \"team_note\": \"ship on fridays\",\n", 1);
+        lines[last].push(' ');
+        let old_default = format!("{}\n", lines.join("\n"));
+        std::fs::write(temp.path().join(".asimov/sprint.json"), &local).unwrap();
+        record_protocol_base(temp.path(), "sprint.json", &default).unwrap();
+
+        // Default unchanged since generation: the edit is kept as is
+        let check = check_protocol_integrity(temp.path());
+        assert!(check
+            .iter()
+            .any(|c| c.filename == "sprint.json" && c.customized && !c.outdated));
+        let plan = plan_protocol_refresh(temp.path()).unwrap();
+        assert_eq!(outcome(&plan, "sprint.json"), ProtocolOutcome::Customized);
+
+        // Generated from an older default: the edit is merged into the new one
+        let local = old_default.replacen("{\n", "{\n  \"team_note\": \"ship on fridays\",\n", 1);
+        std::fs::write(temp.path().join(".asimov/sprint.json"), &local).unwrap();
+        record_protocol_base(temp.path(), "sprint.json", &old_default).unwrap();
+        let plan = plan_protocol_refresh(temp.path()).unwrap();
+        assert_eq!(outcome(&plan, "sprint.json"), ProtocolOutcome::Merged);
+        apply_protocol_refresh(temp.path(), &plan).unwrap();
+        let merged = std::fs::read_to_string(temp.path().join(".asimov/sprint.json")).unwrap();
+        assert!(merged.contains("team_note"));
+        assert_eq!(
+            merged.replace("  \"team_note\": \"ship on fridays\",\n", ""),
+            default
+        );
+    }
+
+    #[test]
+    fn test_refresh_writes_conflicts_for_review() {
+        let temp = project();
+        let local = "{\"rule\": \"ours\"}\n";
+        std::fs::write(temp.path().join(".asimov/sprint.json"), local).unwrap();
+
+        // No recorded base: the local file is kept, the conflict written next to it
+        let plan = plan_protocol_refresh(temp.path()).unwrap();
+        let sprint = plan.iter().find(|p| p.filename == "sprint.json").unwrap();
+        assert_eq!(sprint.outcome, ProtocolOutcome::Conflict);
+        assert!(!sprint.had_base);
+        apply_protocol_refresh(temp.path(), &plan).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(temp.path().join(".asimov/sprint.json")).unwrap(),
+            local
+        );
+        let conflict =
+            std::fs::read_to_string(protocol_conflict_path(temp.path(), "sprint.json")).unwrap();
+        assert!(conflict.starts_with("<<<<<<< local .asimov/sprint.json\n"));
+        assert!(conflict.contains(">>>>>>> asimov "));
+        assert!(!protocol_base_path(temp.path(), "sprint.json").exists());
+        assert!(check_protocol_integrity(temp.path())
+            .iter()
+            .any(|c| c.filename == "sprint.json" && c.conflict_pending));
+
+        // Resolving the file clears the conflict on the next refresh
+        std::fs::write(temp.path().join(".asimov/sprint.json"), sprint_json()).unwrap();
+        let plan = plan_protocol_refresh(temp.path()).unwrap();
+        apply_protocol_refresh(temp.path(), &plan).unwrap();
+        assert!(!protocol_conflict_path(temp.path(), "sprint.json").exists());
+    }
+
+    #[test]
+    fn test_protocol_trust_states() {
+        let temp = project();
+        assert!(check_protocol_trust(temp.path()).is_empty());
+        let plan = plan_protocol_refresh(temp.path()).unwrap();
+        apply_protocol_refresh(temp.path(), &plan).unwrap();
+        let state = |filename: &str| {
+            check_protocol_trust(temp.path())
+                .into_iter()
+                .find(|t| t.filename == filename)
+                .unwrap()
+                .state
+        };
+        assert_eq!(state("sprint.json"), TrustState::UnsignedLocal);
+
+        let path = temp.path().join(".asimov/sprint.json");
+        std::fs::write(&path, sprint_json().replace("\"rule\"", "\"my_rule\"")).unwrap();
+        assert_eq!(state("sprint.json"), TrustState::Tampered);
+
+        // Deleting the record does not launder an edit of a written default
+        let manifest = temp.path().join(".asimov").join(PROTOCOL_MANIFEST);
+        let recorded = std::fs::read_to_string(&manifest).unwrap();
+        std::fs::remove_file(&manifest).unwrap();
+        assert_eq!(state("sprint.json"), TrustState::Tampered);
+        assert_eq!(state("green.json"), TrustState::UnsignedLocal);
+
+        // A customization refresh kept and recorded is not tampering
+        std::fs::write(&manifest, recorded).unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        record_protocol_hashes(temp.path(), &[("sprint.json", &written)]).unwrap();
+        assert_eq!(state("sprint.json"), TrustState::UnsignedLocal);
+
+        // Projects from an older asimov have neither record nor base
+        std::fs::remove_file(&manifest).unwrap();
+        std::fs::remove_dir_all(temp.path().join(".asimov").join(PROTOCOL_BASE_DIR)).unwrap();
+        assert_eq!(state("sprint.json"), TrustState::UnsignedLocal);
+    }
+}
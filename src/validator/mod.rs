@@ -1,11 +1,21 @@
 //! Core validation logic for RoyalBit Asimov files
 
 use crate::error::{Error, Result};
-use crate::schemas::{schema_for_file, schema_type_for_file};
+use crate::schemas::{
+    current_schema_version, schema_for_file, schema_type_for_file, schema_version_of,
+};
+use crate::yaml_span::{code_frame, locate, pointer_to_path};
 use colored::Colorize;
-use jsonschema::Validator;
+use jsonschema::error::ValidationErrorKind;
+use jsonschema::{ValidationError, Validator};
 use std::path::Path;
 
+mod customizations;
+mod integrity;
+
+pub use customizations::*;
+pub use integrity::*;
+
 /// Validation result for a single file
 #[derive(Debug)]
 pub struct ValidationResult {
@@ -15,6 +25,8 @@ pub struct ValidationResult {
     pub errors: Vec<String>,
     pub warnings: Vec<String>,
     pub regenerated: bool,
+    /// Schema errors with source locations (one per entry in `errors` from the schema)
+    pub diagnostics: Vec<SchemaDiagnostic>,
 }
 
 impl ValidationResult {
@@ -26,6 +38,7 @@ impl ValidationResult {
             errors: Vec::new(),
             warnings: Vec::new(),
             regenerated: false,
+            diagnostics: Vec::new(),
         }
     }
 
@@ -37,6 +50,7 @@ impl ValidationResult {
             errors,
             warnings: Vec::new(),
             regenerated: false,
+            diagnostics: Vec::new(),
         }
     }
 
@@ -59,6 +73,73 @@ impl ValidationResult {
     }
 }
 
+/// A schema violation mapped back to its location in the YAML source
+///
+/// Displays as `file:line:column: path message`, the format editors and
+/// terminals recognize for jump-to-location.
+#[derive(Debug, Clone)]
+pub struct SchemaDiagnostic {
+    pub file: String,
+    /// Dotted path of the offending node (e.g. `current.status`), empty for the root
+    pub path: String,
+    pub line: usize,
+    pub column: usize,
+    /// Message including the path (e.g. `current.status must be one of ...`)
+    pub message: String,
+    /// Source excerpt with a caret under the offending column
+    pub code_frame: String,
+}
+
+impl std::fmt::Display for SchemaDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {}",
+            self.file, self.line, self.column, self.message
+        )
+    }
+}
+
+impl SchemaDiagnostic {
+    fn from_error(file: &str, content: &str, error: &ValidationError) -> Self {
+        let pointer = error.instance_path().to_string();
+        let span = locate(content, &pointer);
+        let path = pointer_to_path(&pointer);
+
+        let detail = match error.kind() {
+            ValidationErrorKind::Enum { options } => {
+                let options: Vec<String> = options
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|v| v.as_str().map(String::from).unwrap_or(v.to_string()))
+                    .collect();
+                format!(
+                    "must be one of {} (found {})",
+                    options.join(", "),
+                    error.instance()
+                )
+            }
+            _ => error.to_string(),
+        };
+
+        let message = match (path.is_empty(), error.kind()) {
+            (true, _) => detail,
+            (false, ValidationErrorKind::Enum { .. }) => format!("{} {}", path, detail),
+            (false, _) => format!("{}: {}", path, detail),
+        };
+
+        Self {
+            file: file.to_string(),
+            path,
+            line: span.map(|s| s.line).unwrap_or(1),
+            column: span.map(|s| s.column).unwrap_or(1),
+            message,
+            code_frame: span.map(|s| code_frame(content, s)).unwrap_or_default(),
+        }
+    }
+}
+
 /// File size limits for self-healing protocol (ADR-007)
 pub struct FileSizeLimits {
     /// Soft limit (triggers warning)
@@ -97,14 +178,42 @@ pub fn validate_file(path: &Path) -> Result<ValidationResult> {
         return Err(Error::FileNotFound(path.display().to_string()));
     }
 
+    // Determine schema type before reading (unknown files are never opened)
+    if schema_for_file(filename).is_none() {
+        return Err(Error::UnknownFileType(filename.to_string()));
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    validate_content(path, &content)
+}
+
+/// Validate in-memory content as if it were the file at `path`
+///
+/// Used by the language server to validate unsaved editor buffers. The file
+/// name selects the schema; the file itself is never read.
+pub fn validate_content(path: &Path, content: &str) -> Result<ValidationResult> {
+    let filename = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("unknown");
+
     // Determine schema type
     let schema_json =
         schema_for_file(filename).ok_or_else(|| Error::UnknownFileType(filename.to_string()))?;
     let schema_type = schema_type_for_file(filename).unwrap_or("unknown");
 
-    // Read and parse YAML
-    let content = std::fs::read_to_string(path)?;
-    let yaml_value: serde_yaml_ng::Value = serde_yaml_ng::from_str(&content)?;
+    validate_against_schema(path, content, schema_json, schema_type)
+}
+
+/// Validate YAML content against an explicit schema (e.g. custom templates)
+fn validate_against_schema(
+    path: &Path,
+    content: &str,
+    schema_json: &str,
+    schema_type: &str,
+) -> Result<ValidationResult> {
+    // Parse YAML
+    let yaml_value: serde_yaml_ng::Value = serde_yaml_ng::from_str(content)?;
 
     // Convert YAML to JSON for schema validation
     let json_value = yaml_to_json(&yaml_value)?;
@@ -117,27 +226,24 @@ pub fn validate_file(path: &Path) -> Result<ValidationResult> {
     let validator = Validator::new(&schema)
         .map_err(|e| Error::SchemaError(format!("Failed to compile schema: {}", e)))?;
 
-    // Collect all validation errors
-    let error_messages: Vec<String> = validator
+    // Collect all validation errors, located in the YAML source
+    let display_path = path.display().to_string();
+    let display_path = display_path.strip_prefix("./").unwrap_or(&display_path);
+    let diagnostics: Vec<SchemaDiagnostic> = validator
         .iter_errors(&json_value)
-        .map(|e| {
-            let path = e.instance_path().to_string();
-            if path.is_empty() {
-                e.to_string()
-            } else {
-                format!("{}: {}", path, e)
-            }
-        })
+        .map(|e| SchemaDiagnostic::from_error(display_path, content, &e))
         .collect();
 
-    let mut result = if error_messages.is_empty() {
+    let mut result = if diagnostics.is_empty() {
         ValidationResult::success(path.display().to_string(), schema_type.to_string())
     } else {
-        ValidationResult::failure(
+        let mut failure = ValidationResult::failure(
             path.display().to_string(),
             schema_type.to_string(),
-            error_messages,
-        )
+            diagnostics.iter().map(|d| d.to_string()).collect(),
+        );
+        failure.diagnostics = diagnostics;
+        failure
     };
 
     // Add size warnings based on file type (ADR-007)
@@ -145,10 +251,29 @@ pub fn validate_file(path: &Path) -> Result<ValidationResult> {
     let size_warnings = check_file_size(schema_type, line_count);
     result = result.with_warnings(size_warnings);
 
+    // Versioned data files - point at `asimov refresh` / `asimov update`
+    if let Some(current) = current_schema_version(schema_type) {
+        match schema_version_of(content) {
+            Ok(found) if found < current => {
+                result = result.with_warning(format!(
+                    "schema_version {} is outdated (current: {}). Run: asimov refresh",
+                    found, current
+                ));
+            }
+            Ok(found) if found > current => {
+                result = result.with_warning(format!(
+                    "schema_version {} is newer than this asimov supports ({}). Run: asimov update",
+                    found, current
+                ));
+            }
+            _ => {}
+        }
+    }
+
     // NOTE: ethics.yaml validation removed - asimov.yaml is canonical (ADR-031)
     // Structure validation for asimov.yaml (Three Laws)
     if schema_type == "asimov" {
-        let structure_errors = check_asimov_structure(&content);
+        let structure_errors = check_asimov_structure(content);
         if !structure_errors.is_empty() {
             // Asimov structure errors are CRITICAL - fail validation
             result.is_valid = false;
@@ -159,7 +284,7 @@ pub fn validate_file(path: &Path) -> Result<ValidationResult> {
     // Structure validation for warmup.yaml (v7.0.6: minimal validation)
     // Note: warmup.yaml now only contains project-specific config
     if schema_type == "warmup" {
-        let (_errors, warnings) = check_warmup_structure(&content);
+        let (_errors, warnings) = check_warmup_structure(content);
         result = result.with_warnings(warnings);
     }
 
@@ -256,46 +381,55 @@ pub fn validate_directory_with_regeneration(
     validate_directory_internal(dir, regenerate)
 }
 
+/// Required data files that are missing, with the content regeneration writes
+///
+/// `migrations.yaml` is required while its activation rules hold (see
+/// `protocols::activation`). A monorepo package inherits missing data files
+/// from the root context, so nothing is regenerated (or required) there.
+pub fn missing_data_files(base_dir: &Path) -> Vec<(&'static str, String)> {
+    use crate::protocols::{is_builtin_active, ProjectFacts, MIGRATIONS_FILE};
+    use crate::templates::{migrations_template, roadmap_template};
+
+    if crate::context::AsimovContext::discover(base_dir).is_some_and(|c| c.is_nested()) {
+        return Vec::new();
+    }
+    let protocol_dir = resolve_protocol_dir(base_dir);
+    let mut required = vec![("roadmap.yaml", roadmap_template as fn() -> String)];
+    if is_builtin_active(MIGRATIONS_FILE, &ProjectFacts::discover(base_dir)) {
+        required.push(("migrations.yaml", migrations_template));
+    }
+    required
+        .into_iter()
+        .filter(|(filename, _)| !protocol_dir.join(filename).exists())
+        .map(|(filename, template)| (filename, template()))
+        .collect()
+}
+
 /// Internal implementation that returns both results and regeneration info
 fn validate_directory_internal(
     base_dir: &Path,
     regenerate: bool,
 ) -> Result<(Vec<ValidationResult>, RegenerationInfo)> {
-    use crate::templates::roadmap_template;
-
-    // Resolve protocol directory (.asimov/ or root for backwards compatibility)
-    let protocol_dir = resolve_protocol_dir(base_dir);
-
     let mut results = Vec::new();
     let mut regen_info = RegenerationInfo::default();
-
-    // Required files with their templates and warn level
-    // (filename, template_fn, is_warn_level)
-    // NOTE: v8.0.0 - Protocol YAMLs no longer regenerated (hardcoded in binary)
-    // Only roadmap.yaml is regenerated (project data, not protocol)
-    #[allow(clippy::type_complexity)]
-    let required_files: Vec<(&str, Box<dyn Fn() -> String>, bool)> = vec![
-        ("roadmap.yaml", Box::new(roadmap_template), false), // INFO - project data
-    ];
+    let nested = crate::context::AsimovContext::discover(base_dir).is_some_and(|c| c.is_nested());
 
     // Check and regenerate missing required files
-    for (filename, template_fn, is_warn) in &required_files {
-        let file_path = protocol_dir.join(filename);
-        if !file_path.exists() && regenerate {
+    // NOTE: v8.0.0 - Protocol YAMLs no longer regenerated (hardcoded in binary)
+    // Only roadmap.yaml (and migrations.yaml where active) is regenerated
+    if regenerate {
+        for (filename, content) in missing_data_files(base_dir) {
             // Ensure .asimov directory exists before regenerating
             ensure_protocol_dir(base_dir)?;
             let regen_path = base_dir.join(PROTOCOL_DIR).join(filename);
-            // Regenerate the file
-            let content = template_fn();
             if let Err(e) = std::fs::write(&regen_path, &content) {
                 return Err(Error::ValidationError(format!(
                     "Failed to regenerate {}: {}",
                     filename, e
                 )));
             }
-            regen_info
-                .regenerated
-                .push((filename.to_string(), *is_warn));
+            // INFO level - project data
+            regen_info.regenerated.push((filename.to_string(), false));
         }
     }
 
@@ -305,8 +439,9 @@ fn validate_directory_internal(
     // Look for data files (v8.0.0: protocol YAMLs are deprecated, hardcoded in binary)
     // v8.1.0: project.yaml replaces deprecated checkpoint (ADR-032)
     let protocol_files = [
-        "roadmap.yaml", // Project data - WHAT to build (required)
-        "project.yaml", // Project context - HOW to build (ADR-032)
+        "roadmap.yaml",    // Project data - WHAT to build (required)
+        "project.yaml",    // Project context - HOW to build (ADR-032)
+        "migrations.yaml", // Functional equivalence - migration projects only
     ];
 
     for filename in &protocol_files {
@@ -324,13 +459,16 @@ fn validate_directory_internal(
     // CLAUDE.md is deprecated - delete if found
     delete_deprecated_claude_md(base_dir);
 
-    if results.is_empty() {
+    if results.is_empty() && !nested {
         return Err(Error::ValidationError(
             "No data files found in .asimov/ (roadmap.yaml required). Run: asimov init --full"
                 .to_string(),
         ));
     }
 
+    // Roles, templates, protocol overrides and hook templates
+    results.extend(validate_customizations(&base_dir.join(PROTOCOL_DIR)));
+
     Ok((results, regen_info))
 }
 
@@ -428,94 +566,6 @@ pub fn delete_deprecated_claude_md(dir: &Path) {
     }
 }
 
-// ============================================================================
-// PROTOCOL INTEGRITY CHECK (v9.0.0)
-// ============================================================================
-
-/// Result of checking a single protocol file
-#[derive(Debug, Clone)]
-pub struct ProtocolCheck {
-    pub filename: String,
-    pub exists: bool,
-    pub matches: bool,
-    pub outdated: bool, // v9.0.0: renamed from tampered - could be old version, not malicious
-}
-
-/// Check all protocol JSON files against expected (hardcoded) content
-/// Returns list of checks with status for each file
-pub fn check_protocol_integrity(dir: &Path) -> Vec<ProtocolCheck> {
-    use crate::protocols::PROTOCOL_FILES;
-
-    let asimov_dir = dir.join(".asimov");
-    let mut checks = Vec::new();
-
-    for (filename, generator) in PROTOCOL_FILES {
-        let file_path = asimov_dir.join(filename);
-        let expected = generator();
-
-        let (exists, matches, outdated) = if file_path.exists() {
-            match std::fs::read_to_string(&file_path) {
-                Ok(content) => {
-                    // Normalize whitespace for comparison
-                    let content_normalized = content.trim();
-                    let expected_normalized = expected.trim();
-                    let matches = content_normalized == expected_normalized;
-                    (true, matches, !matches)
-                }
-                Err(_) => (true, false, true), // Can't read = outdated/corrupt
-            }
-        } else {
-            (false, false, false) // Missing, not outdated
-        };
-
-        checks.push(ProtocolCheck {
-            filename: filename.to_string(),
-            exists,
-            matches,
-            outdated,
-        });
-    }
-
-    checks
-}
-
-/// Regenerate protocol files and return which ones were changed
-/// Returns: (filename, was_different)
-pub fn regenerate_protocol_files(dir: &Path) -> Result<Vec<(String, bool)>> {
-    use crate::protocols::PROTOCOL_FILES;
-
-    let asimov_dir = dir.join(".asimov");
-    if !asimov_dir.exists() {
-        return Err(Error::ValidationError(
-            "Not in an asimov project (.asimov/ not found)".to_string(),
-        ));
-    }
-
-    let mut results = Vec::new();
-
-    for (filename, generator) in PROTOCOL_FILES {
-        let file_path = asimov_dir.join(filename);
-        let expected = generator();
-
-        let was_different = if file_path.exists() {
-            match std::fs::read_to_string(&file_path) {
-                Ok(content) => content.trim() != expected.trim(),
-                Err(_) => true,
-            }
-        } else {
-            true // Missing = different
-        };
-
-        // Write the correct content
-        std::fs::write(&file_path, &expected)
-            .map_err(|e| Error::ValidationError(format!("Failed to write {}: {}", filename, e)))?;
-
-        results.push((filename.to_string(), was_different));
-    }
-
-    Ok(results)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
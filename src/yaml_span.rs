@@ -0,0 +1,458 @@
+//! Source locations for YAML documents
+//!
+//! Schema validation runs on JSON converted from YAML, so jsonschema errors
+//! only carry a JSON pointer (e.g. `/current/status`). This module maps that
+//! pointer back to a line and column in the original YAML text so errors can
+//! be reported as `roadmap.yaml:14:5: ...` with a code frame.
+//!
+//! Block-style YAML (what asimov writes and documents) is located exactly.
+//! Flow-style collections resolve to the nearest enclosing key.
+
+/// A 1-based position in a source file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceSpan {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// A significant line: 0-based line index, 0-based column, trimmed text
+#[derive(Debug, Clone)]
+struct Entry<'a> {
+    line: usize,
+    column: usize,
+    text: &'a str,
+}
+
+impl Entry<'_> {
+    fn span(&self) -> SourceSpan {
+        SourceSpan {
+            line: self.line + 1,
+            column: self.column + 1,
+        }
+    }
+
+    fn is_sequence_item(&self) -> bool {
+        self.text == "-" || self.text.starts_with("- ")
+    }
+}
+
+/// Locate the YAML node addressed by a JSON pointer
+///
+/// Returns the position of the value when it sits on the same line as its
+/// key, otherwise the position of the key. When the pointer goes deeper than
+/// the document (e.g. a missing property), the deepest existing ancestor is
+/// returned. An empty pointer addresses the document root.
+pub fn locate(content: &str, pointer: &str) -> Option<SourceSpan> {
+    let mut entries: Vec<Entry> = content
+        .lines()
+        .enumerate()
+        .filter_map(|(line, raw)| {
+            let text = raw.trim_start();
+            let trimmed = text.trim_end();
+            if trimmed.is_empty()
+                || trimmed.starts_with('#')
+                || trimmed == "---"
+                || trimmed == "..."
+            {
+                return None;
+            }
+            Some(Entry {
+                line,
+                column: raw.len() - text.len(),
+                text: trimmed,
+            })
+        })
+        .collect();
+
+    let mut best = entries.first().map(|e| SourceSpan {
+        line: e.line + 1,
+        column: 1,
+    })?;
+
+    for segment in pointer_segments(pointer) {
+        let Some(first) = entries.first() else {
+            break;
+        };
+        let indent = first.column;
+
+        let found = if first.is_sequence_item() {
+            segment
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| find_sequence_item(&entries, indent, index))
+        } else {
+            find_mapping_key(&entries, indent, &segment)
+        };
+
+        let Some((position, next)) = found else {
+            break;
+        };
+        best = position;
+        entries = next;
+    }
+
+    Some(best)
+}
+
+/// One level of nesting while walking towards a position
+enum Frame {
+    Key { indent: usize, name: String },
+    Item { indent: usize, index: usize },
+}
+
+impl Frame {
+    fn indent(&self) -> usize {
+        match self {
+            Frame::Key { indent, .. } | Frame::Item { indent, .. } => *indent,
+        }
+    }
+
+    fn segment(&self) -> String {
+        match self {
+            Frame::Key { name, .. } => name.clone(),
+            Frame::Item { index, .. } => index.to_string(),
+        }
+    }
+}
+
+/// Path segments (JSON pointer tokens) of the node at a position
+///
+/// The inverse of [`locate`]: given a 1-based line and column, returns the
+/// keys and sequence indices leading to the node there, including the key
+/// written on that line. On a blank line the column decides the nesting, so
+/// a cursor at column 3 under `current:` resolves to `["current"]`.
+pub fn path_at(content: &str, span: SourceSpan) -> Vec<String> {
+    let mut stack: Vec<Frame> = Vec::new();
+
+    for (number, raw) in content.lines().enumerate().take(span.line) {
+        let text = raw.trim_start();
+        let trimmed = text.trim_end();
+        let is_target = number + 1 == span.line;
+
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed == "---" {
+            if is_target {
+                let column = span.column.saturating_sub(1);
+                stack.retain(|f| f.indent() < column);
+            }
+            continue;
+        }
+
+        let mut column = raw.len() - text.len();
+        let mut rest = trimmed;
+
+        if rest == "-" || rest.starts_with("- ") {
+            while stack.last().is_some_and(|f| f.indent() > column) {
+                stack.pop();
+            }
+            let index = match stack.last() {
+                Some(Frame::Item { indent, index }) if *indent == column => {
+                    let next = index + 1;
+                    stack.pop();
+                    next
+                }
+                _ => 0,
+            };
+            stack.push(Frame::Item {
+                indent: column,
+                index,
+            });
+
+            let after = rest[1..].trim_start();
+            column += rest.len() - after.len();
+            rest = after;
+        } else {
+            while stack.last().is_some_and(|f| f.indent() >= column) {
+                stack.pop();
+            }
+        }
+
+        if let Some((key, _)) = split_key(rest) {
+            stack.push(Frame::Key {
+                indent: column,
+                name: key.to_string(),
+            });
+        }
+    }
+
+    stack.iter().map(Frame::segment).collect()
+}
+
+/// Split a JSON pointer into unescaped reference tokens
+pub fn pointer_segments(pointer: &str) -> Vec<String> {
+    pointer
+        .split('/')
+        .skip(1)
+        .map(|s| s.replace("~1", "/").replace("~0", "~"))
+        .collect()
+}
+
+/// Render a JSON pointer as a dotted path (`/next/0/version` -> `next[0].version`)
+pub fn pointer_to_path(pointer: &str) -> String {
+    let mut path = String::new();
+    for segment in pointer_segments(pointer) {
+        if segment.parse::<usize>().is_ok() {
+            path.push_str(&format!("[{}]", segment));
+        } else {
+            if !path.is_empty() {
+                path.push('.');
+            }
+            path.push_str(&segment);
+        }
+    }
+    path
+}
+
+/// Render a code frame around a position, with a caret under the column
+pub fn code_frame(content: &str, span: SourceSpan) -> String {
+    const CONTEXT_LINES: usize = 2;
+
+    let lines: Vec<&str> = content.lines().collect();
+    if span.line == 0 || span.line > lines.len() {
+        return String::new();
+    }
+
+    let first = span.line.saturating_sub(CONTEXT_LINES).max(1);
+    let width = span.line.to_string().len();
+    let mut frame = String::new();
+
+    for number in first..=span.line {
+        let marker = if number == span.line { '>' } else { ' ' };
+        frame.push_str(&format!(
+            "{} {:>width$} | {}\n",
+            marker,
+            number,
+            lines[number - 1],
+            width = width
+        ));
+    }
+    frame.push_str(&format!(
+        "  {:>width$} | {}^",
+        "",
+        " ".repeat(span.column.saturating_sub(1)),
+        width = width
+    ));
+
+    frame
+}
+
+/// Entries belonging to the block that starts after `start` and is indented
+/// deeper than `indent`
+fn child_block<'a>(entries: &[Entry<'a>], start: usize, indent: usize) -> Vec<Entry<'a>> {
+    entries[start + 1..]
+        .iter()
+        .take_while(|e| e.column > indent)
+        .cloned()
+        .collect()
+}
+
+fn find_mapping_key<'a>(
+    entries: &[Entry<'a>],
+    indent: usize,
+    key: &str,
+) -> Option<(SourceSpan, Vec<Entry<'a>>)> {
+    let index = entries
+        .iter()
+        .position(|e| e.column == indent && split_key(e.text).is_some_and(|(k, _)| k == key))?;
+    let entry = &entries[index];
+    let (_, value_offset) = split_key(entry.text)?;
+
+    let position = match value_offset {
+        Some(offset) => SourceSpan {
+            line: entry.line + 1,
+            column: entry.column + offset + 1,
+        },
+        None => entry.span(),
+    };
+
+    Some((position, child_block(entries, index, indent)))
+}
+
+fn find_sequence_item<'a>(
+    entries: &[Entry<'a>],
+    indent: usize,
+    item: usize,
+) -> Option<(SourceSpan, Vec<Entry<'a>>)> {
+    let index = entries
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| e.column == indent && e.is_sequence_item())
+        .map(|(i, _)| i)
+        .nth(item)?;
+    let entry = &entries[index];
+
+    // "- key: value" opens a mapping whose first key shares the dash line
+    let rest = entry.text[1..].trim_start();
+    let mut block = Vec::new();
+    if !rest.is_empty() {
+        let column = entry.column + (entry.text.len() - rest.len());
+        block.push(Entry {
+            line: entry.line,
+            column,
+            text: rest,
+        });
+    }
+    block.extend(child_block(entries, index, indent));
+
+    let position = block.first().map(Entry::span).unwrap_or(entry.span());
+    Some((position, block))
+}
+
+/// Split `key: value` into the unquoted key and the byte offset of the value
+fn split_key(text: &str) -> Option<(&str, Option<usize>)> {
+    let (key, after) = if let Some(quote @ ('"' | '\'')) = text.chars().next() {
+        let end = text[1..].find(quote)? + 1;
+        let after = &text[end + 1..];
+        if !after.starts_with(':') {
+            return None;
+        }
+        (&text[1..end], end + 1)
+    } else {
+        let colon = text
+            .match_indices(':')
+            .map(|(i, _)| i)
+            .find(|&i| text[i + 1..].is_empty() || text[i + 1..].starts_with(' '))?;
+        (text[..colon].trim_end(), colon)
+    };
+
+    let value = &text[after + 1..];
+    let value_trimmed = value.trim_start();
+    let offset = if value_trimmed.is_empty() || value_trimmed.starts_with('#') {
+        None
+    } else {
+        Some(after + 1 + (value.len() - value_trimmed.len()))
+    };
+
+    Some((key, offset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROADMAP: &str = r#"# Roadmap
+current:
+  version: "1.0.0"
+  status: done
+  summary: "First"
+
+next:
+  - version: "1.1.0"
+    summary: "Next"
+  -
+    version: "1.2.0"
+    goal: 42
+"#;
+
+    #[test]
+    fn test_locate_nested_value() {
+        let span = locate(ROADMAP, "/current/status").unwrap();
+        assert_eq!(
+            span,
+            SourceSpan {
+                line: 4,
+                column: 11
+            }
+        );
+    }
+
+    #[test]
+    fn test_locate_block_key() {
+        let span = locate(ROADMAP, "/current").unwrap();
+        assert_eq!(span, SourceSpan { line: 2, column: 1 });
+    }
+
+    #[test]
+    fn test_locate_sequence_inline_item() {
+        let span = locate(ROADMAP, "/next/0/summary").unwrap();
+        assert_eq!(
+            span,
+            SourceSpan {
+                line: 9,
+                column: 14
+            }
+        );
+        let span = locate(ROADMAP, "/next/0").unwrap();
+        assert_eq!(span, SourceSpan { line: 8, column: 5 });
+    }
+
+    #[test]
+    fn test_locate_sequence_block_item() {
+        let span = locate(ROADMAP, "/next/1/goal").unwrap();
+        assert_eq!(
+            span,
+            SourceSpan {
+                line: 12,
+                column: 11
+            }
+        );
+    }
+
+    #[test]
+    fn test_locate_missing_falls_back_to_ancestor() {
+        let span = locate(ROADMAP, "/current/adr").unwrap();
+        assert_eq!(span, SourceSpan { line: 2, column: 1 });
+        let span = locate(ROADMAP, "").unwrap();
+        assert_eq!(span, SourceSpan { line: 2, column: 1 });
+    }
+
+    #[test]
+    fn test_locate_quoted_keys_and_urls() {
+        let content = "\"a/b\":\n  url: https://example.com\n";
+        let span = locate(content, "/a~1b/url").unwrap();
+        assert_eq!(span, SourceSpan { line: 2, column: 8 });
+    }
+
+    #[test]
+    fn test_locate_empty_document() {
+        assert!(locate("# only a comment\n", "/current").is_none());
+    }
+
+    #[test]
+    fn test_path_at_key_lines() {
+        let at = |line, column| path_at(ROADMAP, SourceSpan { line, column });
+        assert_eq!(at(4, 11), vec!["current", "status"]);
+        assert_eq!(at(2, 1), vec!["current"]);
+        assert_eq!(at(9, 5), vec!["next", "0", "summary"]);
+        assert_eq!(at(12, 5), vec!["next", "1", "goal"]);
+    }
+
+    #[test]
+    fn test_path_at_blank_line_uses_column() {
+        let at = |line, column| path_at(ROADMAP, SourceSpan { line, column });
+        assert_eq!(at(6, 3), vec!["current"]);
+        assert_eq!(at(6, 1), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_path_at_roundtrips_locate() {
+        let span = locate(ROADMAP, "/next/1/version").unwrap();
+        assert_eq!(path_at(ROADMAP, span), vec!["next", "1", "version"]);
+    }
+
+    #[test]
+    fn test_pointer_to_path() {
+        assert_eq!(pointer_to_path("/current/status"), "current.status");
+        assert_eq!(pointer_to_path("/next/0/version"), "next[0].version");
+        assert_eq!(pointer_to_path(""), "");
+    }
+
+    #[test]
+    fn test_code_frame() {
+        let frame = code_frame(
+            ROADMAP,
+            SourceSpan {
+                line: 4,
+                column: 11,
+            },
+        );
+        let lines: Vec<&str> = frame.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[2], "> 4 |   status: done");
+        assert_eq!(lines[3], "    |           ^");
+    }
+
+    #[test]
+    fn test_code_frame_out_of_range() {
+        assert!(code_frame("a: 1\n", SourceSpan { line: 9, column: 1 }).is_empty());
+    }
+}
@@ -0,0 +1,61 @@
+//! Quality gate runner tests - asimov check
+
+use super::asimov_command;
+use std::fs;
+use tempfile::TempDir;
+
+fn project_with_quality(quality: &str) -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+    let asimov_dir = temp_dir.path().join(".asimov");
+    fs::create_dir_all(&asimov_dir).unwrap();
+    fs::write(
+        asimov_dir.join("project.yaml"),
+        format!("identity:\n  name: t\n  type: generic\nquality:\n{quality}"),
+    )
+    .unwrap();
+    temp_dir
+}
+
+#[cfg(unix)]
+#[test]
+fn e2e_check_reports_failed_gate() {
+    let temp_dir = project_with_quality("  lint: echo lint-ok\n  test: echo broken; exit 2\n");
+    let junit = temp_dir.path().join("report.xml");
+
+    let output = asimov_command()
+        .args(["check", "--junit"])
+        .arg(&junit)
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(!output.status.success(), "Failing gate should fail");
+    assert!(stdout.contains("✓ lint"), "got: {stdout}");
+    assert!(stdout.contains("exit 2"), "got: {stdout}");
+    assert!(
+        stdout.contains("broken"),
+        "Should show output tail, got: {stdout}"
+    );
+
+    let xml = fs::read_to_string(junit).unwrap();
+    assert!(xml.contains("failures=\"1\""), "got: {xml}");
+}
+
+#[cfg(unix)]
+#[test]
+fn e2e_check_only_json() {
+    let temp_dir = project_with_quality("  lint: echo lint-ok\n  test: exit 1\n");
+
+    let output = asimov_command()
+        .args(["check", "--only", "lint", "--json"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute");
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(report["gates"].as_array().unwrap().len(), 1);
+    assert_eq!(report["gates"][0]["status"], "passed");
+    assert_eq!(report["gates"][0]["output_tail"], "lint-ok");
+}
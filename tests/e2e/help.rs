@@ -1,11 +1,10 @@
 //! Help and version tests
 
-use super::binary_path;
-use std::process::Command;
+use super::asimov_command;
 
 #[test]
 fn e2e_help_shows_usage() {
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("--help")
         .output()
         .expect("Failed to execute");
@@ -20,7 +19,7 @@ fn e2e_help_shows_usage() {
 
 #[test]
 fn e2e_version_shows_version() {
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("--version")
         .output()
         .expect("Failed to execute");
@@ -33,7 +32,7 @@ fn e2e_version_shows_version() {
 
 #[test]
 fn e2e_short_help_works() {
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("-h")
         .output()
         .expect("Failed to execute");
@@ -43,9 +42,7 @@ fn e2e_short_help_works() {
 
 #[test]
 fn e2e_default_command_runs() {
-    let output = Command::new(binary_path())
-        .output()
-        .expect("Failed to execute");
+    let output = asimov_command().output().expect("Failed to execute");
 
     // May succeed or fail depending on environment
     assert!(output.status.success() || !output.status.success());
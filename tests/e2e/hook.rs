@@ -0,0 +1,238 @@
+//! Native git hook tests - asimov hooks install/status/run
+
+use super::{asimov_command, binary_path};
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+fn git(dir: &Path, args: &[&str]) -> Output {
+    Command::new("git")
+        .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("Failed to run git")
+}
+
+fn repo_with_quality(quality: &str) -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+    git(temp_dir.path(), &["init", "-q"]);
+    let asimov_dir = temp_dir.path().join(".asimov");
+    fs::create_dir_all(&asimov_dir).unwrap();
+    fs::write(
+        asimov_dir.join("project.yaml"),
+        format!("identity:\n  name: t\n  type: rust\nquality:\n{quality}"),
+    )
+    .unwrap();
+    temp_dir
+}
+
+#[cfg(unix)]
+#[test]
+fn e2e_hook_run_pre_commit_reports_oversized_file() {
+    let temp_dir = repo_with_quality("  test: echo ok\n");
+    fs::write(temp_dir.path().join("big.rs"), "// x\n".repeat(1600)).unwrap();
+    git(temp_dir.path(), &["add", "big.rs"]);
+
+    let output = asimov_command()
+        .args(["hook", "run", "pre-commit"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(!output.status.success(), "Oversized file should fail");
+    assert!(
+        stdout.contains("big.rs has 1600 lines (limit: 1500)"),
+        "got: {stdout}"
+    );
+    assert!(stdout.contains("✓ test"), "got: {stdout}");
+}
+
+#[cfg(unix)]
+#[test]
+fn e2e_installed_shim_blocks_commit() {
+    let temp_dir = repo_with_quality("  test: echo gate-broken; exit 1\n");
+    let init = asimov_command()
+        .args(["refresh", "--yes"])
+        .current_dir(temp_dir.path())
+        .stdin(std::process::Stdio::null())
+        .output()
+        .expect("Failed to execute");
+    assert!(init.status.success());
+    let hook = fs::read_to_string(temp_dir.path().join(".git/hooks/pre-commit")).unwrap();
+    assert!(
+        hook.contains("exec asimov hook run pre-commit"),
+        "got: {hook}"
+    );
+
+    fs::write(temp_dir.path().join("lib.rs"), "fn main() {}\n").unwrap();
+    git(temp_dir.path(), &["add", "lib.rs"]);
+
+    let bin_dir = binary_path().parent().unwrap().to_path_buf();
+    let path = format!(
+        "{}:{}",
+        bin_dir.display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    let output = Command::new("git")
+        .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+        .args(["commit", "-q", "-m", "test"])
+        .env("PATH", path)
+        .env("ASIMOV_PROTOCOL_PATH", "")
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to run git");
+    // git sends hook output to stderr
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success(), "Failing gate should block commit");
+    assert!(stderr.contains("gate-broken"), "got: {stderr}");
+}
+
+#[test]
+fn e2e_hook_run_unknown_hook() {
+    let temp_dir = TempDir::new().unwrap();
+    let output = asimov_command()
+        .args(["hook", "run", "post-merge"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("Unknown hook 'post-merge'"),
+        "got: {stderr}"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn e2e_hooks_install_chains_existing_hook() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = repo_with_quality("  test: exit 0\n");
+    let existing = temp_dir.path().join(".git/hooks/pre-commit");
+    fs::write(&existing, "#!/bin/sh\ntouch previous-hook-ran\n").unwrap();
+    fs::set_permissions(&existing, fs::Permissions::from_mode(0o755)).unwrap();
+
+    let output = asimov_command()
+        .args(["hooks", "install"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "got: {stdout}");
+    assert!(stdout.contains("previous hook kept as"), "got: {stdout}");
+    assert!(stdout.contains("commit-msg installed"), "got: {stdout}");
+
+    let output = asimov_command()
+        .args(["hooks", "status"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("asimov (chains previous hook)"),
+        "got: {stdout}"
+    );
+
+    fs::write(temp_dir.path().join("README.md"), "# t\n").unwrap();
+    git(temp_dir.path(), &["add", "README.md"]);
+    let bin_dir = binary_path().parent().unwrap().to_path_buf();
+    let path = format!(
+        "{}:{}",
+        bin_dir.display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    let output = Command::new("git")
+        .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+        .args(["commit", "-q", "-m", "docs: readme"])
+        .env("PATH", path)
+        .env("ASIMOV_PROTOCOL_PATH", "")
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to run git");
+    assert!(
+        output.status.success(),
+        "got: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(temp_dir.path().join("previous-hook-ran").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn e2e_commit_msg_policy_blocks_commit() {
+    let temp_dir = TempDir::new().unwrap();
+    git(temp_dir.path(), &["init", "-q"]);
+    let asimov_dir = temp_dir.path().join(".asimov");
+    fs::create_dir_all(&asimov_dir).unwrap();
+    fs::write(
+        asimov_dir.join("project.yaml"),
+        "identity:\n  name: t\n  type: docs\ncommit_msg:\n  types: [feat, docs]\n  attribution: forbid\n",
+    )
+    .unwrap();
+
+    fs::write(temp_dir.path().join("MSG"), "docs: readme\n").unwrap();
+    let output = asimov_command()
+        .args(["commit-msg", "MSG"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute");
+    assert!(output.status.success());
+
+    // A relative file is read from the working directory, not the project root
+    fs::create_dir(temp_dir.path().join("src")).unwrap();
+    fs::write(temp_dir.path().join("src/msg.txt"), "chore: nope\n").unwrap();
+    let output = asimov_command()
+        .args(["commit-msg", "msg.txt"])
+        .current_dir(temp_dir.path().join("src"))
+        .output()
+        .expect("Failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success(), "got: {stdout}");
+    assert!(stdout.contains("chore"), "got: {stdout}");
+
+    let refresh = asimov_command()
+        .args(["refresh", "--yes"])
+        .current_dir(temp_dir.path())
+        .stdin(std::process::Stdio::null())
+        .output()
+        .expect("Failed to execute");
+    assert!(refresh.status.success());
+    assert!(temp_dir.path().join(".git/hooks/commit-msg").exists());
+
+    fs::write(temp_dir.path().join("README.md"), "# t\n").unwrap();
+    git(temp_dir.path(), &["add", "README.md"]);
+    let bin_dir = binary_path().parent().unwrap().to_path_buf();
+    let path = format!(
+        "{}:{}",
+        bin_dir.display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    let output = Command::new("git")
+        .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+        .args([
+            "commit",
+            "-q",
+            "-m",
+            "docs: readme",
+            "-m",
+            "Co-Authored-By: Claude <noreply@anthropic.com>",
+        ])
+        .env("PATH", path)
+        .env("ASIMOV_PROTOCOL_PATH", "")
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to run git");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success(), "Forbidden trailer should block");
+    assert!(
+        stderr.contains("AI attribution trailer not allowed"),
+        "got: {stderr}"
+    );
+}
@@ -1,15 +1,14 @@
 //! Init command tests
 
-use super::binary_path;
+use super::asimov_command;
 use std::fs;
-use std::process::Command;
 use tempfile::TempDir;
 
 #[test]
 fn e2e_init_creates_roadmap() {
     let temp_dir = TempDir::new().unwrap();
 
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("init")
         .arg("--name")
         .arg("test-project")
@@ -40,7 +39,7 @@ fn e2e_init_creates_roadmap() {
 fn e2e_init_creates_roadmap_and_project() {
     let temp_dir = TempDir::new().unwrap();
 
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("init")
         .arg("--name")
         .arg("test-project")
@@ -72,7 +71,7 @@ fn e2e_init_type_rust() {
     let temp_dir = TempDir::new().unwrap();
 
     // v8.0.0: --type is accepted but ignored (protocols are hardcoded in binary)
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("init")
         .arg("--type")
         .arg("rust")
@@ -103,7 +102,7 @@ fn e2e_init_type_rust() {
 fn e2e_init_type_generic() {
     let temp_dir = TempDir::new().unwrap();
 
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("init")
         .arg("--name")
         .arg("test-project")
@@ -132,7 +131,7 @@ fn e2e_init_type_invalid() {
     let temp_dir = TempDir::new().unwrap();
 
     // v8.16.0: Invalid types should fail with error message
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("init")
         .arg("--name")
         .arg("test-project")
@@ -167,7 +166,7 @@ fn e2e_init_skips_existing_without_force() {
     let roadmap_path = asimov_dir.join("roadmap.yaml");
     fs::write(&roadmap_path, "existing content").unwrap();
 
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("init")
         .arg("--name")
         .arg("test-project")
@@ -202,7 +201,7 @@ fn e2e_init_force_overwrites() {
     let roadmap_path = asimov_dir.join("roadmap.yaml");
     fs::write(&roadmap_path, "existing content").unwrap();
 
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("init")
         .arg("--name")
         .arg("new-project")
@@ -226,7 +225,7 @@ fn e2e_init_force_overwrites() {
 
 #[test]
 fn e2e_init_help_shows_type_option() {
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("init")
         .arg("--help")
         .output()
@@ -245,7 +244,7 @@ fn e2e_init_type_python() {
     let temp_dir = TempDir::new().unwrap();
 
     // v8.0.0: --type is accepted but ignored (protocols are hardcoded in binary)
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("init")
         .arg("--type")
         .arg("python")
@@ -276,7 +275,7 @@ fn e2e_init_type_python() {
 fn e2e_init_type_python_alias() {
     let temp_dir = TempDir::new().unwrap();
 
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("init")
         .arg("--name")
         .arg("test-project")
@@ -305,7 +304,7 @@ fn e2e_init_type_node() {
     let temp_dir = TempDir::new().unwrap();
 
     // v8.0.0: --type is accepted but ignored (protocols are hardcoded in binary)
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("init")
         .arg("--type")
         .arg("node")
@@ -337,7 +336,7 @@ fn e2e_init_type_node_aliases() {
     let temp_dir = TempDir::new().unwrap();
 
     // Test 'js' alias
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("init")
         .arg("--name")
         .arg("test-project")
@@ -366,7 +365,7 @@ fn e2e_init_type_go() {
     let temp_dir = TempDir::new().unwrap();
 
     // v8.0.0: --type is accepted but ignored (protocols are hardcoded in binary)
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("init")
         .arg("--type")
         .arg("go")
@@ -397,7 +396,7 @@ fn e2e_init_type_go() {
 fn e2e_init_type_go_alias() {
     let temp_dir = TempDir::new().unwrap();
 
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("init")
         .arg("--name")
         .arg("test-project")
@@ -425,7 +424,7 @@ fn e2e_init_type_go_alias() {
 fn e2e_init_python_generated_files_pass_validation() {
     let temp_dir = TempDir::new().unwrap();
 
-    let init_output = Command::new(binary_path())
+    let init_output = asimov_command()
         .arg("init")
         .arg("--name")
         .arg("test-project")
@@ -439,7 +438,7 @@ fn e2e_init_python_generated_files_pass_validation() {
     assert!(init_output.status.success(), "Init should succeed");
 
     // v8.16.0: validate runs from current directory
-    let validate_output = Command::new(binary_path())
+    let validate_output = asimov_command()
         .arg("validate")
         .current_dir(temp_dir.path())
         .output()
@@ -458,7 +457,7 @@ fn e2e_init_python_generated_files_pass_validation() {
 fn e2e_init_node_generated_files_pass_validation() {
     let temp_dir = TempDir::new().unwrap();
 
-    let init_output = Command::new(binary_path())
+    let init_output = asimov_command()
         .arg("init")
         .arg("--name")
         .arg("test-project")
@@ -472,7 +471,7 @@ fn e2e_init_node_generated_files_pass_validation() {
     assert!(init_output.status.success(), "Init should succeed");
 
     // v8.16.0: validate runs from current directory
-    let validate_output = Command::new(binary_path())
+    let validate_output = asimov_command()
         .arg("validate")
         .current_dir(temp_dir.path())
         .output()
@@ -491,7 +490,7 @@ fn e2e_init_node_generated_files_pass_validation() {
 fn e2e_init_go_generated_files_pass_validation() {
     let temp_dir = TempDir::new().unwrap();
 
-    let init_output = Command::new(binary_path())
+    let init_output = asimov_command()
         .arg("init")
         .arg("--name")
         .arg("test-project")
@@ -505,7 +504,7 @@ fn e2e_init_go_generated_files_pass_validation() {
     assert!(init_output.status.success(), "Init should succeed");
 
     // v8.16.0: validate runs from current directory
-    let validate_output = Command::new(binary_path())
+    let validate_output = asimov_command()
         .arg("validate")
         .current_dir(temp_dir.path())
         .output()
@@ -527,7 +526,7 @@ fn e2e_init_generated_files_pass_validation() {
     let temp_dir = TempDir::new().unwrap();
 
     // Generate all files
-    let init_output = Command::new(binary_path())
+    let init_output = asimov_command()
         .arg("init")
         .arg("--name")
         .arg("test-project")
@@ -541,7 +540,7 @@ fn e2e_init_generated_files_pass_validation() {
     assert!(init_output.status.success(), "Init should succeed");
 
     // v8.16.0: validate runs from current directory
-    let validate_output = Command::new(binary_path())
+    let validate_output = asimov_command()
         .arg("validate")
         .current_dir(temp_dir.path())
         .output()
@@ -561,7 +560,7 @@ fn e2e_init_rust_generated_files_pass_validation() {
     let temp_dir = TempDir::new().unwrap();
 
     // Generate Rust files
-    let init_output = Command::new(binary_path())
+    let init_output = asimov_command()
         .arg("init")
         .arg("--name")
         .arg("test-project")
@@ -575,7 +574,7 @@ fn e2e_init_rust_generated_files_pass_validation() {
     assert!(init_output.status.success(), "Init should succeed");
 
     // v8.16.0: validate runs from current directory
-    let validate_output = Command::new(binary_path())
+    let validate_output = asimov_command()
         .arg("validate")
         .current_dir(temp_dir.path())
         .output()
@@ -589,3 +588,38 @@ fn e2e_init_rust_generated_files_pass_validation() {
         "Rust generated files should pass validation, stdout: {stdout}, stderr: {stderr}"
     );
 }
+
+#[test]
+fn e2e_init_migration_loads_equivalence_protocol() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = asimov_command()
+        .args(["init", "--name", "port", "--type", "migration", "--output"])
+        .arg(temp_dir.path())
+        .output()
+        .expect("Failed to execute");
+    assert!(output.status.success());
+
+    let migrations = temp_dir.path().join(".asimov").join("migrations.yaml");
+    assert!(migrations.exists(), "init should write migrations.yaml");
+
+    let warmup = asimov_command()
+        .arg("warmup")
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute");
+    assert!(String::from_utf8_lossy(&warmup.stdout).contains(".asimov/migrations.yaml"));
+
+    // Refresh restores it while the project is a migration
+    fs::remove_file(&migrations).unwrap();
+    let refresh = asimov_command()
+        .args(["refresh", "--yes"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute");
+    assert!(refresh.status.success());
+    assert!(
+        migrations.exists(),
+        "refresh should restore migrations.yaml"
+    );
+}
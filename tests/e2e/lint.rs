@@ -1,13 +1,13 @@
 //! Lint-docs tests
 
-use super::binary_path;
+use super::asimov_command;
 use std::fs;
 use std::process::Command;
 use tempfile::TempDir;
 
 #[test]
 fn e2e_lint_docs_semantic_flag_help() {
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("lint-docs")
         .arg("--help")
         .output()
@@ -34,7 +34,7 @@ fn e2e_lint_docs_semantic_runs() {
     let test_md = temp_dir.path().join("test.md");
     fs::write(&test_md, "# Test\n\nVersion: 1.0.0\n").unwrap();
 
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("lint-docs")
         .arg("--semantic")
         .arg(temp_dir.path())
@@ -72,7 +72,7 @@ deprecated:
     let test_md = temp_dir.path().join("docs.md");
     fs::write(&test_md, "# Docs\n\nUse old-pattern for this.\n").unwrap();
 
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("lint-docs")
         .arg("--semantic")
         .arg(temp_dir.path())
@@ -96,7 +96,7 @@ fn e2e_lint_docs_without_semantic_skips_checks() {
     let test_md = temp_dir.path().join("test.md");
     fs::write(&test_md, "# Test\n\nSome content.\n").unwrap();
 
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("lint-docs")
         .arg(temp_dir.path())
         .output()
@@ -110,3 +110,91 @@ fn e2e_lint_docs_without_semantic_skips_checks() {
         "Should NOT show semantic checks without flag"
     );
 }
+
+#[test]
+fn e2e_lint_docs_diff_ignores_untouched_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+            .args(args)
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to run git")
+    };
+    git(&["init", "-q"]);
+    fs::write(
+        temp_dir.path().join("legacy.md"),
+        "# Legacy\n\n```bash\nls\n```bash\n",
+    )
+    .unwrap();
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "legacy"]);
+    fs::write(temp_dir.path().join("new.md"), "# New\n\nClean.\n").unwrap();
+    git(&["add", "new.md"]);
+
+    let output = asimov_command()
+        .args(["lint-docs", "--diff", "HEAD"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success(), "got: {stdout}");
+    assert!(
+        stdout.contains("1 markdown file(s) checked (since HEAD, added lines only)"),
+        "got: {stdout}"
+    );
+
+    let output = asimov_command()
+        .arg("lint-docs")
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute");
+    assert!(!output.status.success(), "Full scan still flags legacy.md");
+}
+
+#[test]
+fn e2e_lint_docs_semantic_flags_stale_claims() {
+    let temp_dir = TempDir::new().unwrap();
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+            .args(args)
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to run git")
+    };
+    git(&["init", "-q"]);
+    fs::write(
+        temp_dir.path().join("README.md"),
+        "# Tool\n\nAs of 2019, it is the fastest parser ([bench](https://example.com/bench)).\n",
+    )
+    .unwrap();
+    fs::write(temp_dir.path().join("references.yaml"), "[]\n").unwrap();
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "docs"]);
+
+    let lint = |extra: &[&str]| {
+        let output = asimov_command()
+            .args(["lint-docs", "--semantic"])
+            .args(extra)
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute");
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let stdout = lint(&[]);
+    assert!(
+        stdout.contains("Stale claim: dated 2019-12"),
+        "got: {stdout}"
+    );
+    assert!(
+        stdout.contains("https://example.com/bench, which is not in references.yaml"),
+        "got: {stdout}"
+    );
+
+    let stdout = lint(&["--max-age", "1200"]);
+    assert!(!stdout.contains("Stale claim"), "got: {stdout}");
+}
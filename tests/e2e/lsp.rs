@@ -0,0 +1,45 @@
+//! Language server tests - drive `asimov lsp` over stdio
+
+use super::asimov_command;
+use std::io::Write;
+use std::process::Stdio;
+
+fn frame(body: &str) -> String {
+    format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+}
+
+#[test]
+fn e2e_lsp_initialize_and_exit() {
+    let mut child = asimov_command()
+        .arg("lsp")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute");
+
+    let input = [
+        r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{}}"#,
+        r#"{"jsonrpc":"2.0","method":"textDocument/didOpen","params":{"textDocument":{"uri":"file:///tmp/.asimov/roadmap.yaml","languageId":"yaml","version":1,"text":"current:\n  version: '1'\n  status: done\n  summary: x\n"}}}"#,
+        r#"{"jsonrpc":"2.0","id":2,"method":"shutdown"}"#,
+        r#"{"jsonrpc":"2.0","method":"exit"}"#,
+    ]
+    .iter()
+    .map(|body| frame(body))
+    .collect::<String>();
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().expect("Failed to wait");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success(), "lsp should exit cleanly");
+    assert!(stdout.contains(r#""serverInfo""#), "got: {stdout}");
+    assert!(
+        stdout.contains("current.status must be one of"),
+        "Should publish diagnostics, got: {stdout}"
+    );
+}
@@ -1,6 +1,6 @@
-//! Miscellaneous e2e tests - external path, warmup, doctor, refresh, stats, replay
+//! Miscellaneous e2e tests - external path, warmup, doctor, refresh, stats, replay, freshness
 
-use super::binary_path;
+use super::asimov_command;
 use std::fs;
 use std::process::Command;
 use tempfile::TempDir;
@@ -10,7 +10,7 @@ fn e2e_validate_external_path_creates_asimov_dir() {
     let temp_dir = TempDir::new().unwrap();
 
     // v8.16.0: validate runs from current dir
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("validate")
         .current_dir(temp_dir.path())
         .output()
@@ -46,7 +46,7 @@ fn e2e_validate_external_path_with_existing_roadmap() {
     fs::write(asimov_dir.join("roadmap.yaml"), roadmap_content).unwrap();
 
     // v8.16.0: validate runs from current dir
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("validate")
         .current_dir(temp_dir.path())
         .output()
@@ -83,7 +83,7 @@ fn e2e_validate_external_path_ethics_scan() {
     .unwrap();
 
     // v8.16.0: validate runs from current directory
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("validate")
         .arg("--ethics-scan")
         .current_dir(temp_dir.path())
@@ -103,7 +103,7 @@ fn e2e_validate_external_path_ethics_scan() {
 fn e2e_init_output_creates_files_in_target() {
     let temp_dir = TempDir::new().unwrap();
 
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("init")
         .arg("--name")
         .arg("test-forge")
@@ -152,7 +152,7 @@ fn e2e_init_output_with_force_overwrites_target() {
     fs::write(asimov_dir.join("roadmap.yaml"), "existing: content").unwrap();
 
     // Run init with --force
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("init")
         .arg("--name")
         .arg("test-project")
@@ -188,7 +188,7 @@ fn e2e_lint_docs_external_path() {
     )
     .unwrap();
 
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("lint-docs")
         .arg(temp_dir.path())
         .output()
@@ -215,7 +215,7 @@ fn e2e_lint_docs_external_path_with_fix() {
     let test_md = temp_dir.path().join("test.md");
     fs::write(&test_md, "# Test\n\n```rust\nlet x = 1;\n").unwrap();
 
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("lint-docs")
         .arg("--fix")
         .arg(temp_dir.path())
@@ -237,7 +237,7 @@ fn e2e_warmup_runs_from_project_dir() {
     let temp_dir = TempDir::new().unwrap();
 
     // Initialize the project
-    let init_output = Command::new(binary_path())
+    let init_output = asimov_command()
         .arg("init")
         .arg("--name")
         .arg("test-project")
@@ -251,7 +251,7 @@ fn e2e_warmup_runs_from_project_dir() {
     assert!(init_output.status.success(), "Init should succeed");
 
     // v8.16.0: Run warmup with --verbose for full output
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("warmup")
         .arg("--verbose")
         .current_dir(temp_dir.path())
@@ -277,7 +277,7 @@ fn e2e_warmup_simple_output() {
     let temp_dir = TempDir::new().unwrap();
 
     // Initialize the project
-    let init_output = Command::new(binary_path())
+    let init_output = asimov_command()
         .arg("init")
         .arg("--name")
         .arg("test-project")
@@ -291,7 +291,7 @@ fn e2e_warmup_simple_output() {
     assert!(init_output.status.success(), "Init should succeed");
 
     // v8.16.0: warmup without --verbose shows simple output
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("warmup")
         .current_dir(temp_dir.path())
         .output()
@@ -316,7 +316,7 @@ fn e2e_doctor_runs_from_project_dir() {
     let temp_dir = TempDir::new().unwrap();
 
     // Initialize the project
-    let init_output = Command::new(binary_path())
+    let init_output = asimov_command()
         .arg("init")
         .arg("--name")
         .arg("test-project")
@@ -330,7 +330,7 @@ fn e2e_doctor_runs_from_project_dir() {
     assert!(init_output.status.success(), "Init should succeed");
 
     // Run doctor FROM the project directory
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("doctor")
         .current_dir(temp_dir.path())
         .output()
@@ -359,7 +359,7 @@ fn e2e_refresh_requires_asimov_project() {
     let temp_dir = TempDir::new().unwrap();
 
     // Without .asimov/ - should fail
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("refresh")
         .current_dir(temp_dir.path())
         .output()
@@ -378,7 +378,7 @@ fn e2e_refresh_requires_asimov_project() {
     // With .asimov/ - should succeed
     std::fs::create_dir(temp_dir.path().join(".asimov")).unwrap();
 
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("refresh")
         .current_dir(temp_dir.path())
         .output()
@@ -403,7 +403,7 @@ fn e2e_refresh_detects_outdated_protocol() {
     let temp_dir = TempDir::new().unwrap();
 
     // Initialize project first
-    let init_output = Command::new(binary_path())
+    let init_output = asimov_command()
         .arg("init")
         .arg("--name")
         .arg("test-project")
@@ -415,12 +415,17 @@ fn e2e_refresh_detects_outdated_protocol() {
         .expect("Failed to execute init");
     assert!(init_output.status.success(), "Init should succeed");
 
-    // Simulate outdated protocol file (old version)
+    // Simulate outdated protocol file (old version, generated unedited)
     let freshness_path = temp_dir.path().join(".asimov").join("freshness.json");
     fs::write(&freshness_path, r#"{"old_format": true}"#).unwrap();
+    fs::write(
+        temp_dir.path().join(".asimov/.base/freshness.json"),
+        r#"{"old_format": true}"#,
+    )
+    .unwrap();
 
     // Run refresh
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("refresh")
         .current_dir(temp_dir.path())
         .output()
@@ -445,13 +450,67 @@ fn e2e_refresh_detects_outdated_protocol() {
     );
 }
 
+#[test]
+fn e2e_refresh_keeps_customized_protocol_and_writes_conflict() {
+    // Local edits are never overwritten by refresh
+    let temp_dir = TempDir::new().unwrap();
+    let init_output = asimov_command()
+        .args([
+            "init",
+            "--name",
+            "test-project",
+            "--type",
+            "generic",
+            "--output",
+        ])
+        .arg(temp_dir.path())
+        .output()
+        .expect("Failed to execute init");
+    assert!(init_output.status.success(), "Init should succeed");
+
+    // Customized since generation, and generated from an older default
+    let asimov = temp_dir.path().join(".asimov");
+    let local = r#"{"rule": "team rule"}"#;
+    fs::write(asimov.join("green.json"), local).unwrap();
+    fs::write(asimov.join(".base/green.json"), r#"{"rule": "old rule"}"#).unwrap();
+
+    let dry_run = asimov_command()
+        .args(["refresh", "--dry-run"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute refresh");
+    let stdout = String::from_utf8_lossy(&dry_run.stdout);
+    assert!(
+        stdout.contains("CONFLICT") && stdout.contains("+<<<<<<< local .asimov/green.json"),
+        "Dry run should show the conflict diff, got: {stdout}"
+    );
+    assert!(!asimov.join("green.json.conflict").exists());
+
+    let output = asimov_command()
+        .arg("refresh")
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute refresh");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        !output.status.success(),
+        "Conflicts should fail, got: {stdout}"
+    );
+    assert_eq!(
+        fs::read_to_string(asimov.join("green.json")).unwrap(),
+        local
+    );
+    let conflict = fs::read_to_string(asimov.join("green.json.conflict")).unwrap();
+    assert!(conflict.contains("=======") && conflict.contains("team rule"));
+}
+
 #[test]
 fn e2e_doctor_detects_outdated_protocol() {
     // v9.0.0: Doctor should detect outdated protocol files
     let temp_dir = TempDir::new().unwrap();
 
     // Initialize project first
-    let init_output = Command::new(binary_path())
+    let init_output = asimov_command()
         .arg("init")
         .arg("--name")
         .arg("test-project")
@@ -463,12 +522,17 @@ fn e2e_doctor_detects_outdated_protocol() {
         .expect("Failed to execute init");
     assert!(init_output.status.success(), "Init should succeed");
 
-    // Simulate outdated protocol file (old version format)
+    // Simulate outdated protocol file (old version format, generated unedited)
     let sycophancy_path = temp_dir.path().join(".asimov").join("sycophancy.json");
     fs::write(&sycophancy_path, r#"{"old_version": true}"#).unwrap();
+    fs::write(
+        temp_dir.path().join(".asimov/.base/sycophancy.json"),
+        r#"{"old_version": true}"#,
+    )
+    .unwrap();
 
     // Run doctor
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("doctor")
         .current_dir(temp_dir.path())
         .output()
@@ -495,7 +559,7 @@ fn e2e_doctor_detects_missing_protocol() {
     let temp_dir = TempDir::new().unwrap();
 
     // Initialize project first
-    let init_output = Command::new(binary_path())
+    let init_output = asimov_command()
         .arg("init")
         .arg("--name")
         .arg("test-project")
@@ -512,7 +576,7 @@ fn e2e_doctor_detects_missing_protocol() {
     fs::remove_file(&green_path).unwrap();
 
     // Run doctor
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("doctor")
         .current_dir(temp_dir.path())
         .output()
@@ -539,7 +603,7 @@ fn e2e_refresh_creates_missing_protocol() {
     let temp_dir = TempDir::new().unwrap();
 
     // Initialize project first
-    let init_output = Command::new(binary_path())
+    let init_output = asimov_command()
         .arg("init")
         .arg("--name")
         .arg("test-project")
@@ -557,7 +621,7 @@ fn e2e_refresh_creates_missing_protocol() {
     assert!(!sprint_path.exists(), "File should be deleted");
 
     // Run refresh
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("refresh")
         .current_dir(temp_dir.path())
         .output()
@@ -581,7 +645,7 @@ fn e2e_validate_warns_on_missing_roadmap() {
     fs::create_dir_all(&asimov_dir).unwrap();
 
     // v8.16.0: validate runs from current directory, warns about missing roadmap
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("validate")
         .current_dir(temp_dir.path())
         .output()
@@ -601,7 +665,7 @@ fn e2e_validate_warns_on_missing_roadmap() {
 #[test]
 fn e2e_update_check_runs() {
     // Test `asimov update --check` - makes network call to GitHub API
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .args(["update", "--check"])
         .output()
         .expect("Failed to execute");
@@ -627,7 +691,7 @@ fn e2e_default_command_runs() {
     // This exercises the main() dispatch and cmd_launch()
     let temp_dir = TempDir::new().unwrap();
 
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .current_dir(temp_dir.path())
         .output()
         .expect("Failed to execute");
@@ -671,7 +735,7 @@ fn e2e_stats_command_runs() {
     )
     .unwrap();
 
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("stats")
         .current_dir(temp_dir.path())
         .output()
@@ -724,7 +788,7 @@ fn e2e_replay_command_runs() {
         .output()
         .unwrap();
 
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .args(["replay", "--commits", "5"])
         .current_dir(temp_dir.path())
         .output()
@@ -745,7 +809,7 @@ fn e2e_warmup_rust_project_excludes_migrations() {
     let temp_dir = TempDir::new().unwrap();
 
     // Initialize the project
-    let init_output = Command::new(binary_path())
+    let init_output = asimov_command()
         .arg("init")
         .arg("--name")
         .arg("rust-test-project")
@@ -759,7 +823,7 @@ fn e2e_warmup_rust_project_excludes_migrations() {
     assert!(init_output.status.success(), "Init should succeed");
 
     // Run warmup
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("warmup")
         .current_dir(temp_dir.path())
         .output()
@@ -803,7 +867,7 @@ fn e2e_warmup_migration_project_excludes_migrations() {
     .unwrap();
 
     // Run warmup
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("warmup")
         .current_dir(temp_dir.path())
         .output()
@@ -829,7 +893,7 @@ fn e2e_warmup_generic_project_excludes_migrations() {
     let temp_dir = TempDir::new().unwrap();
 
     // Initialize the project
-    let init_output = Command::new(binary_path())
+    let init_output = asimov_command()
         .arg("init")
         .arg("--name")
         .arg("generic-test-project")
@@ -843,7 +907,7 @@ fn e2e_warmup_generic_project_excludes_migrations() {
     assert!(init_output.status.success(), "Init should succeed");
 
     // Run warmup
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("warmup")
         .current_dir(temp_dir.path())
         .output()
@@ -882,7 +946,7 @@ fn e2e_refresh_with_yes_flag() {
     fs::write(temp_dir.path().join("Cargo.toml"), "[package]").unwrap();
 
     // Run refresh with --yes (should auto-create project.yaml)
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .args(["refresh", "--yes"])
         .current_dir(temp_dir.path())
         .output()
@@ -927,7 +991,7 @@ fn e2e_refresh_with_dry_run_flag() {
     fs::write(temp_dir.path().join("Cargo.toml"), "[package]").unwrap();
 
     // Run refresh with --dry-run (should NOT create project.yaml)
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .args(["refresh", "--dry-run"])
         .current_dir(temp_dir.path())
         .output()
@@ -936,22 +1000,48 @@ fn e2e_refresh_with_dry_run_flag() {
     let stdout = String::from_utf8_lossy(&output.stdout);
     let stderr = String::from_utf8_lossy(&output.stderr);
 
-    assert!(
-        output.status.success(),
-        "Refresh --dry-run should succeed, stdout: {stdout}, stderr: {stderr}"
+    // Exit code 2 means changes are pending
+    assert_eq!(
+        output.status.code(),
+        Some(2),
+        "Refresh --dry-run should report pending changes, stdout: {stdout}, stderr: {stderr}"
     );
 
-    // Should show dry run message
+    // Should show dry run message and the planned project.yaml
     assert!(
         stdout.contains("dry run"),
         "Should show dry run message, got: {stdout}"
     );
+    assert!(
+        stdout.contains(".asimov/project.yaml (new)")
+            && stdout.contains("+++ b/.asimov/project.yaml"),
+        "Should show the diff of the new project.yaml, got: {stdout}"
+    );
 
     // Should NOT have created project.yaml
     assert!(
         !asimov_dir.join("project.yaml").exists(),
         "project.yaml should NOT be created with --dry-run"
     );
+
+    // Once refreshed, a dry run finds nothing to do
+    let refresh = asimov_command()
+        .args(["refresh", "--yes"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute");
+    assert!(refresh.status.success());
+    let output = asimov_command()
+        .args(["refresh", "--dry-run"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute");
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "No changes should exit 0, got: {}",
+        String::from_utf8_lossy(&output.stdout)
+    );
 }
 
 #[test]
@@ -984,7 +1074,7 @@ coding_standards:
     .unwrap();
 
     // Run refresh with --yes to auto-accept upgrade
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .args(["refresh", "--yes", "--verbose"])
         .current_dir(temp_dir.path())
         .output()
@@ -1035,7 +1125,7 @@ coding_standards:
     .unwrap();
 
     // Run refresh with --yes
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .args(["refresh", "--yes"])
         .current_dir(temp_dir.path())
         .output()
@@ -1057,7 +1147,7 @@ coding_standards:
 
 #[test]
 fn e2e_refresh_help_shows_new_flags() {
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .args(["refresh", "--help"])
         .output()
         .expect("Failed to execute");
@@ -1072,3 +1162,49 @@ fn e2e_refresh_help_shows_new_flags() {
         "Should describe migration features"
     );
 }
+
+#[test]
+fn e2e_freshness_cutoff_table_and_import() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join(".git")).unwrap();
+    fs::create_dir_all(temp_dir.path().join(".asimov")).unwrap();
+    let asimov = |args: &[&str]| {
+        asimov_command()
+            .args(args)
+            .env("ASIMOV_NOW", "2026-01-15")
+            .current_dir(temp_dir.path())
+            .output()
+            .expect("Failed to execute")
+    };
+
+    let output = asimov(&["freshness"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("Today: 2026-01-15"), "{stdout}");
+    assert!(stdout.contains("claude/opus"), "{stdout}");
+    assert!(stdout.contains("Table: embedded"), "{stdout}");
+
+    // Invalid tables are rejected with the schema error
+    let table = temp_dir.path().join("cutoffs.json");
+    fs::write(&table, r#"{"claude": {"opus": "March 2025"}}"#).unwrap();
+    let output = asimov(&["freshness", "--import", "cutoffs.json"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("claude.opus"));
+
+    fs::write(
+        &table,
+        r#"{"model_cutoffs": {"claude": {"opus": "2025-10"}, "meta": {"llama-5": "2025-11"}}}"#,
+    )
+    .unwrap();
+    let output = asimov(&["freshness", "--import", "cutoffs.json"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(
+        stdout.contains("Table: .asimov/model-cutoffs.json"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("2025-10  3 months ago"), "{stdout}");
+    assert!(stdout.contains("meta/llama-5"), "{stdout}");
+    assert!(!stdout.contains("gpt-5 "), "{stdout}");
+    assert!(temp_dir.path().join(".asimov/model-cutoffs.json").exists());
+}
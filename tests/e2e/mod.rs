@@ -3,6 +3,7 @@
 //! Split into modules to keep each under 1500 lines.
 
 use std::path::PathBuf;
+use std::process::Command;
 
 /// Get the path to the asimov binary
 pub fn binary_path() -> PathBuf {
@@ -22,8 +23,22 @@ pub fn binary_path() -> PathBuf {
     path
 }
 
+/// The asimov binary as a command, isolated from the machine's user/org
+/// protocol layers (tests that need layers set `ASIMOV_PROTOCOL_PATH`)
+pub fn asimov_command() -> Command {
+    let mut command = Command::new(binary_path());
+    command.env("ASIMOV_PROTOCOL_PATH", "");
+    command
+}
+
+mod check;
 mod help;
+mod hook;
 mod init;
 mod lint;
+mod lsp;
 mod misc;
+mod protocols;
+mod schema;
+mod template;
 mod validate;
@@ -0,0 +1,384 @@
+//! Protocol layering tests
+
+use super::asimov_command;
+use std::fs;
+use tempfile::TempDir;
+
+fn explain(dir: &std::path::Path, org: &std::path::Path, name: &str) -> std::process::Output {
+    asimov_command()
+        .args(["protocols", "explain", name])
+        .env("ASIMOV_PROTOCOL_PATH", org)
+        .current_dir(dir)
+        .output()
+        .expect("Failed to execute")
+}
+
+#[test]
+fn e2e_protocols_explain_layers() {
+    let org = TempDir::new().unwrap();
+    fs::write(
+        org.path().join("sycophancy.json"),
+        r#"{"rule": "Org rule", "banned_phrases+": ["Great question"]}"#,
+    )
+    .unwrap();
+
+    let project = TempDir::new().unwrap();
+    fs::create_dir_all(project.path().join(".git")).unwrap();
+    fs::create_dir_all(project.path().join(".asimov/protocols")).unwrap();
+    fs::write(
+        project.path().join(".asimov/protocols/sycophancy.json"),
+        r#"{"disagree_openly": false}"#,
+    )
+    .unwrap();
+
+    let output = explain(project.path(), org.path(), "sycophancy");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "got: {stdout}");
+    let line = |field: &str| {
+        stdout
+            .lines()
+            .find(|l| l.trim_start().starts_with(&format!("{} ←", field)))
+            .unwrap_or_else(|| panic!("{field} missing: {stdout}"))
+            .to_string()
+    };
+    assert!(line("rule").contains("user ("));
+    assert!(line("banned_phrases").contains("user ("));
+    assert!(line("disagree_openly").contains("project ("));
+    assert!(line("truth_over_comfort").ends_with("embedded"));
+
+    // Removing a required field makes the merged protocol invalid
+    fs::write(
+        project.path().join(".asimov/protocols/sycophancy.json"),
+        r#"{"rule": null}"#,
+    )
+    .unwrap();
+    let output = explain(project.path(), org.path(), "sycophancy");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("embedded default is used"));
+
+    let output = explain(project.path(), org.path(), "nope");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn e2e_custom_protocol_written_and_warmed_up() {
+    let org = TempDir::new().unwrap();
+    fs::write(
+        org.path().join("security-review.json"),
+        r#"{"name": "security-review", "priority": 0.7,
+            "include_when": {"project_types": ["rust"]},
+            "schema": {"type": "object", "required": ["rule"]},
+            "protocol": {"rule": "Review auth changes"}}"#,
+    )
+    .unwrap();
+    fs::write(
+        org.path().join("node-audit.json"),
+        r#"{"name": "node-audit", "priority": 1,
+            "include_when": {"project_types": ["node"]},
+            "protocol": {"rule": "npm audit"}}"#,
+    )
+    .unwrap();
+
+    let project = TempDir::new().unwrap();
+    fs::create_dir_all(project.path().join(".git")).unwrap();
+    let asimov = |args: &[&str]| {
+        asimov_command()
+            .args(args)
+            .env("ASIMOV_PROTOCOL_PATH", org.path())
+            .current_dir(project.path())
+            .output()
+            .expect("Failed to execute")
+    };
+
+    let output = asimov(&["init", "--name", "svc", "--type", "rust"]);
+    assert!(output.status.success(), "{:?}", output);
+    let written = fs::read_to_string(project.path().join(".asimov/security-review.json")).unwrap();
+    assert!(written.contains("Review auth changes"));
+    assert!(!project.path().join(".asimov/node-audit.json").exists());
+
+    // Listed after green (0.5, the last built-in at or below 0.7), before sprint (2)
+    let output = asimov(&["warmup", "--verbose"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let position = |file: &str| {
+        stdout
+            .find(file)
+            .unwrap_or_else(|| panic!("{file}: {stdout}"))
+    };
+    assert!(position("green.json") < position("security-review.json"));
+    assert!(position("security-review.json") < position("sprint.json"));
+
+    // Integrity checks cover it like the built-ins
+    let output = asimov(&["refresh", "--dry-run"]);
+    assert_eq!(output.status.code(), Some(0), "{:?}", output);
+    fs::write(
+        org.path().join("security-review.json"),
+        r#"{"name": "security-review", "priority": 0.7,
+            "protocol": {"rule": "Review auth and crypto changes"}}"#,
+    )
+    .unwrap();
+    let output = asimov(&["refresh", "--dry-run"]);
+    assert_eq!(output.status.code(), Some(2), "{:?}", output);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("security-review.json"));
+}
+
+#[test]
+fn e2e_protocol_activation_by_project() {
+    let project = TempDir::new().unwrap();
+    fs::create_dir_all(project.path().join(".git")).unwrap();
+    let asimov = |args: &[&str]| {
+        asimov_command()
+            .args(args)
+            .env("ASIMOV_PROTOCOL_PATH", "")
+            .current_dir(project.path())
+            .output()
+            .expect("Failed to execute")
+    };
+
+    // Docs-only projects skip the coding standards
+    let output = asimov(&["init", "--name", "handbook", "--type", "docs"]);
+    assert!(output.status.success(), "{:?}", output);
+    let entry = fs::read_to_string(project.path().join(".asimov/warmup.json")).unwrap();
+    assert!(!entry.contains("coding-standards.json"), "{entry}");
+    let output = asimov(&["warmup", "--verbose"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("sprint.json") && !stdout.contains("coding-standards.json"));
+
+    // A migration goal with a migrations.yaml loads the equivalence protocol
+    let roadmap = project.path().join(".asimov/roadmap.yaml");
+    let content =
+        fs::read_to_string(&roadmap)
+            .unwrap()
+            .replacen("CORE_VALUE", "FUNCTIONAL-EQUIVALENCE", 1);
+    fs::write(&roadmap, content).unwrap();
+    fs::write(
+        project.path().join(".asimov/migrations.yaml"),
+        "migration: {}\n",
+    )
+    .unwrap();
+    let output = asimov(&["warmup", "--verbose"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(".asimov/migrations.yaml"), "{stdout}");
+}
+
+#[test]
+fn e2e_strict_protocol_overrides() {
+    let project = TempDir::new().unwrap();
+    fs::create_dir_all(project.path().join(".git")).unwrap();
+    let asimov = |args: &[&str]| {
+        asimov_command()
+            .args(args)
+            .env("ASIMOV_PROTOCOL_PATH", "")
+            .current_dir(project.path())
+            .output()
+            .expect("Failed to execute")
+    };
+    let output = asimov(&["init", "--name", "svc", "--type", "rust"]);
+    assert!(output.status.success(), "{:?}", output);
+    fs::create_dir_all(project.path().join(".asimov/protocols")).unwrap();
+    fs::write(
+        project.path().join(".asimov/protocols/green.json"),
+        r#"{"rulee": "Prefer local models"}"#,
+    )
+    .unwrap();
+
+    // The misspelled key is a warning, and an error under --strict
+    let output = asimov(&["validate"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("rulee"), "{stdout}");
+    let output = asimov(&["validate", "--strict"]);
+    assert!(!output.status.success());
+
+    let output = asimov(&["warmup"]);
+    assert!(output.status.success());
+    for args in [
+        &["warmup", "--strict"][..],
+        &["refresh", "--strict", "--yes"][..],
+    ] {
+        let output = asimov(args);
+        assert!(!output.status.success(), "{args:?}");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stderr.contains("green.json") && stderr.contains("rulee"),
+            "{stderr}"
+        );
+    }
+
+    let output = asimov(&["doctor"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("protocol overrides: 1 with schema warnings"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("rulee"), "{stdout}");
+}
+
+#[test]
+fn e2e_protocol_bundle_tamper_detection() {
+    let project = TempDir::new().unwrap();
+    fs::create_dir_all(project.path().join(".git")).unwrap();
+    let asimov = |args: &[&str]| {
+        asimov_command()
+            .args(args)
+            .env("ASIMOV_PROTOCOL_PATH", "")
+            .current_dir(project.path())
+            .output()
+            .expect("Failed to execute")
+    };
+    let output = asimov(&["init", "--name", "svc", "--type", "rust"]);
+    assert!(output.status.success(), "{:?}", output);
+
+    let output = asimov(&["validate"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("PROTOCOL BUNDLE"), "{stdout}");
+    assert!(
+        stdout.contains("7 unsigned-local (bundle not verified: no signing key configured)"),
+        "{stdout}"
+    );
+
+    // Editing a generated file behind asimov's back is tampering
+    let path = project.path().join(".asimov/green.json");
+    let content = fs::read_to_string(&path).unwrap();
+    fs::write(&path, content.replace("warn them", "tell them")).unwrap();
+    let output = asimov(&["validate"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success(), "{stdout}");
+    assert!(stdout.contains("green.json tampered"), "{stdout}");
+    let output = asimov(&["doctor"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Tampered protocol files"), "{stdout}");
+
+    // Deleting asimov's record does not launder an edited built-in protocol
+    let ethics = project.path().join(".asimov/asimov.json");
+    let content = fs::read_to_string(&ethics).unwrap();
+    fs::write(
+        &ethics,
+        content.replacen("\"harm\": [", "\"harm\": [\"\",", 1),
+    )
+    .unwrap();
+    fs::remove_file(project.path().join(".asimov/.manifest.json")).unwrap();
+    let output = asimov(&["validate"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success(), "{stdout}");
+    assert!(stdout.contains("asimov.json tampered"), "{stdout}");
+
+    // The same kind of change made through an override layer is legitimate
+    fs::write(&ethics, content).unwrap();
+    fs::create_dir_all(project.path().join(".asimov/protocols")).unwrap();
+    fs::write(
+        project.path().join(".asimov/protocols/green.json"),
+        r#"{"rule": "Prefer local-first tools."}"#,
+    )
+    .unwrap();
+    fs::remove_file(&path).unwrap();
+    let output = asimov(&["refresh", "--yes"]);
+    assert!(output.status.success(), "{:?}", output);
+    let output = asimov(&["validate"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("unsigned-local"), "{stdout}");
+}
+
+#[test]
+fn e2e_protocol_trust_after_upgrade_and_kept_edits() {
+    let project = TempDir::new().unwrap();
+    fs::create_dir_all(project.path().join(".git")).unwrap();
+    let asimov = |args: &[&str]| {
+        asimov_command()
+            .args(args)
+            .current_dir(project.path())
+            .stdin(std::process::Stdio::null())
+            .output()
+            .expect("Failed to execute")
+    };
+    let output = asimov(&["init", "--name", "svc", "--type", "rust"]);
+    assert!(output.status.success(), "{:?}", output);
+    let sprint = project.path().join(".asimov/sprint.json");
+    let default = fs::read_to_string(&sprint).unwrap();
+
+    // An older asimov wrote a different default and recorded nothing
+    fs::write(
+        &sprint,
+        default.replace("Analyze all tasks before starting", "Plan all tasks first"),
+    )
+    .unwrap();
+    fs::remove_file(project.path().join(".asimov/.manifest.json")).unwrap();
+    fs::remove_dir_all(project.path().join(".asimov/.base")).unwrap();
+    let output = asimov(&["validate"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(
+        stdout.contains("sprint.json unsigned-local (written by an older asimov"),
+        "{stdout}"
+    );
+
+    // An edit refresh keeps is a customization, not tampering
+    fs::remove_file(&sprint).unwrap();
+    let output = asimov(&["refresh", "--yes"]);
+    assert!(output.status.success(), "{:?}", output);
+    fs::write(
+        &sprint,
+        default.replace("\"rule\"", "\"team_note\": \"x\",\n  \"rule\""),
+    )
+    .unwrap();
+    let output = asimov(&["validate"]);
+    assert!(!output.status.success(), "Unreviewed edit is tampered");
+    let output = asimov(&["refresh", "--yes"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("KEPT sprint.json"), "{stdout}");
+    let output = asimov(&["validate"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(
+        stdout.contains("sprint.json unsigned-local (customized, kept by refresh)"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn e2e_pinned_dates_are_reproducible() {
+    let org = TempDir::new().unwrap();
+    fs::write(
+        org.path().join("release-notes.json"),
+        r#"{"name": "release-notes", "priority": 1,
+            "protocol": {"rule": "Date entries {TODAY}", "copyright": "(c) {YEAR}"}}"#,
+    )
+    .unwrap();
+    let project = TempDir::new().unwrap();
+    fs::create_dir_all(project.path().join(".git")).unwrap();
+    let asimov = |args: &[&str], now: &str| {
+        asimov_command()
+            .args(args)
+            .env("ASIMOV_PROTOCOL_PATH", org.path())
+            .env("ASIMOV_NOW", now)
+            .env("ASIMOV_TZ", "UTC")
+            .current_dir(project.path())
+            .output()
+            .expect("Failed to execute")
+    };
+    let output = asimov(&["init", "--name", "svc", "--type", "rust"], "2025-03-03");
+    assert!(output.status.success(), "{:?}", output);
+    let path = project.path().join(".asimov/release-notes.json");
+    let first = fs::read_to_string(&path).unwrap();
+    assert!(first.contains("Date entries 2025-03-03"), "{first}");
+    assert!(first.contains("(c) 2025"), "{first}");
+
+    // --date overrides ASIMOV_NOW; same date, byte-identical files
+    fs::remove_file(&path).unwrap();
+    let output = asimov(&["refresh", "--yes", "--date", "2025-03-03"], "1999-01-01");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(fs::read_to_string(&path).unwrap(), first);
+
+    // A new day is neither a pending change nor tampering
+    let output = asimov(&["refresh", "--dry-run"], "2026-01-15");
+    assert_eq!(output.status.code(), Some(0), "{:?}", output);
+    let output = asimov(&["validate"], "2026-01-15");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(!stdout.contains("tampered"), "{stdout}");
+
+    let output = asimov(&["validate", "--tz", "Mars"], "2026-01-15");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("invalid timezone"));
+}
@@ -0,0 +1,49 @@
+//! Schema command tests - list, show and export
+
+use super::asimov_command;
+use tempfile::TempDir;
+
+#[test]
+fn e2e_schema_list() {
+    let output = asimov_command()
+        .args(["schema", "list"])
+        .output()
+        .expect("Failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("roadmap"));
+    assert!(stdout.contains("**/.asimov/project.yaml"));
+    assert!(stdout.contains("**/.asimov/protocols/sprint.json"));
+    assert!(stdout.contains("sprint-yaml - Legacy YAML protocol"));
+}
+
+#[test]
+fn e2e_schema_show_unknown_fails() {
+    let output = asimov_command()
+        .args(["schema", "show", "nope"])
+        .output()
+        .expect("Failed to execute");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("not found"));
+}
+
+#[test]
+fn e2e_schema_export_writes_catalog() {
+    let temp = TempDir::new().unwrap();
+    let output = asimov_command()
+        .args(["schema", "export"])
+        .arg(temp.path())
+        .args(["--base-url", "https://example.com/asimov"])
+        .output()
+        .expect("Failed to execute");
+
+    assert!(output.status.success());
+    let catalog = std::fs::read_to_string(temp.path().join("catalog.json")).unwrap();
+    assert!(catalog.contains("schema-catalog.json"));
+    assert!(catalog.contains(&format!(
+        "https://example.com/asimov/v{}/roadmap.schema.json",
+        env!("CARGO_PKG_VERSION")
+    )));
+}
@@ -0,0 +1,107 @@
+//! Template authoring tests
+
+use super::asimov_command;
+use std::fs;
+use tempfile::TempDir;
+
+fn asimov(dir: &std::path::Path, args: &[&str]) -> std::process::Output {
+    asimov_command()
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("Failed to execute")
+}
+
+#[test]
+fn e2e_template_new_show_lint_and_init() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path();
+
+    let output = asimov(dir, &["template", "new", "service", "--from", "api-rust"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(dir.join(".asimov/templates/service.yaml").exists());
+
+    let output = asimov(dir, &["template", "show", "service", "--var", "OWNER=core"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert!(stdout.contains("owner: \"core\""), "got: {stdout}");
+    assert!(
+        stdout.contains("kind: backend-api"),
+        "Inherited from api-rust: {stdout}"
+    );
+    assert!(
+        stdout.contains("format: \"cargo fmt --check\""),
+        "Inherited from rust: {stdout}"
+    );
+
+    let output = asimov(dir, &["template", "lint"]);
+    assert!(output.status.success());
+
+    let output = asimov(
+        dir,
+        &[
+            "init",
+            "--name",
+            "svc",
+            "--type",
+            "service",
+            "--var",
+            "OWNER=core",
+        ],
+    );
+    assert!(output.status.success(), "{:?}", output);
+    let project = fs::read_to_string(dir.join(".asimov/project.yaml")).unwrap();
+    assert!(project.contains("name: \"svc\""));
+    assert!(project.contains("owner: \"core\""));
+    assert!(!project.contains("@extends"));
+    assert!(!project.contains("# Directives"));
+    assert!(project.contains("# v9.3.0: Coding standards for Rust projects"));
+}
+
+#[test]
+fn e2e_init_rejects_template_that_renders_invalid_project() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path();
+    fs::create_dir_all(dir.join(".asimov/templates")).unwrap();
+    fs::write(
+        dir.join(".asimov/templates/odd.yaml"),
+        "# @extends generic\nidentity:\n  type: cobol\n",
+    )
+    .unwrap();
+
+    let output = asimov(dir, &["template", "lint", "odd"]);
+    assert!(!output.status.success());
+
+    let output = asimov(dir, &["init", "--name", "x", "--type", "odd"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("renders an invalid project.yaml"),
+        "got: {stderr}"
+    );
+    assert!(!dir.join(".asimov/project.yaml").exists());
+
+    let output = asimov(
+        dir,
+        &["init", "--name", "x", "--type", "rust", "--var", "NOPE=1"],
+    );
+    assert!(!output.status.success());
+}
+
+#[test]
+fn e2e_template_diff_override() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path();
+    fs::create_dir_all(dir.join(".asimov/templates")).unwrap();
+    fs::write(
+        dir.join(".asimov/templates/rust.yaml"),
+        "# @extends rust\nquality:\n  lint: cargo clippy --all-targets -- -D warnings\n",
+    )
+    .unwrap();
+
+    let output = asimov(dir, &["template", "diff", "rust"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(stdout.contains("vs embedded rust"), "got: {stdout}");
+    assert!(stdout.contains("+  lint: cargo clippy --all-targets -- -D warnings"));
+}
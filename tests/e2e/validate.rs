@@ -1,8 +1,7 @@
 //! Validate command tests
 
-use super::binary_path;
+use super::asimov_command;
 use std::fs;
-use std::process::Command;
 use tempfile::TempDir;
 
 #[test]
@@ -19,7 +18,7 @@ fn e2e_validate_directory() {
     .unwrap();
 
     // v8.16.0: validate takes no path, runs from current directory
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("validate")
         .current_dir(temp_dir.path())
         .output()
@@ -45,7 +44,7 @@ fn e2e_validate_directory_no_protocol_files() {
     // No roadmap.yaml, just an empty .asimov dir
 
     // v8.16.0: validate takes no args, runs from current directory
-    let output = Command::new(binary_path())
+    let output = asimov_command()
         .arg("validate")
         .current_dir(temp_dir.path())
         .output()
@@ -59,3 +58,152 @@ fn e2e_validate_directory_no_protocol_files() {
         "Should mention roadmap, got: {stdout}"
     );
 }
+
+#[test]
+fn e2e_validate_reports_error_location() {
+    let temp_dir = TempDir::new().unwrap();
+    let asimov_dir = temp_dir.path().join(".asimov");
+    fs::create_dir_all(&asimov_dir).unwrap();
+
+    fs::write(
+        asimov_dir.join("roadmap.yaml"),
+        "current:\n  version: '1.0.0'\n  status: done\n  summary: Test milestone\n",
+    )
+    .unwrap();
+
+    let output = asimov_command()
+        .arg("validate")
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(!output.status.success(), "Invalid status should fail");
+    assert!(
+        stdout.contains(".asimov/roadmap.yaml:3:11: current.status must be one of"),
+        "Should report file:line:col, got: {stdout}"
+    );
+    assert!(
+        stdout.contains("> 3 |   status: done"),
+        "Should show a code frame, got: {stdout}"
+    );
+}
+
+#[test]
+fn e2e_validate_reports_malformed_role() {
+    let temp_dir = TempDir::new().unwrap();
+    let asimov_dir = temp_dir.path().join(".asimov");
+    fs::create_dir_all(asimov_dir.join("roles")).unwrap();
+
+    fs::write(
+        asimov_dir.join("roadmap.yaml"),
+        "current:\n  version: '1.0.0'\n  status: planned\n  summary: Test milestone\n",
+    )
+    .unwrap();
+    fs::write(asimov_dir.join("roles").join("eng.json"), "{ not json").unwrap();
+
+    let output = asimov_command()
+        .arg("validate")
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("Failed to execute");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(!output.status.success(), "Malformed role should fail");
+    assert!(
+        stdout.contains(".asimov/roles/eng.json") && stdout.contains("Invalid JSON"),
+        "Should report the role file, got: {stdout}"
+    );
+}
+
+#[test]
+fn e2e_validate_all_package_contexts() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    fs::create_dir_all(root.join(".git")).unwrap();
+    let roadmap = "current:\n  version: '1.0.0'\n  status: planned\n  summary: Test milestone";
+    for dir in [".asimov", "packages/web/.asimov", "packages/api/.asimov"] {
+        fs::create_dir_all(root.join(dir)).unwrap();
+    }
+    fs::write(root.join(".asimov/roadmap.yaml"), roadmap).unwrap();
+    fs::write(root.join("packages/web/.asimov/roadmap.yaml"), roadmap).unwrap();
+    fs::write(
+        root.join("packages/api/.asimov/roadmap.yaml"),
+        "current:\n  version: [broken",
+    )
+    .unwrap();
+
+    // From inside a package, plain validate only checks the nearest context
+    let output = asimov_command()
+        .arg("validate")
+        .current_dir(root.join("packages/web"))
+        .output()
+        .expect("Failed to execute");
+    assert!(output.status.success());
+
+    // --all walks every package context from the repository root
+    let output = asimov_command()
+        .args(["validate", "--all"])
+        .current_dir(root.join("packages/web"))
+        .output()
+        .expect("Failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        !output.status.success(),
+        "Broken package should fail: {stdout}"
+    );
+    assert!(stdout.contains("PACKAGE packages/api"), "got: {stdout}");
+    assert!(stdout.contains("PACKAGE packages/web"), "got: {stdout}");
+    assert!(stdout.contains("3 package context(s) validated"));
+}
+
+#[test]
+fn e2e_validate_discovers_root_from_subdirectory() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    fs::create_dir_all(root.join(".git")).unwrap();
+    fs::create_dir_all(root.join(".asimov/roles")).unwrap();
+    fs::create_dir_all(root.join("src/deep")).unwrap();
+    fs::write(
+        root.join(".asimov/roadmap.yaml"),
+        "current:\n  version: '1.0.0'\n  status: planned\n  summary: Test milestone\n",
+    )
+    .unwrap();
+    fs::write(root.join(".asimov/roles/eng.json"), "{ not json").unwrap();
+
+    // Running from a subdirectory finds the project root's .asimov/
+    let output = asimov_command()
+        .arg("validate")
+        .current_dir(root.join("src/deep"))
+        .output()
+        .expect("Failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success(), "Malformed role should fail");
+    assert!(stdout.contains("roadmap.yaml"), "got: {stdout}");
+    assert!(
+        stdout.contains("../../.asimov/roles/eng.json"),
+        "got: {stdout}"
+    );
+
+    // -C starts discovery elsewhere
+    let output = asimov_command()
+        .args(["-C", "src/deep", "validate"])
+        .current_dir(root)
+        .output()
+        .expect("Failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("../../.asimov/roles/eng.json"),
+        "got: {stdout}"
+    );
+
+    let output = asimov_command()
+        .args(["-C", "missing", "validate"])
+        .current_dir(root)
+        .output()
+        .expect("Failed to execute");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot change to missing"));
+}